@@ -406,8 +406,12 @@ impl ChannelManager {
         signature: &str,
         message: PusherMessage,
     ) -> bool {
-        let expected = Self::get_expected_signature(app_config, socket_id, message);
-        secure_compare(signature, &expected)
+        let data_to_sign = Self::get_data_to_sign_for_signature(socket_id, message);
+        app_config.active_secrets().into_iter().any(|secret| {
+            let token = Token::new(app_config.key.clone(), secret.to_string());
+            let expected = format!("{}:{}", app_config.key, token.sign(&data_to_sign));
+            secure_compare(signature, &expected)
+        })
     }
 
     pub fn get_expected_signature(
@@ -5,10 +5,9 @@ use sockudo_core::app::AppManager;
 use sockudo_core::channel::PresenceMemberInfo;
 use sockudo_core::error::Result;
 use sockudo_core::namespace::Namespace;
-use sockudo_core::websocket::{SocketId, WebSocketBufferConfig, WebSocketRef};
+use sockudo_core::websocket::{SocketId, TransportWriter, WebSocketBufferConfig, WebSocketRef};
 use sockudo_protocol::messages::PusherMessage;
 use sockudo_protocol::{ProtocolVersion, WireFormat};
-use sockudo_ws::axum_integration::WebSocketWriter;
 use std::any::Any;
 use std::sync::Arc;
 
@@ -16,6 +15,11 @@ pub type DeadNodeEventBusFlavor = mpsc::List<crate::horizontal_adapter::DeadNode
 pub type DeadNodeEventBusSender = crossfire::MTx<DeadNodeEventBusFlavor>;
 pub type DeadNodeEventBusReceiver = crossfire::AsyncRx<DeadNodeEventBusFlavor>;
 
+pub type SubscriptionEventBusFlavor =
+    mpsc::List<crate::horizontal_adapter::SubscriptionChangeEvent>;
+pub type SubscriptionEventBusSender = crossfire::MTx<SubscriptionEventBusFlavor>;
+pub type SubscriptionEventBusReceiver = crossfire::AsyncRx<SubscriptionEventBusFlavor>;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ChannelSocketCount {
     pub count: usize,
@@ -73,7 +77,7 @@ pub trait ConnectionManager: Send + Sync {
     async fn add_socket(
         &self,
         socket_id: SocketId,
-        socket: WebSocketWriter,
+        socket: TransportWriter,
         app_id: &str,
         app_manager: Arc<dyn AppManager + Send + Sync>,
         buffer_config: WebSocketBufferConfig,
@@ -271,6 +275,12 @@ pub trait ConnectionManager: Send + Sync {
     async fn get_namespaces(&self) -> Result<Vec<(String, Arc<Namespace>)>>;
     fn as_any_mut(&mut self) -> &mut dyn Any;
 
+    /// Whether cluster-wide channel counts are answered locally, without asking peers.
+    /// Adapters without peers always are; horizontal ones only with `aggregate_counts`.
+    fn aggregates_channel_counts(&self) -> bool {
+        true
+    }
+
     /// Local-only socket count (no cross-node query)
     async fn get_local_channel_socket_count(&self, app_id: &str, channel: &str) -> usize {
         self.get_channel_socket_count(app_id, channel).await
@@ -308,6 +318,52 @@ pub trait ConnectionManager: Send + Sync {
         None // Default: no clustering support
     }
 
+    /// Configure the bus carrying subscription changes that peers ask this node to apply
+    /// Returns Some(receiver) if configured, None if not supported
+    fn configure_subscription_events(&self) -> Option<SubscriptionEventBusReceiver> {
+        None // Default: no clustering support
+    }
+
+    /// Ask cluster peers to apply a subscription change to their local sockets
+    /// Returns the number of remote sockets it was applied to
+    async fn request_subscription_change(
+        &self,
+        _event: &crate::horizontal_adapter::SubscriptionChangeEvent,
+    ) -> Result<usize> {
+        Ok(0) // Default: no peers
+    }
+
+    /// List every connection of a signed-in user across the cluster
+    async fn get_user_socket_entries(
+        &self,
+        user_id: &str,
+        app_id: &str,
+    ) -> Result<Vec<crate::horizontal_adapter::UserSocketEntry>> {
+        let mut entries = Vec::new();
+        for ws in self.get_user_sockets(user_id, app_id).await? {
+            entries.push(crate::horizontal_adapter::UserSocketEntry::from_connection(&ws).await);
+        }
+        Ok(entries) // Default: local sockets only
+    }
+
+    /// Close one connection of a user, wherever it lives, as replaced by a newer one
+    /// Returns whether the connection was found
+    async fn evict_user_socket(
+        &self,
+        app_id: &str,
+        user_id: &str,
+        socket_id: &str,
+    ) -> Result<bool> {
+        for ws in self.get_user_sockets(user_id, app_id).await? {
+            if ws.get_socket_id_sync().to_string() == socket_id {
+                let error = sockudo_core::error::Error::ConnectionReplaced;
+                ws.close(error.close_code(), error.to_string()).await?;
+                return Ok(true);
+            }
+        }
+        Ok(false) // Default: local sockets only
+    }
+
     /// Tell cluster peers this node is leaving so they prune
     /// heartbeat tracking without waiting for the timeout window
     async fn announce_node_departure(&self) -> Result<()> {
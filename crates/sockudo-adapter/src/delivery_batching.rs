@@ -0,0 +1,473 @@
+//! Per-subscription delivery windows for V2 sockets.
+//!
+//! Works like the AI transport append rollup, on the same sharded deadline wheel:
+//! the first message after a quiet window goes out immediately and arms a
+//! deadline, later messages are held, and a wheel ticking every
+//! [`DELIVERY_WHEEL_TICK_MS`] flushes windows whose deadline passed. A window keeps re-arming while messages keep arriving, so a
+//! busy channel costs one frame per window instead of one per message. A window
+//! that reaches [`MAX_WINDOW_MESSAGES`] or [`MAX_WINDOW_BYTES`] flushes early.
+
+use sockudo_core::deadline_wheel::{DeadlineWheel, ShardedDeadlineMap};
+use sockudo_core::websocket::{DeliveryMode, DeliveryWindow, SocketId, WebSocketRef};
+use sockudo_protocol::messages::{MessageExtras, MessagePriority, PusherMessage};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Event name of the frame that carries every message of a batched window.
+pub const BATCH_EVENT: &str = "sockudo:batch";
+pub const DELIVERY_WHEEL_TICK_MS: u64 = 10;
+/// Messages one window holds before it flushes without waiting for its deadline.
+pub const MAX_WINDOW_MESSAGES: usize = 100;
+/// Serialized bytes one window holds before it flushes without waiting for its deadline.
+pub const MAX_WINDOW_BYTES: usize = 64 * 1024;
+
+/// A published message shared by every paced subscriber it is routed to.
+pub struct WindowMessage {
+    message: PusherMessage,
+    size: usize,
+}
+
+impl WindowMessage {
+    #[must_use]
+    pub fn new(message: PusherMessage) -> Arc<Self> {
+        let size = sonic_rs::to_vec(&message).map_or(0, |bytes| bytes.len());
+        Arc::new(Self { message, size })
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &PusherMessage {
+        &self.message
+    }
+}
+
+/// What a window sends: a lone message as published, or a `sockudo:batch` event.
+pub enum WindowFrame {
+    Single(Arc<WindowMessage>),
+    Batch(Box<PusherMessage>),
+}
+
+impl WindowFrame {
+    #[must_use]
+    pub fn message(&self) -> &PusherMessage {
+        match self {
+            Self::Single(message) => message.message(),
+            Self::Batch(message) => message,
+        }
+    }
+}
+
+/// One frame ready to be written to a paced subscriber.
+pub struct WindowDelivery {
+    pub socket: WebSocketRef,
+    pub frame: WindowFrame,
+    pub coalesced: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct WindowKey {
+    socket_id: SocketId,
+    channel: String,
+}
+
+struct PendingWindow {
+    socket: WebSocketRef,
+    window: DeliveryWindow,
+    deadline_ms: u64,
+    messages: Vec<Arc<WindowMessage>>,
+    bytes: usize,
+}
+
+impl PendingWindow {
+    fn push(&mut self, message: Arc<WindowMessage>) {
+        if self.window.mode == DeliveryMode::Latest {
+            self.messages.clear();
+            self.bytes = 0;
+        }
+        self.bytes += message.size;
+        self.messages.push(message);
+    }
+
+    fn is_full(&self) -> bool {
+        self.messages.len() >= MAX_WINDOW_MESSAGES || self.bytes >= MAX_WINDOW_BYTES
+    }
+
+    /// Drains the held messages into one frame and re-arms the window.
+    fn flush(&mut self, channel: &str, now_ms: u64) -> Option<(WindowFrame, usize)> {
+        let coalesced = self.messages.len();
+        self.bytes = 0;
+        let frame = window_frame(channel, std::mem::take(&mut self.messages))?;
+        self.deadline_ms = now_ms + self.window.window_ms;
+        Some((frame, coalesced))
+    }
+}
+
+/// Holds messages for subscriptions that asked for a delivery window.
+///
+/// Like the rollup engine this is synchronous: callers send the returned frames
+/// outside the shard locks.
+pub struct DeliveryBatcher {
+    windows: ShardedDeadlineMap<WindowKey, PendingWindow>,
+    wheel: DeadlineWheel,
+}
+
+impl Default for DeliveryBatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeliveryBatcher {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            windows: ShardedDeadlineMap::default(),
+            wheel: DeadlineWheel::new(),
+        }
+    }
+
+    /// Routes one message for `socket` through its window on `channel`. Returns the frame
+    /// to send right away, `None` when the message was held for the window.
+    #[must_use]
+    pub fn ingest(
+        &self,
+        socket: &WebSocketRef,
+        channel: &str,
+        window: DeliveryWindow,
+        message: &Arc<WindowMessage>,
+        now_ms: u64,
+    ) -> Option<WindowFrame> {
+        let key = WindowKey {
+            socket_id: *socket.get_socket_id_sync(),
+            channel: channel.to_string(),
+        };
+        let mut windows = self.windows.lock(&key);
+        let Some(pending) = windows.get_mut(&key) else {
+            windows.insert(
+                key,
+                PendingWindow {
+                    socket: socket.clone(),
+                    window,
+                    deadline_ms: now_ms + window.window_ms,
+                    messages: Vec::new(),
+                    bytes: 0,
+                },
+            );
+            return Some(WindowFrame::Single(Arc::clone(message)));
+        };
+
+        pending.window = window;
+        pending.push(Arc::clone(message));
+        // Flush inline when the wheel is running late, rather than stretch the window, or
+        // when the window is full, rather than grow one frame without bound.
+        if now_ms >= pending.deadline_ms || pending.is_full() {
+            return pending.flush(channel, now_ms).map(|(frame, _)| frame);
+        }
+        None
+    }
+
+    /// Flushes every window whose deadline passed and forgets windows that stayed quiet or
+    /// whose subscription no longer asks for pacing.
+    #[must_use]
+    pub fn flush_due(&self, now_ms: u64) -> Vec<WindowDelivery> {
+        let mut deliveries = Vec::new();
+        self.windows.retain(|key, pending| {
+            if now_ms < pending.deadline_ms {
+                return true;
+            }
+            if pending.messages.is_empty()
+                || pending.socket.delivery_window_for(&key.channel).is_none()
+            {
+                return false;
+            }
+            if let Some((frame, coalesced)) = pending.flush(&key.channel, now_ms) {
+                deliveries.push(WindowDelivery {
+                    socket: pending.socket.clone(),
+                    frame,
+                    coalesced,
+                });
+            }
+            true
+        });
+        deliveries
+    }
+
+    #[must_use]
+    pub fn active_windows(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Starts the flush wheel on the current runtime the first time a window is used. The
+    /// wheel stops once the batcher is dropped.
+    pub fn ensure_worker(self: &Arc<Self>) {
+        self.wheel
+            .ensure_started(self, DELIVERY_WHEEL_TICK_MS, |batcher, now_ms| async move {
+                for delivery in batcher.flush_due(now_ms) {
+                    if let Err(error) = delivery.socket.send_message(delivery.frame.message()) {
+                        debug!(
+                            socket_id = %delivery.socket.get_socket_id_sync(),
+                            coalesced = delivery.coalesced,
+                            error = %error,
+                            "failed to flush subscription delivery window"
+                        );
+                    }
+                }
+            });
+    }
+}
+
+/// Builds the frame for one window: the lone message as-is, or a `sockudo:batch` event
+/// whose data is the JSON-encoded array of the window's messages. A batch is sent at low
+/// priority only when every message in it was.
+fn window_frame(channel: &str, mut messages: Vec<Arc<WindowMessage>>) -> Option<WindowFrame> {
+    match messages.len() {
+        0 => None,
+        1 => messages.pop().map(WindowFrame::Single),
+        _ => {
+            let held: Vec<&PusherMessage> = messages.iter().map(|held| held.message()).collect();
+            let batch = sonic_rs::to_value(&held).ok()?;
+            let mut frame = PusherMessage::channel_event(BATCH_EVENT, channel, batch);
+            if held
+                .iter()
+                .all(|message| message.priority() == MessagePriority::Low)
+            {
+                frame.extras = Some(MessageExtras {
+                    priority: Some(MessagePriority::Low),
+                    ..Default::default()
+                });
+            }
+            Some(WindowFrame::Batch(Box::new(frame)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sockudo_core::websocket::{TransportWriter, WebSocket};
+    use sonic_rs::{JsonContainerTrait, JsonValueTrait, Value, json};
+
+    fn socket() -> WebSocketRef {
+        let (writer, _receiver) = TransportWriter::channel(16);
+        let socket = WebSocketRef::new(WebSocket::new(SocketId::new(), writer));
+        socket.set_delivery_window("prices".to_string(), Some(batch(50)));
+        socket
+    }
+
+    fn batch(window_ms: u64) -> DeliveryWindow {
+        DeliveryWindow {
+            mode: DeliveryMode::Batch,
+            window_ms,
+        }
+    }
+
+    fn latest(window_ms: u64) -> DeliveryWindow {
+        DeliveryWindow {
+            mode: DeliveryMode::Latest,
+            window_ms,
+        }
+    }
+
+    fn tick(n: u64) -> Arc<WindowMessage> {
+        WindowMessage::new(PusherMessage::channel_event(
+            "price",
+            "prices",
+            json!({ "n": n }),
+        ))
+    }
+
+    fn batched_numbers(message: &PusherMessage) -> Vec<u64> {
+        assert_eq!(message.event.as_deref(), Some(BATCH_EVENT));
+        let data = message
+            .data
+            .as_ref()
+            .and_then(|data| data.as_string())
+            .unwrap();
+        let frames: Value = sonic_rs::from_str(data).unwrap();
+        frames
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|frame| {
+                let inner: Value = sonic_rs::from_str(frame["data"].as_str().unwrap()).unwrap();
+                inner["n"].as_u64().unwrap()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn first_message_is_immediate_and_the_rest_wait_for_the_window() {
+        let batcher = DeliveryBatcher::new();
+        let socket = socket();
+
+        assert!(
+            batcher
+                .ingest(&socket, "prices", batch(50), &tick(1), 0)
+                .is_some()
+        );
+        for (n, now) in [(2, 10), (3, 20), (4, 30)] {
+            assert!(
+                batcher
+                    .ingest(&socket, "prices", batch(50), &tick(n), now)
+                    .is_none()
+            );
+        }
+        assert!(batcher.flush_due(49).is_empty());
+
+        let out = batcher.flush_due(50);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].coalesced, 3);
+        assert_eq!(batched_numbers(out[0].frame.message()), vec![2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn latest_mode_keeps_only_the_newest_message() {
+        let batcher = DeliveryBatcher::new();
+        let socket = socket();
+        socket.set_delivery_window("prices".to_string(), Some(latest(50)));
+
+        let _ = batcher.ingest(&socket, "prices", latest(50), &tick(1), 0);
+        let _ = batcher.ingest(&socket, "prices", latest(50), &tick(2), 10);
+        let _ = batcher.ingest(&socket, "prices", latest(50), &tick(3), 20);
+
+        let out = batcher.flush_due(50);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].frame.message().event.as_deref(), Some("price"));
+        assert!(
+            out[0]
+                .frame
+                .message()
+                .data
+                .as_ref()
+                .and_then(|data| data.as_string())
+                .unwrap()
+                .contains("3")
+        );
+    }
+
+    #[tokio::test]
+    async fn busy_windows_rearm_and_quiet_windows_are_forgotten() {
+        let batcher = DeliveryBatcher::new();
+        let socket = socket();
+
+        let _ = batcher.ingest(&socket, "prices", batch(50), &tick(1), 0);
+        let _ = batcher.ingest(&socket, "prices", batch(50), &tick(2), 10);
+        assert_eq!(batcher.flush_due(50).len(), 1);
+
+        // Still inside the re-armed window, so this one is held too.
+        assert!(
+            batcher
+                .ingest(&socket, "prices", batch(50), &tick(3), 60)
+                .is_none()
+        );
+        assert_eq!(batcher.flush_due(100).len(), 1);
+
+        assert!(batcher.flush_due(150).is_empty());
+        assert_eq!(batcher.active_windows(), 0);
+        assert!(
+            batcher
+                .ingest(&socket, "prices", batch(50), &tick(4), 160)
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn held_messages_are_dropped_once_the_subscription_stops_pacing() {
+        let batcher = DeliveryBatcher::new();
+        let socket = socket();
+
+        let _ = batcher.ingest(&socket, "prices", batch(50), &tick(1), 0);
+        let _ = batcher.ingest(&socket, "prices", batch(50), &tick(2), 10);
+        socket.set_delivery_window("prices".to_string(), None);
+
+        assert!(batcher.flush_due(50).is_empty());
+        assert_eq!(batcher.active_windows(), 0);
+    }
+
+    #[tokio::test]
+    async fn full_windows_flush_without_waiting_for_the_deadline() {
+        let batcher = DeliveryBatcher::new();
+        let socket = socket();
+
+        let _ = batcher.ingest(&socket, "prices", batch(50), &tick(0), 0);
+        for n in 1..MAX_WINDOW_MESSAGES as u64 {
+            assert!(
+                batcher
+                    .ingest(&socket, "prices", batch(50), &tick(n), 10)
+                    .is_none()
+            );
+        }
+        let frame = batcher
+            .ingest(
+                &socket,
+                "prices",
+                batch(50),
+                &tick(MAX_WINDOW_MESSAGES as u64),
+                10,
+            )
+            .expect("a full window flushes inline");
+        assert_eq!(
+            batched_numbers(frame.message()),
+            (1..=MAX_WINDOW_MESSAGES as u64).collect::<Vec<_>>()
+        );
+
+        let large = |n: u64| {
+            WindowMessage::new(PusherMessage::channel_event(
+                "price",
+                "prices",
+                json!({ "n": n, "pad": "x".repeat(MAX_WINDOW_BYTES / 2) }),
+            ))
+        };
+        assert!(
+            batcher
+                .ingest(&socket, "prices", batch(50), &large(1), 20)
+                .is_none()
+        );
+        let frame = batcher
+            .ingest(&socket, "prices", batch(50), &large(2), 20)
+            .expect("a window over the byte cap flushes inline");
+        assert_eq!(batched_numbers(frame.message()), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn batches_stay_low_priority_only_when_every_message_is() {
+        let batcher = DeliveryBatcher::new();
+        let socket = socket();
+        let with_priority = |n: u64, priority: MessagePriority| {
+            let mut message = PusherMessage::channel_event("price", "prices", json!({ "n": n }));
+            message.extras = Some(MessageExtras {
+                priority: Some(priority),
+                ..Default::default()
+            });
+            WindowMessage::new(message)
+        };
+
+        let _ = batcher.ingest(&socket, "prices", batch(50), &tick(0), 0);
+        let _ = batcher.ingest(
+            &socket,
+            "prices",
+            batch(50),
+            &with_priority(1, MessagePriority::Low),
+            10,
+        );
+        let _ = batcher.ingest(
+            &socket,
+            "prices",
+            batch(50),
+            &with_priority(2, MessagePriority::Low),
+            20,
+        );
+        let out = batcher.flush_due(50);
+        assert_eq!(out[0].frame.message().priority(), MessagePriority::Low);
+
+        let _ = batcher.ingest(
+            &socket,
+            "prices",
+            batch(50),
+            &with_priority(3, MessagePriority::Low),
+            60,
+        );
+        let _ = batcher.ingest(&socket, "prices", batch(50), &tick(4), 70);
+        let out = batcher.flush_due(100);
+        assert_eq!(out[0].frame.message().priority(), MessagePriority::Normal);
+    }
+}
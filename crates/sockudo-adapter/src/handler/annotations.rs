@@ -224,7 +224,8 @@ impl ConnectionHandler {
         projection: StoredAnnotationProjection,
     ) -> Result<StoredAnnotationProjection> {
         let max_payload = app
-            .event_payload_limit_kb()
+            .resolved_channel_limits(channel)
+            .max_event_payload_in_kb
             .map(|kb| kb as usize * 1024)
             .unwrap_or(self.server_options().websocket_max_payload_kb as usize * 1024);
         if max_payload == 0 {
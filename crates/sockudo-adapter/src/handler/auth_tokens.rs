@@ -1,11 +1,15 @@
 use super::ConnectionHandler;
 use sockudo_core::app::App;
-use sockudo_core::capability_token::{TokenAuthContext, validate_capability_token};
+use sockudo_core::capability_token::{
+    TokenAuthContext, jwks_kid, validate_capability_token_with_jwk,
+};
 use sockudo_core::error::{Error, Result};
-use sockudo_core::websocket::SocketId;
+use sockudo_core::websocket::{SocketId, WebSocketRef};
 use sockudo_protocol::ProtocolVersion;
 use sockudo_protocol::messages::{MessageData, PusherMessage};
-use sockudo_protocol::protocol_version::{CANONICAL_AUTH_SUCCESS, CANONICAL_TOKEN_EXPIRED};
+use sockudo_protocol::protocol_version::{
+    CANONICAL_AUTH_SUCCESS, CANONICAL_REFRESH, CANONICAL_REFRESH_SUCCESS, CANONICAL_TOKEN_EXPIRED,
+};
 use sonic_rs::prelude::*;
 use sonic_rs::{Value, json};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -63,7 +67,11 @@ impl ConnectionHandler {
         app_config: &App,
         token: &str,
     ) -> Result<TokenAuthContext> {
-        let context = validate_capability_token(token, app_config)?;
+        let jwk = match (app_config.jwks_url(), jwks_kid(token, app_config)) {
+            (Some(url), Some(kid)) => self.jwks.key(url, &kid).await?,
+            _ => None,
+        };
+        let context = validate_capability_token_with_jwk(token, app_config, jwk.as_ref())?;
         if self
             .cache_manager
             .has(&jti_revocation_key(&app_config.id, &context.jti))
@@ -100,6 +108,7 @@ impl ConnectionHandler {
             ));
         }
 
+        let mut identity_unchanged = false;
         if is_refresh {
             if let Some(existing) = connection.get_token_auth_context().await {
                 if existing.client_id != context.client_id {
@@ -107,28 +116,45 @@ impl ConnectionHandler {
                         "capability token refresh cannot change client_id".to_string(),
                     ));
                 }
-            } else if let Some(existing_user_id) = connection.get_user_id().await
-                && existing_user_id != context.client_id
-            {
-                return Err(Error::Auth(
-                    "capability token refresh cannot change authenticated identity".to_string(),
-                ));
+                identity_unchanged = true;
+            } else if let Some(existing_user_id) = connection.get_user_id().await {
+                if existing_user_id != context.client_id {
+                    return Err(Error::Auth(
+                        "capability token refresh cannot change authenticated identity".to_string(),
+                    ));
+                }
+                identity_unchanged = true;
             }
         }
 
         connection.set_token_auth_context(context.clone()).await;
-        self.connection_manager.add_user(connection.clone()).await?;
+        if identity_unchanged {
+            // Already counted under this identity; a refresh must not evict its siblings.
+            self.connection_manager.add_user(connection.clone()).await?;
+        } else {
+            self.register_user_connection(&connection, app_config, &context.client_id, None)
+                .await?;
+        }
         self.schedule_token_expiry(socket_id, app_config, &context);
 
         Ok(())
     }
 
-    pub(crate) async fn handle_auth_token_refresh(
+    /// Swaps the connection's capability token in place, for both `sockudo:auth` and
+    /// `sockudo:refresh` (`event` is the canonical name of the request).
+    ///
+    /// `sockudo:refresh` also unsubscribes the connection from every channel the new
+    /// capabilities no longer allow and lists them under `unsubscribed` in
+    /// `sockudo:refresh_success`; `sockudo:auth` answers with `sockudo:auth_success`. Either way
+    /// the connection then joins its personal user channel.
+    pub(crate) async fn handle_token_refresh(
         &self,
         socket_id: &SocketId,
         app_config: &App,
         message: &PusherMessage,
+        event: &str,
     ) -> Result<()> {
+        let wire_event = ProtocolVersion::V2.wire_event(event);
         let Some(connection) = self
             .connection_manager
             .get_connection(socket_id, &app_config.id)
@@ -137,23 +163,34 @@ impl ConnectionHandler {
             return Err(Error::ConnectionNotFound);
         };
         if connection.protocol_version != ProtocolVersion::V2 {
-            return Err(Error::Auth(
-                "sockudo:auth is only supported on protocol V2".to_string(),
-            ));
+            return Err(Error::Auth(format!(
+                "{wire_event} is only supported on protocol V2"
+            )));
         }
 
-        let token = token_from_message(message)?;
+        let token = token_from_message(message, &wire_event)?;
         let context = self.validate_connection_token(app_config, &token).await?;
         self.apply_connection_token(socket_id, app_config, context.clone(), true)
             .await?;
 
+        let mut data = json!({
+            "client_id": context.client_id,
+            "jti": context.jti,
+            "exp": context.exp,
+        });
+        let success_event = if event == CANONICAL_REFRESH {
+            let unsubscribed = self
+                .drop_disallowed_subscriptions(socket_id, app_config, &connection)
+                .await?;
+            data["unsubscribed"] = sonic_rs::to_value(&unsubscribed)?;
+            CANONICAL_REFRESH_SUCCESS
+        } else {
+            CANONICAL_AUTH_SUCCESS
+        };
+
         let response = PusherMessage {
-            event: Some(ProtocolVersion::V2.wire_event(CANONICAL_AUTH_SUCCESS)),
-            data: Some(MessageData::from(json!({
-                "client_id": context.client_id,
-                "jti": context.jti,
-                "exp": context.exp,
-            }))),
+            event: Some(ProtocolVersion::V2.wire_event(success_event)),
+            data: Some(MessageData::from(data)),
             channel: None,
             name: None,
             user_id: None,
@@ -169,7 +206,40 @@ impl ConnectionHandler {
             delta_conflation_key: None,
         };
         self.send_message_to_socket(&app_config.id, socket_id, response)
-            .await
+            .await?;
+        self.join_user_channel(socket_id, app_config).await
+    }
+
+    /// Unsubscribes from channels the connection's current capabilities no longer allow and
+    /// returns them, sorted.
+    async fn drop_disallowed_subscriptions(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        connection: &WebSocketRef,
+    ) -> Result<Vec<String>> {
+        let channels = connection.inner.lock().await.get_subscribed_channels();
+        let mut unsubscribed = Vec::new();
+        for channel in channels {
+            // Personal user channels follow the signed-in user, not subscribe capabilities.
+            if channel.starts_with("#server-to-user-")
+                || app_config
+                    .user_channel_ref()
+                    .is_some_and(|user_channel| user_channel.user_id_of(&channel).is_some())
+                || self
+                    .validate_v2_capability(socket_id, app_config, &channel, "subscribe")
+                    .await
+                    .is_ok()
+            {
+                continue;
+            }
+            debug!(%socket_id, channel, "dropping subscription no longer allowed by refreshed token");
+            self.unsubscribe_channel(socket_id, app_config, channel.clone())
+                .await?;
+            unsubscribed.push(channel);
+        }
+        unsubscribed.sort();
+        Ok(unsubscribed)
     }
 
     fn schedule_token_expiry(
@@ -336,22 +406,21 @@ impl ConnectionHandler {
     }
 }
 
-fn token_from_message(message: &PusherMessage) -> Result<String> {
+fn token_from_message(message: &PusherMessage, event: &str) -> Result<String> {
     let Some(data) = &message.data else {
-        return Err(Error::InvalidMessageFormat(
-            "sockudo:auth requires data.token".to_string(),
-        ));
+        return Err(Error::InvalidMessageFormat(format!(
+            "{event} requires data.token"
+        )));
     };
 
     let value = match data {
         MessageData::Json(value) => value.clone(),
-        MessageData::String(text) => sonic_rs::from_str::<Value>(text).map_err(|_| {
-            Error::InvalidMessageFormat("sockudo:auth data must be JSON".to_string())
-        })?,
+        MessageData::String(text) => sonic_rs::from_str::<Value>(text)
+            .map_err(|_| Error::InvalidMessageFormat(format!("{event} data must be JSON")))?,
         MessageData::Structured { .. } => {
-            return Err(Error::InvalidMessageFormat(
-                "sockudo:auth data must contain token".to_string(),
-            ));
+            return Err(Error::InvalidMessageFormat(format!(
+                "{event} data must contain token"
+            )));
         }
     };
 
@@ -359,5 +428,5 @@ fn token_from_message(message: &PusherMessage) -> Result<String> {
         .get("token")
         .and_then(Value::as_str)
         .map(ToOwned::to_owned)
-        .ok_or_else(|| Error::InvalidMessageFormat("sockudo:auth requires data.token".to_string()))
+        .ok_or_else(|| Error::InvalidMessageFormat(format!("{event} requires data.token")))
 }
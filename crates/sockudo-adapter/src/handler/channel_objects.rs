@@ -0,0 +1,207 @@
+use super::ConnectionHandler;
+use sockudo_core::app::App;
+use sockudo_core::channel_objects::{ObjectOperation, ObjectOperationBatch};
+use sockudo_core::error::{Error, Result};
+use sockudo_core::history::now_ms;
+use sockudo_core::websocket::SocketId;
+use sockudo_protocol::ProtocolVersion;
+use sockudo_protocol::messages::{
+    MessageData, OBJECT_EVENT_NAME, OBJECT_SNAPSHOT_EVENT_NAME, PusherMessage,
+};
+use sonic_rs::{JsonValueTrait, Value};
+
+#[derive(Debug)]
+struct ObjectFrameRequest {
+    channel: String,
+    operations: Vec<ObjectOperation>,
+}
+
+impl ConnectionHandler {
+    /// Applies a batch of object operations to `channel` and fans the applied batch out to its
+    /// subscribers as `sockudo_internal:object`. Returns the serial the store assigned.
+    pub async fn apply_channel_object_operations(
+        &self,
+        app: &App,
+        channel: &str,
+        operations: Vec<ObjectOperation>,
+        client_id: Option<String>,
+    ) -> Result<u64> {
+        let config = &self.server_options().channel_objects;
+        let max_operations = config.max_operations_per_request;
+        if operations.len() > max_operations {
+            return Err(Error::InvalidMessageFormat(format!(
+                "At most {max_operations} object operations are allowed per request"
+            )));
+        }
+
+        let serial = self
+            .channel_object_store()
+            .apply_operations(&app.id, channel, &operations, &config.limits())
+            .await?;
+
+        let batch = ObjectOperationBatch {
+            serial,
+            client_id,
+            timestamp: now_ms(),
+            operations,
+        };
+        let message = object_message(OBJECT_EVENT_NAME, channel, sonic_rs::to_value(&batch)?);
+        if let Err(err) = self
+            .broadcast_to_channel_force_full(app, channel, message, None, None)
+            .await
+        {
+            tracing::error!(
+                channel = %channel,
+                serial,
+                error = %err,
+                "channel object operations failed to fan out through cluster broadcast"
+            );
+            return Err(err);
+        }
+
+        Ok(serial)
+    }
+
+    /// Handles a `sockudo:object` frame from a V2 socket subscribed to the target channel.
+    pub(crate) async fn handle_object_operation_request(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        message: &PusherMessage,
+    ) -> Result<()> {
+        let connection = self
+            .connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+            .ok_or(Error::ConnectionNotFound)?;
+
+        if connection.protocol_version != ProtocolVersion::V2 {
+            return Err(Error::Protocol(
+                "object operations are only supported on protocol V2".to_string(),
+            ));
+        }
+        let request = parse_object_frame(message)?;
+        if !self.server_options().channel_objects.enabled {
+            return Err(Error::Channel(format!(
+                "Channel objects are disabled globally for channel '{}'",
+                request.channel
+            )));
+        }
+
+        self.verify_channel_subscription(socket_id, app_config, &request.channel)
+            .await?;
+        self.validate_v2_capability(socket_id, app_config, &request.channel, "object_publish")
+            .await?;
+
+        self.apply_channel_object_operations(
+            app_config,
+            &request.channel,
+            request.operations,
+            connection.get_user_id().await,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Sends the channel's current objects to a V2 socket that just attached. Channels without
+    /// objects get no frame.
+    pub(crate) async fn send_channel_object_snapshot(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        channel: &str,
+    ) -> Result<()> {
+        if !self.server_options().channel_objects.enabled {
+            return Ok(());
+        }
+        let Some(connection) = self
+            .connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+        else {
+            return Ok(());
+        };
+        if connection.protocol_version != ProtocolVersion::V2 {
+            return Ok(());
+        }
+
+        let snapshot = self
+            .channel_object_store()
+            .snapshot(&app_config.id, channel)
+            .await?;
+        if snapshot.is_empty() {
+            return Ok(());
+        }
+
+        let message = object_message(
+            OBJECT_SNAPSHOT_EVENT_NAME,
+            channel,
+            sonic_rs::to_value(&snapshot)?,
+        );
+        self.send_message_to_socket(&app_config.id, socket_id, message)
+            .await
+    }
+}
+
+fn object_message(event: &str, channel: &str, data: Value) -> PusherMessage {
+    PusherMessage {
+        event: Some(event.to_string()),
+        channel: Some(channel.to_string()),
+        data: Some(MessageData::Json(data)),
+        name: None,
+        user_id: None,
+        tags: None,
+        sequence: None,
+        conflation_key: None,
+        message_id: None,
+        stream_id: None,
+        serial: None,
+        idempotency_key: None,
+        extras: None,
+        delta_sequence: None,
+        delta_conflation_key: None,
+    }
+}
+
+fn parse_object_frame(message: &PusherMessage) -> Result<ObjectFrameRequest> {
+    let (root, structured_channel): (Value, Option<String>) = match &message.data {
+        Some(MessageData::String(value)) => (
+            sonic_rs::from_str(value).map_err(|e| {
+                Error::InvalidMessageFormat(format!("Invalid object data JSON: {e}"))
+            })?,
+            None,
+        ),
+        Some(MessageData::Json(value)) => (value.clone(), None),
+        Some(MessageData::Structured { channel, extra, .. }) => (
+            sonic_rs::to_value(extra)
+                .map_err(|e| Error::InvalidMessageFormat(format!("Invalid object data: {e}")))?,
+            channel.clone(),
+        ),
+        None => {
+            return Err(Error::InvalidMessageFormat(
+                "Missing data in object message".to_string(),
+            ));
+        }
+    };
+
+    let channel = structured_channel
+        .or_else(|| {
+            root.get("channel")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .or_else(|| message.channel.clone())
+        .ok_or_else(|| {
+            Error::InvalidMessageFormat("Missing channel in object message".to_string())
+        })?;
+    let operations = root.get("operations").ok_or_else(|| {
+        Error::InvalidMessageFormat("Missing operations in object message".to_string())
+    })?;
+    let operations = sonic_rs::from_value(operations)
+        .map_err(|e| Error::InvalidMessageFormat(format!("Invalid object operations: {e}")))?;
+
+    Ok(ObjectFrameRequest {
+        channel,
+        operations,
+    })
+}
@@ -0,0 +1,244 @@
+// src/adapter/handler/connect_proxy.rs
+use super::ConnectionHandler;
+use crate::channel_manager::ChannelManager;
+use crate::handler::types::SubscriptionRequest;
+use serde::{Deserialize, Serialize};
+use sockudo_core::app::App;
+use sockudo_core::channel::ChannelType;
+use sockudo_core::error::{Error, Result};
+use sockudo_core::websocket::{ConnectionCapabilities, SocketId};
+use sockudo_protocol::ProtocolVersion;
+use sockudo_protocol::messages::PusherMessage;
+use sonic_rs::{Value, json};
+use std::collections::BTreeMap;
+use tracing::{debug, warn};
+
+/// What a client presented when it connected. Forwarded to the app's connect proxy.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConnectContext {
+    /// `websocket`, `sse`, `long_polling` or `mqtt`.
+    pub transport: String,
+    /// Request headers by lowercase name; repeated headers are joined with `, `.
+    pub headers: BTreeMap<String, String>,
+    pub origin: Option<String>,
+    /// Query string of the connect request, without the leading `?`.
+    pub query: Option<String>,
+    pub remote_addr: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ConnectProxyRequest<'a> {
+    app_id: &'a str,
+    socket_id: String,
+    protocol: u8,
+    transport: &'a str,
+    headers: &'a BTreeMap<String, String>,
+    origin: Option<&'a str>,
+    query: Option<&'a str>,
+    remote_addr: Option<&'a str>,
+}
+
+/// The backend's verdict on a new connection.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ConnectProxyResponse {
+    pub allow: bool,
+    /// Signs the connection in as this user, as if it had completed `pusher:signin`.
+    pub user_id: Option<String>,
+    pub user_info: Option<Value>,
+    pub capabilities: Option<ConnectionCapabilities>,
+    /// Channels the connection is subscribed to once it is established.
+    pub channels: Vec<String>,
+    /// Sent to the client when the connection is denied.
+    pub reason: Option<String>,
+}
+
+/// What an allowed connection receives after `connection_established`.
+#[derive(Debug, Default)]
+pub(crate) struct ConnectGrant {
+    user_data: Option<String>,
+    user_id: Option<String>,
+    user_info: Option<Value>,
+    channels: Vec<String>,
+}
+
+impl ConnectionHandler {
+    /// Asks the app's connect proxy whether the connection may proceed and signs it in as the
+    /// returned user. Returns `None` when the app has no connect proxy or the backend failed
+    /// and the endpoint is fail-open.
+    ///
+    /// Connections that already authenticated with a capability token keep that identity; only
+    /// the verdict and the channels of the answer apply to them.
+    pub(crate) async fn authorize_connect(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        protocol_version: ProtocolVersion,
+        context: &ConnectContext,
+    ) -> Result<Option<ConnectGrant>> {
+        let Some(endpoint) = app_config.connect_proxy() else {
+            return Ok(None);
+        };
+
+        let request = ConnectProxyRequest {
+            app_id: &app_config.id,
+            socket_id: socket_id.to_string(),
+            protocol: protocol_version as u8,
+            transport: &context.transport,
+            headers: &context.headers,
+            origin: context.origin.as_deref(),
+            query: context.query.as_deref(),
+            remote_addr: context.remote_addr.as_deref(),
+        };
+        let response: ConnectProxyResponse = match self
+            .proxy
+            .call(app_config, "connect", endpoint, &request)
+            .await
+        {
+            Ok(response) => response,
+            Err(_) if endpoint.fail_open => {
+                debug!(%socket_id, "connect proxy unavailable, admitting connection");
+                return Ok(None);
+            }
+            Err(_) => {
+                self.mark_connect_proxy_rejection(app_config, "connect_proxy_unavailable");
+                return Err(Error::Auth(
+                    "connection could not be authorized".to_string(),
+                ));
+            }
+        };
+
+        if !response.allow {
+            self.mark_connect_proxy_rejection(app_config, "connect_proxy_denied");
+            return Err(Error::Auth(
+                response
+                    .reason
+                    .unwrap_or_else(|| "connection denied".to_string()),
+            ));
+        }
+
+        let mut grant = ConnectGrant {
+            channels: response.channels,
+            ..Default::default()
+        };
+        let Some(user_id) = response.user_id else {
+            return Ok(Some(grant));
+        };
+
+        let token_authenticated = match self
+            .connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+        {
+            Some(connection) => connection.get_token_auth_context().await.is_some(),
+            None => return Err(Error::ConnectionNotFound),
+        };
+        if token_authenticated {
+            debug!(%socket_id, "ignoring connect proxy user for token-authenticated connection");
+            return Ok(Some(grant));
+        }
+
+        let mut user_data = json!({ "id": user_id.as_str() });
+        if let Some(info) = response.user_info.as_ref() {
+            user_data["user_info"] = info.clone();
+        }
+        if let Some(capabilities) = response.capabilities.as_ref() {
+            user_data["capabilities"] = sonic_rs::to_value(capabilities)?;
+        }
+        let user_data = sonic_rs::to_string(&user_data)?;
+        let user_info = self.parse_and_validate_user_data(&user_data)?;
+        self.update_connection_with_user_info(socket_id, app_config, &user_info)
+            .await?;
+
+        grant.user_data = Some(user_data);
+        grant.user_id = Some(user_id);
+        grant.user_info = response.user_info;
+        Ok(Some(grant))
+    }
+
+    /// Announces the proxy-assigned user and joins the granted channels. A channel that cannot
+    /// be joined is reported to the client like a failed `pusher:subscribe`.
+    pub(crate) async fn apply_connect_grant(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        grant: ConnectGrant,
+    ) -> Result<()> {
+        if let Some(user_data) = grant.user_data {
+            self.connection_manager
+                .send_message(
+                    &app_config.id,
+                    socket_id,
+                    PusherMessage::signin_success(user_data),
+                )
+                .await?;
+        }
+
+        for channel in grant.channels {
+            let result = match self.signed_subscription(
+                socket_id,
+                app_config,
+                &channel,
+                grant.user_id.as_deref(),
+                grant.user_info.as_ref(),
+            ) {
+                Ok(request) => {
+                    self.handle_subscribe_request(socket_id, app_config, request)
+                        .await
+                }
+                Err(error) => Err(error),
+            };
+            if let Err(error) = result {
+                warn!(%socket_id, channel, error = %error, "connect proxy subscription failed");
+                if error.is_fatal() || matches!(error, Error::ConnectionClosed(_)) {
+                    return Err(error);
+                }
+                self.send_error(&app_config.id, socket_id, &error, Some(channel))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds a subscription for a channel the backend granted, signed with the app secret like
+    /// the backend's own `/auth` endpoint would sign it.
+    pub(crate) fn signed_subscription(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        channel: &str,
+        user_id: Option<&str>,
+        user_info: Option<&Value>,
+    ) -> Result<SubscriptionRequest> {
+        let mut data = json!({ "channel": channel });
+        if ChannelType::from_name(channel) == ChannelType::Presence {
+            let Some(user_id) = user_id else {
+                return Err(Error::Auth(
+                    "presence channels granted by the connect proxy require a user_id".to_string(),
+                ));
+            };
+            let mut member = json!({ "user_id": user_id });
+            if let Some(info) = user_info {
+                member["user_info"] = info.clone();
+            }
+            data["channel_data"] = Value::from(sonic_rs::to_string(&member)?.as_str());
+        }
+        if ChannelType::from_name(channel).requires_authentication() {
+            let unsigned: PusherMessage = sonic_rs::from_value(
+                &json!({ "event": "pusher:subscribe", "data": data.clone() }),
+            )?;
+            data["auth"] = Value::from(
+                ChannelManager::get_expected_signature(app_config, socket_id, unsigned).as_str(),
+            );
+        }
+        let message: PusherMessage =
+            sonic_rs::from_value(&json!({ "event": "pusher:subscribe", "data": data }))?;
+        SubscriptionRequest::from_message(&message, &self.server_options.event_name_filtering)
+    }
+
+    fn mark_connect_proxy_rejection(&self, app_config: &App, error_type: &str) {
+        if let Some(ref metrics) = self.metrics {
+            metrics.mark_connection_error(&app_config.id, error_type);
+        }
+    }
+}
@@ -57,7 +57,8 @@ fn sanitize_v2_feature_flags(
             && extras.ephemeral.is_none()
             && extras.idempotency_key.is_none()
             && extras.echo.is_none()
-            && extras.ai.is_none();
+            && extras.ai.is_none()
+            && extras.priority.is_none();
         if extras_empty {
             message.extras = None;
         }
@@ -338,9 +339,10 @@ impl ConnectionHandler {
 
         if !message.is_ephemeral() {
             #[cfg(feature = "recovery")]
-            let recovery_enabled = app_config
-                .resolved_connection_recovery(&self.server_options().connection_recovery)
-                .enabled;
+            let recovery_policy = app_config
+                .resolved_connection_recovery(channel, &self.server_options().connection_recovery);
+            #[cfg(feature = "recovery")]
+            let recovery_enabled = recovery_policy.enabled;
             #[cfg(not(feature = "recovery"))]
             let recovery_enabled = false;
 
@@ -532,12 +534,18 @@ impl ConnectionHandler {
 
                 #[cfg(feature = "recovery")]
                 if recovery_enabled && let Some(ref replay_buffer) = self.replay_buffer {
-                    replay_buffer.store(
+                    replay_buffer.store_with_limits(
                         &app_config.id,
                         channel,
                         message.stream_id.as_deref(),
                         message.serial.unwrap_or(0),
                         serialized.clone(),
+                        crate::replay_buffer::ReplayLimits {
+                            max_buffer_size: recovery_policy.max_buffer_size,
+                            buffer_ttl: std::time::Duration::from_secs(
+                                recovery_policy.buffer_ttl_seconds,
+                            ),
+                        },
                     );
                 }
 
@@ -869,25 +877,27 @@ impl ConnectionHandler {
     /// - Exact channel name match (e.g., "market-data")
     /// - Wildcard patterns (e.g., "market-*" matches "market-btc", "market-eth")
     /// - Prefix patterns (e.g., "private-*")
+    /// - The channel namespace's `delta_compression`
     fn get_channel_delta_settings(
         app_config: &App,
         channel: &str,
     ) -> Option<sockudo_delta::ChannelDeltaSettings> {
-        let channel_delta_map = app_config.channel_delta_compression_ref()?;
-
-        // First try exact match
-        if let Some(config) = channel_delta_map.get(channel) {
-            return Self::convert_channel_config_to_settings(config);
-        }
-
-        // Try pattern matching for wildcard patterns
-        for (pattern, config) in channel_delta_map.iter() {
-            if Self::matches_pattern(channel, pattern) {
+        if let Some(channel_delta_map) = app_config.channel_delta_compression_ref() {
+            // First try exact match
+            if let Some(config) = channel_delta_map.get(channel) {
                 return Self::convert_channel_config_to_settings(config);
             }
+
+            // Try pattern matching for wildcard patterns
+            for (pattern, config) in channel_delta_map.iter() {
+                if Self::matches_pattern(channel, pattern) {
+                    return Self::convert_channel_config_to_settings(config);
+                }
+            }
         }
 
-        None
+        // Fall back to the channel's namespace
+        app_config.resolved_channel_delta(channel)
     }
 
     #[cfg(feature = "delta")]
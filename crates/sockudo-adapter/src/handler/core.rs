@@ -16,6 +16,27 @@ use std::sync::atomic::Ordering;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, error, info, warn};
 
+/// Upper bound on the conflation keys a cache channel keeps a latest message for. Expired keys
+/// still count until a subscriber reads the channel or the whole hash expires.
+const MAX_CACHED_CONFLATION_KEYS: usize = 1000;
+
+fn channel_cache_key(app_id: &str, channel: &str) -> String {
+    format!("app:{app_id}:channel:{channel}:cache_miss")
+}
+
+/// Hash of the latest message per conflation key. Fields hold `{expires_at_ms}:{message}` since
+/// they share the key's TTL, which every write resets.
+fn keyed_cache_key(app_id: &str, channel: &str) -> String {
+    format!("app:{app_id}:channel:{channel}:cache_miss:keyed")
+}
+
+fn keyed_cache_expiry_ms(ttl_seconds: u64) -> i64 {
+    if ttl_seconds == 0 {
+        return 0;
+    }
+    sockudo_core::history::now_ms().saturating_add(ttl_seconds.saturating_mul(1000) as i64)
+}
+
 impl ConnectionHandler {
     pub async fn send_connection_established(
         &self,
@@ -36,6 +57,16 @@ impl ConnectionHandler {
         error: &Error,
         channel: Option<String>,
     ) -> Result<()> {
+        if let Error::ChannelSubscriberLimit(channel) = error {
+            let message = PusherMessage::subscription_error(
+                channel.clone(),
+                u32::from(error.close_code()),
+                error.to_string(),
+            );
+            return self
+                .send_message_to_socket(app_id, socket_id, message)
+                .await;
+        }
         let error_data = ErrorData {
             message: error.to_string(),
             code: Some(u32::from(error.close_code())),
@@ -54,7 +85,18 @@ impl ConnectionHandler {
     ) -> Result<()> {
         // Extract channel name from message
         let channel_name = self.extract_channel_from_unsubscribe_message(message)?;
+        self.unsubscribe_channel(socket_id, app_config, channel_name)
+            .await
+    }
 
+    /// Removes the socket from `channel_name` with the same bookkeeping as a client
+    /// `pusher:unsubscribe`: presence removal, metrics and subscription count events.
+    pub(crate) async fn unsubscribe_channel(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        channel_name: String,
+    ) -> Result<()> {
         // Get user ID before unsubscribing (for presence channels)
         let user_id = self.get_user_id_for_socket(socket_id, app_config).await?;
 
@@ -1027,14 +1069,37 @@ impl ConnectionHandler {
 
     pub async fn send_missed_cache_if_exists(
         &self,
-        app_id: &str,
+        app: &App,
         socket_id: &SocketId,
         channel: &str,
     ) -> Result<()> {
-        let cache_key = format!("app:{app_id}:channel:{channel}:cache_miss");
+        let app_id = app.id.as_str();
+        let cache_key = channel_cache_key(app_id, channel);
+
+        // Replay the latest message of every conflation key first, so a late joiner sees the
+        // current state of each key rather than only whichever key published last.
+        let keyed_messages = self.keyed_cache_messages(app_id, channel).await;
+        for (_, cache_message) in &keyed_messages {
+            self.send_message_to_socket(app_id, socket_id, cache_message.clone())
+                .await?;
+        }
 
         match self.cache_manager.get(&cache_key).await {
             Ok(Some(cache_content)) => {
+                // The keyed replay already covers the last message unless its key was not kept.
+                if let Some(conflation_key) =
+                    self.cache_conflation_key(app, channel, cache_content.as_bytes())
+                    && keyed_messages.iter().any(|(key, _)| *key == conflation_key)
+                {
+                    info!(
+                        "Sent {} keyed cache entries to socket {} for channel {}",
+                        keyed_messages.len(),
+                        socket_id,
+                        channel
+                    );
+                    return Ok(());
+                }
+
                 // Found cached content, send it to the socket
                 let cache_message: PusherMessage =
                     sonic_rs::from_str(&cache_content).map_err(|e| {
@@ -1048,6 +1113,14 @@ impl ConnectionHandler {
                     socket_id, channel
                 );
             }
+            Ok(None) if !keyed_messages.is_empty() => {
+                info!(
+                    "Sent {} keyed cache entries to socket {} for channel {}",
+                    keyed_messages.len(),
+                    socket_id,
+                    channel
+                );
+            }
             Ok(None) => {
                 // No cache found, send cache miss event
                 let cache_miss_message = PusherMessage::cache_miss_event(channel.to_string());
@@ -1056,11 +1129,8 @@ impl ConnectionHandler {
                     .await?;
 
                 // Send cache miss webhook if configured
-                if let Some(app_config) = self.app_manager.find_by_id(app_id).await?
-                    && let Some(webhook_integration) = &self.webhook_integration
-                    && let Err(e) = webhook_integration
-                        .send_cache_missed(&app_config, channel)
-                        .await
+                if let Some(webhook_integration) = &self.webhook_integration
+                    && let Err(e) = webhook_integration.send_cache_missed(app, channel).await
                 {
                     warn!(
                         "Failed to send cache_missed webhook for channel {}: {}",
@@ -1094,43 +1164,173 @@ impl ConnectionHandler {
     /// Store a message in cache for a channel
     pub async fn store_cache_for_channel(
         &self,
-        app_id: &str,
+        app: &App,
         channel: &str,
         message: &PusherMessage,
         ttl_seconds: Option<u64>,
     ) -> Result<()> {
-        let cache_key = format!("app:{app_id}:channel:{channel}:cache_miss");
-
         let message_json = sonic_rs::to_string(message).map_err(|e| {
             Error::InvalidMessageFormat(format!("Failed to serialize message for cache: {e}"))
         })?;
 
-        match ttl_seconds {
-            Some(ttl) => {
-                self.cache_manager
-                    .set(&cache_key, &message_json, ttl)
-                    .await
-                    .map_err(|e| Error::Internal(format!("Failed to store cache with TTL: {e}")))?;
+        self.store_cache_payload_for_channel(app, channel, &message_json, ttl_seconds.unwrap_or(0))
+            .await
+    }
+
+    /// Store an already serialized message in cache for a channel. When the channel resolves a
+    /// conflation key path, the message is also kept as the latest one for its key.
+    pub async fn store_cache_payload_for_channel(
+        &self,
+        app: &App,
+        channel: &str,
+        payload: &str,
+        ttl_seconds: u64,
+    ) -> Result<()> {
+        let cache_key = channel_cache_key(&app.id, channel);
+
+        self.cache_manager
+            .set(&cache_key, payload, ttl_seconds)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to store cache: {e}")))?;
+
+        if let Some(conflation_key) = self.cache_conflation_key(app, channel, payload.as_bytes()) {
+            self.store_keyed_cache_payload(&app.id, channel, &conflation_key, payload, ttl_seconds)
+                .await?;
+        }
+
+        debug!("Stored cache for channel {} in app {}", channel, app.id);
+        Ok(())
+    }
+
+    async fn store_keyed_cache_payload(
+        &self,
+        app_id: &str,
+        channel: &str,
+        conflation_key: &str,
+        payload: &str,
+        ttl_seconds: u64,
+    ) -> Result<()> {
+        let key = keyed_cache_key(app_id, channel);
+        let entry = format!("{}:{payload}", keyed_cache_expiry_ms(ttl_seconds));
+        let added = self
+            .cache_manager
+            .hash_set(&key, conflation_key, &entry, ttl_seconds)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to store keyed cache: {e}")))?;
+
+        // Counting after the write keeps two nodes from both slipping a key in under the cap.
+        if added
+            && self
+                .cache_manager
+                .hash_len(&key)
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to count keyed cache: {e}")))?
+                > MAX_CACHED_CONFLATION_KEYS
+        {
+            warn!(
+                "Channel {} in app {} already caches {} conflation keys, not caching key '{}'",
+                channel, app_id, MAX_CACHED_CONFLATION_KEYS, conflation_key
+            );
+            self.cache_manager
+                .hash_remove(&key, &[conflation_key.to_string()])
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to store keyed cache: {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// The latest message of every conflation key, ordered by key. Entries past their TTL are
+    /// skipped and removed here rather than on the publish path.
+    async fn keyed_cache_messages(
+        &self,
+        app_id: &str,
+        channel: &str,
+    ) -> Vec<(String, PusherMessage)> {
+        let key = keyed_cache_key(app_id, channel);
+        let mut entries = match self.cache_manager.hash_get_all(&key).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to read keyed cache for channel {}: {}", channel, e);
+                return Vec::new();
             }
-            None => {
-                self.cache_manager
-                    .set(&cache_key, &message_json, 0)
-                    .await
-                    .map_err(|e| Error::Internal(format!("Failed to store cache: {e}")))?;
+        };
+        entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        let now_ms = sockudo_core::history::now_ms();
+        let mut expired = Vec::new();
+        let mut messages = Vec::with_capacity(entries.len());
+        for (conflation_key, entry) in entries {
+            let Some((expires_at_ms, content)) = entry
+                .split_once(':')
+                .and_then(|(expiry, content)| Some((expiry.parse::<i64>().ok()?, content)))
+            else {
+                warn!(
+                    "Invalid keyed cache entry '{}' for channel {}",
+                    conflation_key, channel
+                );
+                expired.push(conflation_key);
+                continue;
+            };
+            if expires_at_ms != 0 && expires_at_ms <= now_ms {
+                expired.push(conflation_key);
+                continue;
+            }
+            match sonic_rs::from_str(content) {
+                Ok(message) => messages.push((conflation_key, message)),
+                Err(e) => warn!(
+                    "Invalid keyed cache entry '{}' for channel {}: {}",
+                    conflation_key, channel, e
+                ),
             }
         }
 
-        debug!("Stored cache for channel {} in app {}", channel, app_id);
-        Ok(())
+        if !expired.is_empty()
+            && let Err(e) = self.cache_manager.hash_remove(&key, &expired).await
+        {
+            warn!("Failed to prune keyed cache for channel {}: {}", channel, e);
+        }
+        messages
+    }
+
+    #[cfg(feature = "delta")]
+    fn cache_conflation_key(&self, app: &App, channel: &str, payload: &[u8]) -> Option<String> {
+        let channel_settings = app.resolved_channel_delta(channel);
+        let path = channel_settings
+            .as_ref()
+            .and_then(|s| s.conflation_key.as_ref())
+            .or(self.delta_compression.get_conflation_key_path())?;
+        let key = self
+            .delta_compression
+            .extract_conflation_key_from_path(payload, path);
+        (!key.is_empty()).then_some(key)
+    }
+
+    #[cfg(not(feature = "delta"))]
+    fn cache_conflation_key(&self, _app: &App, _channel: &str, _payload: &[u8]) -> Option<String> {
+        None
     }
 
     /// Clear cache for a specific channel
     pub async fn clear_cache_for_channel(&self, app_id: &str, channel: &str) -> Result<()> {
-        let cache_key = format!("app:{app_id}:channel:{channel}:cache_miss");
-
-        self.cache_manager.remove(&cache_key).await.map_err(|e| {
-            Error::Internal(format!("Failed to clear cache for channel {channel}: {e}"))
-        })?;
+        let cache_key = channel_cache_key(app_id, channel);
+        let keyed_key = keyed_cache_key(app_id, channel);
+        let clear_error =
+            |e: Error| Error::Internal(format!("Failed to clear cache for channel {channel}: {e}"));
+        if self
+            .cache_manager
+            .has(&keyed_key)
+            .await
+            .map_err(clear_error)?
+        {
+            self.cache_manager
+                .remove(&keyed_key)
+                .await
+                .map_err(clear_error)?;
+        }
+        self.cache_manager
+            .remove(&cache_key)
+            .await
+            .map_err(clear_error)?;
 
         debug!("Cleared cache for channel {} in app {}", channel, app_id);
         Ok(())
@@ -1138,7 +1338,7 @@ impl ConnectionHandler {
 
     /// Check if a channel has cached content
     pub async fn has_cache_for_channel(&self, app_id: &str, channel: &str) -> Result<bool> {
-        let cache_key = format!("app:{app_id}:channel:{channel}:cache_miss");
+        let cache_key = channel_cache_key(app_id, channel);
 
         match self.cache_manager.get(&cache_key).await {
             Ok(Some(_)) => Ok(true),
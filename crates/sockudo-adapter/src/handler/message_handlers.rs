@@ -164,7 +164,7 @@ impl ConnectionHandler {
         &self,
         socket_id: &SocketId,
         app_config: &App,
-        request: SubscriptionRequest,
+        mut request: SubscriptionRequest,
     ) -> Result<()> {
         let t_start = std::time::Instant::now();
 
@@ -176,10 +176,18 @@ impl ConnectionHandler {
 
         // Check authentication if required
         let t_before_auth = t_start.elapsed().as_micros();
-        let is_authenticated = match self
-            .verify_channel_authentication(app_config, socket_id, &request)
+        let auth_result = match self
+            .authorize_subscription_via_proxy(socket_id, app_config, &mut request)
             .await
         {
+            Ok(Some(authenticated)) => Ok(authenticated),
+            Ok(None) => {
+                self.verify_channel_authentication(app_config, socket_id, &request)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let is_authenticated = match auth_result {
             Ok(authenticated) => authenticated,
             Err(e) => {
                 // Track authentication failures
@@ -208,6 +216,10 @@ impl ConnectionHandler {
             .get_connection(socket_id, &app_config.id)
             .await
             .ok_or(Error::ConnectionNotFound)?;
+        if !connection.is_subscribed_to(&request.channel).await {
+            self.enforce_channel_subscriber_limit(app_config, &request.channel)
+                .await?;
+        }
         let captures_attach_serial = connection.protocol_version
             == sockudo_protocol::ProtocolVersion::V2
             && app_config
@@ -333,6 +345,9 @@ impl ConnectionHandler {
         self.send_signin_success(socket_id, app_config, &request)
             .await?;
 
+        // Join the personal user channel, if the app configures one
+        self.join_user_channel(socket_id, app_config).await?;
+
         Ok(())
     }
 
@@ -352,6 +367,10 @@ impl ConnectionHandler {
 
         // Rate limit check is handled in the main message handler
 
+        let request = self
+            .review_client_event_via_proxy(socket_id, app_config, request)
+            .await?;
+
         // Send the event
         let message = PusherMessage {
             channel: Some(request.channel.clone()),
@@ -5,19 +5,26 @@ pub mod ai_orphans;
 pub mod annotations;
 pub mod auth_tokens;
 pub mod authentication;
+pub mod channel_objects;
+pub mod connect_proxy;
 pub mod connection_management;
 mod core;
 mod history_frames;
 pub mod message_handlers;
 pub mod origin_validation;
 pub mod presence_update;
+pub mod publish_proxy;
 pub mod rate_limiting;
 #[cfg(feature = "recovery")]
 pub mod recovery;
+mod rpc;
+mod server_subscriptions;
 pub mod signin_management;
 pub mod subscription_management;
 pub mod timeout_management;
+pub mod transport_session;
 pub mod types;
+mod user_connection_limits;
 pub mod validation;
 pub mod webhook_management;
 
@@ -30,6 +37,7 @@ use sockudo_core::annotations::{AnnotationStore, MemoryAnnotationStore};
 use sockudo_core::app::App;
 use sockudo_core::app::AppManager;
 use sockudo_core::cache::CacheManager;
+use sockudo_core::channel_objects::{ChannelObjectStore, MemoryChannelObjectStore};
 use sockudo_core::error::{Error, Result};
 use sockudo_core::history::{HistoryStore, NoopHistoryStore};
 use sockudo_core::metrics::MetricsInterface;
@@ -37,16 +45,17 @@ use sockudo_core::options::ServerOptions;
 use sockudo_core::presence_history::{NoopPresenceHistoryStore, PresenceHistoryStore};
 use sockudo_core::rate_limiter::RateLimiter;
 use sockudo_core::version_store::{NoopVersionStore, VersionStore};
-use sockudo_core::websocket::SocketId;
+use sockudo_core::websocket::{SocketId, TransportWriter};
 use sockudo_protocol::constants::CLIENT_EVENT_PREFIX;
 use sockudo_protocol::messages::{MessageData, PusherMessage, is_ai_event};
 use sockudo_protocol::{ProtocolVersion, WireFormat};
 use sockudo_webhook::WebhookIntegration;
 
+use crate::handler::connect_proxy::ConnectContext;
 use crate::handler::types::{ClientEventRequest, SignInRequest, SubscriptionRequest};
 use dashmap::DashMap;
 use sockudo_ws::Message;
-use sockudo_ws::axum_integration::{WebSocket, WebSocketReader, WebSocketWriter};
+use sockudo_ws::axum_integration::{WebSocket, WebSocketReader};
 use sonic_rs::Value;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize};
@@ -69,14 +78,20 @@ pub struct ConnectionHandler {
     pub(crate) metrics: Option<Arc<dyn MetricsInterface + Send + Sync>>,
     pub(crate) history_store: Arc<dyn HistoryStore + Send + Sync>,
     pub(crate) annotation_store: Arc<dyn AnnotationStore + Send + Sync>,
+    pub(crate) channel_object_store: Arc<dyn ChannelObjectStore + Send + Sync>,
     pub(crate) version_store: Arc<dyn VersionStore + Send + Sync>,
     #[cfg(feature = "ai-transport")]
     pub(crate) ai_rollup_engine: Option<Arc<RollupEngine>>,
     #[cfg(feature = "ai-transport")]
     pub(crate) ai_observability_tracker: Option<Arc<AiObservabilityTracker>>,
     pub(crate) presence_history_store: Arc<dyn PresenceHistoryStore + Send + Sync>,
+    pub(crate) jwks: crate::jwks::JwksCache,
+    pub(crate) proxy: crate::proxy::ProxyClient,
     webhook_integration: Option<Arc<WebhookIntegration>>,
     client_event_limiters: Arc<FastDashMap<SocketId, SharedRateLimiter>>,
+    /// Limiters for namespaces with their own client event rate, keyed by app, namespace and
+    /// rate; each counts per socket.
+    namespace_client_event_limiters: Arc<FastDashMap<String, SharedRateLimiter>>,
     message_limiters: Arc<FastDashMap<SocketId, SharedRateLimiter>>,
     presence_update_limiters: Arc<FastDashMap<SocketId, SharedRateLimiter>>,
     history_request_limits: Arc<FastDashMap<SocketId, Arc<Semaphore>>>,
@@ -106,6 +121,7 @@ pub struct ConnectionHandlerBuilder {
     metrics: Option<Arc<dyn MetricsInterface + Send + Sync>>,
     history_store: Option<Arc<dyn HistoryStore + Send + Sync>>,
     annotation_store: Option<Arc<dyn AnnotationStore + Send + Sync>>,
+    channel_object_store: Option<Arc<dyn ChannelObjectStore + Send + Sync>>,
     version_store: Option<Arc<dyn VersionStore + Send + Sync>>,
     presence_history_store: Option<Arc<dyn PresenceHistoryStore + Send + Sync>>,
     webhook_integration: Option<Arc<WebhookIntegration>>,
@@ -131,6 +147,7 @@ impl ConnectionHandlerBuilder {
             metrics: None,
             history_store: None,
             annotation_store: None,
+            channel_object_store: None,
             version_store: None,
             presence_history_store: None,
             webhook_integration: None,
@@ -165,6 +182,14 @@ impl ConnectionHandlerBuilder {
         self
     }
 
+    pub fn channel_object_store(
+        mut self,
+        channel_object_store: Arc<dyn ChannelObjectStore + Send + Sync>,
+    ) -> Self {
+        self.channel_object_store = Some(channel_object_store);
+        self
+    }
+
     pub fn version_store(mut self, version_store: Arc<dyn VersionStore + Send + Sync>) -> Self {
         self.version_store = Some(version_store);
         self
@@ -241,13 +266,19 @@ impl ConnectionHandlerBuilder {
         #[cfg(feature = "ai-transport")]
         if let Some(engine) = ai_rollup_engine.as_ref() {
             start_ai_rollup_worker(
-                Arc::clone(engine),
+                engine,
                 Arc::clone(&self.connection_manager),
                 self.metrics.clone(),
                 self.server_options.ai_transport.rollup.wheel_tick_ms,
             );
         }
 
+        let jwks = crate::jwks::JwksCache::new(
+            Arc::clone(&self.cache_manager),
+            self.server_options.jwks.clone(),
+        );
+        let proxy = crate::proxy::ProxyClient::new(self.metrics.clone());
+
         let handler = ConnectionHandler {
             app_manager: self.app_manager,
             connection_manager: self.connection_manager,
@@ -260,6 +291,9 @@ impl ConnectionHandlerBuilder {
             annotation_store: self
                 .annotation_store
                 .unwrap_or_else(|| Arc::new(MemoryAnnotationStore::new())),
+            channel_object_store: self
+                .channel_object_store
+                .unwrap_or_else(|| Arc::new(MemoryChannelObjectStore::new())),
             version_store: self
                 .version_store
                 .unwrap_or_else(|| Arc::new(NoopVersionStore)),
@@ -274,8 +308,11 @@ impl ConnectionHandlerBuilder {
             presence_history_store: self
                 .presence_history_store
                 .unwrap_or_else(|| Arc::new(NoopPresenceHistoryStore)),
+            jwks,
+            proxy,
             webhook_integration: self.webhook_integration,
             client_event_limiters: Arc::new(fast_dashmap()),
+            namespace_client_event_limiters: Arc::new(fast_dashmap()),
             message_limiters: Arc::new(fast_dashmap()),
             presence_update_limiters: Arc::new(fast_dashmap()),
             history_request_limits: Arc::new(fast_dashmap()),
@@ -313,58 +350,55 @@ impl ConnectionHandlerBuilder {
 
 #[cfg(feature = "ai-transport")]
 fn start_ai_rollup_worker(
-    engine: Arc<RollupEngine>,
+    engine: &Arc<RollupEngine>,
     connection_manager: Arc<dyn ConnectionManager + Send + Sync>,
     metrics: Option<Arc<dyn MetricsInterface + Send + Sync>>,
     wheel_tick_ms: u64,
 ) {
-    let Ok(handle) = tokio::runtime::Handle::try_current() else {
-        return;
-    };
-    let tick_ms = wheel_tick_ms.max(1);
-    handle.spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_millis(tick_ms));
-        loop {
-            interval.tick().await;
-            let now_ms = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|duration| duration.as_millis().min(u128::from(u64::MAX)) as u64)
-                .unwrap_or_default();
-            let mut deliveries = engine.flush_due(now_ms);
-            deliveries.extend(engine.sweep_orphans(now_ms));
-            for delivery in deliveries {
-                if let Some(metrics) = metrics.as_ref() {
-                    metrics.mark_ai_rollup_append_delivered(&delivery.app_id);
-                    metrics.observe_ai_rollup_ratio(&delivery.app_id, delivery.coalesced as f64);
-                    metrics.observe_ai_rollup_flush_latency(
-                        &delivery.app_id,
-                        delivery.latency_ms as f64,
-                    );
-                    metrics.update_ai_rollup_active_streams(
-                        &delivery.app_id,
-                        engine.active_streams() as u64,
-                    );
-                }
-                if let Err(error) = connection_manager
-                    .send(
-                        &delivery.channel,
-                        delivery.message,
-                        None,
-                        &delivery.app_id,
-                        None,
-                    )
-                    .await
-                {
-                    warn!(
-                        app_id = %delivery.app_id,
-                        channel = %delivery.channel,
-                        error = %error,
-                        "failed to flush AI append rollup delivery"
-                    );
+    sockudo_core::deadline_wheel::spawn_deadline_wheel(
+        Arc::downgrade(engine),
+        wheel_tick_ms,
+        move |engine, now_ms| {
+            let connection_manager = Arc::clone(&connection_manager);
+            let metrics = metrics.clone();
+            async move {
+                let mut deliveries = engine.flush_due(now_ms);
+                deliveries.extend(engine.sweep_orphans(now_ms));
+                for delivery in deliveries {
+                    if let Some(metrics) = metrics.as_ref() {
+                        metrics.mark_ai_rollup_append_delivered(&delivery.app_id);
+                        metrics
+                            .observe_ai_rollup_ratio(&delivery.app_id, delivery.coalesced as f64);
+                        metrics.observe_ai_rollup_flush_latency(
+                            &delivery.app_id,
+                            delivery.latency_ms as f64,
+                        );
+                        metrics.update_ai_rollup_active_streams(
+                            &delivery.app_id,
+                            engine.active_streams() as u64,
+                        );
+                    }
+                    if let Err(error) = connection_manager
+                        .send(
+                            &delivery.channel,
+                            delivery.message,
+                            None,
+                            &delivery.app_id,
+                            None,
+                        )
+                        .await
+                    {
+                        warn!(
+                            app_id = %delivery.app_id,
+                            channel = %delivery.channel,
+                            error = %error,
+                            "failed to flush AI append rollup delivery"
+                        );
+                    }
                 }
             }
-        }
-    });
+        },
+    );
 }
 
 #[cfg(feature = "ai-transport")]
@@ -443,6 +477,10 @@ impl ConnectionHandler {
         &self.annotation_store
     }
 
+    pub fn channel_object_store(&self) -> &Arc<dyn ChannelObjectStore + Send + Sync> {
+        &self.channel_object_store
+    }
+
     pub fn version_store(&self) -> &Arc<dyn VersionStore + Send + Sync> {
         &self.version_store
     }
@@ -466,23 +504,10 @@ impl ConnectionHandler {
         wire_format: WireFormat,
         echo_messages: bool,
         initial_token: Option<String>,
+        connect_context: ConnectContext,
     ) -> Result<()> {
         // Early validation and setup
-        let app_config = match self.validate_and_get_app(&app_key).await {
-            Ok(app) => app,
-            Err(e) => {
-                // Track application validation errors
-                if let Some(ref metrics) = self.metrics {
-                    let error_type = match &e {
-                        Error::ApplicationNotFound => "app_not_found",
-                        Error::ApplicationDisabled => "app_disabled",
-                        _ => "app_validation_failed",
-                    };
-                    metrics.mark_connection_error(&app_key, error_type);
-                }
-                return Err(e);
-            }
-        };
+        let app_config = self.validate_app_for_connection(&app_key).await?;
 
         // Origin validation will happen after WebSocket upgrade to allow error message sending
         let (socket_rx, mut socket_tx) = socket.split();
@@ -561,7 +586,7 @@ impl ConnectionHandler {
         let socket_id = SocketId::new();
         self.initialize_socket_with_quota_check(
             socket_id,
-            socket_tx,
+            socket_tx.into(),
             &app_config,
             protocol_version,
             wire_format,
@@ -571,55 +596,16 @@ impl ConnectionHandler {
 
         // Wrapped in an async block so any early return still reaches cleanup_socket below.
         let result = async {
-            // Setup rate limiting if needed
-            self.setup_rate_limiting(&socket_id, &app_config).await?;
-            self.setup_message_rate_limiting(&socket_id, &app_config)
-                .await?;
-
-            if let Some(token) = initial_token.as_deref() {
-                let auth_result = async {
-                    if protocol_version != ProtocolVersion::V2 {
-                        return Err(Error::Auth(
-                            "capability tokens require protocol V2".to_string(),
-                        ));
-                    }
-                    let context = self.validate_connection_token(&app_config, token).await?;
-                    self.apply_connection_token(&socket_id, &app_config, context, false)
-                        .await
-                }
-                .await;
-
-                if let Err(error) = auth_result {
-                    let _ = self
-                        .send_error(&app_config.id, &socket_id, &error, None)
-                        .await;
-                    let _ = self
-                        .close_connection(
-                            &socket_id,
-                            &app_config,
-                            error.close_code(),
-                            &error.to_string(),
-                        )
-                        .await;
-                    return Err(error);
-                }
-            }
-
-            // Get the cancellation token so the reader loop can be cancelled
-            // when the connection is cleaned up (e.g., ghost connection timeout)
             let shutdown_token = self
-                .connection_manager
-                .get_connection(&socket_id, &app_config.id)
-                .await
-                .map(|conn| conn.cancellation_token());
-
-            // Send connection established
-            self.send_connection_established(&app_config.id, &socket_id)
+                .prepare_connection(
+                    &socket_id,
+                    &app_config,
+                    protocol_version,
+                    initial_token.as_deref(),
+                    Some(&connect_context),
+                )
                 .await?;
 
-            // Setup timeouts
-            self.setup_initial_timeouts(&socket_id, &app_config).await?;
-
             // Main message loop
             self.run_message_loop(socket_rx, &socket_id, &app_config, shutdown_token)
                 .await
@@ -631,10 +617,90 @@ impl ConnectionHandler {
         result
     }
 
-    async fn initialize_socket_with_quota_check(
+    /// Runs the post-registration steps shared by every transport: rate limiters, the optional
+    /// capability token, the app's connect proxy, `connection_established` and the initial
+    /// timeouts. Connections opened without a `connect_context` skip the connect proxy.
+    ///
+    /// Returns the connection's cancellation token so the caller's read loop can stop when the
+    /// connection is cleaned up elsewhere (e.g., ghost connection timeout).
+    pub(crate) async fn prepare_connection(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        protocol_version: ProtocolVersion,
+        initial_token: Option<&str>,
+        connect_context: Option<&ConnectContext>,
+    ) -> Result<Option<tokio_util::sync::CancellationToken>> {
+        // Setup rate limiting if needed
+        self.setup_rate_limiting(socket_id, app_config).await?;
+        self.setup_message_rate_limiting(socket_id, app_config)
+            .await?;
+
+        let auth_result = async {
+            if let Some(token) = initial_token {
+                if protocol_version != ProtocolVersion::V2 {
+                    return Err(Error::Auth(
+                        "capability tokens require protocol V2".to_string(),
+                    ));
+                }
+                let context = self.validate_connection_token(app_config, token).await?;
+                self.apply_connection_token(socket_id, app_config, context, false)
+                    .await?;
+            }
+            match connect_context {
+                Some(context) => {
+                    self.authorize_connect(socket_id, app_config, protocol_version, context)
+                        .await
+                }
+                None => Ok(None),
+            }
+        }
+        .await;
+
+        let connect_grant = match auth_result {
+            Ok(grant) => grant,
+            Err(error) => {
+                let _ = self
+                    .send_error(&app_config.id, socket_id, &error, None)
+                    .await;
+                let _ = self
+                    .close_connection(
+                        socket_id,
+                        app_config,
+                        error.close_code(),
+                        &error.to_string(),
+                    )
+                    .await;
+                return Err(error);
+            }
+        };
+
+        let shutdown_token = self
+            .connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+            .map(|conn| conn.cancellation_token());
+
+        // Send connection established
+        self.send_connection_established(&app_config.id, socket_id)
+            .await?;
+
+        if let Some(grant) = connect_grant {
+            self.apply_connect_grant(socket_id, app_config, grant)
+                .await?;
+        }
+        self.join_user_channel(socket_id, app_config).await?;
+
+        // Setup timeouts
+        self.setup_initial_timeouts(socket_id, app_config).await?;
+
+        Ok(shutdown_token)
+    }
+
+    pub(crate) async fn initialize_socket_with_quota_check(
         &self,
         socket_id: SocketId,
-        socket_tx: WebSocketWriter,
+        socket_tx: TransportWriter,
         app_config: &App,
         protocol_version: ProtocolVersion,
         wire_format: WireFormat,
@@ -704,6 +770,21 @@ impl ConnectionHandler {
         Ok(())
     }
 
+    /// Looks up the app for a new connection, recording a connection error metric on failure.
+    pub(crate) async fn validate_app_for_connection(&self, app_key: &str) -> Result<App> {
+        self.validate_and_get_app(app_key).await.inspect_err(|e| {
+            // Track application validation errors
+            if let Some(ref metrics) = self.metrics {
+                let error_type = match e {
+                    Error::ApplicationNotFound => "app_not_found",
+                    Error::ApplicationDisabled => "app_disabled",
+                    _ => "app_validation_failed",
+                };
+                metrics.mark_connection_error(app_key, error_type);
+            }
+        })
+    }
+
     async fn validate_and_get_app(&self, app_key: &str) -> Result<App> {
         match self.app_manager.find_by_key(app_key).await {
             Ok(Some(app)) if app.enabled => Ok(app),
@@ -756,29 +837,11 @@ impl ConnectionHandler {
                     break;
                 }
                 Message::Text(_) | Message::Binary(_) => {
-                    if let Err(e) = self
-                        .handle_message(message, socket_id, app_config.clone())
-                        .await
+                    if !self
+                        .process_inbound_frame(message, socket_id, app_config)
+                        .await?
                     {
-                        if e.is_fatal() {
-                            error!("Message handling error for socket {}: {}", socket_id, e);
-                            self.handle_fatal_error(socket_id, app_config, &e).await?;
-                            break;
-                        } else if matches!(&e, Error::ConnectionClosed(_)) {
-                            debug!("Message handling error for socket {}: {}", socket_id, e);
-                            break;
-                        } else {
-                            error!("Message handling error for socket {}: {}", socket_id, e);
-                            // Send pusher:error for non-fatal errors
-                            if let Err(send_err) =
-                                self.send_error(&app_config.id, socket_id, &e, None).await
-                            {
-                                error!(
-                                    "Failed to send error to socket {}: {}",
-                                    socket_id, send_err
-                                );
-                            }
-                        }
+                        break;
                     }
                 }
                 _ => {
@@ -790,6 +853,58 @@ impl ConnectionHandler {
         Ok(())
     }
 
+    /// Handles one inbound data frame and reports whether the connection should stay open.
+    ///
+    /// Fatal errors close the connection; other errors are reported back as `pusher:error`.
+    pub(crate) async fn process_inbound_frame(
+        &self,
+        message: Message,
+        socket_id: &SocketId,
+        app_config: &App,
+    ) -> Result<bool> {
+        match self
+            .dispatch_inbound_frame(message, socket_id, app_config)
+            .await
+        {
+            Err(e) => {
+                error!("Message handling error for socket {}: {}", socket_id, e);
+                // Send pusher:error for non-fatal errors
+                if let Err(send_err) = self.send_error(&app_config.id, socket_id, &e, None).await {
+                    error!("Failed to send error to socket {}: {}", socket_id, send_err);
+                }
+                Ok(true)
+            }
+            open => open,
+        }
+    }
+
+    /// Like [`Self::process_inbound_frame`], but hands non-fatal errors back to the caller
+    /// instead of reporting them to the client.
+    pub(crate) async fn dispatch_inbound_frame(
+        &self,
+        message: Message,
+        socket_id: &SocketId,
+        app_config: &App,
+    ) -> Result<bool> {
+        let Err(e) = self
+            .handle_message(message, socket_id, app_config.clone())
+            .await
+        else {
+            return Ok(true);
+        };
+
+        if e.is_fatal() {
+            error!("Message handling error for socket {}: {}", socket_id, e);
+            self.handle_fatal_error(socket_id, app_config, &e).await?;
+            Ok(false)
+        } else if matches!(&e, Error::ConnectionClosed(_)) {
+            debug!("Message handling error for socket {}: {}", socket_id, e);
+            Ok(false)
+        } else {
+            Err(e)
+        }
+    }
+
     async fn handle_message(
         &self,
         message: Message,
@@ -861,8 +976,13 @@ impl ConnectionHandler {
 
         // Handle rate limiting for client events
         if event_name.starts_with(CLIENT_EVENT_PREFIX) {
-            self.check_client_event_rate_limit(socket_id, &app_config, event_name)
-                .await?;
+            self.check_client_event_rate_limit(
+                socket_id,
+                &app_config,
+                parsed.channel.as_deref(),
+                event_name,
+            )
+            .await?;
         }
 
         // Route message to appropriate handler using canonical event names.
@@ -898,8 +1018,8 @@ impl ConnectionHandler {
                 self.handle_signin_request(socket_id, &app_config, request)
                     .await
             }
-            Some((CANONICAL_AUTH, _)) => {
-                self.handle_auth_token_refresh(socket_id, &app_config, &parsed)
+            Some((event @ (CANONICAL_AUTH | CANONICAL_REFRESH), _)) => {
+                self.handle_token_refresh(socket_id, &app_config, &parsed, event)
                     .await
             }
             Some((CANONICAL_PONG, _)) => self.handle_pong(&app_config.id, socket_id).await,
@@ -919,6 +1039,14 @@ impl ConnectionHandler {
                 self.handle_channel_history_request(socket_id, &app_config, &parsed)
                     .await
             }
+            Some((CANONICAL_RPC, false)) => {
+                self.handle_rpc_request(socket_id, &app_config, &parsed)
+                    .await
+            }
+            Some((CANONICAL_OBJECT, false)) => {
+                self.handle_object_operation_request(socket_id, &app_config, &parsed)
+                    .await
+            }
             Some((CANONICAL_PRESENCE_UPDATE, false)) => {
                 self.handle_presence_update(socket_id, &app_config, &parsed)
                     .await
@@ -1022,7 +1150,7 @@ impl ConnectionHandler {
         Ok(())
     }
 
-    async fn cleanup_socket(&self, socket_id: &SocketId, app_config: &App) {
+    pub(crate) async fn cleanup_socket(&self, socket_id: &SocketId, app_config: &App) {
         // Remove rate limiters
         self.client_event_limiters.remove(socket_id);
         self.message_limiters.remove(socket_id);
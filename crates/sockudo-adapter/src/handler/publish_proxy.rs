@@ -0,0 +1,104 @@
+// src/adapter/handler/publish_proxy.rs
+use super::ConnectionHandler;
+use super::types::ClientEventRequest;
+use serde::{Deserialize, Serialize};
+use sockudo_core::app::App;
+use sockudo_core::error::{Error, Result};
+use sockudo_core::websocket::SocketId;
+use sonic_rs::Value;
+use tracing::debug;
+
+#[derive(Serialize)]
+struct PublishProxyRequest<'a> {
+    app_id: &'a str,
+    socket_id: String,
+    channel: &'a str,
+    event: &'a str,
+    data: &'a Value,
+    user_id: Option<String>,
+}
+
+/// The backend's verdict on a client event.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PublishProxyResponse {
+    pub allow: bool,
+    /// Replaces the event name; must still be a `client-` event.
+    pub event: Option<String>,
+    /// Replaces the event payload.
+    pub data: Option<Value>,
+    /// Sent to the client when the event is rejected.
+    pub reason: Option<String>,
+}
+
+impl ConnectionHandler {
+    /// Passes a client event through the namespace's publish proxy before it is broadcast.
+    /// Returns the event unchanged when the channel has no publish proxy or the backend
+    /// failed and the endpoint is fail-open, and the rewritten event otherwise.
+    ///
+    /// A rewritten event goes through client event validation again, so the backend cannot
+    /// turn it into a reserved event or push it past the payload limit.
+    pub(crate) async fn review_client_event_via_proxy(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        request: ClientEventRequest,
+    ) -> Result<ClientEventRequest> {
+        let Some(endpoint) = app_config.publish_proxy_for_channel(&request.channel) else {
+            return Ok(request);
+        };
+
+        let user_id = match self
+            .connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+        {
+            Some(connection) => connection.get_user_id().await,
+            None => return Err(Error::ConnectionNotFound),
+        };
+        let proxy_request = PublishProxyRequest {
+            app_id: &app_config.id,
+            socket_id: socket_id.to_string(),
+            channel: &request.channel,
+            event: &request.event,
+            data: &request.data,
+            user_id,
+        };
+        let response: PublishProxyResponse = match self
+            .proxy
+            .call(app_config, "publish", endpoint, &proxy_request)
+            .await
+        {
+            Ok(response) => response,
+            Err(_) if endpoint.fail_open => {
+                debug!(%socket_id, channel = %request.channel, "publish proxy unavailable, passing client event through");
+                return Ok(request);
+            }
+            Err(_) => {
+                return Err(Error::ClientEvent(
+                    "client event could not be validated".to_string(),
+                ));
+            }
+        };
+
+        if !response.allow {
+            return Err(Error::ClientEvent(
+                response
+                    .reason
+                    .unwrap_or_else(|| "client event rejected".to_string()),
+            ));
+        }
+        if response.event.is_none() && response.data.is_none() {
+            return Ok(request);
+        }
+
+        let rewritten = ClientEventRequest {
+            event: response.event.unwrap_or(request.event),
+            channel: request.channel,
+            data: response.data.unwrap_or(request.data),
+        };
+        self.validate_client_event(socket_id, app_config, &rewritten)
+            .await?;
+        Ok(rewritten)
+    }
+}
@@ -4,6 +4,7 @@ use crate::memory_rate_limiter::MemoryRateLimiter;
 use sockudo_core::app::App;
 use sockudo_core::error::{Error, Result};
 use sockudo_core::rate_limiter::RateLimiter;
+use sockudo_core::utils;
 use sockudo_core::websocket::SocketId;
 use std::sync::Arc;
 use tracing::{debug, warn};
@@ -88,8 +89,24 @@ impl ConnectionHandler {
         &self,
         socket_id: &SocketId,
         app_config: &App,
+        channel: Option<&str>,
         event_name: &str,
     ) -> Result<()> {
+        if let Some(channel) = channel {
+            let limits = app_config.resolved_channel_limits(channel);
+            if limits.namespace_client_event_rate {
+                return self
+                    .check_namespace_client_event_rate_limit(
+                        socket_id,
+                        app_config,
+                        channel,
+                        limits.max_client_events_per_second,
+                        event_name,
+                    )
+                    .await;
+            }
+        }
+
         if let Some(limiter_arc) = self.client_event_limiters.get(socket_id) {
             if let Some(ref metrics) = self.metrics {
                 metrics.mark_rate_limit_check(&app_config.id, "client_events");
@@ -123,4 +140,51 @@ impl ConnectionHandler {
 
         Ok(())
     }
+
+    /// Client event rate limit for channels whose namespace overrides
+    /// `max_client_events_per_second`. `0` disables the limit for the namespace.
+    async fn check_namespace_client_event_rate_limit(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        channel: &str,
+        max_events_per_second: u32,
+        event_name: &str,
+    ) -> Result<()> {
+        if max_events_per_second == 0 {
+            return Ok(());
+        }
+        let namespace = utils::channel_namespace_name(channel).unwrap_or_default();
+        let decay_seconds = app_config.client_event_decay_seconds();
+        let limiter = self
+            .namespace_client_event_limiters
+            .entry(format!(
+                "{}\0{namespace}\0{max_events_per_second}\0{decay_seconds}",
+                app_config.id
+            ))
+            .or_insert_with(|| {
+                Arc::new(MemoryRateLimiter::new(max_events_per_second, decay_seconds))
+            })
+            .clone();
+
+        if let Some(ref metrics) = self.metrics {
+            metrics.mark_rate_limit_check(&app_config.id, "client_events");
+        }
+        let limit_result = limiter.increment(&socket_id.to_string()).await?;
+        if limit_result.allowed {
+            return Ok(());
+        }
+
+        if let Some(ref metrics) = self.metrics {
+            metrics.mark_rate_limit_triggered(&app_config.id, "client_events");
+        }
+        warn!(
+            "Client event rate limit of namespace '{}' exceeded for socket {}: event '{}'",
+            namespace, socket_id, event_name
+        );
+        if app_config.terminate_on_limit() {
+            return Err(Error::ClientEventRateLimitTerminate);
+        }
+        Err(Error::ClientEventRateLimit)
+    }
 }
@@ -5,6 +5,7 @@ use sockudo_core::app::App;
 use sockudo_core::error::{Error, Result};
 use sockudo_core::history::{HistoryDirection, HistoryQueryBounds, HistoryReadRequest};
 use sockudo_core::websocket::SocketId;
+use sockudo_protocol::ProtocolVersion;
 use sockudo_protocol::messages::{MessageData, PusherMessage};
 use sonic_rs::json;
 use std::collections::HashMap;
@@ -205,7 +206,7 @@ impl ConnectionHandler {
             delta_sequence: None,
             delta_conflation_key: None,
         };
-        self.send_message_to_socket(&app_config.id, socket_id, success_msg)
+        self.send_after_replay(&app_config.id, socket_id, success_msg)
             .await?;
 
         Ok(())
@@ -562,6 +563,35 @@ impl ConnectionHandler {
         }
     }
 
+    /// Send a message queued behind the replayed bytes, so the client sees it only after
+    /// every recovered message.
+    async fn send_after_replay(
+        &self,
+        app_id: &str,
+        socket_id: &SocketId,
+        mut message: PusherMessage,
+    ) -> Result<()> {
+        let Some(conn) = self
+            .connection_manager
+            .get_connection(socket_id, app_id)
+            .await
+        else {
+            return Err(Error::ConnectionClosed(format!(
+                "Socket {} not found during replay",
+                socket_id
+            )));
+        };
+        if conn.protocol_version == ProtocolVersion::V2 {
+            message.rewrite_prefix(ProtocolVersion::V2);
+        }
+        let message_size = sonic_rs::to_string(&message).unwrap_or_default().len();
+        conn.send_message_after_broadcasts(&message)?;
+        if let Some(ref metrics) = self.metrics {
+            metrics.mark_ws_message_sent(app_id, message_size);
+        }
+        Ok(())
+    }
+
     async fn send_replayed_bytes(
         &self,
         socket_id: &SocketId,
@@ -0,0 +1,233 @@
+use super::ConnectionHandler;
+use serde::{Deserialize, Serialize};
+use sockudo_core::app::App;
+use sockudo_core::error::{Error, Result};
+use sockudo_core::websocket::{ConnectionCapabilities, SocketId};
+use sockudo_protocol::ProtocolVersion;
+use sockudo_protocol::messages::{MessageData, PusherMessage};
+use sockudo_protocol::protocol_version::CANONICAL_RPC_RESULT;
+use sonic_rs::{JsonValueTrait, Value, json};
+
+const MAX_RPC_ID_LENGTH: usize = 128;
+const MAX_RPC_METHOD_LENGTH: usize = 128;
+
+#[derive(Debug)]
+struct RpcFrameRequest {
+    id: String,
+    method: String,
+    payload: Value,
+}
+
+#[derive(Serialize)]
+struct RpcProxyRequest<'a> {
+    app_id: &'a str,
+    socket_id: String,
+    id: &'a str,
+    method: &'a str,
+    payload: &'a Value,
+    user_id: Option<String>,
+    capabilities: Option<ConnectionCapabilities>,
+}
+
+/// The backend's answer to an RPC call. `error` wins over `result` when both are present.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RpcProxyResponse {
+    result: Option<Value>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RpcError {
+    code: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: &str, message: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl ConnectionHandler {
+    /// Forwards a `sockudo:rpc` frame to the app's RPC backend and answers the calling socket
+    /// with `sockudo:rpc_result`. Backend failures are reported in the result frame under the
+    /// call's `id`; only malformed frames are connection errors.
+    pub(crate) async fn handle_rpc_request(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        message: &PusherMessage,
+    ) -> Result<()> {
+        let connection = self
+            .connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+            .ok_or(Error::ConnectionNotFound)?;
+
+        if connection.protocol_version != ProtocolVersion::V2 {
+            return Err(Error::Protocol(
+                "rpc is only supported on protocol V2".to_string(),
+            ));
+        }
+
+        let request = parse_rpc_frame(message)?;
+        let outcome = match app_config.rpc_proxy() {
+            None => Err(RpcError::new(
+                "not_configured",
+                "RPC is not enabled for this app",
+            )),
+            Some(endpoint) => {
+                let proxy_request = RpcProxyRequest {
+                    app_id: &app_config.id,
+                    socket_id: socket_id.to_string(),
+                    id: &request.id,
+                    method: &request.method,
+                    payload: &request.payload,
+                    user_id: connection.get_user_id().await,
+                    capabilities: connection.get_connection_capabilities().await,
+                };
+                match self
+                    .proxy
+                    .call::<_, RpcProxyResponse>(app_config, "rpc", endpoint, &proxy_request)
+                    .await
+                {
+                    Ok(RpcProxyResponse {
+                        error: Some(error), ..
+                    }) => Err(error),
+                    Ok(response) => Ok(response.result.unwrap_or_default()),
+                    Err(_) => Err(RpcError::new("unavailable", "RPC backend unavailable")),
+                }
+            }
+        };
+
+        let data = match outcome {
+            Ok(result) => json!({ "id": request.id, "result": result }),
+            Err(error) => json!({ "id": request.id, "error": sonic_rs::to_value(&error)? }),
+        };
+        self.send_message_to_socket(
+            &app_config.id,
+            socket_id,
+            PusherMessage {
+                event: Some(ProtocolVersion::V2.wire_event(CANONICAL_RPC_RESULT)),
+                channel: None,
+                data: Some(MessageData::Json(data)),
+                name: None,
+                user_id: None,
+                tags: None,
+                sequence: None,
+                conflation_key: None,
+                message_id: None,
+                stream_id: None,
+                serial: None,
+                idempotency_key: None,
+                extras: None,
+                delta_sequence: None,
+                delta_conflation_key: None,
+            },
+        )
+        .await
+    }
+}
+
+fn parse_rpc_frame(message: &PusherMessage) -> Result<RpcFrameRequest> {
+    let root = match &message.data {
+        Some(MessageData::String(value)) => sonic_rs::from_str(value)
+            .map_err(|e| Error::InvalidMessageFormat(format!("Invalid rpc data JSON: {e}")))?,
+        Some(MessageData::Json(value)) => value.clone(),
+        Some(MessageData::Structured { extra, .. }) => sonic_rs::to_value(extra)
+            .map_err(|e| Error::InvalidMessageFormat(format!("Invalid rpc data: {e}")))?,
+        None => {
+            return Err(Error::InvalidMessageFormat(
+                "Missing data in rpc message".to_string(),
+            ));
+        }
+    };
+
+    let id = root
+        .get("id")
+        .and_then(Value::as_str)
+        .filter(|id| !id.is_empty() && id.len() <= MAX_RPC_ID_LENGTH)
+        .ok_or_else(|| {
+            Error::InvalidMessageFormat(format!(
+                "rpc id must be a non-empty string of at most {MAX_RPC_ID_LENGTH} characters"
+            ))
+        })?;
+    let method = root
+        .get("method")
+        .and_then(Value::as_str)
+        .filter(|method| !method.is_empty() && method.len() <= MAX_RPC_METHOD_LENGTH)
+        .ok_or_else(|| {
+            Error::InvalidMessageFormat(format!(
+                "rpc method must be a non-empty string of at most {MAX_RPC_METHOD_LENGTH} characters"
+            ))
+        })?;
+
+    Ok(RpcFrameRequest {
+        id: id.to_string(),
+        method: method.to_string(),
+        payload: root.get("payload").cloned().unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc_message(data: Value) -> PusherMessage {
+        PusherMessage {
+            event: Some("sockudo:rpc".to_string()),
+            channel: None,
+            data: Some(MessageData::Json(data)),
+            name: None,
+            user_id: None,
+            tags: None,
+            sequence: None,
+            conflation_key: None,
+            message_id: None,
+            stream_id: None,
+            serial: None,
+            idempotency_key: None,
+            extras: None,
+            delta_sequence: None,
+            delta_conflation_key: None,
+        }
+    }
+
+    #[test]
+    fn rpc_frame_carries_id_method_and_payload() {
+        let request = parse_rpc_frame(&rpc_message(json!({
+            "id": "call-1",
+            "method": "mark_read",
+            "payload": { "message_id": 42 }
+        })))
+        .unwrap();
+
+        assert_eq!(request.id, "call-1");
+        assert_eq!(request.method, "mark_read");
+        assert_eq!(request.payload["message_id"].as_u64(), Some(42));
+
+        let request =
+            parse_rpc_frame(&rpc_message(json!({ "id": "call-2", "method": "ping" }))).unwrap();
+        assert!(request.payload.is_null());
+    }
+
+    #[test]
+    fn rpc_frame_requires_id_and_method() {
+        for data in [
+            json!({ "method": "mark_read" }),
+            json!({ "id": "", "method": "mark_read" }),
+            json!({ "id": "call-1" }),
+            json!({ "id": "call-1", "method": "x".repeat(MAX_RPC_METHOD_LENGTH + 1) }),
+        ] {
+            assert!(matches!(
+                parse_rpc_frame(&rpc_message(data)),
+                Err(Error::InvalidMessageFormat(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// src/adapter/handler/server_subscriptions.rs
+use super::ConnectionHandler;
+use crate::horizontal_adapter::SubscriptionChangeEvent;
+use sockudo_core::app::App;
+use sockudo_core::error::{Error, Result};
+use sockudo_core::websocket::{SocketId, WebSocketRef};
+use sockudo_protocol::messages::PusherMessage;
+use tracing::warn;
+
+impl ConnectionHandler {
+    /// Subscribes or unsubscribes the targeted socket, or every socket of the targeted user,
+    /// on this node and asks cluster peers to do the same for theirs. Returns how many sockets
+    /// matched across the cluster.
+    pub async fn change_subscriptions(
+        &self,
+        app_config: &App,
+        change: SubscriptionChangeEvent,
+    ) -> Result<usize> {
+        let local = self.apply_subscription_change(app_config, &change).await?;
+        let remote = self
+            .connection_manager
+            .request_subscription_change(&change)
+            .await?;
+        Ok(local + remote)
+    }
+
+    /// Applies a subscription change forwarded by a peer to this node's sockets.
+    pub async fn handle_subscription_change_event(
+        &self,
+        event: SubscriptionChangeEvent,
+    ) -> Result<()> {
+        let app_config = self
+            .app_manager
+            .find_by_id(&event.app_id)
+            .await?
+            .ok_or(Error::ApplicationNotFound)?;
+        self.apply_subscription_change(&app_config, &event).await?;
+        Ok(())
+    }
+
+    async fn apply_subscription_change(
+        &self,
+        app_config: &App,
+        change: &SubscriptionChangeEvent,
+    ) -> Result<usize> {
+        let connections = self.subscription_change_targets(app_config, change).await?;
+        for connection in &connections {
+            let socket_id = connection.socket_id;
+            let subscribed = connection.is_subscribed_to(&change.channel).await;
+            let result = match (change.subscribe, subscribed) {
+                (true, false) => {
+                    self.subscribe_from_server(&socket_id, app_config, connection, change)
+                        .await
+                }
+                (false, true) => {
+                    self.unsubscribe_from_server(&socket_id, app_config, &change.channel)
+                        .await
+                }
+                _ => Ok(()),
+            };
+            if let Err(error) = result {
+                warn!(%socket_id, channel = %change.channel, error = %error, "server-side subscription change failed");
+                if change.subscribe && !matches!(error, Error::ConnectionClosed(_)) {
+                    self.send_error(
+                        &app_config.id,
+                        &socket_id,
+                        &error,
+                        Some(change.channel.clone()),
+                    )
+                    .await
+                    .ok();
+                }
+            }
+        }
+        Ok(connections.len())
+    }
+
+    async fn subscription_change_targets(
+        &self,
+        app_config: &App,
+        change: &SubscriptionChangeEvent,
+    ) -> Result<Vec<WebSocketRef>> {
+        if let Some(socket_id) = &change.socket_id {
+            let socket_id =
+                SocketId::from_string(socket_id).map_err(Error::InvalidMessageFormat)?;
+            return Ok(self
+                .connection_manager
+                .get_connection(&socket_id, &app_config.id)
+                .await
+                .into_iter()
+                .collect());
+        }
+        match &change.user_id {
+            Some(user_id) => {
+                self.connection_manager
+                    .get_user_sockets(user_id, &app_config.id)
+                    .await
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Joins the channel as though the client had sent a signed `pusher:subscribe`, so the
+    /// client receives the usual `subscription_succeeded`.
+    async fn subscribe_from_server(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        connection: &WebSocketRef,
+        change: &SubscriptionChangeEvent,
+    ) -> Result<()> {
+        let user_id = match &change.user_id {
+            Some(user_id) => Some(user_id.clone()),
+            None => connection.get_user_id().await,
+        };
+        let request = self.signed_subscription(
+            socket_id,
+            app_config,
+            &change.channel,
+            user_id.as_deref(),
+            change.user_info.as_ref(),
+        )?;
+        self.handle_subscribe_request(socket_id, app_config, request)
+            .await
+    }
+
+    async fn unsubscribe_from_server(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        channel: &str,
+    ) -> Result<()> {
+        self.unsubscribe_channel(socket_id, app_config, channel.to_string())
+            .await?;
+        self.connection_manager
+            .send_message(
+                &app_config.id,
+                socket_id,
+                PusherMessage::unsubscribed(channel.to_string()),
+            )
+            .await
+    }
+}
@@ -73,10 +73,15 @@ impl ConnectionHandler {
             conn_locked.set_user_info(user_info.clone());
         }
 
-        // Re-add user to adapter's tracking (this updates user associations)
-        self.connection_manager
-            .add_user(connection_arc.clone())
-            .await?;
+        // Re-add user to adapter's tracking (this updates user associations), then apply the
+        // per-user connection limit with this connection counted.
+        self.register_user_connection(
+            &connection_arc,
+            app_config,
+            &user_info.id,
+            user_info.device_class(),
+        )
+        .await?;
 
         Ok(())
     }
@@ -174,6 +179,52 @@ impl ConnectionHandler {
             .await
     }
 
+    /// Subscribes a signed-in connection to its personal channel from `policy.user_channel`.
+    /// Failures are reported to the client like a failed `pusher:subscribe`.
+    pub(crate) async fn join_user_channel(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+    ) -> Result<()> {
+        if app_config.user_channel_ref().is_none() {
+            return Ok(());
+        }
+        let Some(connection) = self
+            .connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+        else {
+            return Err(Error::ConnectionNotFound);
+        };
+        let Some(user_id) = connection.get_user_id().await else {
+            return Ok(());
+        };
+        let Some(channel) = app_config.user_channel_for(&user_id) else {
+            return Ok(());
+        };
+        if connection.is_subscribed_to(&channel).await {
+            return Ok(());
+        }
+
+        let result =
+            match self.signed_subscription(socket_id, app_config, &channel, Some(&user_id), None) {
+                Ok(request) => {
+                    self.handle_subscribe_request(socket_id, app_config, request)
+                        .await
+                }
+                Err(error) => Err(error),
+            };
+        if let Err(error) = result {
+            warn!(%socket_id, channel, error = %error, "personal user channel subscription failed");
+            if error.is_fatal() || matches!(error, Error::ConnectionClosed(_)) {
+                return Err(error);
+            }
+            self.send_error(&app_config.id, socket_id, &error, Some(channel))
+                .await?;
+        }
+        Ok(())
+    }
+
     pub(crate) async fn get_watchers_for_user(
         &self,
         app_id: &str,
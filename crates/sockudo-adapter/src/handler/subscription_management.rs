@@ -4,10 +4,11 @@ use super::annotations::clipped_contributor_count;
 use super::types::*;
 use crate::channel_manager::ChannelManager;
 use crate::channel_manager::JoinResponse;
+use serde::{Deserialize, Serialize};
 use sockudo_core::annotations::AnnotationProjectionsForChannelRequest;
 use sockudo_core::app::App;
 use sockudo_core::channel::{ChannelType, PresenceMemberInfo};
-use sockudo_core::error::Result;
+use sockudo_core::error::{Error, Result};
 use sockudo_core::history::{HistoryDirection, HistoryItem, HistoryReadRequest, now_ms};
 #[cfg(feature = "delta")]
 use sockudo_delta::DeltaCompressionManager;
@@ -20,7 +21,7 @@ use sockudo_protocol::messages::{
     AnnotationSummaryEnvelope, MESSAGE_SUMMARY_EVENT_NAME, MessageData, MessageExtras,
     MessageSummaryData, PresenceData, PusherMessage,
 };
-use sonic_rs::{JsonValueMutTrait, Value};
+use sonic_rs::{JsonValueMutTrait, JsonValueTrait, Value};
 use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use std::time::Instant;
@@ -39,7 +40,149 @@ pub struct PresenceMember {
     pub user_info: Value,
 }
 
+#[derive(Serialize)]
+struct SubscribeProxyRequest<'a> {
+    app_id: &'a str,
+    socket_id: String,
+    channel: &'a str,
+    user_id: Option<String>,
+    /// Presence member data as sent by the client; unverified.
+    channel_data: Option<&'a str>,
+}
+
+/// The backend's verdict on a subscription.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SubscribeProxyResponse {
+    pub allow: bool,
+    /// Presence member data (`user_id`, `user_info`), as an object or a JSON string. Required
+    /// for presence channels; replaces whatever the client sent.
+    pub channel_data: Option<Value>,
+    /// Replaces the tag filter the client subscribed with.
+    pub tags_filter: Option<Value>,
+    /// Sent to the client when the subscription is denied.
+    pub reason: Option<String>,
+}
+
 impl ConnectionHandler {
+    /// Asks the namespace's subscribe proxy whether the subscription may proceed, applying the
+    /// channel data and tag filter it returns to `request`. Returns `None` when the channel has
+    /// no subscribe proxy or the backend failed and the endpoint is fail-open, in which case
+    /// the channel signature decides as usual.
+    pub(crate) async fn authorize_subscription_via_proxy(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        request: &mut SubscriptionRequest,
+    ) -> Result<Option<bool>> {
+        let Some(endpoint) = app_config.subscribe_proxy_for_channel(&request.channel) else {
+            return Ok(None);
+        };
+
+        let user_id = match self
+            .connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+        {
+            Some(connection) => connection.get_user_id().await,
+            None => return Err(Error::ConnectionNotFound),
+        };
+        let proxy_request = SubscribeProxyRequest {
+            app_id: &app_config.id,
+            socket_id: socket_id.to_string(),
+            channel: &request.channel,
+            user_id,
+            channel_data: request.channel_data.as_deref(),
+        };
+        let response: SubscribeProxyResponse = match self
+            .proxy
+            .call(app_config, "subscribe", endpoint, &proxy_request)
+            .await
+        {
+            Ok(response) => response,
+            Err(_) if endpoint.fail_open => {
+                tracing::debug!(%socket_id, channel = %request.channel, "subscribe proxy unavailable, falling back to channel auth");
+                return Ok(None);
+            }
+            Err(_) => {
+                return Err(Error::Auth(
+                    "subscription could not be authorized".to_string(),
+                ));
+            }
+        };
+
+        if !response.allow {
+            return Err(Error::Auth(
+                response
+                    .reason
+                    .unwrap_or_else(|| "subscription denied".to_string()),
+            ));
+        }
+
+        request.channel_data = match response.channel_data {
+            Some(data) => match data.as_str() {
+                Some(raw) => Some(raw.to_string()),
+                None => Some(sonic_rs::to_string(&data)?),
+            },
+            None => None,
+        };
+        if request.channel.starts_with("presence-") && request.channel_data.is_none() {
+            return Err(Error::Auth(
+                "subscribe proxy returned no channel_data for presence channel".to_string(),
+            ));
+        }
+
+        #[cfg(feature = "tag-filtering")]
+        if let Some(raw) = response.tags_filter.as_ref() {
+            request.tags_filter = Some(SubscriptionRequest::tags_filter_from_value(raw)?);
+        }
+
+        Ok(Some(true))
+    }
+
+    /// Rejects a socket joining `channel` once the channel holds `max_subscribers_per_channel`
+    /// sockets. The count covers every node and is read from the gossiped channel counts, so
+    /// a horizontal adapter must run with `adapter.aggregate_counts`.
+    pub(crate) async fn enforce_channel_subscriber_limit(
+        &self,
+        app_config: &App,
+        channel: &str,
+    ) -> Result<()> {
+        let Some(limit) = app_config
+            .resolved_channel_limits(channel)
+            .max_subscribers_per_channel
+        else {
+            return Ok(());
+        };
+        if !self.connection_manager.aggregates_channel_counts() {
+            // Apps loaded at runtime escape the startup check; refuse rather than ask every
+            // node on each subscribe or count only this node.
+            tracing::error!(
+                app_id = %app_config.id,
+                channel,
+                "max_subscribers_per_channel requires adapter.aggregate_counts with a horizontal adapter"
+            );
+            return Err(Error::Configuration(format!(
+                "Channel '{channel}' has a subscriber limit, which requires adapter.aggregate_counts"
+            )));
+        }
+        let subscribers = self
+            .connection_manager
+            .get_channel_socket_count(&app_config.id, channel)
+            .await;
+        if subscribers >= limit as usize {
+            tracing::warn!(
+                app_id = %app_config.id,
+                channel,
+                subscribers,
+                limit,
+                "rejecting subscription over the channel subscriber limit"
+            );
+            return Err(Error::ChannelSubscriberLimit(channel.to_string()));
+        }
+        Ok(())
+    }
+
     pub async fn execute_subscription(
         &self,
         socket_id: &SocketId,
@@ -378,7 +521,7 @@ impl ConnectionHandler {
 
         // Handle cache channels
         if is_cache_channel(&request.channel) {
-            self.send_missed_cache_if_exists(&app_config.id, socket_id, &request.channel)
+            self.send_missed_cache_if_exists(app_config, socket_id, &request.channel)
                 .await?;
         }
 
@@ -623,6 +766,7 @@ impl ConnectionHandler {
                         request.annotation_subscribe,
                     )
                     .await;
+                conn_arc.set_delivery_window(request.channel.clone(), request.delivery);
             }
 
             // Register with filter index for O(1) message routing (if local adapter is available).
@@ -671,6 +815,8 @@ impl ConnectionHandler {
 
         self.send_annotation_summary_snapshots(socket_id, app_config, &request.channel)
             .await?;
+        self.send_channel_object_snapshot(socket_id, app_config, &request.channel)
+            .await?;
 
         Ok(())
     }
@@ -799,8 +945,8 @@ impl ConnectionHandler {
         app_config: &App,
         channel: &str,
     ) -> Option<crate::replay_buffer::ReplayPosition> {
-        let recovery_policy =
-            app_config.resolved_connection_recovery(&self.server_options().connection_recovery);
+        let recovery_policy = app_config
+            .resolved_connection_recovery(channel, &self.server_options().connection_recovery);
         if !recovery_policy.enabled {
             return None;
         }
@@ -1107,17 +1253,7 @@ impl ConnectionHandler {
         }
 
         // Get channel-specific delta compression settings from app config
-        let channel_settings = app_config
-            .channel_delta_compression_ref()
-            .and_then(|map| map.get(channel))
-            .and_then(|config| {
-                // Convert ChannelDeltaConfig to ChannelDeltaSettings
-                use sockudo_delta::ChannelDeltaConfig;
-                match config {
-                    ChannelDeltaConfig::Full(settings) => Some(settings.clone()),
-                    _ => None,
-                }
-            });
+        let channel_settings = app_config.resolved_channel_delta(channel);
 
         // Only send cache sync if channel has conflation key configured
         let conflation_key = channel_settings
@@ -0,0 +1,145 @@
+// src/adapter/handler/transport_session.rs
+use super::ConnectionHandler;
+use crate::handler::connect_proxy::ConnectContext;
+use crate::handler::origin_validation::OriginValidator;
+use sockudo_core::app::App;
+use sockudo_core::error::{Error, Result};
+use sockudo_core::websocket::{SocketId, TransportWriter};
+use sockudo_protocol::messages::PusherMessage;
+use sockudo_protocol::{ProtocolVersion, WireFormat};
+use sockudo_ws::Message;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+/// Connection parameters for a transport that is not a WebSocket (SSE, long-polling, ...).
+#[derive(Debug, Clone)]
+pub struct TransportSessionOptions {
+    pub origin: Option<String>,
+    pub protocol_version: ProtocolVersion,
+    pub echo_messages: bool,
+    pub token: Option<String>,
+    /// Forwarded to the app's connect proxy. `None` skips the connect proxy, which is only
+    /// appropriate for sessions opened by an already authenticated backend.
+    pub connect_context: Option<ConnectContext>,
+}
+
+/// A connection registered with the adapter whose outbound frames are delivered over an
+/// in-process channel instead of a WebSocket writer.
+///
+/// The session behaves like any other socket: it counts against quotas, is reachable through the
+/// connection manager and receives broadcasts. The transport drains `receiver` and must call
+/// [`ConnectionHandler::close_transport_session`] once the client goes away.
+#[derive(Debug)]
+pub struct TransportSession {
+    pub socket_id: SocketId,
+    pub app: App,
+    pub receiver: mpsc::Receiver<Message>,
+    pub shutdown_token: Option<CancellationToken>,
+}
+
+impl ConnectionHandler {
+    /// Opens a channel-backed connection, running the same admission steps as a WebSocket
+    /// upgrade: app lookup, origin check, connection quota, rate limiters, optional capability
+    /// token, connect proxy, `connection_established` and the initial timeouts.
+    ///
+    /// Frames are always JSON encoded, since channel transports carry text.
+    pub async fn open_transport_session(
+        &self,
+        app_key: &str,
+        options: TransportSessionOptions,
+    ) -> Result<TransportSession> {
+        let app_config = self.validate_app_for_connection(app_key).await?;
+
+        if let Some(allowed_origins) = app_config.allowed_origins_ref()
+            && !allowed_origins.is_empty()
+            && !OriginValidator::validate_origin(
+                options.origin.as_deref().unwrap_or(""),
+                allowed_origins,
+            )
+        {
+            if let Some(ref metrics) = self.metrics {
+                metrics.mark_connection_error(&app_config.id, "origin_not_allowed");
+            }
+            return Err(Error::OriginNotAllowed);
+        }
+
+        let capacity = self
+            .server_options
+            .websocket
+            .to_buffer_config()
+            .channel_capacity();
+        let (writer, receiver) = TransportWriter::channel(capacity);
+
+        let socket_id = SocketId::new();
+        self.initialize_socket_with_quota_check(
+            socket_id,
+            writer,
+            &app_config,
+            options.protocol_version,
+            WireFormat::Json,
+            options.echo_messages,
+        )
+        .await?;
+
+        match self
+            .prepare_connection(
+                &socket_id,
+                &app_config,
+                options.protocol_version,
+                options.token.as_deref(),
+                options.connect_context.as_ref(),
+            )
+            .await
+        {
+            Ok(shutdown_token) => Ok(TransportSession {
+                socket_id,
+                app: app_config,
+                receiver,
+                shutdown_token,
+            }),
+            Err(e) => {
+                self.cleanup_socket(&socket_id, &app_config).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Feeds a client frame into a transport session as if it had arrived on a WebSocket.
+    ///
+    /// Returns `false` once the connection has been closed by the server.
+    pub async fn handle_transport_message(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        message: &PusherMessage,
+    ) -> Result<bool> {
+        let payload = sonic_rs::to_string(message)
+            .map_err(|e| Error::InvalidMessageFormat(format!("Serialization failed: {e}")))?;
+        self.process_inbound_frame(Message::text(payload), socket_id, app_config)
+            .await
+    }
+
+    /// Feeds a client frame into a transport session and returns the outcome of the request.
+    ///
+    /// Unlike [`Self::handle_transport_message`], non-fatal errors are returned instead of
+    /// being sent to the client as `pusher:error`, for transports that acknowledge each
+    /// request themselves (MQTT PUBACK, for instance).
+    pub async fn handle_transport_request(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        message: &PusherMessage,
+    ) -> Result<bool> {
+        let payload = sonic_rs::to_string(message)
+            .map_err(|e| Error::InvalidMessageFormat(format!("Serialization failed: {e}")))?;
+        self.dispatch_inbound_frame(Message::text(payload), socket_id, app_config)
+            .await
+    }
+
+    /// Tears down a transport session after its client has gone away.
+    pub async fn close_transport_session(&self, socket_id: &SocketId, app_config: &App) {
+        debug!("Closing transport session {}", socket_id);
+        self.cleanup_socket(socket_id, app_config).await;
+    }
+}
@@ -1,6 +1,7 @@
 use sockudo_core::channel::ChannelType;
 use sockudo_core::history::HistoryQueryBounds;
 use sockudo_core::options::EventNameFilteringConfig;
+use sockudo_core::websocket::DeliveryWindow;
 #[cfg(feature = "delta")]
 use sockudo_delta::DeltaAlgorithm;
 #[cfg(feature = "tag-filtering")]
@@ -154,6 +155,8 @@ pub struct SubscriptionRequest {
     pub event_name_filter: Option<Vec<String>>,
     /// V2 only. Enables raw annotation event delivery for this subscription.
     pub annotation_subscribe: bool,
+    /// V2 only. Batching or latest-only pacing for this subscription.
+    pub delivery: Option<DeliveryWindow>,
 }
 
 #[derive(Debug, Clone)]
@@ -174,119 +177,129 @@ impl SubscriptionRequest {
         message: &PusherMessage,
         event_name_filtering: &EventNameFilteringConfig,
     ) -> sockudo_core::error::Result<Self> {
-        let (channel, auth, channel_data, _tags_filter_raw, _delta_raw, rewind_raw, modes_raw) =
-            match &message.data {
-                Some(MessageData::Structured {
-                    channel,
-                    extra,
+        let (
+            channel,
+            auth,
+            channel_data,
+            _tags_filter_raw,
+            _delta_raw,
+            rewind_raw,
+            modes_raw,
+            delivery_raw,
+        ) = match &message.data {
+            Some(MessageData::Structured {
+                channel,
+                extra,
+                channel_data,
+                ..
+            }) => {
+                let ch = channel.as_ref().ok_or_else(|| {
+                    sockudo_core::error::Error::InvalidMessageFormat("Missing channel field".into())
+                })?;
+                let channel_data = if ChannelType::from_name(ch) == ChannelType::Presence {
+                    Some(channel_data.as_ref().unwrap().clone())
+                } else {
+                    None
+                };
+                let auth = extra.get("auth").and_then(Value::as_str).map(String::from);
+
+                // Accept both "filter" (client-side) and "tags_filter" (server-side) for compatibility
+                let tags_filter_raw: Option<&Value> =
+                    extra.get("filter").or_else(|| extra.get("tags_filter"));
+
+                // Parse per-subscription delta settings
+                let delta_raw: Option<&Value> = extra.get("delta");
+                let rewind_raw: Option<&Value> = extra.get("rewind");
+                let modes_raw: Option<&Value> = extra.get("modes");
+                let delivery_raw: Option<&Value> = extra.get("delivery");
+
+                (
+                    ch.clone(),
+                    auth,
                     channel_data,
-                    ..
-                }) => {
-                    let ch = channel.as_ref().ok_or_else(|| {
-                        sockudo_core::error::Error::InvalidMessageFormat(
-                            "Missing channel field".into(),
-                        )
-                    })?;
-                    let channel_data = if ChannelType::from_name(ch) == ChannelType::Presence {
-                        Some(channel_data.as_ref().unwrap().clone())
-                    } else {
-                        None
-                    };
-                    let auth = extra.get("auth").and_then(Value::as_str).map(String::from);
-
-                    // Accept both "filter" (client-side) and "tags_filter" (server-side) for compatibility
-                    let tags_filter_raw: Option<&Value> =
-                        extra.get("filter").or_else(|| extra.get("tags_filter"));
-
-                    // Parse per-subscription delta settings
-                    let delta_raw: Option<&Value> = extra.get("delta");
-                    let rewind_raw: Option<&Value> = extra.get("rewind");
-                    let modes_raw: Option<&Value> = extra.get("modes");
-
-                    (
-                        ch.clone(),
-                        auth,
-                        channel_data,
-                        tags_filter_raw.cloned(),
-                        delta_raw.cloned(),
-                        rewind_raw.cloned(),
-                        modes_raw.cloned(),
+                    tags_filter_raw.cloned(),
+                    delta_raw.cloned(),
+                    rewind_raw.cloned(),
+                    modes_raw.cloned(),
+                    delivery_raw.cloned(),
+                )
+            }
+            Some(MessageData::Json(data)) => {
+                let ch = data.get("channel").and_then(Value::as_str).ok_or_else(|| {
+                    sockudo_core::error::Error::InvalidMessageFormat("Missing channel field".into())
+                })?;
+                let auth = data.get("auth").and_then(Value::as_str).map(String::from);
+                let channel_data = data
+                    .get("channel_data")
+                    .and_then(Value::as_str)
+                    .map(String::from);
+
+                let tags_filter_raw = data
+                    .get("filter")
+                    .or_else(|| data.get("tags_filter"))
+                    .cloned();
+
+                let delta_raw = data.get("delta").cloned();
+                let rewind_raw = data.get("rewind").cloned();
+                let modes_raw = data.get("modes").cloned();
+                let delivery_raw = data.get("delivery").cloned();
+
+                return Self::build(
+                    ch.to_string(),
+                    auth,
+                    channel_data,
+                    tags_filter_raw,
+                    delta_raw,
+                    rewind_raw,
+                    modes_raw,
+                    delivery_raw,
+                    event_name_filtering,
+                );
+            }
+            Some(MessageData::String(s)) => {
+                let data: Value = sonic_rs::from_str(s).map_err(|_| {
+                    sockudo_core::error::Error::InvalidMessageFormat(
+                        "Failed to parse subscription data".into(),
                     )
-                }
-                Some(MessageData::Json(data)) => {
-                    let ch = data.get("channel").and_then(Value::as_str).ok_or_else(|| {
-                        sockudo_core::error::Error::InvalidMessageFormat(
-                            "Missing channel field".into(),
-                        )
-                    })?;
-                    let auth = data.get("auth").and_then(Value::as_str).map(String::from);
-                    let channel_data = data
-                        .get("channel_data")
-                        .and_then(Value::as_str)
-                        .map(String::from);
-
-                    let tags_filter_raw = data
-                        .get("filter")
-                        .or_else(|| data.get("tags_filter"))
-                        .cloned();
-
-                    let delta_raw = data.get("delta").cloned();
-                    let rewind_raw = data.get("rewind").cloned();
-                    let modes_raw = data.get("modes").cloned();
-
-                    return Self::build(
-                        ch.to_string(),
-                        auth,
-                        channel_data,
-                        tags_filter_raw,
-                        delta_raw,
-                        rewind_raw,
-                        modes_raw,
-                        event_name_filtering,
-                    );
-                }
-                Some(MessageData::String(s)) => {
-                    let data: Value = sonic_rs::from_str(s).map_err(|_| {
-                        sockudo_core::error::Error::InvalidMessageFormat(
-                            "Failed to parse subscription data".into(),
-                        )
-                    })?;
-                    let ch = data.get("channel").and_then(Value::as_str).ok_or_else(|| {
-                        sockudo_core::error::Error::InvalidMessageFormat(
-                            "Missing channel field in string data".into(),
-                        )
-                    })?;
-                    let auth = data.get("auth").and_then(Value::as_str).map(String::from);
-                    let channel_data = data
-                        .get("channel_data")
-                        .and_then(Value::as_str)
-                        .map(String::from);
-
-                    let tags_filter_raw = data
-                        .get("filter")
-                        .or_else(|| data.get("tags_filter"))
-                        .cloned();
-
-                    let delta_raw = data.get("delta").cloned();
-                    let rewind_raw = data.get("rewind").cloned();
-                    let modes_raw = data.get("modes").cloned();
-
-                    (
-                        ch.to_string(),
-                        auth,
-                        channel_data,
-                        tags_filter_raw,
-                        delta_raw,
-                        rewind_raw,
-                        modes_raw,
+                })?;
+                let ch = data.get("channel").and_then(Value::as_str).ok_or_else(|| {
+                    sockudo_core::error::Error::InvalidMessageFormat(
+                        "Missing channel field in string data".into(),
                     )
-                }
-                _ => {
-                    return Err(sockudo_core::error::Error::InvalidMessageFormat(
-                        "Invalid subscription data format".into(),
-                    ));
-                }
-            };
+                })?;
+                let auth = data.get("auth").and_then(Value::as_str).map(String::from);
+                let channel_data = data
+                    .get("channel_data")
+                    .and_then(Value::as_str)
+                    .map(String::from);
+
+                let tags_filter_raw = data
+                    .get("filter")
+                    .or_else(|| data.get("tags_filter"))
+                    .cloned();
+
+                let delta_raw = data.get("delta").cloned();
+                let rewind_raw = data.get("rewind").cloned();
+                let modes_raw = data.get("modes").cloned();
+                let delivery_raw = data.get("delivery").cloned();
+
+                (
+                    ch.to_string(),
+                    auth,
+                    channel_data,
+                    tags_filter_raw,
+                    delta_raw,
+                    rewind_raw,
+                    modes_raw,
+                    delivery_raw,
+                )
+            }
+            _ => {
+                return Err(sockudo_core::error::Error::InvalidMessageFormat(
+                    "Invalid subscription data format".into(),
+                ));
+            }
+        };
 
         Self::build(
             channel,
@@ -296,6 +309,7 @@ impl SubscriptionRequest {
             _delta_raw,
             rewind_raw,
             modes_raw,
+            delivery_raw,
             event_name_filtering,
         )
     }
@@ -309,6 +323,7 @@ impl SubscriptionRequest {
         #[allow(unused_variables)] delta_raw: Option<Value>,
         rewind_raw: Option<Value>,
         modes_raw: Option<Value>,
+        delivery_raw: Option<Value>,
         event_name_filtering: &EventNameFilteringConfig,
     ) -> sockudo_core::error::Result<Self> {
         // Extract event name filter from filter.events (V2 only).
@@ -373,6 +388,13 @@ impl SubscriptionRequest {
             None => None,
         };
         let annotation_subscribe = Self::parse_annotation_subscribe_mode(modes_raw.as_ref())?;
+        let delivery = match delivery_raw.as_ref() {
+            Some(raw) => Some(
+                DeliveryWindow::from_value(raw)
+                    .map_err(sockudo_core::error::Error::InvalidMessageFormat)?,
+            ),
+            None => None,
+        };
 
         Ok(Self {
             channel,
@@ -385,9 +407,31 @@ impl SubscriptionRequest {
             rewind,
             event_name_filter,
             annotation_subscribe,
+            delivery,
         })
     }
 
+    /// Parses a tag filter supplied by the server side (e.g. a subscribe proxy). Unlike
+    /// client filters, an unparseable filter is an error rather than no filter.
+    #[cfg(feature = "tag-filtering")]
+    pub fn tags_filter_from_value(raw: &Value) -> sockudo_core::error::Result<FilterNode> {
+        let mut filter = sonic_rs::to_vec(raw)
+            .and_then(|bytes| sonic_rs::from_slice::<FilterNode>(&bytes))
+            .map_err(|e| {
+                sockudo_core::error::Error::InvalidMessageFormat(format!(
+                    "Invalid tags filter: {e}"
+                ))
+            })?;
+        filter.optimize();
+        if let Some(err) = filter.validate() {
+            return Err(sockudo_core::error::Error::InvalidMessageFormat(format!(
+                "Invalid tags filter: {}",
+                err
+            )));
+        }
+        Ok(filter)
+    }
+
     fn parse_annotation_subscribe_mode(
         modes_raw: Option<&Value>,
     ) -> sockudo_core::error::Result<bool> {
@@ -605,6 +649,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &event_name_filtering_config(),
         )
         .unwrap();
@@ -633,6 +678,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &event_name_filtering_config(),
         );
         assert!(result.is_err());
@@ -658,6 +704,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &event_name_filtering_config(),
         );
         assert!(result.is_err());
@@ -683,6 +730,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &event_name_filtering_config(),
         );
         assert!(result.is_ok());
@@ -707,6 +755,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &config,
         );
         assert!(result.is_ok());
@@ -723,6 +772,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &event_name_filtering_config(),
         );
         assert!(result.is_ok());
@@ -739,6 +789,7 @@ mod tests {
             None,
             None,
             Some(json!(["ANNOTATION_SUBSCRIBE"])),
+            None,
             &event_name_filtering_config(),
         )
         .unwrap();
@@ -756,6 +807,48 @@ mod tests {
             None,
             None,
             Some(json!("ANNOTATION_SUBSCRIBE")),
+            None,
+            &event_name_filtering_config(),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_delivery_window_parses_mode_and_window() {
+        let request = SubscriptionRequest::build(
+            "test-channel".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(json!({ "mode": "latest", "window_ms": 100 })),
+            &event_name_filtering_config(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            request.delivery,
+            Some(DeliveryWindow {
+                mode: sockudo_core::websocket::DeliveryMode::Latest,
+                window_ms: 100,
+            })
+        );
+    }
+
+    #[test]
+    fn test_delivery_window_rejects_unknown_mode() {
+        let result = SubscriptionRequest::build(
+            "test-channel".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(json!({ "mode": "sample", "window_ms": 100 })),
             &event_name_filtering_config(),
         );
 
@@ -0,0 +1,178 @@
+// src/adapter/handler/user_connection_limits.rs
+use super::ConnectionHandler;
+use crate::horizontal_adapter::UserSocketEntry;
+use sockudo_core::app::{App, UserConnectionLimitAction};
+use sockudo_core::error::{Error, Result};
+use sockudo_core::websocket::WebSocketRef;
+use tracing::{info, warn};
+
+impl ConnectionHandler {
+    /// Registers `connection` as one of `user_id`'s connections and applies
+    /// `policy.user_connections`.
+    ///
+    /// The connection is added before the user's connections are counted on every node, so two
+    /// connections identifying at the same time always see each other. When the connection is
+    /// refused the registration is rolled back.
+    pub(crate) async fn register_user_connection(
+        &self,
+        connection: &WebSocketRef,
+        app_config: &App,
+        user_id: &str,
+        device_class: Option<&str>,
+    ) -> Result<()> {
+        self.connection_manager.add_user(connection.clone()).await?;
+        if let Err(error) = self
+            .enforce_user_connection_limit(connection, app_config, user_id, device_class)
+            .await
+        {
+            if let Err(rollback) = self
+                .connection_manager
+                .remove_user(connection.clone())
+                .await
+            {
+                warn!(user_id, error = %rollback, "failed to roll back refused user connection");
+            }
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Counts the user's connections on every node, the registered `connection` included. Over
+    /// the limit, the connection is either rejected or the user's connections older than it are
+    /// evicted to make room. A count that cannot reach the whole cluster rejects in `reject`
+    /// mode rather than admit the connection unchecked.
+    async fn enforce_user_connection_limit(
+        &self,
+        connection: &WebSocketRef,
+        app_config: &App,
+        user_id: &str,
+        device_class: Option<&str>,
+    ) -> Result<()> {
+        let Some(policy) = app_config.user_connections_ref() else {
+            return Ok(());
+        };
+        let limit = policy.max_connections as usize;
+        if limit == 0 {
+            return Ok(());
+        }
+
+        let socket_id = connection.get_socket_id_sync().to_string();
+        let entries = match self
+            .connection_manager
+            .get_user_socket_entries(user_id, &app_config.id)
+            .await
+        {
+            Ok(entries) => entries,
+            Err(error) => {
+                warn!(%socket_id, user_id, error = %error, "could not count user connections");
+                return match policy.on_limit {
+                    UserConnectionLimitAction::Reject => Err(Error::UserConnectionLimit),
+                    UserConnectionLimitAction::EvictOldest => Ok(()),
+                };
+            }
+        };
+        let own_connected_at = entries
+            .iter()
+            .find(|entry| entry.socket_id == socket_id)
+            .map(|entry| entry.connected_at_ms);
+        let competing = competing_connections(
+            entries,
+            &socket_id,
+            policy.per_device_class.then_some(device_class),
+        );
+        if competing.len() < limit {
+            return Ok(());
+        }
+
+        match policy.on_limit {
+            UserConnectionLimitAction::Reject => Err(Error::UserConnectionLimit),
+            UserConnectionLimitAction::EvictOldest => {
+                // Only older connections make room, so two connections arriving together never
+                // evict each other.
+                let older = competing.iter().filter(|entry| {
+                    own_connected_at.is_none_or(|own| {
+                        (entry.connected_at_ms, entry.socket_id.as_str())
+                            < (own, socket_id.as_str())
+                    })
+                });
+                for entry in older.take(competing.len() + 1 - limit) {
+                    info!(
+                        %socket_id,
+                        user_id,
+                        evicted = %entry.socket_id,
+                        "evicting oldest user connection over the per-user limit"
+                    );
+                    if let Err(error) = self
+                        .connection_manager
+                        .evict_user_socket(&app_config.id, user_id, &entry.socket_id)
+                        .await
+                    {
+                        warn!(evicted = %entry.socket_id, error = %error, "failed to evict user connection");
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The user's other connections that count against the limit, oldest first. With a device
+/// class filter only connections of that class compete.
+fn competing_connections(
+    entries: Vec<UserSocketEntry>,
+    own_socket_id: &str,
+    device_class: Option<Option<&str>>,
+) -> Vec<UserSocketEntry> {
+    let mut competing: Vec<_> = entries
+        .into_iter()
+        .filter(|entry| entry.socket_id != own_socket_id)
+        .filter(|entry| device_class.is_none_or(|class| entry.device_class.as_deref() == class))
+        .collect();
+    competing
+        .sort_by(|a, b| (a.connected_at_ms, &a.socket_id).cmp(&(b.connected_at_ms, &b.socket_id)));
+    competing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(socket_id: &str, connected_at_ms: i64, device_class: Option<&str>) -> UserSocketEntry {
+        UserSocketEntry {
+            socket_id: socket_id.to_string(),
+            connected_at_ms,
+            device_class: device_class.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn competing_connections_are_oldest_first_and_exclude_self() {
+        let entries = vec![
+            entry("1.3", 300, None),
+            entry("1.1", 100, None),
+            entry("1.2", 200, None),
+        ];
+
+        let competing = competing_connections(entries, "1.2", None);
+
+        let ids: Vec<_> = competing.iter().map(|e| e.socket_id.as_str()).collect();
+        assert_eq!(ids, ["1.1", "1.3"]);
+    }
+
+    #[test]
+    fn competing_connections_filter_by_device_class() {
+        let entries = vec![
+            entry("1.1", 100, Some("desktop")),
+            entry("1.2", 200, Some("mobile")),
+            entry("1.3", 300, None),
+        ];
+
+        let mobile = competing_connections(entries.clone(), "1.9", Some(Some("mobile")));
+        assert_eq!(mobile.len(), 1);
+        assert_eq!(mobile[0].socket_id, "1.2");
+
+        let unclassified = competing_connections(entries, "1.9", Some(None));
+        assert_eq!(unclassified.len(), 1);
+        assert_eq!(unclassified[0].socket_id, "1.3");
+    }
+}
@@ -65,6 +65,7 @@ fn validate_capability_permission(
                 && capabilities.allows_annotation_subscribe(channel)
         }
         "history" => capabilities.allows_history(channel),
+        "object_publish" => capabilities.allows_object_publish(channel),
         "presence" => capabilities
             .presence
             .as_deref()
@@ -253,10 +254,41 @@ impl ConnectionHandler {
             }
 
             utils::validate_wildcard_subscription_pattern(&request.channel)?;
+            if app_config
+                .user_channel_ref()
+                .is_some_and(|user_channel| user_channel.overlaps_wildcard(&request.channel))
+            {
+                return Err(Error::Auth(format!(
+                    "Wildcard subscription '{}' may match personal user channels",
+                    request.channel
+                )));
+            }
         } else {
             utils::validate_channel_name(app_config, &request.channel).await?;
         }
 
+        // Personal user channels are joined on sign-in; only their owner may subscribe, and
+        // ownership stands in for the namespace and capability checks below.
+        let owner = app_config
+            .user_channel_ref()
+            .and_then(|user_channel| user_channel.user_id_of(&request.channel));
+        let is_own_user_channel = match owner {
+            Some(owner) => {
+                let user_id = match connection.as_ref() {
+                    Some(connection) => connection.get_user_id().await,
+                    None => None,
+                };
+                if user_id.as_deref() != Some(owner) {
+                    return Err(Error::Auth(format!(
+                        "Channel '{}' is the personal channel of another user",
+                        request.channel
+                    )));
+                }
+                true
+            }
+            None => false,
+        };
+
         if request.rewind.is_some() {
             validate_rewind_request(
                 app_config,
@@ -265,6 +297,11 @@ impl ConnectionHandler {
                 &self.server_options().history,
             )?;
         }
+        if request.delivery.is_some() && !is_v2 {
+            return Err(Error::Channel(
+                "Subscription delivery windows are only supported on protocol V2".into(),
+            ));
+        }
         if request.annotation_subscribe {
             if !self.server_options().annotations.enabled {
                 return Err(Error::Channel(format!(
@@ -284,13 +321,19 @@ impl ConnectionHandler {
         let requires_auth =
             request.channel.starts_with("presence-") || request.channel.starts_with("private-");
 
-        if requires_auth && request.auth.is_none() {
+        // A subscribe proxy authorizes the subscription in place of the signature.
+        if requires_auth
+            && request.auth.is_none()
+            && app_config
+                .subscribe_proxy_for_channel(&request.channel)
+                .is_none()
+        {
             return Err(Error::Auth(
                 "Authentication signature required for this channel".into(),
             ));
         }
 
-        if is_v2 {
+        if is_v2 && !is_own_user_channel {
             self.validate_v2_namespace_permissions(app_config, &request.channel)
                 .await?;
             self.validate_v2_capability(socket_id, app_config, &request.channel, "subscribe")
@@ -349,7 +392,10 @@ impl ConnectionHandler {
         }
 
         // Check member count limit
-        if let Some(max_members) = app_config.max_presence_members_limit() {
+        if let Some(max_members) = app_config
+            .resolved_channel_limits(&request.channel)
+            .max_presence_members_per_channel
+        {
             let current_count = self
                 .get_channel_member_count(app_config, &request.channel)
                 .await?;
@@ -433,7 +479,10 @@ impl ConnectionHandler {
         }
 
         // Validate payload size
-        if let Some(max_payload_kb) = app_config.event_payload_limit_kb() {
+        if let Some(max_payload_kb) = app_config
+            .resolved_channel_limits(&request.channel)
+            .max_event_payload_in_kb
+        {
             let payload_size = utils::data_to_bytes_flexible(vec![request.data.clone()]);
             if payload_size > (max_payload_kb as usize * 1024) {
                 return Err(Error::ClientEvent(format!(
@@ -569,6 +618,11 @@ impl ConnectionHandler {
                     "Connection is not allowed to receive raw annotations for channel '{channel}'"
                 )));
             }
+            if action == "object_publish" {
+                return Err(Error::Auth(format!(
+                    "Connection is not allowed to apply object operations on channel '{channel}'"
+                )));
+            }
             return Ok(());
         };
 
@@ -630,6 +684,10 @@ mod tests {
             allow_presence_for_client: None,
             history: None,
             presence_history: None,
+            proxy: None,
+            connection_recovery: None,
+            delta_compression: None,
+            limits: None,
         };
 
         let err =
@@ -653,6 +711,10 @@ mod tests {
             allow_presence_for_client: None,
             history: None,
             presence_history: None,
+            proxy: None,
+            connection_recovery: None,
+            delta_compression: None,
+            limits: None,
         };
 
         let err = validate_namespace_permission(&namespace, "private-chat:room-1", "publish")
@@ -774,6 +836,10 @@ mod tests {
                             max_bytes_per_channel: None,
                         }),
                         presence_history: None,
+                        proxy: None,
+                        connection_recovery: None,
+                        delta_compression: None,
+                        limits: None,
                     }]),
                     ..Default::default()
                 },
@@ -9,7 +9,8 @@ use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use sockudo_core::channel::PresenceMemberInfo;
 use sockudo_core::metrics::MetricsInterface;
-use sockudo_core::websocket::SocketId;
+use sockudo_core::websocket::{SocketId, WebSocketRef};
+use sonic_rs::JsonValueTrait;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicU64;
 use std::sync::{Arc, OnceLock};
@@ -59,6 +60,14 @@ pub enum RequestType {
     // Tier 1A: aggregate channel counts (gossip, fire-and-forget)
     ChannelCountUpdate, // Peer's absolute local counts for changed channels
     ChannelCountSync,   // Full snapshot of a peer's local channel counts (on join)
+
+    // Server-side subscription changes from the HTTP API
+    SubscribeSockets,   // Subscribe a socket or a user's sockets to a channel
+    UnsubscribeSockets, // Unsubscribe a socket or a user's sockets from a channel
+
+    // Per-user connection limits
+    UserSockets,     // List a user's sockets with their connect time and device class
+    EvictUserSocket, // Close one socket of a user to make room for a newer connection
 }
 
 impl RequestType {
@@ -207,6 +216,69 @@ pub struct DeadNodeEvent {
     pub orphaned_members: Vec<OrphanedMember>,
 }
 
+/// Subscription change requested through the HTTP API for a socket or every socket of a user.
+/// Peers receive it as `SubscribeSockets`/`UnsubscribeSockets` and apply it to their own sockets.
+#[derive(Debug, Clone)]
+pub struct SubscriptionChangeEvent {
+    pub app_id: String,
+    pub channel: String,
+    pub socket_id: Option<String>,
+    pub user_id: Option<String>,
+    /// `user_info` for the presence member on presence channels.
+    pub user_info: Option<sonic_rs::Value>,
+    pub subscribe: bool,
+}
+
+/// One connection of a signed-in user. Peers report these for `UserSockets` in the response's
+/// `members` map, keyed by socket id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserSocketEntry {
+    pub socket_id: String,
+    pub connected_at_ms: i64,
+    pub device_class: Option<String>,
+}
+
+impl UserSocketEntry {
+    pub async fn from_connection(ws: &WebSocketRef) -> Self {
+        let ws = ws.inner.lock().await;
+        Self {
+            socket_id: ws.state.socket_id.to_string(),
+            connected_at_ms: ws.state.connected_at_ms,
+            device_class: ws
+                .state
+                .user_info
+                .as_ref()
+                .and_then(|user_info| user_info.device_class())
+                .map(str::to_string),
+        }
+    }
+
+    pub fn to_member(&self, user_id: &str) -> PresenceMemberInfo {
+        PresenceMemberInfo {
+            user_id: user_id.to_string(),
+            user_info: Some(sonic_rs::json!({
+                "connected_at_ms": self.connected_at_ms,
+                "device_class": self.device_class,
+            })),
+        }
+    }
+
+    pub fn from_member(socket_id: String, member: &PresenceMemberInfo) -> Self {
+        let info = member.user_info.as_ref();
+        Self {
+            socket_id,
+            connected_at_ms: info
+                .and_then(|info| info.get("connected_at_ms"))
+                .and_then(|value| value.as_i64())
+                .unwrap_or_default(),
+            device_class: info
+                .and_then(|info| info.get("device_class"))
+                .and_then(|value| value.as_str())
+                .map(str::to_string),
+        }
+    }
+}
+
 /// Information about an orphaned presence member that needs cleanup
 #[derive(Debug, Clone)]
 pub struct OrphanedMember {
@@ -232,6 +304,9 @@ pub struct HorizontalAdapter {
 
     pub metrics: OnceLock<Arc<dyn MetricsInterface + Send + Sync>>,
 
+    /// Hands subscription changes requested by peers to the connection handler
+    pub subscription_events: OnceLock<crate::connection_manager::SubscriptionEventBusSender>,
+
     /// Complete cluster-wide presence registry (node-first structure for efficient cleanup)
     ///AHashMap<node_id,AHashMap<channel,AHashMap<socket_id, PresenceEntry>>>
     pub cluster_presence_registry: Arc<RwLock<ClusterPresenceRegistry>>,
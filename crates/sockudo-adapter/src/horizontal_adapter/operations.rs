@@ -17,6 +17,7 @@ impl HorizontalAdapter {
             pending_requests: Arc::new(fast_dashmap()),
             requests_timeout: AtomicU64::new(5000),
             metrics: OnceLock::new(),
+            subscription_events: OnceLock::new(),
             cluster_presence_registry: Arc::new(RwLock::new(AHashMap::new())),
             node_heartbeats: Arc::new(RwLock::new(AHashMap::new())),
             sequence_counter: Arc::new(AtomicU64::new(0)),
@@ -375,6 +376,65 @@ impl HorizontalAdapter {
                     }
                 }
             }
+            RequestType::SubscribeSockets | RequestType::UnsubscribeSockets => {
+                if let Some(channel) = &request.channel {
+                    // Report how many local sockets match; the handler applies the change
+                    response.sockets_count = if let Some(socket_id) = &request.socket_id {
+                        let socket_id = SocketId::from_string(socket_id)
+                            .map_err(Error::InvalidMessageFormat)?;
+                        usize::from(
+                            self.local_adapter
+                                .get_connection(&socket_id, &request.app_id)
+                                .await
+                                .is_some(),
+                        )
+                    } else if let Some(user_id) = &request.user_id {
+                        self.local_adapter
+                            .get_user_sockets(user_id, &request.app_id)
+                            .await?
+                            .len()
+                    } else {
+                        0
+                    };
+
+                    if response.sockets_count > 0
+                        && let Some(sender) = self.subscription_events.get()
+                    {
+                        let event = SubscriptionChangeEvent {
+                            app_id: request.app_id.clone(),
+                            channel: channel.clone(),
+                            socket_id: request.socket_id.clone(),
+                            user_id: request.user_id.clone(),
+                            user_info: request.user_info.clone(),
+                            subscribe: request.request_type == RequestType::SubscribeSockets,
+                        };
+                        if let Err(e) = sender.send(event) {
+                            warn!("Failed to hand subscription change to handler: {}", e);
+                        }
+                    }
+                }
+            }
+            RequestType::UserSockets => {
+                if let Some(user_id) = &request.user_id {
+                    let entries = self
+                        .local_adapter
+                        .get_user_socket_entries(user_id, &request.app_id)
+                        .await?;
+                    response.sockets_count = entries.len();
+                    response.members = entries
+                        .iter()
+                        .map(|entry| (entry.socket_id.clone(), entry.to_member(user_id)))
+                        .collect();
+                }
+            }
+            RequestType::EvictUserSocket => {
+                if let (Some(user_id), Some(socket_id)) = (&request.user_id, &request.socket_id) {
+                    response.exists = self
+                        .local_adapter
+                        .evict_user_socket(&request.app_id, user_id, socket_id)
+                        .await?;
+                }
+            }
             RequestType::ChannelCountUpdate | RequestType::ChannelCountSync => {
                 // Tier 1A: peer reported absolute local counts (Update = changed
                 // channels, Sync = full snapshot on join). Apply to the registry;
@@ -871,6 +931,21 @@ impl HorizontalAdapter {
                 RequestType::ChannelCountUpdate | RequestType::ChannelCountSync => {
                     // Tier 1A: gossip-only, no response aggregation needed
                 }
+
+                RequestType::SubscribeSockets | RequestType::UnsubscribeSockets => {
+                    // Sum the sockets each node applied the change to
+                    combined_response.sockets_count += response.sockets_count;
+                }
+
+                RequestType::UserSockets => {
+                    // Socket ids are unique per node, so the maps never collide
+                    combined_response.sockets_count += response.sockets_count;
+                    combined_response.members.extend(response.members);
+                }
+                RequestType::EvictUserSocket => {
+                    // Only the node holding the socket reports it
+                    combined_response.exists = combined_response.exists || response.exists;
+                }
             }
         }
 
@@ -20,7 +20,7 @@ where
     async fn add_socket(
         &self,
         socket_id: SocketId,
-        socket: WebSocketWriter,
+        socket: TransportWriter,
         app_id: &str,
         app_manager: Arc<dyn AppManager + Send + Sync>,
         buffer_config: sockudo_core::websocket::WebSocketBufferConfig,
@@ -79,16 +79,7 @@ where
             // Get app config to check for channel-specific delta settings
             if let Ok(Some(app)) = app_manager.find_by_id(app_id).await {
                 // Get channel-specific delta compression settings
-                let channel_settings = app
-                    .channel_delta_compression_ref()
-                    .and_then(|map| map.get(channel))
-                    .and_then(|config| {
-                        use sockudo_delta::ChannelDeltaConfig;
-                        match config {
-                            ChannelDeltaConfig::Full(settings) => Some(settings.clone()),
-                            _ => None,
-                        }
-                    });
+                let channel_settings = app.resolved_channel_delta(channel);
 
                 // Use compression-aware sending if we have settings with conflation key
                 if channel_settings
@@ -841,9 +832,95 @@ where
         Some(self)
     }
 
+    fn aggregates_channel_counts(&self) -> bool {
+        self.aggregate_counts
+    }
+
     fn configure_dead_node_events(&self) -> Option<DeadNodeEventBusReceiver> {
         let (event_sender, event_receiver) = mpsc::unbounded_async();
         self.set_event_bus(event_sender);
         Some(event_receiver)
     }
+
+    fn configure_subscription_events(&self) -> Option<SubscriptionEventBusReceiver> {
+        let (event_sender, event_receiver) = mpsc::unbounded_async();
+        let _ = self.horizontal.subscription_events.set(event_sender);
+        Some(event_receiver)
+    }
+
+    async fn request_subscription_change(&self, event: &SubscriptionChangeEvent) -> Result<usize> {
+        let request = RequestBody {
+            request_id: Uuid::new_v4().to_string(),
+            node_id: self.horizontal.node_id.clone(),
+            app_id: event.app_id.clone(),
+            request_type: if event.subscribe {
+                RequestType::SubscribeSockets
+            } else {
+                RequestType::UnsubscribeSockets
+            },
+            channel: Some(event.channel.clone()),
+            socket_id: event.socket_id.clone(),
+            user_id: event.user_id.clone(),
+            user_info: event.user_info.clone(),
+            timestamp: None,
+            dead_node_id: None,
+            target_node_id: None,
+            reply_to: None,
+            channels: None,
+        };
+        let response = self.send_request_with_body(request).await?;
+        Ok(response.sockets_count)
+    }
+
+    async fn get_user_socket_entries(
+        &self,
+        user_id: &str,
+        app_id: &str,
+    ) -> Result<Vec<UserSocketEntry>> {
+        let mut entries = self
+            .local_adapter
+            .get_user_socket_entries(user_id, app_id)
+            .await?;
+
+        match self
+            .send_request(app_id, RequestType::UserSockets, None, None, Some(user_id))
+            .await
+        {
+            Ok(response) => entries.extend(response.members.iter().map(|(socket_id, member)| {
+                UserSocketEntry::from_member(socket_id.clone(), member)
+            })),
+            Err(e) => {
+                // A partial count would let a connection limit pass unchecked.
+                error!("Failed to get remote user sockets: {}", e);
+                return Err(e);
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn evict_user_socket(
+        &self,
+        app_id: &str,
+        user_id: &str,
+        socket_id: &str,
+    ) -> Result<bool> {
+        if self
+            .local_adapter
+            .evict_user_socket(app_id, user_id, socket_id)
+            .await?
+        {
+            return Ok(true);
+        }
+
+        let response = self
+            .send_request(
+                app_id,
+                RequestType::EvictUserSocket,
+                None,
+                Some(socket_id),
+                Some(user_id),
+            )
+            .await?;
+        Ok(response.exists)
+    }
 }
@@ -511,23 +511,16 @@ where
                                 let channel_settings = if let Ok(Some(app)) =
                                     app_manager.find_by_id(&broadcast.app_id).await
                                 {
-                                    app.channel_delta_compression_ref()
-                                        .and_then(|map| map.get(&broadcast.channel))
-                                        .and_then(|config| {
-                                            use sockudo_delta::ChannelDeltaConfig;
-                                            match config {
-                                                ChannelDeltaConfig::Full(settings) => {
-                                                    // Use the conflation key from compression metadata
-                                                    let mut settings = settings.clone();
-                                                    if compression_meta.conflation_key.is_some() {
-                                                        settings.conflation_key =
-                                                            compression_meta.conflation_key.clone();
-                                                    }
-                                                    Some(settings)
-                                                }
-                                                _ => None,
+                                    app.resolved_channel_delta(&broadcast.channel).map(
+                                        |mut settings| {
+                                            // Use the conflation key from compression metadata
+                                            if compression_meta.conflation_key.is_some() {
+                                                settings.conflation_key =
+                                                    compression_meta.conflation_key.clone();
                                             }
-                                        })
+                                            settings
+                                        },
+                                    )
                                 } else {
                                     None
                                 };
@@ -14,11 +14,13 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use crate::connection_manager::{
-    ConnectionManager, DeadNodeEventBusReceiver, DeadNodeEventBusSender, HorizontalAdapterInterface,
+    ConnectionManager, DeadNodeEventBusReceiver, DeadNodeEventBusSender,
+    HorizontalAdapterInterface, SubscriptionEventBusReceiver,
 };
 use crate::horizontal_adapter::{
     AggregationStats, BroadcastMessage, DeadNodeEvent, HorizontalAdapter, OrphanedMember,
-    PendingRequest, RequestBody, RequestType, ResponseBody, current_timestamp, generate_request_id,
+    PendingRequest, RequestBody, RequestType, ResponseBody, SubscriptionChangeEvent,
+    UserSocketEntry, current_timestamp, generate_request_id,
 };
 use crate::horizontal_transport::{HorizontalTransport, TransportConfig, TransportHandlers};
 use crate::local_adapter::LocalAdapter;
@@ -30,9 +32,8 @@ use sockudo_core::error::{Error, Result};
 use sockudo_core::metrics::MetricsInterface;
 use sockudo_core::namespace::Namespace;
 use sockudo_core::options::ClusterHealthConfig;
-use sockudo_core::websocket::{SocketId, WebSocketRef};
+use sockudo_core::websocket::{SocketId, TransportWriter, WebSocketRef};
 use sockudo_protocol::messages::PusherMessage;
-use sockudo_ws::axum_integration::WebSocketWriter;
 use tokio::sync::Notify;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
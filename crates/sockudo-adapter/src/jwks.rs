@@ -0,0 +1,418 @@
+use dashmap::DashMap;
+use futures_util::StreamExt;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use sockudo_core::cache::CacheManager;
+use sockudo_core::error::{Error, Result};
+use sockudo_core::options::JwksConfig;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Largest JWKS document accepted from an app's endpoint.
+pub const MAX_JWKS_BYTES: usize = 256 * 1024;
+
+fn key_set_key(url: &str) -> String {
+    format!("sockudo:jwks:{url}")
+}
+
+fn unknown_kid_key(url: &str, kid: &str) -> String {
+    format!("sockudo:jwks:{url}:unknown_kid:{kid}")
+}
+
+fn fetch_throttle_key(url: &str) -> String {
+    format!("sockudo:jwks:{url}:fetched")
+}
+
+/// Resolves capability-token keys from the JWKS documents apps point at.
+///
+/// Key sets live in the shared `CacheManager`, so every node serves the same document. Each URL
+/// that is in use gets a background task that refetches it every `refresh_interval_seconds`
+/// and stops once the URL has not been used for `cache_ttl_seconds`. A `kid` missing from the
+/// cached set triggers one on-demand fetch; if the endpoint does not know it either, the `kid`
+/// is remembered for `negative_cache_ttl_seconds`, and on-demand fetches of a URL are spaced
+/// at least `min_refetch_interval_seconds` apart, so made-up kids cannot hammer the endpoint.
+#[derive(Clone)]
+pub struct JwksCache {
+    cache_manager: Arc<dyn CacheManager + Send + Sync>,
+    client: reqwest::Client,
+    config: JwksConfig,
+    /// Last lookup time of every URL that has a background refresh task.
+    refreshed_urls: Arc<DashMap<String, Instant, ahash::RandomState>>,
+}
+
+impl JwksCache {
+    pub fn new(cache_manager: Arc<dyn CacheManager + Send + Sync>, config: JwksConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.request_timeout_ms.max(1)))
+            .build()
+            .unwrap_or_default();
+        Self {
+            cache_manager,
+            client,
+            config,
+            refreshed_urls: Arc::new(DashMap::with_hasher(ahash::RandomState::new())),
+        }
+    }
+
+    /// Returns the key published under `kid` at `url`, or `None` when the endpoint does not
+    /// have it.
+    pub async fn key(&self, url: &str, kid: &str) -> Result<Option<Jwk>> {
+        self.track(url);
+
+        if let Some(key_set) = self.cached_key_set(url).await?
+            && let Some(jwk) = key_set.find(kid)
+        {
+            return Ok(Some(jwk.clone()));
+        }
+        if self.cache_manager.has(&unknown_kid_key(url, kid)).await? {
+            return Ok(None);
+        }
+
+        // Any on-demand fetch within the throttle window already saw the current document, so
+        // a kid missing from it is unknown rather than freshly rotated.
+        let throttled = !self
+            .cache_manager
+            .set_if_not_exists(
+                &fetch_throttle_key(url),
+                "1",
+                self.config.min_refetch_interval_seconds.max(1),
+            )
+            .await?;
+        let jwk = if throttled {
+            None
+        } else {
+            self.fetch(url).await?.find(kid).cloned()
+        };
+
+        if jwk.is_none() {
+            debug!(url, kid, "capability token kid not found in JWKS");
+            self.cache_manager
+                .set(
+                    &unknown_kid_key(url, kid),
+                    "1",
+                    self.config.negative_cache_ttl_seconds.max(1),
+                )
+                .await?;
+        }
+        Ok(jwk)
+    }
+
+    async fn cached_key_set(&self, url: &str) -> Result<Option<JwkSet>> {
+        let Some(document) = self.cache_manager.get(&key_set_key(url)).await? else {
+            return Ok(None);
+        };
+        match sonic_rs::from_str(&document) {
+            Ok(key_set) => Ok(Some(key_set)),
+            Err(error) => {
+                warn!(url, error = %error, "discarding unreadable cached JWKS");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Downloads the key set at `url` and stores it in the cache manager.
+    async fn fetch(&self, url: &str) -> Result<JwkSet> {
+        let unavailable = |error: &dyn std::fmt::Display| {
+            warn!(url, error = %error, "failed to fetch JWKS");
+            Error::Auth("capability token keys are unavailable".to_string())
+        };
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| unavailable(&e))?;
+        // Refuse oversized documents before reading them: first by the declared length, then
+        // while streaming, for responses that omit or understate it.
+        if response
+            .content_length()
+            .is_some_and(|length| length > MAX_JWKS_BYTES as u64)
+        {
+            return Err(unavailable(&"document exceeds 256 KiB"));
+        }
+        let mut body = Vec::new();
+        let mut chunks = response.bytes_stream();
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk.map_err(|e| unavailable(&e))?;
+            if body.len() + chunk.len() > MAX_JWKS_BYTES {
+                return Err(unavailable(&"document exceeds 256 KiB"));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        let document = String::from_utf8(body).map_err(|e| unavailable(&e))?;
+        let key_set: JwkSet = sonic_rs::from_str(&document).map_err(|e| unavailable(&e))?;
+
+        self.cache_manager
+            .set(
+                &key_set_key(url),
+                &document,
+                self.config.cache_ttl_seconds.max(1),
+            )
+            .await?;
+        Ok(key_set)
+    }
+
+    fn track(&self, url: &str) {
+        if self
+            .refreshed_urls
+            .insert(url.to_string(), Instant::now())
+            .is_some()
+        {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let jwks = self.clone();
+        let url = url.to_string();
+        let refresh_interval = Duration::from_secs(self.config.refresh_interval_seconds.max(1));
+        let idle_after = Duration::from_secs(self.config.cache_ttl_seconds.max(1));
+        handle.spawn(async move {
+            loop {
+                tokio::time::sleep(refresh_interval).await;
+                if jwks
+                    .refreshed_urls
+                    .remove_if(&url, |_, last_used| last_used.elapsed() > idle_after)
+                    .is_some()
+                {
+                    debug!(url, "stopping JWKS refresh for unused URL");
+                    return;
+                }
+                let _ = jwks.fetch(&url).await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::Mutex;
+
+    const ED25519_X: &str = "A4fPbq1jHC_s6bgrVUYlGGLG0vFQ9wOMfj36jE4MUIA";
+
+    /// Cache manager that honours TTLs, so negative caching and throttling expire.
+    #[derive(Default)]
+    struct TestCache {
+        entries: Mutex<HashMap<String, (String, Instant)>>,
+    }
+
+    impl TestCache {
+        async fn live(&self, key: &str) -> Option<String> {
+            let entries = self.entries.lock().await;
+            entries
+                .get(key)
+                .filter(|(_, expires_at)| *expires_at > Instant::now())
+                .map(|(value, _)| value.clone())
+        }
+    }
+
+    #[async_trait]
+    impl CacheManager for TestCache {
+        async fn has(&self, key: &str) -> Result<bool> {
+            Ok(self.live(key).await.is_some())
+        }
+
+        async fn get(&self, key: &str) -> Result<Option<String>> {
+            Ok(self.live(key).await)
+        }
+
+        async fn set(&self, key: &str, value: &str, ttl_seconds: u64) -> Result<()> {
+            self.entries.lock().await.insert(
+                key.to_string(),
+                (
+                    value.to_string(),
+                    Instant::now() + Duration::from_secs(ttl_seconds),
+                ),
+            );
+            Ok(())
+        }
+
+        async fn remove(&self, key: &str) -> Result<()> {
+            self.entries.lock().await.remove(key);
+            Ok(())
+        }
+
+        async fn disconnect(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn ttl(&self, _key: &str) -> Result<Option<Duration>> {
+            Ok(None)
+        }
+
+        async fn set_if_not_exists(
+            &self,
+            key: &str,
+            value: &str,
+            ttl_seconds: u64,
+        ) -> Result<bool> {
+            if self.live(key).await.is_some() {
+                return Ok(false);
+            }
+            self.set(key, value, ttl_seconds).await?;
+            Ok(true)
+        }
+    }
+
+    /// Local HTTP stand-in for an identity provider's JWKS endpoint.
+    struct JwksEndpoint {
+        url: String,
+        document: Arc<Mutex<String>>,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl JwksEndpoint {
+        async fn start(kids: &[&str]) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!(
+                "http://{}/.well-known/jwks.json",
+                listener.local_addr().unwrap()
+            );
+            let document = Arc::new(Mutex::new(key_set(kids)));
+            let requests = Arc::new(AtomicUsize::new(0));
+
+            let served = document.clone();
+            let counter = requests.clone();
+            tokio::spawn(async move {
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    let mut request = [0u8; 4096];
+                    let _ = stream.read(&mut request).await;
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    let body = served.lock().await.clone();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                }
+            });
+
+            Self {
+                url,
+                document,
+                requests,
+            }
+        }
+
+        async fn publish(&self, kids: &[&str]) {
+            *self.document.lock().await = key_set(kids);
+        }
+
+        fn requests(&self) -> usize {
+            self.requests.load(Ordering::SeqCst)
+        }
+    }
+
+    fn key_set(kids: &[&str]) -> String {
+        let keys: Vec<String> = kids
+            .iter()
+            .map(|kid| {
+                format!(
+                    r#"{{"kty":"OKP","crv":"Ed25519","alg":"EdDSA","use":"sig","kid":"{kid}","x":"{ED25519_X}"}}"#
+                )
+            })
+            .collect();
+        format!(r#"{{"keys":[{}]}}"#, keys.join(","))
+    }
+
+    fn jwks_cache(config: JwksConfig) -> (JwksCache, Arc<TestCache>) {
+        let cache = Arc::new(TestCache::default());
+        (JwksCache::new(cache.clone(), config), cache)
+    }
+
+    #[tokio::test]
+    async fn serves_cached_keys_and_negative_caches_unknown_kids() {
+        let endpoint = JwksEndpoint::start(&["key-1"]).await;
+        let (jwks, _) = jwks_cache(JwksConfig::default());
+
+        let jwk = jwks.key(&endpoint.url, "key-1").await.unwrap().unwrap();
+        assert_eq!(jwk.common.key_id.as_deref(), Some("key-1"));
+        assert!(jwks.key(&endpoint.url, "key-1").await.unwrap().is_some());
+        assert_eq!(endpoint.requests(), 1);
+
+        for kid in ["bogus-1", "bogus-2", "bogus-1", "bogus-3"] {
+            assert!(jwks.key(&endpoint.url, kid).await.unwrap().is_none());
+        }
+        assert_eq!(endpoint.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn refetches_when_a_rotated_kid_appears() {
+        let endpoint = JwksEndpoint::start(&["key-1"]).await;
+        let (jwks, _) = jwks_cache(JwksConfig {
+            min_refetch_interval_seconds: 1,
+            ..Default::default()
+        });
+
+        assert!(jwks.key(&endpoint.url, "key-1").await.unwrap().is_some());
+        endpoint.publish(&["key-1", "key-2"]).await;
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        let jwk = jwks.key(&endpoint.url, "key-2").await.unwrap();
+        assert_eq!(jwk.unwrap().common.key_id.as_deref(), Some("key-2"));
+        assert_eq!(endpoint.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn refreshes_key_sets_in_the_background() {
+        let endpoint = JwksEndpoint::start(&["key-1"]).await;
+        let (jwks, cache) = jwks_cache(JwksConfig {
+            refresh_interval_seconds: 1,
+            ..Default::default()
+        });
+
+        assert!(jwks.key(&endpoint.url, "key-1").await.unwrap().is_some());
+        endpoint.publish(&["key-2"]).await;
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        assert!(endpoint.requests() >= 2);
+        let cached = cache.live(&key_set_key(&endpoint.url)).await.unwrap();
+        assert!(cached.contains(r#""kid":"key-2""#));
+        assert!(jwks.key(&endpoint.url, "key-2").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn oversized_documents_are_rejected_while_streaming() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/jwks.json", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request).await;
+                // No content-length: the limit has to be enforced on the streamed body.
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\n\r\n")
+                    .await;
+                let padding = vec![b' '; 64 * 1024];
+                for _ in 0..8 {
+                    if stream.write_all(&padding).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        let (jwks, cache) = jwks_cache(JwksConfig::default());
+
+        assert!(matches!(jwks.key(&url, "key-1").await, Err(Error::Auth(_))));
+        assert!(cache.live(&key_set_key(&url)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn unreachable_endpoint_is_an_auth_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/jwks.json", listener.local_addr().unwrap());
+        drop(listener);
+        let (jwks, _) = jwks_cache(JwksConfig::default());
+
+        assert!(matches!(jwks.key(&url, "key-1").await, Err(Error::Auth(_))));
+    }
+}
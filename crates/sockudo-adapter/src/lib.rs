@@ -1,6 +1,7 @@
 pub mod channel_manager;
 pub mod cleanup;
 pub mod connection_manager;
+pub mod delivery_batching;
 pub mod factory;
 #[cfg(feature = "tag-filtering")]
 pub mod filter_index;
@@ -12,6 +13,7 @@ pub mod horizontal_adapter_base;
 pub mod horizontal_transport;
 #[cfg(feature = "iggy")]
 pub mod iggy_adapter;
+pub mod jwks;
 #[cfg(feature = "kafka")]
 pub mod kafka_adapter;
 pub mod local_adapter;
@@ -19,6 +21,7 @@ pub mod memory_rate_limiter;
 #[cfg(feature = "nats")]
 pub mod nats_adapter;
 pub mod presence;
+pub mod proxy;
 #[cfg(feature = "pulsar")]
 pub mod pulsar_adapter;
 #[cfg(feature = "rabbitmq")]
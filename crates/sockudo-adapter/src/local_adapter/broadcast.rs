@@ -1,11 +1,12 @@
 use super::LocalAdapter;
+use crate::delivery_batching::WindowMessage;
 #[cfg(feature = "delta")]
 use ahash::AHashMap as HashMap;
 use bytes::Bytes;
 use sockudo_core::error::{Error, Result};
 use sockudo_core::namespace::Namespace;
-use sockudo_core::websocket::{SocketId, WebSocketRef};
-use sockudo_protocol::messages::PusherMessage;
+use sockudo_core::websocket::{DeliveryWindow, SocketId, WebSocketRef};
+use sockudo_protocol::messages::{MessagePriority, PusherMessage};
 use sockudo_protocol::versioned_messages::extract_runtime_action;
 #[cfg(feature = "delta")]
 use std::hash::{Hash, Hasher};
@@ -109,6 +110,7 @@ impl LocalAdapter {
         &self,
         target_socket_refs: Vec<WebSocketRef>,
         message_bytes: Bytes,
+        priority: MessagePriority,
     ) -> Vec<Result<()>> {
         use futures::stream::{self, StreamExt};
 
@@ -141,7 +143,7 @@ impl LocalAdapter {
                     let chunk_results: Vec<Result<()>> = stream::iter(chunk_vec)
                         .map(|socket_ref| {
                             let bytes = message_bytes.clone();
-                            async move { socket_ref.send_broadcast(bytes) }
+                            async move { socket_ref.send_broadcast_with_priority(bytes, priority) }
                         })
                         .buffer_unordered(chunk_size)
                         .collect()
@@ -1030,7 +1032,10 @@ impl LocalAdapter {
             sonic_rs::to_vec(&v1_message)
                 .map_err(|e| Error::InvalidMessageFormat(format!("Serialization failed: {e}")))?,
         );
-        Self::log_send_errors(self.send_messages_concurrent(sockets, v1_bytes).await);
+        Self::log_send_errors(
+            self.send_messages_concurrent(sockets, v1_bytes, message.priority())
+                .await,
+        );
         Ok(())
     }
 
@@ -1141,6 +1146,39 @@ impl LocalAdapter {
         }
     }
 
+    /// Hands a prepared V2 message to the delivery window of each paced socket and sends
+    /// the ones that open a new window right away. High-priority messages skip the windows.
+    pub(super) fn send_to_paced_sockets(
+        &self,
+        channel: &str,
+        paced: Vec<(WebSocketRef, DeliveryWindow)>,
+        message: &PusherMessage,
+    ) {
+        if paced.is_empty() {
+            return;
+        }
+        if message.priority() == MessagePriority::High {
+            let results = paced
+                .into_iter()
+                .map(|(socket, _)| socket.send_message(message))
+                .collect();
+            Self::log_send_errors(results);
+            return;
+        }
+        self.delivery_batcher.ensure_worker();
+        let now_ms = sockudo_core::history::now_ms() as u64;
+        let shared = WindowMessage::new(message.clone());
+        let results = paced
+            .into_iter()
+            .filter_map(|(socket, window)| {
+                self.delivery_batcher
+                    .ingest(&socket, channel, window, &shared, now_ms)
+                    .map(|frame| socket.send_message(frame.message()))
+            })
+            .collect();
+        Self::log_send_errors(results);
+    }
+
     // Updated to return WebSocketRef instead of Arc<Mutex<WebSocket>>
     pub async fn get_all_connections(&self, app_id: &str) -> Vec<SocketId> {
         self.existing_namespace(app_id)
@@ -7,9 +7,8 @@ use sockudo_core::app::AppManager;
 use sockudo_core::channel::PresenceMemberInfo;
 use sockudo_core::error::{Error, Result};
 use sockudo_core::namespace::{Namespace, SocketInitOptions};
-use sockudo_core::websocket::{SocketId, WebSocketRef};
+use sockudo_core::websocket::{SocketId, TransportWriter, WebSocketRef};
 use sockudo_protocol::messages::{PusherMessage, generate_message_id};
-use sockudo_ws::axum_integration::WebSocketWriter;
 use std::any::Any;
 use std::sync::Arc;
 use tracing::{debug, error, info};
@@ -27,7 +26,7 @@ impl ConnectionManager for LocalAdapter {
     async fn add_socket(
         &self,
         socket_id: SocketId,
-        socket: WebSocketWriter,
+        socket: TransportWriter,
         app_id: &str,
         app_manager: Arc<dyn AppManager + Send + Sync>,
         buffer_config: sockudo_core::websocket::WebSocketBufferConfig,
@@ -142,16 +141,7 @@ impl ConnectionManager for LocalAdapter {
                 // Get app config to check for channel-specific delta settings
                 if let Ok(Some(app)) = app_manager.find_by_id(app_id).await {
                     // Get channel-specific delta compression settings
-                    let channel_settings = app
-                        .channel_delta_compression_ref()
-                        .and_then(|map| map.get(channel))
-                        .and_then(|config| {
-                            use sockudo_delta::ChannelDeltaConfig;
-                            match config {
-                                ChannelDeltaConfig::Full(settings) => Some(settings.clone()),
-                                _ => None,
-                            }
-                        });
+                    let channel_settings = app.resolved_channel_delta(channel);
 
                     // Use compression-aware sending if we have settings with conflation key
                     if channel_settings
@@ -221,14 +211,19 @@ impl ConnectionManager for LocalAdapter {
         );
         self.split_rewind_gated_sockets_in_place(channel, &message, &mut filtered_socket_refs)
             .await;
+        let paced_socket_refs =
+            crate::v2_broadcast::take_paced_sockets(channel, &mut filtered_socket_refs);
 
         // Send to filtered V2 sockets (Sockudo-native: sockudo: prefix, serial + message_id)
-        if !filtered_socket_refs.is_empty() {
+        if !filtered_socket_refs.is_empty() || !paced_socket_refs.is_empty() {
             let (v2_message, _v2_bytes) = crate::v2_broadcast::prepare_v2_message(message)?;
-            Self::log_send_errors(
-                self.send_protocol_messages_concurrent(filtered_socket_refs, v2_message)
-                    .await,
-            );
+            self.send_to_paced_sockets(channel, paced_socket_refs, &v2_message);
+            if !filtered_socket_refs.is_empty() {
+                Self::log_send_errors(
+                    self.send_protocol_messages_concurrent(filtered_socket_refs, v2_message)
+                        .await,
+                );
+            }
         }
 
         Ok(())
@@ -298,14 +293,21 @@ impl ConnectionManager for LocalAdapter {
         );
         self.split_rewind_gated_sockets_in_place(channel, &message, &mut filtered_socket_refs)
             .await;
+        // Paced subscriptions receive full messages; a window frame has no delta base.
+        let paced_socket_refs =
+            crate::v2_broadcast::take_paced_sockets(channel, &mut filtered_socket_refs);
 
         let message = self.maybe_strip_tags(message, channel_settings);
 
         // V2 sockets get delta compression
-        if !filtered_socket_refs.is_empty() {
+        if !filtered_socket_refs.is_empty() || !paced_socket_refs.is_empty() {
             let mut v2_message = message;
             v2_message.rewrite_prefix(sockudo_protocol::ProtocolVersion::V2);
             v2_message.idempotency_key = None;
+            self.send_to_paced_sockets(channel, paced_socket_refs, &v2_message);
+            if filtered_socket_refs.is_empty() {
+                return Ok(());
+            }
             let v2_event_name = v2_message.event.as_deref().unwrap_or("").to_string();
             let v2_bytes = sonic_rs::to_vec(&v2_message)
                 .map_err(|e| Error::InvalidMessageFormat(format!("Serialization failed: {e}")))?;
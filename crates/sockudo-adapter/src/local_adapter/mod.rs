@@ -3,6 +3,7 @@ mod connection_manager;
 mod helpers;
 
 use self::helpers::{PendingPresenceMember, pending_presence_channel_key};
+use crate::delivery_batching::DeliveryBatcher;
 #[cfg(feature = "tag-filtering")]
 use crate::filter_index::FilterIndex;
 use compact_str::CompactString;
@@ -35,6 +36,8 @@ pub struct LocalAdapter {
     pub max_concurrent: usize,
     // Global semaphore to limit total concurrent broadcast operations across all channels
     broadcast_semaphore: Arc<Semaphore>,
+    // Holds messages for V2 subscriptions that asked for a batching or latest-only window
+    delivery_batcher: Arc<DeliveryBatcher>,
     #[cfg(feature = "delta")]
     // Delta compression manager for bandwidth optimization (OnceLock for lock-free reads after init)
     delta_compression: Arc<OnceLock<Arc<sockudo_delta::DeltaCompressionManager>>>,
@@ -62,6 +65,7 @@ impl Clone for LocalAdapter {
             buffer_multiplier_per_cpu: self.buffer_multiplier_per_cpu,
             max_concurrent: self.max_concurrent,
             broadcast_semaphore: Arc::clone(&self.broadcast_semaphore),
+            delivery_batcher: Arc::clone(&self.delivery_batcher),
             #[cfg(feature = "delta")]
             delta_compression: Arc::clone(&self.delta_compression),
             #[cfg(feature = "delta")]
@@ -127,6 +131,7 @@ impl LocalAdapter {
             buffer_multiplier_per_cpu: multiplier,
             max_concurrent,
             broadcast_semaphore: Arc::new(Semaphore::new(max_concurrent * 8)),
+            delivery_batcher: Arc::new(DeliveryBatcher::new()),
             #[cfg(feature = "delta")]
             delta_compression: Arc::new(OnceLock::new()),
             #[cfg(feature = "delta")]
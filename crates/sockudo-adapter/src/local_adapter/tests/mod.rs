@@ -28,6 +28,7 @@ fn v1_compatible_message_strips_v2_only_fields_for_plain_messages() {
             push: None,
             echo: Some(false),
             ai: None,
+            priority: None,
         }),
         delta_sequence: None,
         delta_conflation_key: None,
@@ -1070,8 +1070,8 @@ mod tests {
         MemoryPresenceHistoryStore, PresenceHistoryDirection, PresenceHistoryEventCause,
         PresenceHistoryEventKind, PresenceHistoryReadRequest, PresenceHistoryRetentionPolicy,
     };
+    use sockudo_core::websocket::TransportWriter;
     use sockudo_protocol::WireFormat;
-    use sockudo_ws::axum_integration::WebSocketWriter;
     use std::any::Any;
     use std::collections::VecDeque;
     use std::sync::Mutex as StdMutex;
@@ -1125,7 +1125,7 @@ mod tests {
         async fn add_socket(
             &self,
             _socket_id: SocketId,
-            _socket: WebSocketWriter,
+            _socket: TransportWriter,
             _app_id: &str,
             _app_manager: Arc<dyn sockudo_core::app::AppManager + Send + Sync>,
             _buffer_config: sockudo_core::websocket::WebSocketBufferConfig,
@@ -0,0 +1,138 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sockudo_core::app::{App, AppProxyEndpoint};
+use sockudo_core::error::{Error, Result};
+use sockudo_core::metrics::MetricsInterface;
+use sockudo_core::token::Token;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Largest response body accepted from a proxy backend.
+pub const MAX_PROXY_RESPONSE_BYTES: usize = 64 * 1024;
+
+/// Calls the HTTP backends apps configure under `proxy`.
+///
+/// Every request is a JSON `POST` signed like a webhook: `X-Pusher-Key` carries the app key and
+/// `X-Pusher-Signature` the hex HMAC-SHA256 of the body under the app's signing secret. Any
+/// transport error, timeout, non-2xx status or unreadable body is returned as an error; whether
+/// that admits or rejects the action is the caller's decision, driven by the endpoint's
+/// `fail_open`.
+#[derive(Clone)]
+pub struct ProxyClient {
+    client: reqwest::Client,
+    metrics: Option<Arc<dyn MetricsInterface + Send + Sync>>,
+}
+
+impl ProxyClient {
+    pub fn new(metrics: Option<Arc<dyn MetricsInterface + Send + Sync>>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            metrics,
+        }
+    }
+
+    /// Posts `body` to `endpoint` and decodes the backend's answer. `proxy` names the call
+    /// (`connect`, ...) in logs and metrics.
+    pub async fn call<B, R>(
+        &self,
+        app: &App,
+        proxy: &str,
+        endpoint: &AppProxyEndpoint,
+        body: &B,
+    ) -> Result<R>
+    where
+        B: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let started = Instant::now();
+        let result = self.send(app, endpoint, body).await;
+
+        if endpoint.metrics
+            && let Some(metrics) = self.metrics.as_ref()
+        {
+            let outcome = match &result {
+                Ok(_) => "success",
+                Err(ProxyFailure::Timeout) => "timeout",
+                Err(_) => "error",
+            };
+            metrics.mark_proxy_request(&app.id, proxy, outcome);
+            metrics.track_proxy_request_latency(
+                &app.id,
+                proxy,
+                started.elapsed().as_secs_f64() * 1000.0,
+            );
+        }
+
+        result.map_err(|failure| {
+            warn!(
+                app_id = %app.id,
+                proxy,
+                url = %endpoint.url,
+                error = %failure,
+                "proxy backend call failed"
+            );
+            Error::Connection(format!("{proxy} proxy request failed: {failure}"))
+        })
+    }
+
+    async fn send<B, R>(
+        &self,
+        app: &App,
+        endpoint: &AppProxyEndpoint,
+        body: &B,
+    ) -> std::result::Result<R, ProxyFailure>
+    where
+        B: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let payload = sonic_rs::to_string(body).map_err(|e| ProxyFailure::Body(e.to_string()))?;
+        let signature =
+            Token::new(app.key.clone(), app.signing_secret().to_string()).sign(&payload);
+
+        let response = self
+            .client
+            .post(&endpoint.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Pusher-Key", &app.key)
+            .header("X-Pusher-Signature", signature)
+            .timeout(Duration::from_millis(endpoint.timeout_ms.max(1)))
+            .body(payload)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(ProxyFailure::from)?;
+        let document = response.text().await.map_err(ProxyFailure::from)?;
+        if document.len() > MAX_PROXY_RESPONSE_BYTES {
+            return Err(ProxyFailure::Body("response exceeds 64 KiB".to_string()));
+        }
+        sonic_rs::from_str(&document).map_err(|e| ProxyFailure::Body(e.to_string()))
+    }
+}
+
+#[derive(Debug)]
+enum ProxyFailure {
+    Timeout,
+    Request(reqwest::Error),
+    Body(String),
+}
+
+impl From<reqwest::Error> for ProxyFailure {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            Self::Timeout
+        } else {
+            Self::Request(error)
+        }
+    }
+}
+
+impl std::fmt::Display for ProxyFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout => f.write_str("timed out"),
+            Self::Request(error) => write!(f, "{error}"),
+            Self::Body(reason) => write!(f, "invalid response: {reason}"),
+        }
+    }
+}
@@ -25,6 +25,7 @@ struct ChannelBufferState {
     messages: VecDeque<BufferedMessage>,
     current_stream_id: Option<String>,
     last_touched: Instant,
+    limits: ReplayLimits,
 }
 
 struct ChannelBuffer {
@@ -32,6 +33,13 @@ struct ChannelBuffer {
     next_serial: AtomicU64,
 }
 
+/// Size and age bounds of one channel's buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayLimits {
+    pub max_buffer_size: usize,
+    pub buffer_ttl: Duration,
+}
+
 pub enum ReplayLookup {
     Recovered(Vec<Bytes>),
     Expired,
@@ -47,16 +55,18 @@ pub enum ReplayLookup {
 pub struct ReplayBuffer {
     /// Key: "app_id\0channel" → ChannelBuffer
     buffers: BufferMap,
-    max_buffer_size: usize,
-    buffer_ttl: Duration,
+    /// Bounds for buffers created before any per-channel limits are known.
+    default_limits: ReplayLimits,
 }
 
 impl ReplayBuffer {
     pub fn new(max_buffer_size: usize, buffer_ttl: Duration) -> Self {
         Self {
             buffers: DashMap::with_hasher(ahash::RandomState::new()),
-            max_buffer_size,
-            buffer_ttl,
+            default_limits: ReplayLimits {
+                max_buffer_size,
+                buffer_ttl,
+            },
         }
     }
 
@@ -69,16 +79,17 @@ impl ReplayBuffer {
     }
 
     fn new_channel_buffer(
-        max_buffer_size: usize,
+        limits: ReplayLimits,
         stream_id: Option<String>,
         next_serial: u64,
         now: Instant,
     ) -> ChannelBuffer {
         ChannelBuffer {
             state: Mutex::new(ChannelBufferState {
-                messages: VecDeque::with_capacity(max_buffer_size),
+                messages: VecDeque::with_capacity(limits.max_buffer_size),
                 current_stream_id: stream_id,
                 last_touched: now,
+                limits,
             }),
             next_serial: AtomicU64::new(next_serial),
         }
@@ -117,7 +128,7 @@ impl ReplayBuffer {
         let key = Self::buffer_key(app_id, channel);
         let now = Instant::now();
         let entry = self.buffers.entry(key).or_insert_with(|| {
-            Self::new_channel_buffer(self.max_buffer_size, Some(Self::new_stream_id()), 1, now)
+            Self::new_channel_buffer(self.default_limits, Some(Self::new_stream_id()), 1, now)
         });
 
         let mut state = entry.state.lock();
@@ -143,7 +154,7 @@ impl ReplayBuffer {
         let next_serial = serial.saturating_add(1);
         let entry = self.buffers.entry(key).or_insert_with(|| {
             Self::new_channel_buffer(
-                self.max_buffer_size,
+                self.default_limits,
                 Some(stream_id.to_string()),
                 next_serial,
                 now,
@@ -169,7 +180,7 @@ impl ReplayBuffer {
         let key = Self::buffer_key(app_id, channel);
         let now = Instant::now();
         let entry = self.buffers.entry(key).or_insert_with(|| {
-            Self::new_channel_buffer(self.max_buffer_size, Some(Self::new_stream_id()), 1, now)
+            Self::new_channel_buffer(self.default_limits, Some(Self::new_stream_id()), 1, now)
         });
 
         let mut state = entry.state.lock();
@@ -196,12 +207,32 @@ impl ReplayBuffer {
         stream_id: Option<&str>,
         serial: u64,
         message_bytes: Bytes,
+    ) {
+        self.store_with_limits(
+            app_id,
+            channel,
+            stream_id,
+            serial,
+            message_bytes,
+            self.default_limits,
+        );
+    }
+
+    /// Store a serialized message, bounding the channel's buffer by `limits` from now on.
+    pub fn store_with_limits(
+        &self,
+        app_id: &str,
+        channel: &str,
+        stream_id: Option<&str>,
+        serial: u64,
+        message_bytes: Bytes,
+        limits: ReplayLimits,
     ) {
         let key = Self::buffer_key(app_id, channel);
         let now = Instant::now();
         let entry = self.buffers.entry(key).or_insert_with(|| {
             Self::new_channel_buffer(
-                self.max_buffer_size,
+                limits,
                 stream_id.map(ToString::to_string),
                 serial.saturating_add(1),
                 now,
@@ -211,9 +242,10 @@ impl ReplayBuffer {
         let mut state = entry.state.lock();
         state.current_stream_id = stream_id.map(ToString::to_string);
         state.last_touched = now;
+        state.limits = limits;
         Self::raise_next_serial(entry.value(), serial.saturating_add(1));
         // Evict oldest if at capacity
-        while state.messages.len() >= self.max_buffer_size {
+        while state.messages.len() >= limits.max_buffer_size.max(1) {
             state.messages.pop_front();
         }
         state.messages.push_back(BufferedMessage {
@@ -264,10 +296,11 @@ impl ReplayBuffer {
             };
         }
 
-        Self::prune_expired_locked(&mut state.messages, self.buffer_ttl, now);
+        let buffer_ttl = state.limits.buffer_ttl;
+        Self::prune_expired_locked(&mut state.messages, buffer_ttl, now);
 
         if state.messages.is_empty() {
-            if now.duration_since(state.last_touched) >= self.buffer_ttl {
+            if now.duration_since(state.last_touched) >= buffer_ttl {
                 return ReplayLookup::Expired;
             }
             // No buffered messages — client is either up-to-date or buffer was evicted.
@@ -303,17 +336,16 @@ impl ReplayBuffer {
         ReplayLookup::Recovered(result)
     }
 
-    /// Evict messages older than `buffer_ttl` and remove empty channel buffers.
+    /// Evict messages older than each buffer's TTL and remove empty channel buffers.
     pub fn evict_expired(&self) {
         let now = Instant::now();
         let mut empty_keys = Vec::new();
 
         for entry in self.buffers.iter() {
             let mut state = entry.value().state.lock();
-            Self::prune_expired_locked(&mut state.messages, self.buffer_ttl, now);
-            if state.messages.is_empty()
-                && now.duration_since(state.last_touched) >= self.buffer_ttl
-            {
+            let buffer_ttl = state.limits.buffer_ttl;
+            Self::prune_expired_locked(&mut state.messages, buffer_ttl, now);
+            if state.messages.is_empty() && now.duration_since(state.last_touched) >= buffer_ttl {
                 empty_keys.push(entry.key().clone());
             }
         }
@@ -323,7 +355,7 @@ impl ReplayBuffer {
             self.buffers.remove_if(&key, |_, v| {
                 let state = v.state.lock();
                 state.messages.is_empty()
-                    && now.duration_since(state.last_touched) >= self.buffer_ttl
+                    && now.duration_since(state.last_touched) >= state.limits.buffer_ttl
             });
         }
     }
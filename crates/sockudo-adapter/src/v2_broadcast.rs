@@ -11,7 +11,7 @@ use sockudo_core::namespace::Namespace;
 use sockudo_core::utils::{is_wildcard_subscription_pattern, wildcard_pattern_matches};
 #[cfg(feature = "tag-filtering")]
 use sockudo_core::websocket::SocketId;
-use sockudo_core::websocket::WebSocketRef;
+use sockudo_core::websocket::{DeliveryWindow, WebSocketRef};
 use sockudo_protocol::messages::PusherMessage;
 
 /// Prepare and serialize a V2 message (rewrite prefix, keep serial/message_id).
@@ -148,6 +148,24 @@ pub(crate) fn apply_event_name_filter_in_place(
     sockets.retain(|socket| event_name_filter_allows(socket, channel, event_name));
 }
 
+/// Move sockets whose subscription paces `channel` out of `sockets`, paired with their
+/// delivery window.
+pub(crate) fn take_paced_sockets(
+    channel: &str,
+    sockets: &mut Vec<WebSocketRef>,
+) -> Vec<(WebSocketRef, DeliveryWindow)> {
+    let mut paced = Vec::new();
+    let mut index = 0;
+    while index < sockets.len() {
+        if let Some(window) = sockets[index].delivery_window_for(channel) {
+            paced.push((sockets.swap_remove(index), window));
+        } else {
+            index += 1;
+        }
+    }
+    paced
+}
+
 /// Strip tags from a message if tag inclusion is disabled for the channel.
 #[cfg(feature = "tag-filtering")]
 pub(crate) fn strip_tags_if_disabled(message: PusherMessage, enable_tags: bool) -> PusherMessage {
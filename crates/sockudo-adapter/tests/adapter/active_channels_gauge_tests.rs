@@ -41,7 +41,7 @@ async fn register_socket(adapter: &LocalAdapter, app_manager: Arc<MemoryAppManag
     adapter
         .add_socket(
             socket_id,
-            make_writer().await,
+            make_writer().await.into(),
             APP_ID,
             app_manager as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
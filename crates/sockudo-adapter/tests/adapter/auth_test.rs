@@ -2,7 +2,7 @@ use chrono::Utc;
 use sockudo_adapter::handler::types::SignInRequest;
 use sockudo_app::MemoryAppManager;
 use sockudo_core::app::AppManager;
-use sockudo_core::app::{App, AppPolicy};
+use sockudo_core::app::{ApiKeyScope, App, AppApiKey, AppPolicy};
 use sockudo_core::auth::AuthValidator;
 use sockudo_core::auth::EventQuery;
 use sockudo_core::error::Error;
@@ -436,6 +436,150 @@ async fn test_api_auth_rejects_route_app_id_mismatch() {
     }
 }
 
+async fn create_scoped_key_app_manager() -> Arc<dyn AppManager> {
+    let manager = MemoryAppManager::new();
+    let mut app = test_app("test-app-id", "test-app-key", "test-app-secret");
+    app.policy.api_keys = Some(vec![
+        AppApiKey {
+            key: "publisher-key".to_string(),
+            secret: "publisher-secret".to_string(),
+            scopes: vec![ApiKeyScope::Publish],
+            channels: Some(vec!["orders-*".to_string()]),
+        },
+        AppApiKey {
+            key: "reader-key".to_string(),
+            secret: "reader-secret".to_string(),
+            scopes: vec![ApiKeyScope::Read],
+            channels: None,
+        },
+    ]);
+    manager.create_app(app).await.unwrap();
+    Arc::new(manager)
+}
+
+async fn scoped_key_request(
+    key: &str,
+    secret: &str,
+    method: &str,
+    request_path: &str,
+    body: Option<&[u8]>,
+) -> Result<bool, Error> {
+    let auth_validator = AuthValidator::new(create_scoped_key_app_manager().await);
+    let current_timestamp = Utc::now().timestamp().to_string();
+    let mut query_params = BTreeMap::new();
+    query_params.insert("auth_key".to_string(), key.to_string());
+    query_params.insert("auth_timestamp".to_string(), current_timestamp.clone());
+    let body_md5 = body
+        .map(|body| format!("{:x}", md5::compute(body)))
+        .unwrap_or_default();
+    if body.is_some() {
+        query_params.insert("body_md5".to_string(), body_md5.clone());
+    }
+
+    let signature = generate_valid_signature(key, secret, method, request_path, &query_params);
+    let auth_query = EventQuery {
+        auth_key: key.to_string(),
+        auth_timestamp: current_timestamp,
+        auth_version: "1.0".to_string(),
+        body_md5,
+        auth_signature: signature,
+    };
+
+    auth_validator
+        .validate_pusher_api_request(&auth_query, method, request_path, &query_params, body)
+        .await
+}
+
+#[tokio::test]
+async fn test_api_auth_scoped_key_publishes_to_allowed_channels() {
+    let body = br#"{"name":"created","channels":["orders-1","orders-2"],"data":"{}"}"#;
+    let result = scoped_key_request(
+        "publisher-key",
+        "publisher-secret",
+        "POST",
+        "/apps/test-app-id/events",
+        Some(body),
+    )
+    .await;
+
+    assert!(result.unwrap());
+}
+
+#[tokio::test]
+async fn test_api_auth_scoped_key_rejects_channel_outside_patterns() {
+    let body = br#"{"batch":[{"name":"created","channel":"orders-1","data":"{}"},{"name":"created","channel":"billing","data":"{}"}]}"#;
+    let result = scoped_key_request(
+        "publisher-key",
+        "publisher-secret",
+        "POST",
+        "/apps/test-app-id/batch_events",
+        Some(body),
+    )
+    .await;
+
+    match result {
+        Err(Error::Forbidden(msg)) => assert!(msg.contains("billing")),
+        other => panic!("Expected Forbidden error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_api_auth_scoped_key_requires_route_scope() {
+    let read = scoped_key_request(
+        "reader-key",
+        "reader-secret",
+        "GET",
+        "/apps/test-app-id/channels/orders-1/history",
+        None,
+    )
+    .await;
+    assert!(read.unwrap());
+
+    let purge = scoped_key_request(
+        "reader-key",
+        "reader-secret",
+        "POST",
+        "/apps/test-app-id/channels/orders-1/history/purge",
+        Some(b"{}"),
+    )
+    .await;
+    assert!(matches!(purge, Err(Error::Forbidden(_))));
+
+    let objects = scoped_key_request(
+        "publisher-key",
+        "publisher-secret",
+        "POST",
+        "/apps/test-app-id/channels/orders-1/objects",
+        Some(br#"{"operations":[]}"#),
+    )
+    .await;
+    assert!(objects.unwrap());
+
+    let publish = scoped_key_request(
+        "publisher-key",
+        "publisher-secret",
+        "POST",
+        "/apps/test-app-id/users/user-1/terminate_connections",
+        Some(b"{}"),
+    )
+    .await;
+    assert!(matches!(publish, Err(Error::Forbidden(_))));
+}
+
+#[tokio::test]
+async fn test_api_auth_scoped_key_rejects_primary_secret() {
+    let result = scoped_key_request(
+        "reader-key",
+        "test-app-secret",
+        "GET",
+        "/apps/test-app-id/channels",
+        None,
+    )
+    .await;
+
+    assert!(matches!(result, Err(Error::Auth(_))));
+}
+
 #[tokio::test]
 async fn test_api_auth_post_with_body_md5() {
     let app_manager = create_test_app_manager().await;
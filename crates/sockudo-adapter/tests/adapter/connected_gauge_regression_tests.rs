@@ -11,6 +11,7 @@ use async_trait::async_trait;
 use sockudo_adapter::ConnectionManager;
 use sockudo_adapter::connection_manager::{ChannelSocketCount, HorizontalAdapterInterface};
 use sockudo_adapter::handler::ConnectionHandler;
+use sockudo_adapter::handler::connect_proxy::ConnectContext;
 use sockudo_adapter::local_adapter::LocalAdapter;
 use sockudo_app::memory_app_manager::MemoryAppManager;
 use sockudo_core::app::{App, AppLimitsPolicy, AppManager, AppPolicy};
@@ -20,7 +21,7 @@ use sockudo_core::error::{Error, Result};
 use sockudo_core::metrics::MetricsInterface;
 use sockudo_core::namespace::Namespace;
 use sockudo_core::options::ServerOptions;
-use sockudo_core::websocket::{SocketId, WebSocketBufferConfig, WebSocketRef};
+use sockudo_core::websocket::{SocketId, TransportWriter, WebSocketBufferConfig, WebSocketRef};
 use sockudo_protocol::messages::PusherMessage;
 use sockudo_protocol::{ProtocolVersion, WireFormat};
 use sockudo_ws::axum_integration::{WebSocket, WebSocketWriter};
@@ -127,7 +128,7 @@ impl ConnectionManager for FullCapacityAdapter {
     async fn add_socket(
         &self,
         _: SocketId,
-        _: WebSocketWriter,
+        _: TransportWriter,
         _: &str,
         _: Arc<dyn AppManager + Send + Sync>,
         _: WebSocketBufferConfig,
@@ -375,7 +376,7 @@ async fn connected_gauge_setup_failure_decrements_on_cleanup() {
     adapter
         .add_socket(
             socket_id,
-            writer,
+            writer.into(),
             APP_ID,
             app_manager.clone() as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -421,7 +422,7 @@ async fn connected_gauge_no_double_decrement_on_close_and_cleanup() {
     adapter
         .add_socket(
             socket_id,
-            writer,
+            writer.into(),
             APP_ID,
             app_manager.clone() as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -457,7 +458,7 @@ async fn connected_gauge_socket_replacement_nets_zero() {
     adapter
         .add_socket(
             socket_id,
-            writer1,
+            writer1.into(),
             APP_ID,
             app_manager.clone() as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -484,7 +485,7 @@ async fn connected_gauge_socket_replacement_nets_zero() {
     adapter
         .add_socket(
             socket_id,
-            writer2,
+            writer2.into(),
             APP_ID,
             app_manager.clone() as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -539,6 +540,7 @@ async fn connected_gauge_over_quota_never_increments() {
             WireFormat::Json,
             true,
             None,
+            ConnectContext::default(),
         )
         .await;
 
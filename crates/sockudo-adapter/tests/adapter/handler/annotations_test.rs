@@ -315,7 +315,7 @@ async fn connect_v2_socket(harness: &AnnotationHarness) -> (SocketId, ClientRead
         .adapter
         .add_socket(
             socket_id,
-            server_writer,
+            server_writer.into(),
             &harness.app.id,
             harness.app_manager.clone() as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -364,6 +364,7 @@ fn subscribe_request(annotation_subscribe: bool) -> SubscriptionRequest {
         rewind: None,
         event_name_filter: None,
         annotation_subscribe,
+        delivery: None,
     }
 }
 
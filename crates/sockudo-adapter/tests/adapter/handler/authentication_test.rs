@@ -21,6 +21,7 @@ async fn test_verify_channel_authentication_public_channel() {
         rewind: None,
         event_name_filter: None,
         annotation_subscribe: false,
+        delivery: None,
     };
 
     let result = handler
@@ -45,6 +46,7 @@ async fn test_verify_channel_authentication_private_channel_no_auth() {
         rewind: None,
         event_name_filter: None,
         annotation_subscribe: false,
+        delivery: None,
     };
 
     let result = handler
@@ -96,6 +98,7 @@ async fn test_verify_channel_authentication_private_channel_with_auth() {
         rewind: None,
         event_name_filter: None,
         annotation_subscribe: false,
+        delivery: None,
     };
 
     let result = handler
@@ -150,6 +153,7 @@ async fn test_verify_channel_authentication_presence_channel() {
         rewind: None,
         event_name_filter: None,
         annotation_subscribe: false,
+        delivery: None,
     };
 
     let result = handler
@@ -0,0 +1,147 @@
+use crate::mocks::transport_session_mock::{
+    drain, handler_with_options, open_session, send, token,
+};
+use sockudo_adapter::ConnectionHandler;
+use sockudo_adapter::handler::transport_session::TransportSession;
+use sockudo_core::app::{App, AppLimitsPolicy, AppPolicy};
+use sockudo_core::options::ServerOptions;
+use sonic_rs::{JsonContainerTrait, JsonValueTrait, Value, json};
+
+const CHANNEL: &str = "poll-room";
+
+fn app() -> App {
+    App::from_policy(
+        "app".to_string(),
+        "app-key".to_string(),
+        "app-secret".to_string(),
+        true,
+        AppPolicy {
+            limits: AppLimitsPolicy {
+                max_connections: 100,
+                max_client_events_per_second: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+}
+
+fn options() -> ServerOptions {
+    let mut options = ServerOptions::default();
+    options.channel_objects.enabled = true;
+    options
+}
+
+async fn subscribe(handler: &ConnectionHandler, session: &mut TransportSession) -> Vec<Value> {
+    send(
+        handler,
+        session,
+        json!({ "event": "sockudo:subscribe", "data": { "channel": CHANNEL } }),
+    )
+    .await
+}
+
+fn vote_frame() -> Value {
+    json!({
+        "event": "sockudo:object",
+        "data": {
+            "channel": CHANNEL,
+            "operations": [{ "op": "counter.increment", "object": "votes" }]
+        }
+    })
+}
+
+fn find<'a>(events: &'a [Value], event: &str) -> Option<&'a Value> {
+    events
+        .iter()
+        .find(|candidate| candidate["event"].as_str() == Some(event))
+}
+
+#[tokio::test]
+async fn object_operations_fan_out_and_late_joiners_get_a_snapshot() {
+    let app = app();
+    let handler = handler_with_options(&app, options()).await;
+    let capability = r#"{"poll-*":["subscribe","object-publish"]}"#;
+    let mut voter = open_session(&handler, token(capability, "voter", "jti-1", 60)).await;
+    let mut watcher = open_session(&handler, token(capability, "watcher", "jti-2", 60)).await;
+
+    let events = subscribe(&handler, &mut voter).await;
+    assert!(find(&events, "sockudo_internal:object_snapshot").is_none());
+    subscribe(&handler, &mut watcher).await;
+
+    let events = send(&handler, &mut voter, vote_frame()).await;
+    let delta = find(&events, "sockudo_internal:object").expect("voter sees its own delta");
+    assert_eq!(delta["channel"].as_str(), Some(CHANNEL));
+    assert_eq!(delta["data"]["serial"].as_u64(), Some(1));
+    assert_eq!(delta["data"]["client_id"].as_str(), Some("voter"));
+    assert_eq!(
+        delta["data"]["operations"][0]["op"].as_str(),
+        Some("counter.increment")
+    );
+    let events = drain(&mut watcher).await;
+    assert!(find(&events, "sockudo_internal:object").is_some());
+
+    send(&handler, &mut voter, vote_frame()).await;
+
+    let mut late = open_session(&handler, token(capability, "late", "jti-3", 60)).await;
+    let events = subscribe(&handler, &mut late).await;
+    let snapshot =
+        find(&events, "sockudo_internal:object_snapshot").expect("late joiner gets a snapshot");
+    assert_eq!(snapshot["data"]["serial"].as_u64(), Some(2));
+    assert_eq!(
+        snapshot["data"]["objects"]["votes"]["type"].as_str(),
+        Some("counter")
+    );
+    assert_eq!(
+        snapshot["data"]["objects"]["votes"]["value"].as_i64(),
+        Some(2)
+    );
+    assert_eq!(snapshot["data"]["objects"].as_object().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn object_operations_require_the_object_publish_capability() {
+    let app = app();
+    let handler = handler_with_options(&app, options()).await;
+    let mut reader = open_session(
+        &handler,
+        token(r#"{"poll-*":["subscribe"]}"#, "reader", "jti-1", 60),
+    )
+    .await;
+
+    subscribe(&handler, &mut reader).await;
+    let events = send(&handler, &mut reader, vote_frame()).await;
+
+    assert!(find(&events, "sockudo:error").is_some());
+    assert!(find(&events, "sockudo_internal:object").is_none());
+    let snapshot = handler
+        .channel_object_store()
+        .snapshot(&app.id, CHANNEL)
+        .await
+        .unwrap();
+    assert!(snapshot.is_empty());
+}
+
+#[tokio::test]
+async fn object_operations_over_the_configured_limits_are_refused() {
+    let app = app();
+    let mut options = options();
+    options.channel_objects.max_objects_per_channel = 1;
+    let handler = handler_with_options(&app, options).await;
+    let increment = |object: &str| {
+        sonic_rs::from_value(&json!({ "op": "counter.increment", "object": object })).unwrap()
+    };
+
+    handler
+        .apply_channel_object_operations(&app, CHANNEL, vec![increment("votes")], None)
+        .await
+        .unwrap();
+    let refused = handler
+        .apply_channel_object_operations(&app, CHANNEL, vec![increment("skips")], None)
+        .await;
+
+    assert!(matches!(
+        refused,
+        Err(sockudo_core::error::Error::InvalidMessageFormat(_))
+    ));
+}
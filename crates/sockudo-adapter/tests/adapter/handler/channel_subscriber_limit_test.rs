@@ -0,0 +1,89 @@
+use crate::mocks::transport_session_mock::{handler_with_app, open_session, send, token};
+use sockudo_adapter::ConnectionHandler;
+use sockudo_adapter::handler::transport_session::TransportSession;
+use sockudo_core::app::{App, AppLimitsPolicy, AppPolicy};
+use sonic_rs::{JsonValueTrait, Value, json};
+
+const CAPABILITY: &str = r#"{"*":["subscribe"]}"#;
+
+fn app() -> App {
+    App::from_policy(
+        "app".to_string(),
+        "app-key".to_string(),
+        "app-secret".to_string(),
+        true,
+        AppPolicy {
+            limits: AppLimitsPolicy {
+                max_connections: 100,
+                max_client_events_per_second: 10,
+                max_subscribers_per_channel: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+}
+
+async fn subscribe(
+    handler: &ConnectionHandler,
+    session: &mut TransportSession,
+    channel: &str,
+) -> Vec<Value> {
+    send(
+        handler,
+        session,
+        json!({ "event": "sockudo:subscribe", "data": { "channel": channel } }),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn subscriptions_over_the_channel_limit_are_rejected() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut first = open_session(&handler, token(CAPABILITY, "client-1", "jti-1", 60)).await;
+    let mut second = open_session(&handler, token(CAPABILITY, "client-2", "jti-2", 60)).await;
+
+    let events = subscribe(&handler, &mut first, "room-a").await;
+    assert_eq!(
+        events[0]["event"].as_str(),
+        Some("sockudo_internal:subscription_succeeded")
+    );
+
+    let events = subscribe(&handler, &mut second, "room-a").await;
+    assert_eq!(
+        events[0]["event"].as_str(),
+        Some("sockudo:subscription_error")
+    );
+    assert_eq!(events[0]["channel"].as_str(), Some("room-a"));
+    assert_eq!(events[0]["data"]["status"].as_u64(), Some(4304));
+
+    let connection = handler
+        .connection_manager()
+        .get_connection(&second.socket_id, &app.id)
+        .await
+        .unwrap();
+    assert!(!connection.is_subscribed_to("room-a").await);
+
+    let events = subscribe(&handler, &mut second, "room-b").await;
+    assert_eq!(
+        events[0]["event"].as_str(),
+        Some("sockudo_internal:subscription_succeeded")
+    );
+}
+
+#[tokio::test]
+async fn existing_subscribers_can_resubscribe_at_the_limit() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = open_session(&handler, token(CAPABILITY, "client-1", "jti-1", 60)).await;
+
+    subscribe(&handler, &mut session, "room-a").await;
+    let events = subscribe(&handler, &mut session, "room-a").await;
+
+    assert!(
+        events
+            .iter()
+            .all(|event| event["event"].as_str() != Some("sockudo:subscription_error"))
+    );
+}
@@ -305,7 +305,7 @@ async fn connect_v2_socket(node: &ClusterNode, app: &App) -> (SocketId, ClientRe
     node.adapter
         .add_socket(
             socket_id,
-            server_writer,
+            server_writer.into(),
             &app.id,
             node.app_manager.clone() as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -482,6 +482,7 @@ async fn publish_on_node_a_reaches_subscriber_on_node_b_via_redis() {
                 rewind: None,
                 event_name_filter: None,
                 annotation_subscribe: false,
+                delivery: None,
             },
         )
         .await
@@ -545,6 +546,7 @@ async fn fresh_node_with_empty_replay_buffer_uses_cold_recovery_after_cross_node
                 rewind: None,
                 event_name_filter: None,
                 annotation_subscribe: false,
+                delivery: None,
             },
         )
         .await
@@ -818,6 +820,7 @@ async fn versioned_cold_recovery_replays_mutations_across_nodes_via_redis() {
                 rewind: None,
                 event_name_filter: None,
                 annotation_subscribe: false,
+                delivery: None,
             },
         )
         .await
@@ -1051,6 +1054,7 @@ async fn degraded_durable_state_causes_explicit_cross_node_recovery_failure_via_
                 rewind: None,
                 event_name_filter: None,
                 annotation_subscribe: false,
+                delivery: None,
             },
         )
         .await
@@ -1208,6 +1212,7 @@ async fn rewind_live_handoff_across_nodes_has_no_gap_and_no_duplicates_via_redis
         rewind: Some(SubscriptionRewind::Count(2)),
         event_name_filter: None,
         annotation_subscribe: false,
+        delivery: None,
     };
 
     let (started_rx, continue_tx) = history_store.arm_gate().await;
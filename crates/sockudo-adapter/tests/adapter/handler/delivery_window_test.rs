@@ -0,0 +1,176 @@
+use crate::mocks::transport_session_mock::{drain, handler_with_app, open_session, send, token};
+use sockudo_adapter::ConnectionHandler;
+use sockudo_adapter::handler::transport_session::TransportSession;
+use sockudo_core::app::{App, AppLimitsPolicy, AppPolicy};
+use sockudo_protocol::messages::{MessageExtras, MessagePriority, PusherMessage};
+use sonic_rs::{JsonContainerTrait, JsonValueTrait, Value, json};
+
+const CAPABILITY: &str = r#"{"*":["subscribe"]}"#;
+
+fn app() -> App {
+    App::from_policy(
+        "app".to_string(),
+        "app-key".to_string(),
+        "app-secret".to_string(),
+        true,
+        AppPolicy {
+            limits: AppLimitsPolicy {
+                max_connections: 100,
+                max_client_events_per_second: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+}
+
+async fn subscribe(handler: &ConnectionHandler, delivery: Value) -> TransportSession {
+    let mut session = open_session(handler, token(CAPABILITY, "client-1", "jti-1", 60)).await;
+    let events = send(
+        handler,
+        &mut session,
+        json!({
+            "event": "sockudo:subscribe",
+            "data": { "channel": "prices", "delivery": delivery },
+        }),
+    )
+    .await;
+    assert_eq!(
+        events[0]["event"].as_str(),
+        Some("sockudo_internal:subscription_succeeded")
+    );
+    session
+}
+
+async fn publish_ticks(handler: &ConnectionHandler, ticks: std::ops::RangeInclusive<u64>) {
+    for n in ticks {
+        handler
+            .connection_manager()
+            .send(
+                "prices",
+                PusherMessage::channel_event("price", "prices", json!({ "n": n })),
+                None,
+                "app",
+                None,
+            )
+            .await
+            .unwrap();
+    }
+}
+
+fn tick(event: &Value) -> u64 {
+    let data: Value = sonic_rs::from_str(event["data"].as_str().unwrap()).unwrap();
+    data["n"].as_u64().unwrap()
+}
+
+#[tokio::test]
+async fn batched_subscriptions_receive_one_frame_per_window() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = subscribe(&handler, json!({ "mode": "batch", "window_ms": 50 })).await;
+
+    publish_ticks(&handler, 1..=5).await;
+
+    let events = drain(&mut session).await;
+    assert_eq!(events.len(), 2);
+    assert_eq!(tick(&events[0]), 1);
+    assert_eq!(events[1]["event"].as_str(), Some("sockudo:batch"));
+    assert_eq!(events[1]["channel"].as_str(), Some("prices"));
+    let batch: Value = sonic_rs::from_str(events[1]["data"].as_str().unwrap()).unwrap();
+    let ticks: Vec<u64> = batch.as_array().unwrap().iter().map(tick).collect();
+    assert_eq!(ticks, vec![2, 3, 4, 5]);
+}
+
+#[tokio::test]
+async fn latest_only_subscriptions_receive_the_newest_message_per_window() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = subscribe(&handler, json!({ "mode": "latest", "window_ms": 50 })).await;
+
+    publish_ticks(&handler, 1..=5).await;
+
+    let events = drain(&mut session).await;
+    let ticks: Vec<u64> = events.iter().map(tick).collect();
+    assert_eq!(ticks, vec![1, 5]);
+}
+
+#[tokio::test]
+async fn high_priority_messages_skip_the_window() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = subscribe(&handler, json!({ "mode": "batch", "window_ms": 50 })).await;
+
+    publish_ticks(&handler, 1..=2).await;
+    let mut alert = PusherMessage::channel_event("alert", "prices", json!({ "n": 0 }));
+    alert.extras = Some(MessageExtras {
+        priority: Some(MessagePriority::High),
+        ..Default::default()
+    });
+    handler
+        .connection_manager()
+        .send("prices", alert, None, "app", None)
+        .await
+        .unwrap();
+
+    let events = drain(&mut session).await;
+    let names: Vec<&str> = events
+        .iter()
+        .map(|event| event["event"].as_str().unwrap())
+        .collect();
+    // The alert may also overtake the first tick, but never waits for the window.
+    assert_eq!(names.len(), 3);
+    assert_eq!(names[2], "price");
+    assert_eq!(tick(&events[2]), 2);
+}
+
+#[tokio::test]
+async fn published_priority_survives_extras_sanitization() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = subscribe(&handler, json!({ "mode": "batch", "window_ms": 50 })).await;
+
+    publish_ticks(&handler, 1..=2).await;
+    let mut alert = PusherMessage::channel_event("alert", "prices", json!({ "n": 0 }));
+    alert.extras = Some(MessageExtras {
+        priority: Some(MessagePriority::High),
+        ..Default::default()
+    });
+    handler
+        .publish_to_channel_with_timing(&app, "prices", alert, None, None, false)
+        .await
+        .unwrap();
+
+    let events = drain(&mut session).await;
+    let names: Vec<&str> = events
+        .iter()
+        .map(|event| event["event"].as_str().unwrap())
+        .collect();
+    assert_eq!(names.len(), 3);
+    assert!(names[..2].contains(&"alert"));
+    assert_eq!(names[2], "price");
+}
+
+#[tokio::test]
+async fn out_of_range_windows_are_rejected() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = open_session(&handler, token(CAPABILITY, "client-1", "jti-1", 60)).await;
+
+    let events = send(
+        &handler,
+        &mut session,
+        json!({
+            "event": "sockudo:subscribe",
+            "data": { "channel": "prices", "delivery": { "window_ms": 1 } },
+        }),
+    )
+    .await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event"].as_str(), Some("sockudo:error"));
+    assert!(
+        events[0]["data"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("delivery.window_ms")
+    );
+}
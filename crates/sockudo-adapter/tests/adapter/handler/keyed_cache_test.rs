@@ -0,0 +1,287 @@
+use crate::mocks::transport_session_mock::drain;
+use ahash::AHashMap;
+use async_trait::async_trait;
+use sockudo_adapter::handler::transport_session::TransportSessionOptions;
+use sockudo_adapter::local_adapter::LocalAdapter;
+use sockudo_adapter::{ConnectionHandler, ConnectionHandlerBuilder};
+use sockudo_app::memory_app_manager::MemoryAppManager;
+use sockudo_core::app::{App, AppChannelsPolicy, AppLimitsPolicy, AppManager, AppPolicy};
+use sockudo_core::cache::CacheManager;
+use sockudo_core::delta_types::{ChannelDeltaConfig, ChannelDeltaSettings};
+use sockudo_core::error::Result;
+use sockudo_core::options::ServerOptions;
+use sockudo_protocol::ProtocolVersion;
+use sockudo_protocol::messages::PusherMessage;
+use sonic_rs::{JsonValueTrait, Value, json};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Default)]
+struct MemoryCache {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+#[async_trait]
+impl CacheManager for MemoryCache {
+    async fn has(&self, key: &str) -> Result<bool> {
+        Ok(self.entries.lock().unwrap().contains_key(key))
+    }
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.entries.lock().unwrap().get(key).cloned())
+    }
+    async fn set(&self, key: &str, value: &str, _ttl_seconds: u64) -> Result<()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+    async fn remove(&self, key: &str) -> Result<()> {
+        self.entries.lock().unwrap().remove(key);
+        Ok(())
+    }
+    async fn disconnect(&self) -> Result<()> {
+        Ok(())
+    }
+    async fn ttl(&self, _key: &str) -> Result<Option<Duration>> {
+        Ok(None)
+    }
+}
+
+fn app() -> App {
+    let mut channel_delta = AHashMap::new();
+    channel_delta.insert(
+        "cache-prices".to_string(),
+        ChannelDeltaConfig::Full(ChannelDeltaSettings {
+            conflation_key: Some("symbol".to_string()),
+            ..Default::default()
+        }),
+    );
+    App::from_policy(
+        "app".to_string(),
+        "app-key".to_string(),
+        "app-secret".to_string(),
+        true,
+        AppPolicy {
+            limits: AppLimitsPolicy {
+                max_connections: 100,
+                max_client_events_per_second: 10,
+                ..Default::default()
+            },
+            channels: AppChannelsPolicy {
+                channel_delta_compression: Some(channel_delta),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+}
+
+async fn handler_with_app(app: &App) -> (Arc<ConnectionHandler>, Arc<MemoryCache>) {
+    let app_manager = Arc::new(MemoryAppManager::new());
+    app_manager.create_app(app.clone()).await.unwrap();
+    let cache = Arc::new(MemoryCache::default());
+    let handler = ConnectionHandlerBuilder::new(
+        app_manager,
+        Arc::new(LocalAdapter::new()),
+        cache.clone(),
+        ServerOptions::default(),
+    )
+    .build();
+    (Arc::new(handler), cache)
+}
+
+async fn publish(handler: &ConnectionHandler, app: &App, channel: &str, data: Value) {
+    let payload = json!({
+        "event": "price",
+        "channel": channel,
+        "data": data.to_string(),
+    });
+    handler
+        .store_cache_payload_for_channel(app, channel, &payload.to_string(), 3600)
+        .await
+        .unwrap();
+}
+
+async fn subscribe(handler: &ConnectionHandler, channel: &str) -> Vec<Value> {
+    let mut session = handler
+        .open_transport_session(
+            "app-key",
+            TransportSessionOptions {
+                origin: None,
+                protocol_version: ProtocolVersion::V1,
+                echo_messages: true,
+                token: None,
+                connect_context: None,
+            },
+        )
+        .await
+        .unwrap();
+    drain(&mut session).await;
+
+    let frame = json!({ "event": "pusher:subscribe", "data": { "channel": channel } });
+    let message: PusherMessage = sonic_rs::from_value(&frame).unwrap();
+    handler
+        .handle_transport_message(&session.socket_id, &session.app, &message)
+        .await
+        .unwrap();
+    drain(&mut session)
+        .await
+        .into_iter()
+        .filter(|event| event["event"].as_str() != Some("pusher_internal:subscription_succeeded"))
+        .collect()
+}
+
+fn symbol_and_price(event: &Value) -> (String, u64) {
+    let data: Value = sonic_rs::from_str(event["data"].as_str().unwrap()).unwrap();
+    (
+        data["symbol"].as_str().unwrap().to_string(),
+        data["price"].as_u64().unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn late_joiners_receive_the_latest_message_of_every_conflation_key() {
+    let app = app();
+    let (handler, _) = handler_with_app(&app).await;
+
+    publish(
+        &handler,
+        &app,
+        "cache-prices",
+        json!({"symbol": "AAPL", "price": 1}),
+    )
+    .await;
+    publish(
+        &handler,
+        &app,
+        "cache-prices",
+        json!({"symbol": "MSFT", "price": 2}),
+    )
+    .await;
+    publish(
+        &handler,
+        &app,
+        "cache-prices",
+        json!({"symbol": "AAPL", "price": 3}),
+    )
+    .await;
+
+    let events = subscribe(&handler, "cache-prices").await;
+    let prices: Vec<_> = events.iter().map(symbol_and_price).collect();
+    assert_eq!(
+        prices,
+        vec![("AAPL".to_string(), 3), ("MSFT".to_string(), 2)]
+    );
+}
+
+#[tokio::test]
+async fn the_last_message_follows_the_keyed_replay_when_it_has_no_key() {
+    let app = app();
+    let (handler, _) = handler_with_app(&app).await;
+
+    publish(
+        &handler,
+        &app,
+        "cache-prices",
+        json!({"symbol": "AAPL", "price": 1}),
+    )
+    .await;
+    publish(&handler, &app, "cache-prices", json!({"status": "closed"})).await;
+
+    let events = subscribe(&handler, "cache-prices").await;
+    assert_eq!(events.len(), 2);
+    assert_eq!(symbol_and_price(&events[0]), ("AAPL".to_string(), 1));
+    assert!(events[1]["data"].as_str().unwrap().contains("closed"));
+}
+
+#[tokio::test]
+async fn channels_without_a_conflation_key_keep_a_single_cached_message() {
+    let app = app();
+    let (handler, cache) = handler_with_app(&app).await;
+
+    publish(
+        &handler,
+        &app,
+        "cache-news",
+        json!({"symbol": "AAPL", "price": 1}),
+    )
+    .await;
+    publish(
+        &handler,
+        &app,
+        "cache-news",
+        json!({"symbol": "MSFT", "price": 2}),
+    )
+    .await;
+
+    let events = subscribe(&handler, "cache-news").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(symbol_and_price(&events[0]), ("MSFT".to_string(), 2));
+    assert!(
+        !cache
+            .entries
+            .lock()
+            .unwrap()
+            .keys()
+            .any(|key| key.contains("cache-news:cache_miss:key"))
+    );
+}
+
+#[tokio::test]
+async fn clearing_the_channel_cache_drops_keyed_entries() {
+    let app = app();
+    let (handler, cache) = handler_with_app(&app).await;
+
+    publish(
+        &handler,
+        &app,
+        "cache-prices",
+        json!({"symbol": "AAPL", "price": 1}),
+    )
+    .await;
+    handler
+        .clear_cache_for_channel(&app.id, "cache-prices")
+        .await
+        .unwrap();
+
+    assert!(cache.entries.lock().unwrap().is_empty());
+    let events = subscribe(&handler, "cache-prices").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event"].as_str(), Some("pusher:cache_miss"));
+}
+
+#[tokio::test]
+async fn expired_keys_are_skipped_and_pruned_when_the_channel_is_read() {
+    let app = app();
+    let (handler, cache) = handler_with_app(&app).await;
+
+    let short_lived = json!({
+        "event": "price",
+        "channel": "cache-prices",
+        "data": json!({"symbol": "MSFT", "price": 2}).to_string(),
+    });
+    handler
+        .store_cache_payload_for_channel(&app, "cache-prices", &short_lived.to_string(), 1)
+        .await
+        .unwrap();
+    publish(
+        &handler,
+        &app,
+        "cache-prices",
+        json!({"symbol": "AAPL", "price": 1}),
+    )
+    .await;
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+
+    let events = subscribe(&handler, "cache-prices").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(symbol_and_price(&events[0]), ("AAPL".to_string(), 1));
+    let fields = cache
+        .hash_get_all("app:app:channel:cache-prices:cache_miss:keyed")
+        .await
+        .unwrap();
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].0, "AAPL");
+}
@@ -1,6 +1,13 @@
 pub mod annotations_test;
 pub mod authentication_test;
+pub mod channel_objects_test;
+pub mod channel_subscriber_limit_test;
 pub mod clustered_runtime_rewind_recovery_redis_test;
+pub mod delivery_window_test;
+pub mod keyed_cache_test;
 pub mod runtime_rewind_recovery_e2e_test;
 pub mod signin_test;
+pub mod token_refresh_test;
+pub mod user_channel_test;
+pub mod user_connections_test;
 pub mod validation_test;
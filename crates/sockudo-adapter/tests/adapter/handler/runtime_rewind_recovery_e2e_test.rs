@@ -261,7 +261,7 @@ async fn connect_v2_socket(harness: &TestHarness) -> (SocketId, ClientReader) {
         .adapter
         .add_socket(
             socket_id,
-            server_writer,
+            server_writer.into(),
             &harness.app.id,
             harness.app_manager.clone() as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -428,6 +428,7 @@ async fn subscribe_v2(
                 rewind,
                 event_name_filter: None,
                 annotation_subscribe: false,
+                delivery: None,
             },
         )
         .await
@@ -1073,6 +1074,7 @@ async fn rewind_handoff_has_no_gap_and_suppresses_duplicates_e2e() {
         rewind: Some(SubscriptionRewind::Count(2)),
         event_name_filter: None,
         annotation_subscribe: false,
+        delivery: None,
     };
 
     let (started_rx, continue_tx) = harness.history_store.arm_gate().await;
@@ -1157,6 +1159,7 @@ async fn hot_recovery_replays_real_deliveries_e2e() {
         rewind: None,
         event_name_filter: None,
         annotation_subscribe: false,
+        delivery: None,
     };
     harness
         .handler
@@ -1258,6 +1261,7 @@ async fn hot_recovery_resumes_idle_subscription_e2e() {
                 rewind: None,
                 event_name_filter: None,
                 annotation_subscribe: false,
+                delivery: None,
             },
         )
         .await
@@ -1386,6 +1390,7 @@ async fn cold_recovery_replays_real_deliveries_e2e() {
                 rewind: None,
                 event_name_filter: None,
                 annotation_subscribe: false,
+                delivery: None,
             },
         )
         .await
@@ -1448,7 +1453,7 @@ async fn cold_recovery_replays_real_deliveries_e2e() {
     adapter
         .add_socket(
             resume_socket_id,
-            server_task.await.unwrap(),
+            server_task.await.unwrap().into(),
             &harness_a.app.id,
             app_manager as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -1659,7 +1664,7 @@ async fn writer_queue_full_fault_rejects_publish_without_live_delivery() {
     adapter
         .add_socket(
             socket_id,
-            server_task.await.unwrap(),
+            server_task.await.unwrap().into(),
             &app.id,
             app_manager as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -1685,6 +1690,7 @@ async fn writer_queue_full_fault_rejects_publish_without_live_delivery() {
                 rewind: None,
                 event_name_filter: None,
                 annotation_subscribe: false,
+                delivery: None,
             },
         )
         .await
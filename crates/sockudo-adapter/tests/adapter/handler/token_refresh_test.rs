@@ -0,0 +1,117 @@
+use crate::mocks::transport_session_mock::{handler_with_app, open_session, send, token};
+use sockudo_core::app::App;
+use sonic_rs::{JsonContainerTrait, JsonValueTrait, Value, json};
+
+fn app() -> App {
+    App::from_policy(
+        "app".to_string(),
+        "app-key".to_string(),
+        "app-secret".to_string(),
+        true,
+        Default::default(),
+    )
+}
+
+#[tokio::test]
+async fn refresh_replaces_capabilities_and_drops_disallowed_subscriptions() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = open_session(
+        &handler,
+        token(r#"{"room-*":["subscribe"]}"#, "client-1", "jti-1", 60),
+    )
+    .await;
+    for channel in ["room-a", "room-b"] {
+        let events = send(
+            &handler,
+            &mut session,
+            json!({ "event": "sockudo:subscribe", "data": { "channel": channel } }),
+        )
+        .await;
+        assert_eq!(
+            events[0]["event"].as_str(),
+            Some("sockudo_internal:subscription_succeeded")
+        );
+    }
+
+    let events = send(
+        &handler,
+        &mut session,
+        json!({
+            "event": "sockudo:refresh",
+            "data": { "token": token(r#"{"room-a":["subscribe"]}"#, "client-1", "jti-2", 3600) }
+        }),
+    )
+    .await;
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event"].as_str(), Some("sockudo:refresh_success"));
+    let data = &events[0]["data"];
+    assert_eq!(data["jti"].as_str(), Some("jti-2"));
+    let unsubscribed: Vec<&str> = data["unsubscribed"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    assert_eq!(unsubscribed, ["room-b"]);
+
+    let connection = handler
+        .connection_manager()
+        .get_connection(&session.socket_id, &app.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        connection.get_token_auth_context().await.unwrap().jti,
+        "jti-2"
+    );
+    assert!(connection.is_subscribed_to("room-a").await);
+    assert!(!connection.is_subscribed_to("room-b").await);
+    assert_eq!(
+        handler
+            .connection_manager()
+            .get_channel_socket_count(&app.id, "room-b")
+            .await,
+        0
+    );
+
+    let events = send(
+        &handler,
+        &mut session,
+        json!({ "event": "sockudo:subscribe", "data": { "channel": "room-b" } }),
+    )
+    .await;
+    assert_eq!(events[0]["event"].as_str(), Some("sockudo:error"));
+}
+
+#[tokio::test]
+async fn refresh_rejects_a_token_for_another_client() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = open_session(
+        &handler,
+        token(r#"{"*":["subscribe"]}"#, "client-1", "jti-1", 60),
+    )
+    .await;
+
+    let events = send(
+        &handler,
+        &mut session,
+        json!({
+            "event": "sockudo:refresh",
+            "data": { "token": token(r#"{"*":["subscribe"]}"#, "client-2", "jti-2", 3600) }
+        }),
+    )
+    .await;
+
+    assert_eq!(events[0]["event"].as_str(), Some("sockudo:error"));
+    let connection = handler
+        .connection_manager()
+        .get_connection(&session.socket_id, &app.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        connection.get_token_auth_context().await.unwrap().client_id,
+        "client-1"
+    );
+}
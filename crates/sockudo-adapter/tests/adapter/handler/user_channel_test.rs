@@ -0,0 +1,130 @@
+use crate::mocks::transport_session_mock::{
+    drain, handler_with_app, open_session, send, token, try_open_session,
+};
+use sockudo_adapter::handler::transport_session::TransportSessionOptions;
+use sockudo_core::app::{App, AppPolicy, AppUserChannelConfig};
+use sockudo_protocol::ProtocolVersion;
+use sonic_rs::{JsonContainerTrait, JsonValueTrait, json};
+
+fn app() -> App {
+    App::from_policy(
+        "app".to_string(),
+        "app-key".to_string(),
+        "app-secret".to_string(),
+        true,
+        AppPolicy {
+            user_channel: Some(AppUserChannelConfig::default()),
+            ..Default::default()
+        },
+    )
+}
+
+#[tokio::test]
+async fn token_connections_join_their_personal_channel() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = try_open_session(
+        &handler,
+        token(r#"{"room-*":["subscribe"]}"#, "client-1", "jti-1", 60),
+    )
+    .await
+    .unwrap();
+    let events = drain(&mut session).await;
+
+    assert_eq!(
+        events[0]["event"].as_str(),
+        Some("sockudo:connection_established")
+    );
+    assert_eq!(
+        events[1]["event"].as_str(),
+        Some("sockudo_internal:subscription_succeeded")
+    );
+    assert_eq!(events[1]["channel"].as_str(), Some("#user:client-1"));
+
+    let events = send(
+        &handler,
+        &mut session,
+        json!({
+            "event": "sockudo:refresh",
+            "data": { "token": token(r#"{"room-a":["subscribe"]}"#, "client-1", "jti-2", 3600) }
+        }),
+    )
+    .await;
+    assert_eq!(events[0]["event"].as_str(), Some("sockudo:refresh_success"));
+    assert!(
+        events[0]["data"]["unsubscribed"]
+            .as_array()
+            .unwrap()
+            .is_empty()
+    );
+
+    let connection = handler
+        .connection_manager()
+        .get_connection(&session.socket_id, &app.id)
+        .await
+        .unwrap();
+    assert!(connection.is_subscribed_to("#user:client-1").await);
+}
+
+#[tokio::test]
+async fn personal_channels_reject_other_users() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = open_session(
+        &handler,
+        token(r#"{"*":["subscribe"]}"#, "client-2", "jti-1", 60),
+    )
+    .await;
+
+    let events = send(
+        &handler,
+        &mut session,
+        json!({ "event": "sockudo:subscribe", "data": { "channel": "#user:client-1" } }),
+    )
+    .await;
+    assert_eq!(events[0]["event"].as_str(), Some("sockudo:error"));
+
+    let connection = handler
+        .connection_manager()
+        .get_connection(&session.socket_id, &app.id)
+        .await
+        .unwrap();
+    assert!(!connection.is_subscribed_to("#user:client-1").await);
+    assert!(connection.is_subscribed_to("#user:client-2").await);
+}
+
+#[tokio::test]
+async fn refreshing_into_an_identity_joins_the_personal_channel() {
+    let app = app();
+    let handler = handler_with_app(&app).await;
+    let mut session = handler
+        .open_transport_session(
+            "app-key",
+            TransportSessionOptions {
+                origin: None,
+                protocol_version: ProtocolVersion::V2,
+                echo_messages: true,
+                token: None,
+                connect_context: None,
+            },
+        )
+        .await
+        .unwrap();
+    drain(&mut session).await;
+
+    let events = send(
+        &handler,
+        &mut session,
+        json!({
+            "event": "sockudo:refresh",
+            "data": { "token": token(r#"{"room-*":["subscribe"]}"#, "client-3", "jti-1", 60) }
+        }),
+    )
+    .await;
+    assert_eq!(events[0]["event"].as_str(), Some("sockudo:refresh_success"));
+    assert_eq!(
+        events[1]["event"].as_str(),
+        Some("sockudo_internal:subscription_succeeded")
+    );
+    assert_eq!(events[1]["channel"].as_str(), Some("#user:client-3"));
+}
@@ -0,0 +1,91 @@
+use crate::mocks::transport_session_mock::{handler_with_app, token, try_open_session};
+use sockudo_core::app::{App, AppPolicy, AppUserConnectionsPolicy, UserConnectionLimitAction};
+use sockudo_core::error::Error;
+
+fn app(on_limit: UserConnectionLimitAction) -> App {
+    App::from_policy(
+        "app".to_string(),
+        "app-key".to_string(),
+        "app-secret".to_string(),
+        true,
+        AppPolicy {
+            user_connections: Some(AppUserConnectionsPolicy {
+                max_connections: 1,
+                on_limit,
+                per_device_class: false,
+            }),
+            ..Default::default()
+        },
+    )
+}
+
+#[tokio::test]
+async fn reject_mode_refuses_a_second_connection_for_the_user() {
+    let app = app(UserConnectionLimitAction::Reject);
+    let handler = handler_with_app(&app).await;
+    let first = try_open_session(&handler, token("{}", "trader-1", "jti-1", 60))
+        .await
+        .unwrap();
+
+    let error = try_open_session(&handler, token("{}", "trader-1", "jti-2", 60))
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(error, Error::UserConnectionLimit));
+    assert_eq!(error.close_code(), 4010);
+
+    try_open_session(&handler, token("{}", "trader-2", "jti-3", 60))
+        .await
+        .unwrap();
+    assert!(
+        handler
+            .connection_manager()
+            .get_connection(&first.socket_id, &app.id)
+            .await
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn evict_mode_closes_the_oldest_connection() {
+    let app = app(UserConnectionLimitAction::EvictOldest);
+    let handler = handler_with_app(&app).await;
+    let first = try_open_session(&handler, token("{}", "trader-1", "jti-1", 60))
+        .await
+        .unwrap();
+    let second = try_open_session(&handler, token("{}", "trader-1", "jti-2", 60))
+        .await
+        .unwrap();
+
+    assert!(first.shutdown_token.unwrap().is_cancelled());
+    assert!(!second.shutdown_token.unwrap().is_cancelled());
+}
+
+#[tokio::test]
+async fn concurrent_connections_cannot_exceed_the_limit() {
+    let app = app(UserConnectionLimitAction::Reject);
+    let handler = handler_with_app(&app).await;
+
+    let results = futures::future::join_all(
+        (0..5)
+            .map(|n| try_open_session(&handler, token("{}", "trader-1", &format!("jti-{n}"), 60))),
+    )
+    .await;
+
+    assert!(results.iter().filter(|result| result.is_ok()).count() <= 1);
+    assert!(
+        results
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+            .all(|error| matches!(error, Error::UserConnectionLimit))
+    );
+    assert!(
+        handler
+            .connection_manager()
+            .get_user_socket_entries("trader-1", &app.id)
+            .await
+            .unwrap()
+            .len()
+            <= 1
+    );
+}
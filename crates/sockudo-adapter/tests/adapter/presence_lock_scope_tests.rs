@@ -31,10 +31,9 @@ use sockudo_core::presence_history::{
     PresenceHistoryEventKind, PresenceHistoryReadRequest, PresenceHistoryRetentionPolicy,
     PresenceHistoryStore,
 };
-use sockudo_core::websocket::{SocketId, WebSocketBufferConfig, WebSocketRef};
+use sockudo_core::websocket::{SocketId, TransportWriter, WebSocketBufferConfig, WebSocketRef};
 use sockudo_protocol::messages::PusherMessage;
 use sockudo_protocol::{ProtocolVersion, WireFormat};
-use sockudo_ws::axum_integration::WebSocketWriter;
 use std::any::Any;
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex as StdMutex};
@@ -72,7 +71,7 @@ impl ConnectionManager for ScriptedCM {
     async fn add_socket(
         &self,
         _socket_id: SocketId,
-        _socket: WebSocketWriter,
+        _socket: TransportWriter,
         _app_id: &str,
         _app_manager: Arc<dyn sockudo_core::app::AppManager + Send + Sync>,
         _buffer_config: WebSocketBufferConfig,
@@ -1,7 +1,7 @@
 #[cfg(feature = "recovery")]
 mod replay_buffer_regression {
     use bytes::Bytes;
-    use sockudo_adapter::replay_buffer::{ReplayBuffer, ReplayLookup};
+    use sockudo_adapter::replay_buffer::{ReplayBuffer, ReplayLimits, ReplayLookup};
     use std::sync::Arc;
     use std::time::Duration;
 
@@ -129,6 +129,41 @@ mod replay_buffer_regression {
         assert_eq!(msgs.len(), 5);
     }
 
+    #[tokio::test]
+    async fn test_per_channel_limits_override_defaults() {
+        let buf = ReplayBuffer::new(100, Duration::from_secs(60));
+        let ticker_limits = ReplayLimits {
+            max_buffer_size: 2,
+            buffer_ttl: Duration::from_millis(50),
+        };
+
+        for i in 1..=4_u64 {
+            buf.store_with_limits(
+                "app1",
+                "ticker:AAPL",
+                None,
+                i,
+                Bytes::from(format!("tick-{}", i)),
+                ticker_limits,
+            );
+            buf.store("app1", "chat", None, i, Bytes::from(format!("msg-{}", i)));
+        }
+
+        assert_eq!(
+            buf.get_messages_after("app1", "ticker:AAPL", 2)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(buf.get_messages_after("app1", "ticker:AAPL", 1).is_none());
+        assert_eq!(buf.get_messages_after("app1", "chat", 0).unwrap().len(), 4);
+
+        tokio::time::sleep(Duration::from_millis(120)).await;
+
+        assert!(buf.get_messages_after("app1", "ticker:AAPL", 2).is_none());
+        assert_eq!(buf.get_messages_after("app1", "chat", 0).unwrap().len(), 4);
+    }
+
     #[tokio::test]
     async fn test_ttl_expiry() {
         let buf = ReplayBuffer::new(100, Duration::from_millis(50));
@@ -12,10 +12,9 @@ use sockudo_core::error::Result;
 use sockudo_core::metrics::MetricsInterface;
 use sockudo_core::namespace::Namespace;
 use sockudo_core::options::ServerOptions;
-use sockudo_core::websocket::{SocketId, WebSocketRef};
+use sockudo_core::websocket::{SocketId, TransportWriter, WebSocketRef};
 use sockudo_protocol::messages::PusherMessage;
 use sockudo_protocol::wire::WireFormat;
-use sockudo_ws::axum_integration::WebSocketWriter;
 use sonic_rs::Value;
 use std::any::Any;
 use std::sync::Arc;
@@ -45,7 +44,7 @@ impl ConnectionManager for MockAdapter {
     async fn add_socket(
         &self,
         _socket_id: SocketId,
-        _socket: WebSocketWriter,
+        _socket: TransportWriter,
         _app_id: &str,
         _app_manager: Arc<dyn AppManager + Send + Sync>,
         _buffer_config: sockudo_core::websocket::WebSocketBufferConfig,
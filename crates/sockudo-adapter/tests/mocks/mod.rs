@@ -1 +1,2 @@
 pub mod connection_handler_mock;
+pub mod transport_session_mock;
@@ -0,0 +1,119 @@
+use crate::mocks::connection_handler_mock::MockCacheManager;
+use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use serde::Serialize;
+use sockudo_adapter::handler::transport_session::{TransportSession, TransportSessionOptions};
+use sockudo_adapter::local_adapter::LocalAdapter;
+use sockudo_adapter::{ConnectionHandler, ConnectionHandlerBuilder};
+use sockudo_app::memory_app_manager::MemoryAppManager;
+use sockudo_core::app::{App, AppManager};
+use sockudo_core::error::Result;
+use sockudo_core::options::ServerOptions;
+use sockudo_protocol::ProtocolVersion;
+use sockudo_protocol::messages::PusherMessage;
+use sockudo_ws::Message;
+use sonic_rs::Value;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    #[serde(rename = "x-sockudo-capability")]
+    capability: &'a str,
+    #[serde(rename = "x-sockudo-client-id")]
+    client_id: &'a str,
+    iat: i64,
+    exp: i64,
+    jti: &'a str,
+}
+
+/// A connection token for the `app-key`/`app-secret` app, valid for `lifetime` seconds.
+pub fn token(capability: &str, client_id: &str, jti: &str, lifetime: i64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let mut header = Header::new(Algorithm::HS256);
+    header.kid = Some("app-key".to_string());
+    encode(
+        &header,
+        &Claims {
+            capability,
+            client_id,
+            iat: now,
+            exp: now + lifetime,
+            jti,
+        },
+        &EncodingKey::from_secret(b"app-secret"),
+    )
+    .unwrap()
+}
+
+pub async fn handler_with_app(app: &App) -> Arc<ConnectionHandler> {
+    handler_with_options(app, ServerOptions::default()).await
+}
+
+pub async fn handler_with_options(app: &App, options: ServerOptions) -> Arc<ConnectionHandler> {
+    let app_manager = Arc::new(MemoryAppManager::new());
+    app_manager.create_app(app.clone()).await.unwrap();
+    Arc::new(
+        ConnectionHandlerBuilder::new(
+            app_manager,
+            Arc::new(LocalAdapter::new()),
+            Arc::new(MockCacheManager::new()),
+            options,
+        )
+        .build(),
+    )
+}
+
+/// Opens a V2 transport session authenticated with `token`.
+pub async fn try_open_session(
+    handler: &ConnectionHandler,
+    token: String,
+) -> Result<TransportSession> {
+    handler
+        .open_transport_session(
+            "app-key",
+            TransportSessionOptions {
+                origin: None,
+                protocol_version: ProtocolVersion::V2,
+                echo_messages: true,
+                token: Some(token),
+                connect_context: None,
+            },
+        )
+        .await
+}
+
+/// Opens a session and discards the frames sent on connect.
+pub async fn open_session(handler: &ConnectionHandler, token: String) -> TransportSession {
+    let mut session = try_open_session(handler, token).await.unwrap();
+    drain(&mut session).await;
+    session
+}
+
+/// Handles `frame` as if the client sent it and returns the frames sent back.
+pub async fn send(
+    handler: &ConnectionHandler,
+    session: &mut TransportSession,
+    frame: Value,
+) -> Vec<Value> {
+    let message: PusherMessage = sonic_rs::from_value(&frame).unwrap();
+    let _ = handler
+        .handle_transport_message(&session.socket_id, &session.app, &message)
+        .await;
+    drain(session).await
+}
+
+/// Every frame the session receives until it stays quiet for 100ms.
+pub async fn drain(session: &mut TransportSession) -> Vec<Value> {
+    let mut events = Vec::new();
+    while let Ok(Some(message)) =
+        tokio::time::timeout(Duration::from_millis(100), session.receiver.recv()).await
+    {
+        if let Message::Text(bytes) = message {
+            events.push(sonic_rs::from_slice(&bytes).unwrap());
+        }
+    }
+    events
+}
@@ -4,13 +4,13 @@ pub mod observability;
 
 use ahash::AHashMap;
 use parking_lot::Mutex;
+use sockudo_core::deadline_wheel::{DEFAULT_SHARDS, ShardedDeadlineMap};
 use sockudo_protocol::messages::PusherMessage;
 use sockudo_protocol::versioned_messages::{
     MessageAction, extract_runtime_action, extract_runtime_message_serial,
 };
 use std::hash::Hash;
 
-const DEFAULT_SHARDS: usize = 64;
 const TERMINAL_COMPLETE: &str = "complete";
 const TERMINAL_CANCELLED: &str = "cancelled";
 
@@ -124,17 +124,13 @@ impl PendingStream {
     }
 }
 
-struct Shard {
-    streams: Mutex<AHashMap<StreamKey, PendingStream>>,
-}
-
 /// Coalesces versioned `message.append` fan-out without changing persistence.
 ///
 /// The engine is synchronous by design. Callers take the returned deliveries
 /// outside the engine lock and perform async I/O separately.
 pub struct RollupEngine {
     config: RollupConfig,
-    shards: Vec<Shard>,
+    streams: ShardedDeadlineMap<StreamKey, PendingStream>,
     active_by_channel: Mutex<AHashMap<ChannelKey, usize>>,
     appends_received: std::sync::atomic::AtomicU64,
     appends_delivered: std::sync::atomic::AtomicU64,
@@ -143,15 +139,9 @@ pub struct RollupEngine {
 impl RollupEngine {
     #[must_use]
     pub fn new(config: RollupConfig) -> Self {
-        let shard_count = config.shards.max(1);
-        let shards = (0..shard_count)
-            .map(|_| Shard {
-                streams: Mutex::new(AHashMap::new()),
-            })
-            .collect();
         Self {
             config,
-            shards,
+            streams: ShardedDeadlineMap::new(config.shards),
             active_by_channel: Mutex::new(AHashMap::new()),
             appends_received: std::sync::atomic::AtomicU64::new(0),
             appends_delivered: std::sync::atomic::AtomicU64::new(0),
@@ -197,9 +187,8 @@ impl RollupEngine {
             app_id: app_id.to_string(),
             channel: channel.to_string(),
         };
-        let shard = self.shard(&key);
         let mut deliveries = Vec::with_capacity(2);
-        let mut streams = shard.streams.lock();
+        let mut streams = self.streams.lock(&key);
 
         if matches!(action, MessageAction::Update | MessageAction::Delete) {
             if let Some(mut pending) = streams.remove(&key) {
@@ -311,41 +300,30 @@ impl RollupEngine {
     #[must_use]
     pub fn sweep_orphans(&self, now_ms: u64) -> Vec<RollupDelivery> {
         let mut deliveries = Vec::new();
-        for shard in &self.shards {
-            let mut streams = shard.streams.lock();
-            let keys = streams
-                .iter()
-                .filter_map(|(key, pending)| {
-                    (now_ms.saturating_sub(pending.last_seen_ms) >= self.config.orphan_ttl_ms)
-                        .then_some(key.clone())
-                })
-                .collect::<Vec<_>>();
-            for key in keys {
-                if let Some(mut pending) = streams.remove(&key) {
-                    self.decrement_channel(&ChannelKey {
-                        app_id: key.app_id.clone(),
-                        channel: key.channel.clone(),
-                    });
-                    if pending.appended_count > 0 {
-                        deliveries.push(pending.flush(
-                            RollupDeliveryReason::Orphan,
-                            &key.app_id,
-                            &key.channel,
-                        ));
-                    }
-                }
+        self.streams.retain(|key, pending| {
+            if now_ms.saturating_sub(pending.last_seen_ms) < self.config.orphan_ttl_ms {
+                return true;
             }
-        }
+            self.decrement_channel(&ChannelKey {
+                app_id: key.app_id.clone(),
+                channel: key.channel.clone(),
+            });
+            if pending.appended_count > 0 {
+                deliveries.push(pending.flush(
+                    RollupDeliveryReason::Orphan,
+                    &key.app_id,
+                    &key.channel,
+                ));
+            }
+            false
+        });
         self.mark_delivered(deliveries.len() as u64);
         deliveries
     }
 
     #[must_use]
     pub fn active_streams(&self) -> usize {
-        self.shards
-            .iter()
-            .map(|shard| shard.streams.lock().len())
-            .sum()
+        self.streams.len()
     }
 
     #[must_use]
@@ -371,32 +349,21 @@ impl RollupEngine {
         F: Fn(&PendingStream, u64) -> bool,
     {
         let mut deliveries = Vec::new();
-        for shard in &self.shards {
-            let mut streams = shard.streams.lock();
-            let keys = streams
-                .iter()
-                .filter_map(|(key, pending)| should_flush(pending, now_ms).then_some(key.clone()))
-                .collect::<Vec<_>>();
-            for key in keys {
-                if let Some(mut pending) = streams.remove(&key) {
-                    self.decrement_channel(&ChannelKey {
-                        app_id: key.app_id.clone(),
-                        channel: key.channel.clone(),
-                    });
-                    deliveries.push(pending.flush(reason, &key.app_id, &key.channel));
-                }
+        self.streams.retain(|key, pending| {
+            if !should_flush(pending, now_ms) {
+                return true;
             }
-        }
+            self.decrement_channel(&ChannelKey {
+                app_id: key.app_id.clone(),
+                channel: key.channel.clone(),
+            });
+            deliveries.push(pending.flush(reason, &key.app_id, &key.channel));
+            false
+        });
         self.mark_delivered(deliveries.len() as u64);
         deliveries
     }
 
-    #[inline]
-    fn shard(&self, key: &StreamKey) -> &Shard {
-        let index = fast_shard_index(key, self.shards.len());
-        &self.shards[index]
-    }
-
     #[inline]
     fn mark_received(&self) {
         self.appends_received
@@ -465,22 +432,6 @@ fn is_terminal_append(message: &PusherMessage) -> bool {
         .is_some_and(|status| matches!(status, TERMINAL_COMPLETE | TERMINAL_CANCELLED))
 }
 
-#[inline]
-fn fast_shard_index(key: &StreamKey, shards: usize) -> usize {
-    let mut hash = 0xcbf29ce484222325_u64;
-    for bytes in [
-        key.app_id.as_bytes(),
-        key.channel.as_bytes(),
-        key.message_serial.as_bytes(),
-    ] {
-        for byte in bytes {
-            hash ^= u64::from(*byte);
-            hash = hash.wrapping_mul(0x100000001b3);
-        }
-    }
-    (hash as usize) % shards
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
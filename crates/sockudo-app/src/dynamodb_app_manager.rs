@@ -147,6 +147,7 @@ impl DynamoDbAppManager {
                         decay_seconds: None,
                         terminate_on_limit: false,
                         message_rate_limit: None,
+                        max_subscribers_per_channel: get_u32("max_subscribers_per_channel", None),
                     },
                     features: AppFeaturesPolicy {
                         enable_client_messages: get_bool("enable_client_messages", false),
@@ -255,6 +256,13 @@ impl DynamoDbAppManager {
                     },
                     history: None,
                     presence_history: None,
+                    token_auth: None,
+                    secrets: None,
+                    api_keys: None,
+                    network: None,
+                    user_channel: None,
+                    user_connections: None,
+                    proxy: None,
                 },
             ))
         } else {
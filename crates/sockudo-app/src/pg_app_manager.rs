@@ -482,10 +482,10 @@ impl PgSQLAppManager {
     ) -> Result<bool> {
         let app = self.find_by_id(app_id).await?.ok_or(Error::InvalidAppKey)?;
 
-        let token = Token::new(app.key.clone(), app.secret.clone());
-        let expected = token.sign(body);
-
-        Ok(secure_compare(signature, &expected))
+        Ok(app.active_secrets().into_iter().any(|secret| {
+            let expected = Token::new(app.key.clone(), secret.to_string()).sign(body);
+            secure_compare(signature, &expected)
+        }))
     }
 
     /// Validate if a channel name is valid for an app
@@ -535,9 +535,9 @@ impl PgSQLAppManager {
 
         let string_to_sign = format!("{socket_id}::user::{signature}");
 
-        let token = Token::new(app.key.clone(), app.secret.clone());
-
-        Ok(token.verify(&string_to_sign, &signature))
+        Ok(app.active_secrets().into_iter().any(|secret| {
+            Token::new(app.key.clone(), secret.to_string()).verify(&string_to_sign, &signature)
+        }))
     }
 }
 
@@ -612,6 +612,7 @@ impl AppRow {
                     decay_seconds: None,
                     terminate_on_limit: false,
                     message_rate_limit: None,
+                    max_subscribers_per_channel: None,
                 },
                 features: sockudo_core::app::AppFeaturesPolicy {
                     enable_client_messages: self.enable_client_messages,
@@ -629,6 +630,13 @@ impl AppRow {
                 connection_recovery: self.connection_recovery,
                 history: None,
                 presence_history: None,
+                token_auth: None,
+                secrets: None,
+                api_keys: None,
+                network: None,
+                user_channel: None,
+                user_connections: None,
+                proxy: None,
             },
         )
     }
@@ -733,6 +741,7 @@ mod tests {
                     decay_seconds: None,
                     terminate_on_limit: false,
                     message_rate_limit: None,
+                    max_subscribers_per_channel: None,
                 },
                 features: sockudo_core::app::AppFeaturesPolicy {
                     enable_client_messages: true,
@@ -745,6 +754,13 @@ mod tests {
                 connection_recovery: None,
                 history: None,
                 presence_history: None,
+                token_auth: None,
+                secrets: None,
+                api_keys: None,
+                network: None,
+                user_channel: None,
+                user_connections: None,
+                proxy: None,
             },
         )
     }
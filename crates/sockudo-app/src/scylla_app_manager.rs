@@ -335,6 +335,7 @@ impl AppRow {
                     decay_seconds: None,
                     terminate_on_limit: false,
                     message_rate_limit: None,
+                    max_subscribers_per_channel: None,
                 },
                 features: AppFeaturesPolicy {
                     enable_client_messages: self.enable_client_messages.unwrap_or(false),
@@ -396,6 +397,13 @@ impl AppRow {
                 }),
                 history: None,
                 presence_history: None,
+                token_auth: None,
+                secrets: None,
+                api_keys: None,
+                network: None,
+                user_channel: None,
+                user_connections: None,
+                proxy: None,
             },
         )
     }
@@ -628,6 +636,7 @@ mod tests {
                     decay_seconds: None,
                     terminate_on_limit: false,
                     message_rate_limit: None,
+                    max_subscribers_per_channel: None,
                 },
                 features: sockudo_core::app::AppFeaturesPolicy {
                     enable_client_messages: true,
@@ -640,6 +649,13 @@ mod tests {
                 connection_recovery: None,
                 history: None,
                 presence_history: None,
+                token_auth: None,
+                secrets: None,
+                api_keys: None,
+                network: None,
+                user_channel: None,
+                user_connections: None,
+                proxy: None,
             },
         )
     }
@@ -698,6 +714,7 @@ mod tests {
                     decay_seconds: None,
                     terminate_on_limit: false,
                     message_rate_limit: None,
+                    max_subscribers_per_channel: None,
                 },
                 features: sockudo_core::app::AppFeaturesPolicy {
                     enable_client_messages: true,
@@ -718,6 +735,13 @@ mod tests {
                 connection_recovery: None,
                 history: None,
                 presence_history: None,
+                token_auth: None,
+                secrets: None,
+                api_keys: None,
+                network: None,
+                user_channel: None,
+                user_connections: None,
+                proxy: None,
             },
         )
     }
@@ -29,6 +29,7 @@ mod origin_validation_config_integration_tests {
                     decay_seconds: None,
                     terminate_on_limit: false,
                     message_rate_limit: None,
+                    max_subscribers_per_channel: None,
                 },
                 features: AppFeaturesPolicy {
                     enable_client_messages: true,
@@ -447,6 +447,97 @@ impl CacheManager for FallbackCacheManager {
             }
         }
     }
+
+    async fn hash_set(
+        &self,
+        key: &str,
+        field: &str,
+        value: &str,
+        ttl_seconds: u64,
+    ) -> Result<bool> {
+        self.try_recover().await;
+
+        let _guard = self.recovery_lock.read().await;
+
+        if self.is_using_fallback() {
+            return self
+                .fallback
+                .lock()
+                .await
+                .hash_set(key, field, value, ttl_seconds)
+                .await;
+        }
+
+        let primary = self.primary.lock().await;
+        match primary.hash_set(key, field, value, ttl_seconds).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                self.switch_to_fallback(&e.to_string());
+                self.fallback
+                    .lock()
+                    .await
+                    .hash_set(key, field, value, ttl_seconds)
+                    .await
+            }
+        }
+    }
+
+    async fn hash_get_all(&self, key: &str) -> Result<Vec<(String, String)>> {
+        self.try_recover().await;
+
+        let _guard = self.recovery_lock.read().await;
+
+        if self.is_using_fallback() {
+            return self.fallback.lock().await.hash_get_all(key).await;
+        }
+
+        let primary = self.primary.lock().await;
+        match primary.hash_get_all(key).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                self.switch_to_fallback(&e.to_string());
+                self.fallback.lock().await.hash_get_all(key).await
+            }
+        }
+    }
+
+    async fn hash_remove(&self, key: &str, fields: &[String]) -> Result<()> {
+        self.try_recover().await;
+
+        let _guard = self.recovery_lock.read().await;
+
+        if self.is_using_fallback() {
+            return self.fallback.lock().await.hash_remove(key, fields).await;
+        }
+
+        let primary = self.primary.lock().await;
+        match primary.hash_remove(key, fields).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                self.switch_to_fallback(&e.to_string());
+                self.fallback.lock().await.hash_remove(key, fields).await
+            }
+        }
+    }
+
+    async fn hash_len(&self, key: &str) -> Result<usize> {
+        self.try_recover().await;
+
+        let _guard = self.recovery_lock.read().await;
+
+        if self.is_using_fallback() {
+            return self.fallback.lock().await.hash_len(key).await;
+        }
+
+        let primary = self.primary.lock().await;
+        match primary.hash_len(key).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                self.switch_to_fallback(&e.to_string());
+                self.fallback.lock().await.hash_len(key).await
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -3,6 +3,7 @@ use moka::future::Cache;
 use sockudo_core::cache::{CacheManager, CacheScanPage};
 use sockudo_core::error::Result;
 use sockudo_core::options::MemoryCacheOptions;
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 /// A Memory-based implementation of the CacheManager trait using Moka.
@@ -188,6 +189,29 @@ impl CacheManager for MemoryCacheManager {
             .await;
         Ok(entry.into_value().parse::<i64>().unwrap_or(0))
     }
+
+    async fn hash_set(
+        &self,
+        key: &str,
+        field: &str,
+        value: &str,
+        _ttl_seconds: u64,
+    ) -> Result<bool> {
+        let mut added = false;
+        self.cache
+            .entry(self.prefixed_key(key))
+            .and_upsert_with(|entry| {
+                let mut fields: BTreeMap<String, String> = entry
+                    .and_then(|entry| sonic_rs::from_str(entry.value()).ok())
+                    .unwrap_or_default();
+                added = fields
+                    .insert(field.to_string(), value.to_string())
+                    .is_none();
+                std::future::ready(sonic_rs::to_string(&fields).unwrap_or_default())
+            })
+            .await;
+        Ok(added)
+    }
 }
 
 impl MemoryCacheManager {
@@ -276,4 +300,37 @@ mod tests {
 
         assert_eq!(cache.get("counter").await.unwrap().as_deref(), Some("128"));
     }
+
+    #[tokio::test]
+    async fn hash_fields_are_set_listed_and_removed() {
+        let cache = Arc::new(MemoryCacheManager::new(
+            "test".to_string(),
+            MemoryCacheOptions {
+                ttl: 60,
+                cleanup_interval: 60,
+                max_capacity: 1_000,
+            },
+        ));
+
+        let handles = (0..32)
+            .map(|i| {
+                let cache = Arc::clone(&cache);
+                tokio::spawn(async move { cache.hash_set("hash", &i.to_string(), "v", 60).await })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            assert!(handle.await.unwrap().unwrap());
+        }
+        assert!(!cache.hash_set("hash", "0", "w", 60).await.unwrap());
+        assert_eq!(cache.hash_len("hash").await.unwrap(), 32);
+
+        let fields: Vec<String> = (1..32).map(|i| i.to_string()).collect();
+        cache.hash_remove("hash", &fields).await.unwrap();
+        assert_eq!(
+            cache.hash_get_all("hash").await.unwrap(),
+            vec![("0".to_string(), "w".to_string())]
+        );
+        cache.hash_remove("hash", &["0".to_string()]).await.unwrap();
+        assert!(!cache.has("hash").await.unwrap());
+    }
 }
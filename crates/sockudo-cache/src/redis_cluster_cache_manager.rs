@@ -289,6 +289,55 @@ impl CacheManager for RedisClusterCacheManager {
         }
         Ok(value)
     }
+    async fn hash_set(
+        &self,
+        key: &str,
+        field: &str,
+        value: &str,
+        ttl_seconds: u64,
+    ) -> Result<bool> {
+        let prefixed_key = self.prefixed_key(key);
+        let mut connection = self.connection.clone();
+        let mut pipe = redis::pipe();
+        pipe.hset(&prefixed_key, field, value);
+        if ttl_seconds > 0 {
+            pipe.expire(&prefixed_key, ttl_seconds as i64).ignore();
+        } else {
+            pipe.persist(&prefixed_key).ignore();
+        }
+        let (added,): (i64,) = pipe
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| Error::Cache(format!("Redis Cluster hset error: {e}")))?;
+        Ok(added > 0)
+    }
+
+    async fn hash_get_all(&self, key: &str) -> Result<Vec<(String, String)>> {
+        let mut connection = self.connection.clone();
+        connection
+            .hgetall(self.prefixed_key(key))
+            .await
+            .map_err(|e| Error::Cache(format!("Redis Cluster hgetall error: {e}")))
+    }
+
+    async fn hash_remove(&self, key: &str, fields: &[String]) -> Result<()> {
+        if fields.is_empty() {
+            return Ok(());
+        }
+        let mut connection = self.connection.clone();
+        connection
+            .hdel::<_, _, ()>(self.prefixed_key(key), fields)
+            .await
+            .map_err(|e| Error::Cache(format!("Redis Cluster hdel error: {e}")))
+    }
+
+    async fn hash_len(&self, key: &str) -> Result<usize> {
+        let mut connection = self.connection.clone();
+        connection
+            .hlen(self.prefixed_key(key))
+            .await
+            .map_err(|e| Error::Cache(format!("Redis Cluster hlen error: {e}")))
+    }
 }
 
 impl RedisClusterCacheManager {
@@ -6,11 +6,13 @@ use crate::presence_history::PresenceHistoryRetentionPolicy;
 use crate::webhook_types::Webhook;
 use ahash::AHashMap;
 use async_trait::async_trait;
+use ipnet::IpNet;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_aux::field_attributes::{
     deserialize_number_from_string, deserialize_option_number_from_string,
 };
+use std::net::IpAddr;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -23,6 +25,13 @@ pub struct AppPolicy {
     pub connection_recovery: Option<AppConnectionRecoveryConfig>,
     pub history: Option<AppHistoryConfig>,
     pub presence_history: Option<AppPresenceHistoryConfig>,
+    pub token_auth: Option<AppTokenAuthConfig>,
+    pub proxy: Option<AppProxyConfig>,
+    pub secrets: Option<Vec<AppSecret>>,
+    pub api_keys: Option<Vec<AppApiKey>>,
+    pub network: Option<AppNetworkPolicy>,
+    pub user_channel: Option<AppUserChannelConfig>,
+    pub user_connections: Option<AppUserConnectionsPolicy>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -83,6 +92,9 @@ pub struct AppLimitsPolicy {
     /// Rate limit applied to all inbound WebSocket messages (not just client events).
     #[serde(default)]
     pub message_rate_limit: Option<AppMessageRateLimitConfig>,
+    /// Maximum number of sockets subscribed to a single channel, counted across the cluster.
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub max_subscribers_per_channel: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -129,6 +141,38 @@ pub struct NamespaceHistoryConfig {
     pub max_bytes_per_channel: Option<u64>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct NamespaceConnectionRecoveryConfig {
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub buffer_ttl_seconds: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub max_buffer_size: Option<usize>,
+}
+
+/// Delta compression for every channel of a namespace that has no entry of its own in
+/// `channel_delta_compression`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct NamespaceDeltaCompressionConfig {
+    pub enabled: Option<bool>,
+    pub algorithm: Option<crate::delta_types::DeltaAlgorithm>,
+    pub conflation_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct NamespaceLimitsConfig {
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub max_presence_members_per_channel: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub max_event_payload_in_kb: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub max_client_events_per_second: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub max_subscribers_per_channel: Option<u32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(default)]
 pub struct AppPresenceHistoryConfig {
@@ -175,6 +219,17 @@ pub struct ResolvedHistoryPolicy {
     pub max_bytes_per_channel: Option<u64>,
 }
 
+/// Channel limits after namespace overrides are applied over the app's own limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedChannelLimits {
+    pub max_presence_members_per_channel: Option<u32>,
+    pub max_event_payload_in_kb: Option<u32>,
+    pub max_client_events_per_second: u32,
+    /// The namespace sets its own client event rate, counted separately from the app's.
+    pub namespace_client_event_rate: bool,
+    pub max_subscribers_per_channel: Option<u32>,
+}
+
 impl ResolvedHistoryPolicy {
     #[inline]
     pub fn rewind_allowed(self) -> bool {
@@ -227,6 +282,7 @@ pub struct AppLimitsPolicyRef {
     pub decay_seconds: Option<u64>,
     pub terminate_on_limit: bool,
     pub message_rate_limit: Option<AppMessageRateLimitConfig>,
+    pub max_subscribers_per_channel: Option<u32>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -246,6 +302,193 @@ pub struct AppChannelsPolicyRef<'a> {
     pub channel_namespaces: Option<&'a [ChannelNamespace]>,
 }
 
+/// An additional app secret for rotation, accepted from `active_from` until `retires_at`
+/// (Unix seconds). Either bound may be omitted.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppSecret {
+    pub secret: String,
+    pub active_from: Option<i64>,
+    pub retires_at: Option<i64>,
+}
+
+impl AppSecret {
+    #[inline]
+    pub fn is_active_at(&self, now: i64) -> bool {
+        !self.secret.is_empty()
+            && self.active_from.is_none_or(|from| from <= now)
+            && self.retires_at.is_none_or(|until| now < until)
+    }
+}
+
+/// Group of HTTP API routes a scoped API key may call.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Events, batch events, annotations and message update/delete/append.
+    Publish,
+    /// `GET` routes for channels, messages, history, presence history and users.
+    Read,
+    /// Every `/push` route.
+    PushAdmin,
+    /// History resets and purges, token revocation and user termination.
+    Admin,
+}
+
+/// An HTTP API credential next to the app's primary key and secret, limited to `scopes` and,
+/// on routes that name channels, to channels matching one of `channels`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppApiKey {
+    pub key: String,
+    pub secret: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub channels: Option<Vec<String>>,
+}
+
+impl AppApiKey {
+    #[inline]
+    pub fn allows_scope(&self, scope: ApiKeyScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    #[inline]
+    pub fn allows_channel(&self, channel: &str) -> bool {
+        self.channels.as_deref().is_none_or(|patterns| {
+            patterns.iter().any(|pattern| {
+                pattern == "*" || crate::utils::wildcard_pattern_matches(channel, pattern)
+            })
+        })
+    }
+}
+
+/// Personal channel every signed-in connection joins automatically. `template` must contain
+/// `{user_id}` exactly once; only the matching user may subscribe to a rendered channel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppUserChannelConfig {
+    #[serde(deserialize_with = "deserialize_and_validate_user_channel_template")]
+    pub template: String,
+}
+
+impl Default for AppUserChannelConfig {
+    fn default() -> Self {
+        Self {
+            template: "#user:{user_id}".to_string(),
+        }
+    }
+}
+
+impl AppUserChannelConfig {
+    /// The personal channel of `user_id`.
+    #[inline]
+    pub fn channel_for(&self, user_id: &str) -> String {
+        self.template.replacen(USER_CHANNEL_PLACEHOLDER, user_id, 1)
+    }
+
+    /// The user id `channel` was rendered for, if it is a personal channel at all.
+    pub fn user_id_of<'a>(&self, channel: &'a str) -> Option<&'a str> {
+        let (prefix, suffix) = self.template.split_once(USER_CHANNEL_PLACEHOLDER)?;
+        channel
+            .strip_prefix(prefix)?
+            .strip_suffix(suffix)
+            .filter(|user_id| !user_id.is_empty())
+    }
+
+    /// Whether the wildcard `pattern` could match someone's personal channel.
+    pub fn overlaps_wildcard(&self, pattern: &str) -> bool {
+        self.overlaps_wildcard_with(pattern, crate::utils::wildcard_segment_delimiters())
+    }
+
+    /// [`Self::overlaps_wildcard`] with explicit segment delimiters. A segment pattern
+    /// (`*`, `>` or `#` segments) overlaps whenever its literal prefix overlaps the
+    /// template's, since the user id may itself span segments.
+    pub fn overlaps_wildcard_with(&self, pattern: &str, delimiters: &[char]) -> bool {
+        if crate::utils::is_segment_wildcard_pattern_with(pattern, delimiters) {
+            let Some((prefix, _)) = self.template.split_once(USER_CHANNEL_PLACEHOLDER) else {
+                return false;
+            };
+            let pattern_prefix = crate::utils::wildcard_literal_prefix_with(pattern, delimiters);
+            return prefix.starts_with(pattern_prefix) || pattern_prefix.starts_with(prefix);
+        }
+        let (Some((prefix, suffix)), Some((pattern_prefix, pattern_suffix))) = (
+            self.template.split_once(USER_CHANNEL_PLACEHOLDER),
+            pattern.split_once('*'),
+        ) else {
+            return false;
+        };
+        (prefix.starts_with(pattern_prefix) || pattern_prefix.starts_with(prefix))
+            && (suffix.ends_with(pattern_suffix) || pattern_suffix.ends_with(suffix))
+    }
+}
+
+const USER_CHANNEL_PLACEHOLDER: &str = "{user_id}";
+
+/// Caps how many connections one signed-in user may hold across the cluster.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppUserConnectionsPolicy {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_connections: u32,
+    pub on_limit: UserConnectionLimitAction,
+    /// Count connections separately for each `device_class` in the sign-in `user_data`.
+    pub per_device_class: bool,
+}
+
+impl Default for AppUserConnectionsPolicy {
+    fn default() -> Self {
+        Self {
+            max_connections: 1,
+            on_limit: UserConnectionLimitAction::Reject,
+            per_device_class: false,
+        }
+    }
+}
+
+/// What happens to a connection that would take a user over `max_connections`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UserConnectionLimitAction {
+    /// Close the new connection with code 4010.
+    #[default]
+    Reject,
+    /// Close the user's oldest connections with code 4011 to make room.
+    EvictOldest,
+}
+
+/// Client IP rules for an app, kept separately for client connections (WebSocket, SSE and
+/// long-polling) and the HTTP API.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppNetworkPolicy {
+    pub connections: Option<AppIpRules>,
+    pub api: Option<AppIpRules>,
+}
+
+/// CIDR ranges or single addresses. `deny` always wins; a non-empty `allow` admits only the
+/// listed ranges.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppIpRules {
+    #[serde(deserialize_with = "deserialize_and_validate_cidrs")]
+    pub allow: Vec<String>,
+    #[serde(deserialize_with = "deserialize_and_validate_cidrs")]
+    pub deny: Vec<String>,
+}
+
+impl AppIpRules {
+    /// Whether a client at `ip` may proceed. An unknown address is only admitted while
+    /// `allow` is empty.
+    pub fn permits(&self, ip: Option<IpAddr>) -> bool {
+        let Some(ip) = ip.map(|ip| ip.to_canonical()) else {
+            return self.allow.is_empty();
+        };
+        let contains = |entry: &String| parse_cidr(entry).is_some_and(|net| net.contains(&ip));
+        !self.deny.iter().any(contains)
+            && (self.allow.is_empty() || self.allow.iter().any(contains))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct App {
     pub id: String,
@@ -277,6 +520,38 @@ impl App {
         self.policy.clone()
     }
 
+    /// Secrets that currently verify signatures: `secret`, which is always accepted, followed
+    /// by every secret in `policy.secrets` inside its active window.
+    pub fn active_secrets(&self) -> Vec<&str> {
+        self.active_secrets_at(unix_now())
+    }
+
+    pub fn active_secrets_at(&self, now: i64) -> Vec<&str> {
+        let mut secrets = vec![self.secret.as_str()];
+        for rotated in self.policy.secrets.iter().flatten() {
+            if rotated.is_active_at(now) && !secrets.contains(&rotated.secret.as_str()) {
+                secrets.push(rotated.secret.as_str());
+            }
+        }
+        secrets
+    }
+
+    /// Secret outgoing signatures (webhooks, proxy calls) are made with: the active secret in
+    /// `policy.secrets` with the latest `active_from`, or `secret` while none has started.
+    pub fn signing_secret(&self) -> &str {
+        self.signing_secret_at(unix_now())
+    }
+
+    pub fn signing_secret_at(&self, now: i64) -> &str {
+        self.policy
+            .secrets
+            .iter()
+            .flatten()
+            .filter(|rotated| rotated.active_from.is_some() && rotated.is_active_at(now))
+            .max_by_key(|rotated| rotated.active_from)
+            .map_or(self.secret.as_str(), |rotated| rotated.secret.as_str())
+    }
+
     #[inline]
     pub fn policy_ref(&self) -> AppPolicyRef<'_> {
         AppPolicyRef {
@@ -298,6 +573,7 @@ impl App {
                 decay_seconds: self.policy.limits.decay_seconds,
                 terminate_on_limit: self.policy.limits.terminate_on_limit,
                 message_rate_limit: self.policy.limits.message_rate_limit,
+                max_subscribers_per_channel: self.policy.limits.max_subscribers_per_channel,
             },
             features: AppFeaturesPolicyRef {
                 enable_client_messages: self.policy.features.enable_client_messages,
@@ -462,6 +738,106 @@ impl App {
         self.policy.presence_history.as_ref()
     }
 
+    #[inline]
+    pub fn token_auth_ref(&self) -> Option<&AppTokenAuthConfig> {
+        self.policy.token_auth.as_ref()
+    }
+
+    #[inline]
+    pub fn jwks_url(&self) -> Option<&str> {
+        self.token_auth_ref()?.jwks_url.as_deref()
+    }
+
+    #[inline]
+    pub fn proxy_ref(&self) -> Option<&AppProxyConfig> {
+        self.policy.proxy.as_ref()
+    }
+
+    #[inline]
+    pub fn connect_proxy(&self) -> Option<&AppProxyEndpoint> {
+        self.proxy_ref()?.connect.as_ref()
+    }
+
+    #[inline]
+    pub fn rpc_proxy(&self) -> Option<&AppProxyEndpoint> {
+        self.proxy_ref()?.rpc.as_ref()
+    }
+
+    /// Subscribe proxy of the namespace `channel` belongs to.
+    #[inline]
+    pub fn subscribe_proxy_for_channel(&self, channel: &str) -> Option<&AppProxyEndpoint> {
+        self.namespace_for_channel(channel)?
+            .proxy
+            .as_ref()?
+            .subscribe
+            .as_ref()
+    }
+
+    /// Publish proxy of the namespace `channel` belongs to.
+    #[inline]
+    pub fn publish_proxy_for_channel(&self, channel: &str) -> Option<&AppProxyEndpoint> {
+        self.namespace_for_channel(channel)?
+            .proxy
+            .as_ref()?
+            .publish
+            .as_ref()
+    }
+
+    /// Whether a client connection from `ip` passes `policy.network.connections`.
+    #[inline]
+    pub fn permits_connection_ip(&self, ip: Option<IpAddr>) -> bool {
+        self.policy
+            .network
+            .as_ref()
+            .and_then(|network| network.connections.as_ref())
+            .is_none_or(|rules| rules.permits(ip))
+    }
+
+    /// Whether an HTTP API request from `ip` passes `policy.network.api`.
+    #[inline]
+    pub fn permits_api_ip(&self, ip: Option<IpAddr>) -> bool {
+        self.policy
+            .network
+            .as_ref()
+            .and_then(|network| network.api.as_ref())
+            .is_none_or(|rules| rules.permits(ip))
+    }
+
+    #[inline]
+    pub fn user_channel_ref(&self) -> Option<&AppUserChannelConfig> {
+        self.policy.user_channel.as_ref()
+    }
+
+    /// Personal channel `user_id` is subscribed to on sign-in, when `policy.user_channel` is set.
+    #[inline]
+    pub fn user_channel_for(&self, user_id: &str) -> Option<String> {
+        Some(self.user_channel_ref()?.channel_for(user_id))
+    }
+
+    #[inline]
+    pub fn user_connections_ref(&self) -> Option<&AppUserConnectionsPolicy> {
+        self.policy.user_connections.as_ref()
+    }
+
+    /// Scoped HTTP API key registered under `policy.api_keys`.
+    #[inline]
+    pub fn api_key(&self, key: &str) -> Option<&AppApiKey> {
+        self.policy
+            .api_keys
+            .as_deref()?
+            .iter()
+            .find(|api_key| !api_key.key.is_empty() && api_key.key == key)
+    }
+
+    /// Public key registered for asymmetric capability tokens under `kid`.
+    #[inline]
+    pub fn token_public_key(&self, kid: &str) -> Option<&AppTokenPublicKey> {
+        self.token_auth_ref()?
+            .public_keys
+            .iter()
+            .find(|key| key.kid == kid)
+    }
+
     #[inline]
     pub fn namespace_for_channel(&self, channel: &str) -> Option<&ChannelNamespace> {
         let namespace_name = crate::utils::channel_namespace_name(channel)?;
@@ -493,18 +869,97 @@ impl App {
     #[inline]
     pub fn resolved_connection_recovery(
         &self,
+        channel: &str,
         global: &ConnectionRecoveryConfig,
     ) -> ConnectionRecoveryConfig {
-        match self.connection_recovery_override() {
-            Some(app_config) => ConnectionRecoveryConfig {
-                enabled: app_config.enabled.unwrap_or(global.enabled),
-                buffer_ttl_seconds: app_config
-                    .buffer_ttl_seconds
-                    .unwrap_or(global.buffer_ttl_seconds),
-                max_buffer_size: app_config.max_buffer_size.unwrap_or(global.max_buffer_size),
-            },
-            None => global.clone(),
+        let app_config = self.connection_recovery_override();
+        let namespace_config = self
+            .namespace_for_channel(channel)
+            .and_then(|namespace| namespace.connection_recovery.as_ref());
+
+        ConnectionRecoveryConfig {
+            enabled: app_config
+                .and_then(|config| config.enabled)
+                .unwrap_or(global.enabled),
+            buffer_ttl_seconds: namespace_config
+                .and_then(|config| config.buffer_ttl_seconds)
+                .or_else(|| app_config.and_then(|config| config.buffer_ttl_seconds))
+                .unwrap_or(global.buffer_ttl_seconds),
+            max_buffer_size: namespace_config
+                .and_then(|config| config.max_buffer_size)
+                .or_else(|| app_config.and_then(|config| config.max_buffer_size))
+                .unwrap_or(global.max_buffer_size),
+        }
+    }
+
+    /// Whether any channel of this app, directly or through a namespace, caps its subscribers.
+    pub fn has_channel_subscriber_limits(&self) -> bool {
+        self.policy.limits.max_subscribers_per_channel.is_some()
+            || self
+                .policy
+                .channels
+                .channel_namespaces
+                .iter()
+                .flatten()
+                .filter_map(|namespace| namespace.limits.as_ref())
+                .any(|limits| limits.max_subscribers_per_channel.is_some())
+    }
+
+    #[inline]
+    pub fn resolved_channel_limits(&self, channel: &str) -> ResolvedChannelLimits {
+        let namespace_config = self
+            .namespace_for_channel(channel)
+            .and_then(|namespace| namespace.limits.as_ref());
+        let namespace_client_event_rate =
+            namespace_config.and_then(|config| config.max_client_events_per_second);
+
+        ResolvedChannelLimits {
+            max_presence_members_per_channel: namespace_config
+                .and_then(|config| config.max_presence_members_per_channel)
+                .or(self.policy.limits.max_presence_members_per_channel),
+            max_event_payload_in_kb: namespace_config
+                .and_then(|config| config.max_event_payload_in_kb)
+                .or(self.policy.limits.max_event_payload_in_kb),
+            max_client_events_per_second: namespace_client_event_rate
+                .unwrap_or(self.policy.limits.max_client_events_per_second),
+            namespace_client_event_rate: namespace_client_event_rate.is_some(),
+            max_subscribers_per_channel: namespace_config
+                .and_then(|config| config.max_subscribers_per_channel)
+                .or(self.policy.limits.max_subscribers_per_channel),
+        }
+    }
+
+    /// Delta settings for `channel`: its own `channel_delta_compression` entry, otherwise the
+    /// `delta_compression` of its namespace.
+    pub fn resolved_channel_delta(
+        &self,
+        channel: &str,
+    ) -> Option<crate::delta_types::ChannelDeltaSettings> {
+        use crate::delta_types::{ChannelDeltaConfig, ChannelDeltaSettings};
+
+        if let Some(config) = self
+            .channel_delta_compression_ref()
+            .and_then(|map| map.get(channel))
+        {
+            return match config {
+                ChannelDeltaConfig::Full(settings) => Some(settings.clone()),
+                ChannelDeltaConfig::Simple(_) => None,
+            };
+        }
+
+        let namespace_config = self
+            .namespace_for_channel(channel)?
+            .delta_compression
+            .as_ref()?;
+        if namespace_config.enabled == Some(false) {
+            return None;
         }
+        let defaults = ChannelDeltaSettings::default();
+        Some(ChannelDeltaSettings {
+            algorithm: namespace_config.algorithm.unwrap_or(defaults.algorithm),
+            conflation_key: namespace_config.conflation_key.clone(),
+            ..defaults
+        })
     }
 
     #[inline]
@@ -629,6 +1084,8 @@ struct AppSerde {
     terminate_on_limit: bool,
     #[serde(default)]
     message_rate_limit: Option<AppMessageRateLimitConfig>,
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    max_subscribers_per_channel: Option<u32>,
 }
 
 impl<'de> Deserialize<'de> for App {
@@ -669,6 +1126,7 @@ impl<'de> Deserialize<'de> for App {
                     decay_seconds: app.decay_seconds,
                     terminate_on_limit: app.terminate_on_limit,
                     message_rate_limit: app.message_rate_limit,
+                    max_subscribers_per_channel: app.max_subscribers_per_channel,
                 },
                 features: AppFeaturesPolicy {
                     enable_client_messages: app.enable_client_messages,
@@ -686,6 +1144,13 @@ impl<'de> Deserialize<'de> for App {
                 connection_recovery: app.connection_recovery,
                 history: None,
                 presence_history: None,
+                token_auth: None,
+                secrets: None,
+                api_keys: None,
+                network: None,
+                user_channel: None,
+                user_connections: None,
+                proxy: None,
             },
         ))
     }
@@ -711,6 +1176,14 @@ pub struct ChannelNamespace {
     pub history: Option<NamespaceHistoryConfig>,
     #[serde(default)]
     pub presence_history: Option<NamespacePresenceHistoryConfig>,
+    #[serde(default)]
+    pub proxy: Option<NamespaceProxyConfig>,
+    #[serde(default)]
+    pub connection_recovery: Option<NamespaceConnectionRecoveryConfig>,
+    #[serde(default)]
+    pub delta_compression: Option<NamespaceDeltaCompressionConfig>,
+    #[serde(default)]
+    pub limits: Option<NamespaceLimitsConfig>,
 }
 
 impl ChannelNamespace {
@@ -744,6 +1217,15 @@ impl ChannelNamespace {
             ));
         }
 
+        if let Some(recovery) = &self.connection_recovery
+            && (recovery.buffer_ttl_seconds == Some(0) || recovery.max_buffer_size == Some(0))
+        {
+            return Err(format!(
+                "connection_recovery buffer_ttl_seconds and max_buffer_size for namespace '{}' must be greater than 0",
+                self.name
+            ));
+        }
+
         Ok(())
     }
 }
@@ -763,6 +1245,117 @@ pub struct AppConnectionRecoveryConfig {
     pub max_buffer_size: Option<usize>,
 }
 
+/// Keys accepted for capability tokens in addition to the HS256 app secret.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppTokenAuthConfig {
+    pub public_keys: Vec<AppTokenPublicKey>,
+    /// JWKS document consulted for asymmetric tokens whose `kid` is not in `public_keys`.
+    pub jwks_url: Option<String>,
+}
+
+/// A PEM-encoded public key for RS256/384/512, ES256/384 or EdDSA tokens, selected by the
+/// token's `kid` header.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppTokenPublicKey {
+    pub kid: String,
+    pub alg: jsonwebtoken::Algorithm,
+    pub public_key: String,
+}
+
+/// Backend endpoints Sockudo consults over HTTP before admitting client actions.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppProxyConfig {
+    /// Called for every new connection; the backend allows or denies it and may attach a user.
+    pub connect: Option<AppProxyEndpoint>,
+    /// Answers `sockudo:rpc` frames sent by V2 connections.
+    pub rpc: Option<AppProxyEndpoint>,
+}
+
+/// Backend endpoints Sockudo consults over HTTP for channels in a namespace.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct NamespaceProxyConfig {
+    /// Called for every subscription; its verdict replaces the channel signature check.
+    pub subscribe: Option<AppProxyEndpoint>,
+    /// Called for every `client-*` event before it is broadcast; may reject or rewrite it.
+    pub publish: Option<AppProxyEndpoint>,
+}
+
+/// A proxy backend endpoint. Requests are signed like webhooks, with `X-Pusher-Key` and
+/// `X-Pusher-Signature` over the JSON body.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppProxyEndpoint {
+    pub url: String,
+    pub timeout_ms: u64,
+    /// Admit the action when the backend is unreachable, times out or answers with an error
+    /// status. Defaults to false, which rejects it.
+    pub fail_open: bool,
+    /// Record `proxy_requests_total` and `proxy_request_latency_ms` for this endpoint.
+    pub metrics: bool,
+}
+
+impl Default for AppProxyEndpoint {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            timeout_ms: 3000,
+            fail_open: false,
+            metrics: true,
+        }
+    }
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+fn parse_cidr(entry: &str) -> Option<IpNet> {
+    let entry = entry.trim();
+    entry
+        .parse::<IpNet>()
+        .ok()
+        .or_else(|| entry.parse::<IpAddr>().ok().map(IpNet::from))
+}
+
+fn deserialize_and_validate_cidrs<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let value = Vec::<String>::deserialize(deserializer)?;
+
+    if let Some(invalid) = value.iter().find(|entry| parse_cidr(entry).is_none()) {
+        return Err(D::Error::custom(format!(
+            "Invalid CIDR range or IP address: {invalid}"
+        )));
+    }
+
+    Ok(value)
+}
+
+fn deserialize_and_validate_user_channel_template<'de, D>(
+    deserializer: D,
+) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let value = String::deserialize(deserializer)?;
+
+    if value.matches(USER_CHANNEL_PLACEHOLDER).count() != 1 {
+        return Err(D::Error::custom(format!(
+            "User channel template must contain {USER_CHANNEL_PLACEHOLDER} exactly once: {value}"
+        )));
+    }
+
+    Ok(value)
+}
+
 fn deserialize_and_validate_origins<'de, D>(
     deserializer: D,
 ) -> Result<Option<Vec<String>>, D::Error>
@@ -954,6 +1547,10 @@ mod tests {
             allow_presence_for_client: None,
             history: None,
             presence_history: None,
+            proxy: None,
+            connection_recovery: None,
+            delta_compression: None,
+            limits: None,
         };
         assert!(ns.validate().is_err());
     }
@@ -981,6 +1578,7 @@ mod tests {
                     decay_seconds: 60,
                     terminate_on_limit: true,
                 }),
+                max_subscribers_per_channel: Some(5000),
             },
             features: AppFeaturesPolicy {
                 enable_client_messages: true,
@@ -1002,6 +1600,10 @@ mod tests {
                     allow_presence_for_client: Some(true),
                     history: None,
                     presence_history: None,
+                    proxy: None,
+                    connection_recovery: None,
+                    delta_compression: None,
+                    limits: None,
                 }]),
             },
             webhooks: None,
@@ -1022,6 +1624,13 @@ mod tests {
                 max_bytes_per_channel: Some(1024),
             }),
             presence_history: None,
+            token_auth: None,
+            secrets: None,
+            api_keys: None,
+            network: None,
+            user_channel: None,
+            user_connections: None,
+            proxy: None,
         };
 
         let app = App::from_policy(
@@ -1135,6 +1744,10 @@ mod tests {
                             max_bytes_per_channel: Some(4096),
                         }),
                         presence_history: None,
+                        proxy: None,
+                        connection_recovery: None,
+                        delta_compression: None,
+                        limits: None,
                     }]),
                     ..Default::default()
                 },
@@ -1156,6 +1769,112 @@ mod tests {
         assert!(resolved.rewind_allowed());
     }
 
+    #[test]
+    fn resolved_channel_overrides_apply_per_namespace() {
+        let app = App::from_policy(
+            "app".to_string(),
+            "key".to_string(),
+            "secret".to_string(),
+            true,
+            AppPolicy {
+                limits: AppLimitsPolicy {
+                    max_client_events_per_second: 10,
+                    max_event_payload_in_kb: Some(100),
+                    ..Default::default()
+                },
+                connection_recovery: Some(AppConnectionRecoveryConfig {
+                    enabled: Some(true),
+                    buffer_ttl_seconds: Some(30),
+                    max_buffer_size: None,
+                }),
+                channels: AppChannelsPolicy {
+                    channel_namespaces: Some(vec![ChannelNamespace {
+                        name: "ticker".to_string(),
+                        connection_recovery: Some(NamespaceConnectionRecoveryConfig {
+                            buffer_ttl_seconds: Some(5),
+                            max_buffer_size: Some(20),
+                        }),
+                        delta_compression: Some(NamespaceDeltaCompressionConfig {
+                            enabled: None,
+                            algorithm: Some(crate::delta_types::DeltaAlgorithm::Xdelta3),
+                            conflation_key: Some("symbol".to_string()),
+                        }),
+                        limits: Some(NamespaceLimitsConfig {
+                            max_event_payload_in_kb: Some(4),
+                            max_client_events_per_second: Some(200),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let global = ConnectionRecoveryConfig::default();
+
+        let ticker = app.resolved_connection_recovery("ticker:AAPL", &global);
+        assert!(ticker.enabled);
+        assert_eq!(ticker.buffer_ttl_seconds, 5);
+        assert_eq!(ticker.max_buffer_size, 20);
+        let chat = app.resolved_connection_recovery("chat-room", &global);
+        assert_eq!(chat.buffer_ttl_seconds, 30);
+        assert_eq!(chat.max_buffer_size, global.max_buffer_size);
+
+        let limits = app.resolved_channel_limits("ticker:AAPL");
+        assert_eq!(limits.max_event_payload_in_kb, Some(4));
+        assert_eq!(limits.max_client_events_per_second, 200);
+        assert!(limits.namespace_client_event_rate);
+        let limits = app.resolved_channel_limits("chat-room");
+        assert_eq!(limits.max_event_payload_in_kb, Some(100));
+        assert_eq!(limits.max_client_events_per_second, 10);
+        assert!(!limits.namespace_client_event_rate);
+
+        let delta = app.resolved_channel_delta("ticker:AAPL").unwrap();
+        assert_eq!(delta.algorithm, crate::delta_types::DeltaAlgorithm::Xdelta3);
+        assert_eq!(delta.conflation_key.as_deref(), Some("symbol"));
+        assert!(app.resolved_channel_delta("chat-room").is_none());
+    }
+
+    #[test]
+    fn resolved_channel_limits_cap_subscribers_per_namespace() {
+        let app = App::from_policy(
+            "app".to_string(),
+            "key".to_string(),
+            "secret".to_string(),
+            true,
+            AppPolicy {
+                limits: AppLimitsPolicy {
+                    max_subscribers_per_channel: Some(1000),
+                    ..Default::default()
+                },
+                channels: AppChannelsPolicy {
+                    channel_namespaces: Some(vec![ChannelNamespace {
+                        name: "live".to_string(),
+                        limits: Some(NamespaceLimitsConfig {
+                            max_subscribers_per_channel: Some(50_000),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            app.resolved_channel_limits("live:final")
+                .max_subscribers_per_channel,
+            Some(50_000)
+        );
+        assert_eq!(
+            app.resolved_channel_limits("chat-room")
+                .max_subscribers_per_channel,
+            Some(1000)
+        );
+    }
+
     #[test]
     fn resolved_history_annotations_enabled_forces_persistence() {
         let app = App::from_policy(
@@ -1222,4 +1941,82 @@ mod tests {
         assert_eq!(app.policy.limits.decay_seconds, Some(60));
         assert_eq!(app.client_event_decay_seconds(), 60);
     }
+
+    #[test]
+    fn network_rules_deny_before_allow() {
+        let json = test_app_json(
+            "",
+            r#","network":{"api":{"allow":["10.0.0.0/8","192.168.1.7"],"deny":["10.9.0.0/16"]},"connections":{"deny":["2001:db8::/32"]}}"#,
+        );
+        let app: App = sonic_rs::from_str(&json).unwrap();
+        let ip = |ip: &str| Some(ip.parse::<IpAddr>().unwrap());
+
+        assert!(app.permits_api_ip(ip("10.1.2.3")));
+        assert!(app.permits_api_ip(ip("::ffff:192.168.1.7")));
+        assert!(!app.permits_api_ip(ip("10.9.1.1")));
+        assert!(!app.permits_api_ip(ip("172.16.0.1")));
+        assert!(!app.permits_api_ip(None));
+        assert!(app.permits_connection_ip(ip("172.16.0.1")));
+        assert!(app.permits_connection_ip(None));
+        assert!(!app.permits_connection_ip(ip("2001:db8::1")));
+
+        let invalid = test_app_json("", r#","network":{"api":{"allow":["10.0.0.0/33"]}}"#);
+        assert!(sonic_rs::from_str::<App>(&invalid).is_err());
+    }
+
+    #[test]
+    fn user_channel_template_renders_and_matches() {
+        let json = test_app_json(
+            "",
+            r#","user_channel":{"template":"private-inbox-{user_id}"}"#,
+        );
+        let app: App = sonic_rs::from_str(&json).unwrap();
+        let config = app.user_channel_ref().unwrap();
+
+        assert_eq!(
+            app.user_channel_for("alice").as_deref(),
+            Some("private-inbox-alice")
+        );
+        assert_eq!(config.user_id_of("private-inbox-alice"), Some("alice"));
+        assert_eq!(config.user_id_of("private-inbox-"), None);
+        assert_eq!(config.user_id_of("private-other-alice"), None);
+        assert!(config.overlaps_wildcard("private-in*"));
+        assert!(!config.overlaps_wildcard("public-*"));
+
+        let defaults = test_app_json("", r#","user_channel":{}"#);
+        let app: App = sonic_rs::from_str(&defaults).unwrap();
+        assert_eq!(app.user_channel_for("42").as_deref(), Some("#user:42"));
+
+        let config = app.user_channel_ref().unwrap();
+        for pattern in ["#user:>", "#user:#", "#user:*", "#user:42:>", ">", "#"] {
+            assert!(config.overlaps_wildcard_with(pattern, &[':']), "{pattern}");
+        }
+        assert!(!config.overlaps_wildcard_with("#orders:>", &[':']));
+        assert!(!config.overlaps_wildcard_with("#user:>", &[]));
+
+        let json = test_app_json("", r#","user_channel":{"template":"user.{user_id}"}"#);
+        let app: App = sonic_rs::from_str(&json).unwrap();
+        let config = app.user_channel_ref().unwrap();
+        assert!(config.overlaps_wildcard_with("user.>", &['.']));
+        assert!(config.overlaps_wildcard_with("user.#", &['.']));
+        assert!(!config.overlaps_wildcard_with("orders.>", &['.']));
+
+        let invalid = test_app_json("", r##","user_channel":{"template":"#user"}"##);
+        assert!(sonic_rs::from_str::<App>(&invalid).is_err());
+    }
+
+    #[test]
+    fn rotated_secrets_follow_their_windows() {
+        let json = test_app_json(
+            "",
+            r#","secrets":[{"secret":"next","active_from":100},{"secret":"old","retires_at":200}]"#,
+        );
+        let app: App = sonic_rs::from_str(&json).unwrap();
+
+        assert_eq!(app.active_secrets_at(50), vec!["secret", "old"]);
+        assert_eq!(app.active_secrets_at(150), vec!["secret", "next", "old"]);
+        assert_eq!(app.active_secrets_at(250), vec!["secret", "next"]);
+        assert_eq!(app.signing_secret_at(50), "secret");
+        assert_eq!(app.signing_secret_at(150), "next");
+    }
 }
@@ -1,4 +1,4 @@
-use crate::app::{App, AppManager};
+use crate::app::{ApiKeyScope, App, AppApiKey, AppManager};
 use crate::error::Error;
 use crate::token::{Token, secure_compare};
 use crate::websocket::SocketId;
@@ -32,6 +32,40 @@ pub struct EventQuery {
     pub auth_signature: String,
 }
 
+/// Channels named by an events or batch events body.
+#[derive(Deserialize)]
+struct PublishTargets {
+    #[serde(default)]
+    channel: Option<String>,
+    #[serde(default)]
+    channels: Option<Vec<String>>,
+    #[serde(default)]
+    batch: Option<Vec<PublishTargets>>,
+}
+
+impl PublishTargets {
+    fn into_channels(self) -> Vec<String> {
+        let mut channels: Vec<String> = self
+            .channel
+            .into_iter()
+            .chain(self.channels.into_iter().flatten())
+            .collect();
+        for message in self.batch.into_iter().flatten() {
+            channels.extend(message.into_channels());
+        }
+        channels
+    }
+}
+
+fn api_scope_name(scope: ApiKeyScope) -> &'static str {
+    match scope {
+        ApiKeyScope::Publish => "publish",
+        ApiKeyScope::Read => "read",
+        ApiKeyScope::PushAdmin => "push_admin",
+        ApiKeyScope::Admin => "admin",
+    }
+}
+
 pub struct AuthValidator {
     app_manager: Arc<dyn AppManager>,
 }
@@ -81,20 +115,10 @@ impl AuthValidator {
         all_query_params_from_url: &BTreeMap<String, String>,
         request_body_bytes_for_md5_check: Option<&[u8]>,
     ) -> Result<App, Error> {
-        let app = self
-            .app_manager
-            .find_by_key(&auth_params_from_query_struct.auth_key)
+        let (app_config, api_key) = self
+            .resolve_api_credential(&auth_params_from_query_struct.auth_key, request_path)
             .await?;
 
-        if app.is_none() {
-            debug!(
-                "App not found for key: {}",
-                auth_params_from_query_struct.auth_key
-            );
-            return Err(Error::InvalidAppKey);
-        }
-        let app_config = app.unwrap();
-
         if let Some(route_app_id) = Self::route_app_id_from_path(request_path)
             && route_app_id != app_config.id
         {
@@ -197,18 +221,129 @@ impl AuthValidator {
             "Prepared API request signature material"
         );
 
-        let token_signer = Token::new(app_config.key.clone(), app_config.secret.clone());
-        let generated_signature = token_signer.sign(&string_to_sign);
+        // Scoped keys sign with their own secret. The primary key accepts any active secret so
+        // backends keep working while a secret is rotated.
+        let secrets = match &api_key {
+            Some(api_key) => vec![api_key.secret.as_str()],
+            None => app_config.active_secrets(),
+        };
+        let signature_matches = secrets.into_iter().any(|secret| {
+            let generated_signature =
+                Token::new(app_config.key.clone(), secret.to_string()).sign(&string_to_sign);
+            secure_compare(
+                &generated_signature,
+                &auth_params_from_query_struct.auth_signature,
+            )
+        });
 
         debug!("Validated API request signature with redacted signature values");
 
-        if secure_compare(
-            &generated_signature,
-            &auth_params_from_query_struct.auth_signature,
-        ) {
-            Ok(app_config)
-        } else {
-            Err(Error::Auth("Invalid API signature".to_string()))
+        if !signature_matches {
+            return Err(Error::Auth("Invalid API signature".to_string()));
+        }
+        if let Some(api_key) = &api_key {
+            Self::authorize_api_key(
+                api_key,
+                &uppercased_http_method,
+                request_path,
+                request_body_bytes_for_md5_check,
+            )?;
+        }
+        Ok(app_config)
+    }
+
+    /// Finds the app behind an API `auth_key`: the app whose primary key it is, or else the
+    /// route's app when the key is one of its scoped `policy.api_keys`.
+    async fn resolve_api_credential(
+        &self,
+        auth_key: &str,
+        request_path: &str,
+    ) -> Result<(App, Option<AppApiKey>), Error> {
+        if let Some(app) = self.app_manager.find_by_key(auth_key).await? {
+            return Ok((app, None));
+        }
+        if let Some(route_app_id) = Self::route_app_id_from_path(request_path)
+            && let Some(app) = self.app_manager.find_by_id(route_app_id).await?
+            && let Some(api_key) = app.api_key(auth_key).cloned()
+        {
+            return Ok((app, Some(api_key)));
+        }
+        debug!("App not found for key: {}", auth_key);
+        Err(Error::InvalidAppKey)
+    }
+
+    /// Checks that a scoped API key holds the scope the route requires and, when the key is
+    /// limited to channel patterns, that every channel the request names matches one.
+    fn authorize_api_key(
+        api_key: &AppApiKey,
+        http_method: &str,
+        request_path: &str,
+        body: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        let route: Vec<&str> = request_path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .skip(2)
+            .collect();
+        let scope = Self::required_api_scope(http_method, &route);
+        if !api_key.allows_scope(scope) || api_key.channels.is_none() {
+            return Self::authorize_api_key_scope(api_key, scope, &[]);
+        }
+
+        let channels = match route.as_slice() {
+            ["channels", channel, ..] => vec![
+                urlencoding::decode(channel)
+                    .map(|channel| channel.into_owned())
+                    .unwrap_or_else(|_| channel.to_string()),
+            ],
+            ["events"] | ["batch_events"] => {
+                let targets: PublishTargets = body
+                    .filter(|body| !body.is_empty())
+                    .and_then(|body| sonic_rs::from_slice(body).ok())
+                    .ok_or_else(|| {
+                        Error::Forbidden("Request channels could not be read".to_string())
+                    })?;
+                targets.into_channels()
+            }
+            _ => Vec::new(),
+        };
+        Self::authorize_api_key_scope(api_key, scope, &channels)
+    }
+
+    /// Checks that a scoped API key holds `scope` and may access every one of `channels`.
+    /// Shared by the HTTP API and transports that accept API keys without an HTTP route.
+    pub fn authorize_api_key_scope(
+        api_key: &AppApiKey,
+        scope: ApiKeyScope,
+        channels: &[String],
+    ) -> Result<(), Error> {
+        if !api_key.allows_scope(scope) {
+            return Err(Error::Forbidden(format!(
+                "API key does not have the {} scope",
+                api_scope_name(scope)
+            )));
+        }
+        match channels
+            .iter()
+            .find(|channel| !api_key.allows_channel(channel))
+        {
+            Some(channel) => Err(Error::Forbidden(format!(
+                "API key is not allowed to access channel {channel}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Scope required by an API route, given its path after `/apps/{appId}`.
+    fn required_api_scope(http_method: &str, route: &[&str]) -> ApiKeyScope {
+        match route {
+            ["events"] | ["batch_events"] => ApiKeyScope::Publish,
+            ["push", ..] => ApiKeyScope::PushAdmin,
+            ["channels", ..] if http_method == "GET" => ApiKeyScope::Read,
+            ["channels", .., "reset" | "purge"] => ApiKeyScope::Admin,
+            ["channels", _, "messages", ..] => ApiKeyScope::Publish,
+            ["channels", _, "objects"] => ApiKeyScope::Publish,
+            _ => ApiKeyScope::Admin,
         }
     }
 
@@ -245,8 +380,12 @@ impl AuthValidator {
         expected_signature: &str,
         app_config: App,
     ) -> bool {
-        let signature = self.sign_in_token_for_user_data(socket_id, user_data, app_config);
-        secure_compare(&signature, expected_signature)
+        let decoded_string = format!("{socket_id}::user::{user_data}");
+        app_config.active_secrets().into_iter().any(|secret| {
+            let signature =
+                Token::new(app_config.key.clone(), secret.to_string()).sign(&decoded_string);
+            secure_compare(&signature, expected_signature)
+        })
     }
 
     pub fn sign_in_token_for_user_data(
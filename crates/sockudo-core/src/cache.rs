@@ -1,5 +1,6 @@
 use crate::error::Result;
 use async_trait::async_trait;
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -78,4 +79,57 @@ pub trait CacheManager: Send + Sync {
         self.set(key, &next.to_string(), ttl_seconds).await?;
         Ok(next)
     }
+
+    /// Set `field` of the hash stored at `key` and reset the key's TTL. Returns `true` if the
+    /// field did not exist before.
+    ///
+    /// The default implementations keep the hash as one serialized map under `key` and are not
+    /// atomic; the Redis backends use native hashes.
+    async fn hash_set(
+        &self,
+        key: &str,
+        field: &str,
+        value: &str,
+        ttl_seconds: u64,
+    ) -> Result<bool> {
+        let mut fields = read_hash(self.get(key).await?);
+        let added = fields
+            .insert(field.to_string(), value.to_string())
+            .is_none();
+        self.set(key, &sonic_rs::to_string(&fields)?, ttl_seconds)
+            .await?;
+        Ok(added)
+    }
+
+    /// Every field of the hash stored at `key`; empty if the key does not exist.
+    async fn hash_get_all(&self, key: &str) -> Result<Vec<(String, String)>> {
+        Ok(read_hash(self.get(key).await?).into_iter().collect())
+    }
+
+    /// Remove `fields` from the hash stored at `key`. The key is removed with its last field.
+    async fn hash_remove(&self, key: &str, fields: &[String]) -> Result<()> {
+        let Some(raw) = self.get(key).await? else {
+            return Ok(());
+        };
+        let mut hash = read_hash(Some(raw));
+        for field in fields {
+            hash.remove(field);
+        }
+        if hash.is_empty() {
+            return self.remove(key).await;
+        }
+        let ttl_seconds = self.ttl(key).await?.map_or(0, |ttl| ttl.as_secs().max(1));
+        self.set(key, &sonic_rs::to_string(&hash)?, ttl_seconds)
+            .await
+    }
+
+    /// Number of fields in the hash stored at `key`.
+    async fn hash_len(&self, key: &str) -> Result<usize> {
+        Ok(read_hash(self.get(key).await?).len())
+    }
+}
+
+fn read_hash(raw: Option<String>) -> BTreeMap<String, String> {
+    raw.and_then(|raw| sonic_rs::from_str(&raw).ok())
+        .unwrap_or_default()
 }
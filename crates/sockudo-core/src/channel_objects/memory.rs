@@ -0,0 +1,50 @@
+use super::store::ChannelObjectStore;
+use super::types::*;
+use crate::error::Result;
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Keeps channel objects in this process. State is node-local: other nodes in a cluster see
+/// neither the objects nor their serials, and everything is lost on restart.
+#[derive(Clone, Default)]
+pub struct MemoryChannelObjectStore {
+    channels: Arc<RwLock<BTreeMap<String, ChannelObjects>>>,
+}
+
+impl MemoryChannelObjectStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn channel_key(app_id: &str, channel: &str) -> String {
+        format!("{app_id}\0{channel}")
+    }
+}
+
+#[async_trait]
+impl ChannelObjectStore for MemoryChannelObjectStore {
+    async fn apply_operations(
+        &self,
+        app_id: &str,
+        channel: &str,
+        operations: &[ObjectOperation],
+        limits: &ChannelObjectLimits,
+    ) -> Result<u64> {
+        let key = Self::channel_key(app_id, channel);
+        let mut channels = self.channels.write().await;
+        channels.entry(key).or_default().apply(operations, limits)
+    }
+
+    async fn snapshot(&self, app_id: &str, channel: &str) -> Result<ChannelObjects> {
+        let key = Self::channel_key(app_id, channel);
+        Ok(self
+            .channels
+            .read()
+            .await
+            .get(&key)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
@@ -0,0 +1,10 @@
+mod memory;
+mod store;
+mod types;
+
+pub use memory::MemoryChannelObjectStore;
+pub use store::{ChannelObjectStore, NoopChannelObjectStore};
+pub use types::*;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,43 @@
+use super::types::*;
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait ChannelObjectStore: Send + Sync {
+    /// Applies `operations` to the channel's objects as one atomic batch and returns the
+    /// serial assigned to it. Batches that would leave the channel over `limits` are refused.
+    async fn apply_operations(
+        &self,
+        app_id: &str,
+        channel: &str,
+        operations: &[ObjectOperation],
+        limits: &ChannelObjectLimits,
+    ) -> Result<u64>;
+
+    /// The channel's current objects; empty with serial `0` when nothing was written yet.
+    async fn snapshot(&self, app_id: &str, channel: &str) -> Result<ChannelObjects>;
+}
+
+#[derive(Default)]
+pub struct NoopChannelObjectStore;
+
+#[async_trait]
+impl ChannelObjectStore for NoopChannelObjectStore {
+    async fn apply_operations(
+        &self,
+        _app_id: &str,
+        _channel: &str,
+        _operations: &[ObjectOperation],
+        _limits: &ChannelObjectLimits,
+    ) -> Result<u64> {
+        Err(Error::Configuration(
+            "Channel object storage is not configured".to_string(),
+        ))
+    }
+
+    async fn snapshot(&self, _app_id: &str, _channel: &str) -> Result<ChannelObjects> {
+        Err(Error::Configuration(
+            "Channel object storage is not configured".to_string(),
+        ))
+    }
+}
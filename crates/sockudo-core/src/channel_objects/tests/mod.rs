@@ -0,0 +1,270 @@
+use super::*;
+use crate::error::Error;
+use sonic_rs::json;
+
+const LIMITS: ChannelObjectLimits = ChannelObjectLimits {
+    max_objects: 100,
+    max_map_entries: 1000,
+    max_value_bytes: 65_536,
+};
+
+fn increment(object: &str, amount: i64) -> ObjectOperation {
+    ObjectOperation::CounterIncrement {
+        object: object.to_string(),
+        amount,
+    }
+}
+
+fn map_set(object: &str, key: &str, value: sonic_rs::Value) -> ObjectOperation {
+    ObjectOperation::MapSet {
+        object: object.to_string(),
+        key: key.to_string(),
+        value,
+    }
+}
+
+#[test]
+fn operations_parse_from_wire_format() {
+    let operations: Vec<ObjectOperation> = sonic_rs::from_str(
+        r#"[
+            {"op":"counter.increment","object":"votes"},
+            {"op":"counter.increment","object":"votes","amount":-3},
+            {"op":"map.set","object":"cursors","key":"alice","value":{"x":1}},
+            {"op":"map.remove","object":"cursors","key":"bob"},
+            {"op":"register.set","object":"title","value":"Draft"}
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(operations[0], increment("votes", 1));
+    assert_eq!(operations[1], increment("votes", -3));
+    assert_eq!(
+        operations[3],
+        ObjectOperation::MapRemove {
+            object: "cursors".to_string(),
+            key: "bob".to_string(),
+        }
+    );
+    assert_eq!(operations[4].kind(), ObjectKind::Register);
+}
+
+#[test]
+fn batches_apply_in_order_and_bump_serial_once() {
+    let mut objects = ChannelObjects::default();
+
+    let serial = objects
+        .apply(
+            &[
+                increment("votes", 2),
+                increment("votes", 3),
+                map_set("cursors", "alice", json!({"x": 1})),
+                map_set("cursors", "bob", json!({"x": 2})),
+                ObjectOperation::MapRemove {
+                    object: "cursors".to_string(),
+                    key: "bob".to_string(),
+                },
+                ObjectOperation::RegisterSet {
+                    object: "title".to_string(),
+                    value: json!("Draft"),
+                },
+            ],
+            &LIMITS,
+        )
+        .unwrap();
+
+    assert_eq!(serial, 1);
+    assert_eq!(
+        objects.objects["votes"],
+        ChannelObject::Counter { value: 5 }
+    );
+    let ChannelObject::Map { entries } = &objects.objects["cursors"] else {
+        panic!("cursors should be a map");
+    };
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries["alice"], json!({"x": 1}));
+    assert_eq!(
+        objects.objects["title"],
+        ChannelObject::Register {
+            value: json!("Draft")
+        }
+    );
+
+    assert_eq!(objects.apply(&[increment("votes", 1)], &LIMITS).unwrap(), 2);
+}
+
+#[test]
+fn counters_saturate_instead_of_overflowing() {
+    let mut objects = ChannelObjects::default();
+    objects
+        .apply(&[increment("votes", i64::MAX)], &LIMITS)
+        .unwrap();
+    objects.apply(&[increment("votes", 1)], &LIMITS).unwrap();
+
+    assert_eq!(
+        objects.objects["votes"],
+        ChannelObject::Counter { value: i64::MAX }
+    );
+}
+
+#[test]
+fn type_conflicts_reject_the_whole_batch() {
+    let mut objects = ChannelObjects::default();
+    objects.apply(&[increment("votes", 1)], &LIMITS).unwrap();
+
+    let err = objects
+        .apply(
+            &[increment("votes", 1), map_set("votes", "a", json!(1))],
+            &LIMITS,
+        )
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidMessageFormat(_)));
+
+    let err = objects
+        .apply(
+            &[map_set("fresh", "a", json!(1)), increment("fresh", 1)],
+            &LIMITS,
+        )
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidMessageFormat(_)));
+
+    assert_eq!(objects.serial, 1);
+    assert_eq!(
+        objects.objects["votes"],
+        ChannelObject::Counter { value: 1 }
+    );
+    assert!(!objects.objects.contains_key("fresh"));
+}
+
+#[test]
+fn invalid_identifiers_and_empty_batches_are_rejected() {
+    let mut objects = ChannelObjects::default();
+
+    assert!(objects.apply(&[], &LIMITS).is_err());
+    assert!(objects.apply(&[increment(" ", 1)], &LIMITS).is_err());
+    assert!(
+        objects
+            .apply(
+                &[map_set(
+                    "cursors",
+                    &"k".repeat(MAX_OBJECT_MAP_KEY_LENGTH + 1),
+                    json!(1)
+                )],
+                &LIMITS
+            )
+            .is_err()
+    );
+    assert!(objects.is_empty());
+}
+
+#[test]
+fn batches_over_the_limits_are_refused_whole() {
+    let limits = ChannelObjectLimits {
+        max_objects: 2,
+        max_map_entries: 2,
+        max_value_bytes: 16,
+    };
+    let mut objects = ChannelObjects::default();
+    objects
+        .apply(
+            &[
+                map_set("cursors", "alice", json!(1)),
+                map_set("cursors", "bob", json!(2)),
+            ],
+            &limits,
+        )
+        .unwrap();
+
+    let refused = [
+        vec![increment("a", 1), increment("b", 1)],
+        vec![map_set("cursors", "carol", json!(3))],
+        vec![map_set("cursors", "alice", json!("x".repeat(16)))],
+        vec![ObjectOperation::RegisterSet {
+            object: "title".to_string(),
+            value: json!("x".repeat(16)),
+        }],
+    ];
+    for operations in refused {
+        assert!(matches!(
+            objects.apply(&operations, &limits),
+            Err(Error::InvalidMessageFormat(_))
+        ));
+    }
+    assert_eq!(objects.serial, 1);
+    assert_eq!(objects.objects.len(), 1);
+
+    // Removing an entry in the same batch makes room for a new one.
+    objects
+        .apply(
+            &[
+                ObjectOperation::MapRemove {
+                    object: "cursors".to_string(),
+                    key: "bob".to_string(),
+                },
+                map_set("cursors", "carol", json!(3)),
+                map_set("cursors", "alice", json!(4)),
+            ],
+            &limits,
+        )
+        .unwrap();
+}
+
+#[tokio::test]
+async fn memory_store_keeps_channels_separate() {
+    let store = MemoryChannelObjectStore::new();
+
+    assert_eq!(
+        store
+            .apply_operations("app", "poll", &[increment("votes", 1)], &LIMITS)
+            .await
+            .unwrap(),
+        1
+    );
+    store
+        .apply_operations("app", "poll", &[increment("votes", 1)], &LIMITS)
+        .await
+        .unwrap();
+    store
+        .apply_operations("other-app", "poll", &[increment("votes", 7)], &LIMITS)
+        .await
+        .unwrap();
+
+    let snapshot = store.snapshot("app", "poll").await.unwrap();
+    assert_eq!(snapshot.serial, 2);
+    assert_eq!(
+        snapshot.objects["votes"],
+        ChannelObject::Counter { value: 2 }
+    );
+    assert!(store.snapshot("app", "empty").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn noop_store_reports_missing_configuration() {
+    let store = NoopChannelObjectStore;
+    assert!(matches!(
+        store.snapshot("app", "poll").await,
+        Err(Error::Configuration(_))
+    ));
+}
+
+#[test]
+fn snapshots_round_trip_through_json() {
+    let mut objects = ChannelObjects::default();
+    objects
+        .apply(
+            &[
+                increment("votes", -4),
+                map_set("cursors", "alice", json!({"x": 1})),
+                ObjectOperation::RegisterSet {
+                    object: "title".to_string(),
+                    value: json!(null),
+                },
+            ],
+            &LIMITS,
+        )
+        .unwrap();
+
+    let encoded = sonic_rs::to_string(&objects).unwrap();
+    let decoded: ChannelObjects = sonic_rs::from_str(&encoded).unwrap();
+
+    assert_eq!(decoded, objects);
+}
@@ -0,0 +1,405 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sonic_rs::{JsonValueTrait, Value};
+use std::collections::BTreeMap;
+
+pub const MAX_OBJECT_ID_LENGTH: usize = 256;
+pub const MAX_OBJECT_MAP_KEY_LENGTH: usize = 256;
+
+fn default_increment() -> i64 {
+    1
+}
+
+/// One change to an object attached to a channel. Counter increments commute, so concurrent
+/// writers never lose a vote; map entries and registers are last-writer-wins in the order the
+/// store assigns serials.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "op", try_from = "RawObjectOperation")]
+pub enum ObjectOperation {
+    #[serde(rename = "counter.increment")]
+    CounterIncrement {
+        object: String,
+        #[serde(default = "default_increment")]
+        amount: i64,
+    },
+    #[serde(rename = "map.set")]
+    MapSet {
+        object: String,
+        key: String,
+        value: Value,
+    },
+    #[serde(rename = "map.remove")]
+    MapRemove { object: String, key: String },
+    #[serde(rename = "register.set")]
+    RegisterSet { object: String, value: Value },
+}
+
+impl ObjectOperation {
+    pub fn object(&self) -> &str {
+        match self {
+            Self::CounterIncrement { object, .. }
+            | Self::MapSet { object, .. }
+            | Self::MapRemove { object, .. }
+            | Self::RegisterSet { object, .. } => object,
+        }
+    }
+
+    pub fn kind(&self) -> ObjectKind {
+        match self {
+            Self::CounterIncrement { .. } => ObjectKind::Counter,
+            Self::MapSet { .. } | Self::MapRemove { .. } => ObjectKind::Map,
+            Self::RegisterSet { .. } => ObjectKind::Register,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        validate_identifier("object id", self.object(), MAX_OBJECT_ID_LENGTH)?;
+        if let Self::MapSet { key, .. } | Self::MapRemove { key, .. } = self {
+            validate_identifier("map key", key, MAX_OBJECT_MAP_KEY_LENGTH)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind {
+    Counter,
+    Map,
+    Register,
+}
+
+impl ObjectKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Map => "map",
+            Self::Register => "register",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", try_from = "RawChannelObject")]
+pub enum ChannelObject {
+    Counter { value: i64 },
+    Map { entries: BTreeMap<String, Value> },
+    Register { value: Value },
+}
+
+impl ChannelObject {
+    pub fn kind(&self) -> ObjectKind {
+        match self {
+            Self::Counter { .. } => ObjectKind::Counter,
+            Self::Map { .. } => ObjectKind::Map,
+            Self::Register { .. } => ObjectKind::Register,
+        }
+    }
+}
+
+/// Size limits for one channel's objects, checked against the state a batch would leave
+/// behind before any of it is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelObjectLimits {
+    /// Objects a channel may hold.
+    pub max_objects: usize,
+    /// Entries a single map may hold.
+    pub max_map_entries: usize,
+    /// Encoded JSON size of one map entry or register value.
+    pub max_value_bytes: usize,
+}
+
+impl Default for ChannelObjectLimits {
+    fn default() -> Self {
+        Self {
+            max_objects: 100,
+            max_map_entries: 1000,
+            max_value_bytes: 65_536,
+        }
+    }
+}
+
+/// Every object attached to one channel.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ChannelObjects {
+    /// Serial of the last applied batch; `0` while the channel has no objects.
+    pub serial: u64,
+    pub objects: BTreeMap<String, ChannelObject>,
+}
+
+impl ChannelObjects {
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Applies `operations` as one batch and returns its serial. The batch is checked before
+    /// anything changes, so either every operation applies or none does.
+    pub fn apply(
+        &mut self,
+        operations: &[ObjectOperation],
+        limits: &ChannelObjectLimits,
+    ) -> Result<u64> {
+        self.validate_batch(operations, limits)?;
+        for operation in operations {
+            self.apply_one(operation);
+        }
+        self.serial += 1;
+        Ok(self.serial)
+    }
+
+    fn validate_batch(
+        &self,
+        operations: &[ObjectOperation],
+        limits: &ChannelObjectLimits,
+    ) -> Result<()> {
+        if operations.is_empty() {
+            return Err(Error::InvalidMessageFormat(
+                "object operations must not be empty".to_string(),
+            ));
+        }
+        let mut created: BTreeMap<&str, ObjectKind> = BTreeMap::new();
+        for operation in operations {
+            operation.validate()?;
+            let object = operation.object();
+            let existing = self
+                .objects
+                .get(object)
+                .map(ChannelObject::kind)
+                .or_else(|| created.get(object).copied());
+            match existing {
+                Some(kind) if kind != operation.kind() => {
+                    return Err(Error::InvalidMessageFormat(format!(
+                        "object '{object}' is a {}, not a {}",
+                        kind.as_str(),
+                        operation.kind().as_str()
+                    )));
+                }
+                Some(_) => {}
+                None => {
+                    created.insert(object, operation.kind());
+                }
+            }
+            if let ObjectOperation::MapSet { value, .. }
+            | ObjectOperation::RegisterSet { value, .. } = operation
+            {
+                let size = sonic_rs::to_string(value)?.len();
+                if size > limits.max_value_bytes {
+                    return Err(Error::InvalidMessageFormat(format!(
+                        "object '{object}' value is {size} bytes; at most {} are allowed",
+                        limits.max_value_bytes
+                    )));
+                }
+            }
+        }
+
+        if self.objects.len() + created.len() > limits.max_objects {
+            return Err(Error::InvalidMessageFormat(format!(
+                "a channel may hold at most {} objects",
+                limits.max_objects
+            )));
+        }
+        self.validate_map_entries(operations, limits)
+    }
+
+    /// Replays the batch's map writes over the stored entries to find each touched map's size
+    /// once the batch is applied.
+    fn validate_map_entries(
+        &self,
+        operations: &[ObjectOperation],
+        limits: &ChannelObjectLimits,
+    ) -> Result<()> {
+        let mut touched: BTreeMap<&str, BTreeMap<&str, bool>> = BTreeMap::new();
+        for operation in operations {
+            match operation {
+                ObjectOperation::MapSet { object, key, .. } => {
+                    touched.entry(object).or_default().insert(key, true);
+                }
+                ObjectOperation::MapRemove { object, key } => {
+                    touched.entry(object).or_default().insert(key, false);
+                }
+                _ => {}
+            }
+        }
+
+        for (object, keys) in touched {
+            let stored = match self.objects.get(object) {
+                Some(ChannelObject::Map { entries }) => Some(entries),
+                _ => None,
+            };
+            let mut len = stored.map_or(0, BTreeMap::len);
+            for (key, present) in keys {
+                let existed = stored.is_some_and(|entries| entries.contains_key(key));
+                match (existed, present) {
+                    (false, true) => len += 1,
+                    (true, false) => len -= 1,
+                    _ => {}
+                }
+            }
+            if len > limits.max_map_entries {
+                return Err(Error::InvalidMessageFormat(format!(
+                    "map '{object}' may hold at most {} entries",
+                    limits.max_map_entries
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_one(&mut self, operation: &ObjectOperation) {
+        match operation {
+            ObjectOperation::CounterIncrement { object, amount } => {
+                let entry = self
+                    .objects
+                    .entry(object.clone())
+                    .or_insert(ChannelObject::Counter { value: 0 });
+                if let ChannelObject::Counter { value } = entry {
+                    *value = value.saturating_add(*amount);
+                }
+            }
+            ObjectOperation::MapSet { object, key, value } => {
+                let entry =
+                    self.objects
+                        .entry(object.clone())
+                        .or_insert_with(|| ChannelObject::Map {
+                            entries: BTreeMap::new(),
+                        });
+                if let ChannelObject::Map { entries } = entry {
+                    entries.insert(key.clone(), value.clone());
+                }
+            }
+            ObjectOperation::MapRemove { object, key } => {
+                let entry =
+                    self.objects
+                        .entry(object.clone())
+                        .or_insert_with(|| ChannelObject::Map {
+                            entries: BTreeMap::new(),
+                        });
+                if let ChannelObject::Map { entries } = entry {
+                    entries.remove(key);
+                }
+            }
+            ObjectOperation::RegisterSet { object, value } => {
+                self.objects.insert(
+                    object.clone(),
+                    ChannelObject::Register {
+                        value: value.clone(),
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// A batch as delivered to subscribers after it was applied.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ObjectOperationBatch {
+    pub serial: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    pub timestamp: i64,
+    pub operations: Vec<ObjectOperation>,
+}
+
+// Internally tagged enums buffer their fields before dispatching on the tag, which sonic-rs
+// values cannot round-trip through, so both enums deserialize from a flat struct instead.
+#[derive(Deserialize)]
+struct RawObjectOperation {
+    op: String,
+    object: String,
+    #[serde(default)]
+    key: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    value: Option<Value>,
+    #[serde(default)]
+    amount: Option<i64>,
+}
+
+impl TryFrom<RawObjectOperation> for ObjectOperation {
+    type Error = String;
+
+    fn try_from(raw: RawObjectOperation) -> std::result::Result<Self, Self::Error> {
+        let RawObjectOperation {
+            op,
+            object,
+            key,
+            value,
+            amount,
+        } = raw;
+        let key = |op: &str| key.ok_or_else(|| format!("{op} requires a key"));
+        let value = |op: &str| value.ok_or_else(|| format!("{op} requires a value"));
+        match op.as_str() {
+            "counter.increment" => Ok(Self::CounterIncrement {
+                object,
+                amount: amount.unwrap_or_else(default_increment),
+            }),
+            "map.set" => Ok(Self::MapSet {
+                object,
+                key: key("map.set")?,
+                value: value("map.set")?,
+            }),
+            "map.remove" => Ok(Self::MapRemove {
+                object,
+                key: key("map.remove")?,
+            }),
+            "register.set" => Ok(Self::RegisterSet {
+                object,
+                value: value("register.set")?,
+            }),
+            other => Err(format!("unknown object operation: {other}")),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawChannelObject {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    value: Option<Value>,
+    #[serde(default)]
+    entries: Option<BTreeMap<String, Value>>,
+}
+
+impl TryFrom<RawChannelObject> for ChannelObject {
+    type Error = String;
+
+    fn try_from(raw: RawChannelObject) -> std::result::Result<Self, Self::Error> {
+        match raw.kind.as_str() {
+            "counter" => Ok(Self::Counter {
+                value: raw
+                    .value
+                    .as_ref()
+                    .and_then(|value| value.as_i64())
+                    .ok_or("counter requires an integer value")?,
+            }),
+            "map" => Ok(Self::Map {
+                entries: raw.entries.unwrap_or_default(),
+            }),
+            "register" => Ok(Self::Register {
+                value: raw.value.ok_or("register requires a value")?,
+            }),
+            other => Err(format!("unknown object type: {other}")),
+        }
+    }
+}
+
+/// Keeps an explicit `null` as `Some(Value::Null)` so only a missing field reads as `None`.
+fn deserialize_present<'de, D>(deserializer: D) -> std::result::Result<Option<Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Value::deserialize(deserializer).map(Some)
+}
+
+fn validate_identifier(label: &str, value: &str, max_len: usize) -> Result<()> {
+    if value.trim().is_empty() {
+        return Err(Error::InvalidMessageFormat(format!(
+            "{label} must not be empty"
+        )));
+    }
+    if value.len() > max_len {
+        return Err(Error::InvalidMessageFormat(format!(
+            "{label} must be at most {max_len} bytes"
+        )));
+    }
+    Ok(())
+}
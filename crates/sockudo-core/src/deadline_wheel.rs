@@ -0,0 +1,157 @@
+//! Sharded pending-deadline maps and the wheel that flushes them.
+//!
+//! Used by the AI transport append rollup and by per-subscription delivery
+//! windows: pending entries live in independently locked shards, and a task
+//! ticking on a fixed interval flushes the entries whose deadline passed.
+
+use ahash::{AHashMap, RandomState};
+use parking_lot::{Mutex, MutexGuard};
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+pub const DEFAULT_SHARDS: usize = 64;
+
+/// A map split across independently locked shards, so unrelated keys do not contend.
+pub struct ShardedDeadlineMap<K, V> {
+    shards: Vec<Mutex<AHashMap<K, V>>>,
+    hasher: RandomState,
+}
+
+impl<K: Hash + Eq, V> Default for ShardedDeadlineMap<K, V> {
+    fn default() -> Self {
+        Self::new(DEFAULT_SHARDS)
+    }
+}
+
+impl<K: Hash + Eq, V> ShardedDeadlineMap<K, V> {
+    #[must_use]
+    pub fn new(shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1))
+                .map(|_| Mutex::new(AHashMap::new()))
+                .collect(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Locks the shard holding `key`.
+    #[inline]
+    pub fn lock(&self, key: &K) -> MutexGuard<'_, AHashMap<K, V>> {
+        let index = (self.hasher.hash_one(key) as usize) % self.shards.len();
+        self.shards[index].lock()
+    }
+
+    /// Visits every entry one shard at a time, keeping those `keep` returns true for.
+    pub fn retain(&self, mut keep: impl FnMut(&K, &mut V) -> bool) {
+        for shard in &self.shards {
+            shard.lock().retain(|key, value| keep(key, value));
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.lock().len()).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.lock().is_empty())
+    }
+}
+
+/// Starts the flush wheel of one owner at most once.
+#[derive(Default)]
+pub struct DeadlineWheel {
+    started: AtomicBool,
+}
+
+impl DeadlineWheel {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts the wheel on the current runtime unless it already runs. Outside a runtime
+    /// nothing starts, so a later call can try again.
+    pub fn ensure_started<T, F, Fut>(&self, owner: &Arc<T>, tick_ms: u64, flush: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<T>, u64) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        if self.started.load(Ordering::Acquire) || tokio::runtime::Handle::try_current().is_err() {
+            return;
+        }
+        if self.started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        spawn_deadline_wheel(Arc::downgrade(owner), tick_ms, flush);
+    }
+}
+
+/// Calls `flush` with the owner and the current time in milliseconds every `tick_ms` until
+/// the owner is dropped. Returns false outside a tokio runtime.
+pub fn spawn_deadline_wheel<T, F, Fut>(owner: Weak<T>, tick_ms: u64, flush: F) -> bool
+where
+    T: Send + Sync + 'static,
+    F: Fn(Arc<T>, u64) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        return false;
+    };
+    handle.spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(tick_ms.max(1)));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let Some(owner) = owner.upgrade() else {
+                return;
+            };
+            flush(owner, crate::history::now_ms().max(0) as u64).await;
+        }
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[tokio::test]
+    async fn wheel_starts_once_and_stops_with_its_owner() {
+        let owner = Arc::new(AtomicUsize::new(0));
+        let wheel = DeadlineWheel::new();
+        for _ in 0..3 {
+            wheel.ensure_started(&owner, 5, |owner, _| async move {
+                owner.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let ticks = owner.load(Ordering::SeqCst);
+        assert!((1..=12).contains(&ticks), "unexpected tick count {ticks}");
+
+        let weak = Arc::downgrade(&owner);
+        drop(owner);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn retain_visits_every_shard() {
+        let map = ShardedDeadlineMap::new(4);
+        for key in 0..100u32 {
+            map.lock(&key).insert(key, key);
+        }
+        assert_eq!(map.len(), 100);
+
+        map.retain(|_, deadline| *deadline >= 50);
+        assert_eq!(map.len(), 50);
+        assert!(map.lock(&10).get(&10).is_none());
+        assert_eq!(map.lock(&60).get(&60), Some(&60));
+    }
+}
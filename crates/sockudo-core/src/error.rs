@@ -33,6 +33,12 @@ pub enum Error {
     #[error("Origin not allowed")]
     OriginNotAllowed,
 
+    #[error("User is over its connection limit")]
+    UserConnectionLimit,
+
+    #[error("Connection replaced by a newer connection of the same user")]
+    ConnectionReplaced,
+
     // 4100-4199: Reconnect with backoff errors
     #[error("Over capacity")]
     OverCapacity,
@@ -57,6 +63,9 @@ pub enum Error {
     #[error("Watchlist limit exceeded")]
     WatchlistLimitExceeded,
 
+    #[error("Channel {0} is over its subscriber limit")]
+    ChannelSubscriberLimit(String),
+
     // Channel specific errors
     #[error("Channel error: {0}")]
     Channel(String),
@@ -80,6 +89,9 @@ pub enum Error {
     #[error("Invalid key")]
     InvalidKey,
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     // Connection errors
     #[error("Connection error: {0}")]
     Connection(String),
@@ -200,6 +212,8 @@ impl Error {
             Error::NoProtocolVersion => 4008,
             Error::Unauthorized => 4009,
             Error::OriginNotAllowed => 4009,
+            Error::UserConnectionLimit => 4010,
+            Error::ConnectionReplaced => 4011,
 
             // 4100-4199: Reconnect with backoff
             Error::OverCapacity => 4100,
@@ -215,6 +229,7 @@ impl Error {
             Error::WatchlistLimitExceeded => 4302,
 
             Error::Broadcast(_) => 4303,
+            Error::ChannelSubscriberLimit(_) => 4304,
 
             // Map other errors to appropriate ranges
             Error::Channel(_)
@@ -226,7 +241,9 @@ impl Error {
 
             Error::AiTransport { .. } => 4000,
 
-            Error::Auth(_) | Error::InvalidSignature | Error::InvalidKey => 4009,
+            Error::Auth(_) | Error::InvalidSignature | Error::InvalidKey | Error::Forbidden(_) => {
+                4009
+            }
 
             Error::Connection(_) | Error::ConnectionExists | Error::ConnectionNotFound => 4000,
 
@@ -249,6 +266,8 @@ impl Error {
                 | Error::UnsupportedProtocolVersion(_)
                 | Error::NoProtocolVersion
                 | Error::Unauthorized
+                | Error::UserConnectionLimit
+                | Error::ConnectionReplaced
                 | Error::ClientEventRateLimitTerminate
         )
     }
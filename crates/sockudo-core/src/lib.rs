@@ -5,6 +5,8 @@ pub mod auth;
 pub mod cache;
 pub mod capability_token;
 pub mod channel;
+pub mod channel_objects;
+pub mod deadline_wheel;
 pub mod delta_types;
 pub mod error;
 pub mod history;
@@ -24,3 +26,4 @@ pub mod versioned_message_auth;
 pub mod versioned_messages;
 pub mod webhook_types;
 pub mod websocket;
+pub mod wildcard_index;
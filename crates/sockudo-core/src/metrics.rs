@@ -279,6 +279,12 @@ pub trait MetricsInterface: Send + Sync {
     /// Update the number of reset-required presence-history channels for an app.
     fn update_presence_history_reset_required_channels(&self, _app_id: &str, _count: usize) {}
 
+    /// Track a call to an app's proxy backend by proxy kind and result.
+    fn mark_proxy_request(&self, _app_id: &str, _proxy: &str, _result: &str) {}
+
+    /// Track the round-trip latency of a proxy backend call.
+    fn track_proxy_request_latency(&self, _app_id: &str, _proxy: &str, _latency_ms: f64) {}
+
     /// Get the stored metrics as plain text, if possible
     async fn get_metrics_as_plaintext(&self) -> String;
 
@@ -1,14 +1,17 @@
 use crate::app::AppManager;
 use crate::channel::PresenceMemberInfo;
 use crate::error::{Error, Result};
-use crate::utils::wildcard_pattern_matches;
-use crate::websocket::{SocketId, WebSocket, WebSocketBufferConfig, WebSocketRef};
+use crate::utils::{
+    is_segment_wildcard_pattern_with, is_wildcard_subscription_pattern_with,
+    wildcard_pattern_matches_with, wildcard_segment_delimiters,
+};
+use crate::websocket::{SocketId, TransportWriter, WebSocket, WebSocketBufferConfig, WebSocketRef};
+use crate::wildcard_index::SegmentWildcardIndex;
 use ahash::AHashMap as HashMap;
 use ahash::AHashSet;
 use dashmap::mapref::entry::Entry;
 use dashmap::{DashMap, DashSet};
 use futures_util::future::join_all;
-use sockudo_ws::axum_integration::WebSocketWriter;
 use std::sync::Arc;
 use tracing::{debug, error, warn};
 
@@ -17,7 +20,10 @@ pub struct Namespace {
     pub app_id: String,
     pub sockets: DashMap<SocketId, WebSocketRef>,
     pub channels: DashMap<String, DashSet<SocketId>>,
+    // Single-`*` character-level patterns, matched by scanning.
     wildcard_channels: DashSet<String>,
+    // Hierarchical segment patterns, matched through a segment trie.
+    segment_wildcards: SegmentWildcardIndex,
     pub users: DashMap<String, DashSet<WebSocketRef>>,
     pub presence_data: DashMap<SocketId, HashMap<String, PresenceMemberInfo>>,
     // Reverse index of channels joined per socket, for O(channels-per-socket) disconnect cleanup.
@@ -33,11 +39,16 @@ pub struct SocketInitOptions {
 
 impl Namespace {
     pub fn new(app_id: String) -> Self {
+        Self::with_wildcard_segment_delimiters(app_id, wildcard_segment_delimiters())
+    }
+
+    pub fn with_wildcard_segment_delimiters(app_id: String, delimiters: &[char]) -> Self {
         Self {
             app_id,
             sockets: DashMap::new(),
             channels: DashMap::new(),
             wildcard_channels: DashSet::new(),
+            segment_wildcards: SegmentWildcardIndex::new(delimiters),
             users: DashMap::new(),
             presence_data: DashMap::new(),
             socket_channels: DashMap::new(),
@@ -47,7 +58,7 @@ impl Namespace {
     pub async fn add_socket(
         &self,
         socket_id: SocketId,
-        socket_writer: WebSocketWriter,
+        socket_writer: impl Into<TransportWriter>,
         app_manager: Arc<dyn AppManager + Send + Sync>,
         init: SocketInitOptions,
     ) -> Result<WebSocketRef> {
@@ -184,7 +195,7 @@ impl Namespace {
         }
 
         let mut socket_refs = self.get_channel_socket_refs_except(channel, except);
-        if self.wildcard_channels.is_empty() {
+        if !self.has_wildcard_subscriptions() {
             return socket_refs;
         }
 
@@ -227,7 +238,7 @@ impl Namespace {
             }
         }
 
-        if self.wildcard_channels.is_empty() {
+        if !self.has_wildcard_subscriptions() {
             return (v1_refs, v2_refs);
         }
 
@@ -283,13 +294,8 @@ impl Namespace {
         socket_ids: &mut AHashSet<SocketId>,
         include_exact_channels: bool,
     ) {
-        for wildcard_channel in self.wildcard_channels.iter() {
-            let subscribed_channel = wildcard_channel.key();
-            if !wildcard_pattern_matches(channel, subscribed_channel) {
-                continue;
-            }
-
-            if let Some(channel_sockets_ref) = self.channels.get(subscribed_channel) {
+        for subscribed_channel in self.matching_wildcard_channels(channel) {
+            if let Some(channel_sockets_ref) = self.channels.get(&subscribed_channel) {
                 for socket_id_entry in channel_sockets_ref.iter() {
                     let socket_id = socket_id_entry.key();
                     if except != Some(socket_id) {
@@ -311,12 +317,7 @@ impl Namespace {
         seen_socket_ids: &mut Option<AHashSet<SocketId>>,
         socket_refs: &mut Vec<WebSocketRef>,
     ) {
-        for wildcard_channel in self.wildcard_channels.iter() {
-            let subscribed_channel = wildcard_channel.key();
-            if !wildcard_pattern_matches(channel, subscribed_channel) {
-                continue;
-            }
-
+        for subscribed_channel in self.matching_wildcard_channels(channel) {
             let seen_socket_ids = seen_socket_ids.get_or_insert_with(|| {
                 let mut seen = AHashSet::with_capacity(socket_refs.len().saturating_mul(2));
                 for socket_ref in socket_refs.iter() {
@@ -325,7 +326,7 @@ impl Namespace {
                 seen
             });
 
-            if let Some(channel_sockets_ref) = self.channels.get(subscribed_channel) {
+            if let Some(channel_sockets_ref) = self.channels.get(&subscribed_channel) {
                 for socket_id_entry in channel_sockets_ref.iter() {
                     let socket_id = socket_id_entry.key();
                     if except == Some(socket_id) || !seen_socket_ids.insert(*socket_id) {
@@ -348,12 +349,7 @@ impl Namespace {
         v1_refs: &mut Vec<WebSocketRef>,
         v2_refs: &mut Vec<WebSocketRef>,
     ) {
-        for wildcard_channel in self.wildcard_channels.iter() {
-            let subscribed_channel = wildcard_channel.key();
-            if !wildcard_pattern_matches(channel, subscribed_channel) {
-                continue;
-            }
-
+        for subscribed_channel in self.matching_wildcard_channels(channel) {
             let seen_socket_ids = seen_socket_ids.get_or_insert_with(|| {
                 let mut seen =
                     AHashSet::with_capacity((v1_refs.len() + v2_refs.len()).saturating_mul(2));
@@ -363,7 +359,7 @@ impl Namespace {
                 seen
             });
 
-            if let Some(channel_sockets_ref) = self.channels.get(subscribed_channel) {
+            if let Some(channel_sockets_ref) = self.channels.get(&subscribed_channel) {
                 for socket_id_entry in channel_sockets_ref.iter() {
                     let socket_id = socket_id_entry.key();
                     if except == Some(socket_id) || !seen_socket_ids.insert(*socket_id) {
@@ -378,6 +374,47 @@ impl Namespace {
         }
     }
 
+    fn is_wildcard_channel(&self, channel: &str) -> bool {
+        is_wildcard_subscription_pattern_with(channel, self.segment_wildcards.delimiters())
+    }
+
+    fn index_wildcard_channel(&self, channel: &str) {
+        if is_segment_wildcard_pattern_with(channel, self.segment_wildcards.delimiters()) {
+            self.segment_wildcards.insert(channel);
+        } else {
+            self.wildcard_channels.insert(channel.to_string());
+        }
+    }
+
+    fn unindex_wildcard_channel(&self, channel: &str) {
+        if is_segment_wildcard_pattern_with(channel, self.segment_wildcards.delimiters()) {
+            self.segment_wildcards.remove(channel);
+        } else {
+            self.wildcard_channels.remove(channel);
+        }
+    }
+
+    fn has_wildcard_subscriptions(&self) -> bool {
+        !self.wildcard_channels.is_empty() || !self.segment_wildcards.is_empty()
+    }
+
+    /// Subscribed wildcard patterns matching `channel`: segment patterns come from the trie,
+    /// the remaining single-`*` patterns are scanned.
+    fn matching_wildcard_channels(&self, channel: &str) -> Vec<String> {
+        let mut patterns = self.segment_wildcards.matching_patterns(channel);
+        for wildcard_channel in self.wildcard_channels.iter() {
+            let subscribed_channel = wildcard_channel.key();
+            if wildcard_pattern_matches_with(
+                channel,
+                subscribed_channel,
+                self.segment_wildcards.delimiters(),
+            ) {
+                patterns.push(subscribed_channel.clone());
+            }
+        }
+        patterns
+    }
+
     fn push_socket_ref_by_protocol(
         socket_ref: WebSocketRef,
         v1_refs: &mut Vec<WebSocketRef>,
@@ -415,8 +452,9 @@ impl Namespace {
                 set.remove(&socket_id);
                 set.is_empty()
             });
-            if channel_name.contains('*') && !self.channels.contains_key(&channel_name) {
-                self.wildcard_channels.remove(&channel_name);
+            if self.is_wildcard_channel(&channel_name) && !self.channels.contains_key(&channel_name)
+            {
+                self.unindex_wildcard_channel(&channel_name);
             }
         }
 
@@ -489,8 +527,8 @@ impl Namespace {
             .or_default()
             .insert(channel.to_string());
 
-        if channel.contains('*') {
-            self.wildcard_channels.insert(channel.to_string());
+        if self.is_wildcard_channel(channel) {
+            self.index_wildcard_channel(channel);
         }
 
         tracing::debug!(
@@ -521,8 +559,8 @@ impl Namespace {
                 .remove_if(channel, |_, set| set.is_empty())
                 .is_some();
             if vacated {
-                if channel.contains('*') {
-                    self.wildcard_channels.remove(channel);
+                if self.is_wildcard_channel(channel) {
+                    self.unindex_wildcard_channel(channel);
                 }
                 debug!("Removed empty channel entry: {}", channel);
             }
@@ -539,8 +577,8 @@ impl Namespace {
                     set.remove(socket_id);
                     set.is_empty()
                 });
-                if channel.contains('*') && !self.channels.contains_key(channel) {
-                    self.wildcard_channels.remove(channel);
+                if self.is_wildcard_channel(channel) && !self.channels.contains_key(channel) {
+                    self.unindex_wildcard_channel(channel);
                 }
             }
         }
@@ -559,8 +597,8 @@ impl Namespace {
 
     pub fn remove_channel(&self, channel: &str) {
         self.channels.remove(channel);
-        if channel.contains('*') {
-            self.wildcard_channels.remove(channel);
+        if self.is_wildcard_channel(channel) {
+            self.unindex_wildcard_channel(channel);
         }
         debug!("Removed channel entry: {}", channel);
     }
@@ -869,6 +907,57 @@ mod tests {
         assert_eq!(*v2[0].get_socket_id_sync(), wildcard_socket);
     }
 
+    #[tokio::test]
+    async fn segment_wildcard_subscription_matches_through_trie_in_partitioned_lookup() {
+        let namespace = Namespace::with_wildcard_segment_delimiters("app".to_string(), &['.']);
+        let wildcard_socket = SocketId::new();
+        let other_socket = SocketId::new();
+        let app_manager: Arc<dyn AppManager + Send + Sync> = Arc::new(TestAppManager {
+            app: App::from_policy(
+                "app".to_string(),
+                "app-key".to_string(),
+                "app-secret".to_string(),
+                true,
+                AppPolicy::default(),
+            ),
+        });
+
+        for socket_id in [wildcard_socket, other_socket] {
+            namespace
+                .add_socket(
+                    socket_id,
+                    create_server_writer().await,
+                    app_manager.clone(),
+                    SocketInitOptions {
+                        buffer_config: crate::websocket::WebSocketBufferConfig::default(),
+                        protocol_version: ProtocolVersion::V2,
+                        wire_format: WireFormat::Json,
+                        echo_messages: true,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+
+        namespace.add_channel_to_socket("prices.*.equity.>", &wildcard_socket);
+        namespace.add_channel_to_socket("prices.*", &other_socket);
+        assert!(namespace.wildcard_channels.is_empty());
+
+        let (v1, v2) = namespace
+            .get_matching_channel_socket_refs_partitioned_except("prices.eu.equity.AAPL", None);
+        assert!(v1.is_empty());
+        assert_eq!(v2.len(), 1);
+        assert_eq!(*v2[0].get_socket_id_sync(), wildcard_socket);
+
+        let (_, v2) =
+            namespace.get_matching_channel_socket_refs_partitioned_except("prices.eu.fx", None);
+        assert!(v2.is_empty());
+
+        namespace.remove_channel_from_socket("prices.*.equity.>", &wildcard_socket);
+        namespace.remove_channel_from_socket("prices.*", &other_socket);
+        assert!(!namespace.has_wildcard_subscriptions());
+    }
+
     #[test]
     fn remove_connection_clears_all_channel_membership() {
         let namespace = Namespace::new("app".to_string());
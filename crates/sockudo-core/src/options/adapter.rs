@@ -65,6 +65,23 @@ impl Default for AdapterConfig {
     }
 }
 
+impl AdapterConfig {
+    /// Channel subscriber limits read cluster-wide counts on every subscribe, which a
+    /// horizontal adapter only answers locally from the gossiped `aggregate_counts`.
+    pub fn validate_subscriber_limits(&self, apps: &[crate::app::App]) -> Result<(), String> {
+        if self.driver != AdapterDriver::Local
+            && !self.aggregate_counts
+            && let Some(app) = apps.iter().find(|app| app.has_channel_subscriber_limits())
+        {
+            return Err(format!(
+                "app '{}' sets max_subscribers_per_channel, which with a horizontal adapter requires adapter.aggregate_counts = true",
+                app.id
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct RedisAdapterConfig {
@@ -287,3 +304,39 @@ impl Default for IggyConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, AppLimitsPolicy, AppPolicy};
+
+    #[test]
+    fn subscriber_limits_in_a_cluster_require_aggregate_counts() {
+        let app = App::from_policy(
+            "app".to_string(),
+            "app-key".to_string(),
+            "app-secret".to_string(),
+            true,
+            AppPolicy {
+                limits: AppLimitsPolicy {
+                    max_subscribers_per_channel: Some(100),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let apps = [app];
+        let mut adapter = AdapterConfig::default();
+        assert!(adapter.validate_subscriber_limits(&apps).is_ok());
+
+        adapter.driver = AdapterDriver::Redis;
+        let error = adapter.validate_subscriber_limits(&apps).unwrap_err();
+        assert!(
+            error.contains("aggregate_counts"),
+            "unexpected error: {error}"
+        );
+
+        adapter.aggregate_counts = true;
+        assert!(adapter.validate_subscriber_limits(&apps).is_ok());
+    }
+}
@@ -25,6 +25,14 @@ pub struct PresenceConfig {
     pub ungraceful_timeout_seconds: u64,
 }
 
+/// Hierarchical wildcard subscriptions. Each character of `segment_delimiters` splits channel
+/// names into segments; empty keeps wildcards to a single character-level `*`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WildcardSubscriptionsConfig {
+    pub segment_delimiters: String,
+}
+
 impl Default for ChannelLimits {
     fn default() -> Self {
         Self {
@@ -146,6 +146,7 @@ pub(super) fn apply(options: &mut ServerOptions) -> Result<(), Box<dyn std::erro
                     decay_seconds: None,
                     terminate_on_limit: false,
                     message_rate_limit: None,
+                    max_subscribers_per_channel: None,
                 },
                 features: crate::app::AppFeaturesPolicy {
                     enable_client_messages: std::env::var(
@@ -199,6 +200,13 @@ pub(super) fn apply(options: &mut ServerOptions) -> Result<(), Box<dyn std::erro
                 connection_recovery: None,
                 history: None,
                 presence_history: None,
+                token_auth: None,
+                secrets: None,
+                api_keys: None,
+                network: None,
+                user_channel: None,
+                user_connections: None,
+                proxy: None,
             },
         );
 
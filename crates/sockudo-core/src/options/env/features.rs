@@ -12,6 +12,13 @@ pub(super) fn apply(options: &mut ServerOptions) -> Result<(), Box<dyn std::erro
             "VersionedMessages Backend",
         );
     }
+    if let Ok(driver_str) = std::env::var("CHANNEL_OBJECTS_DRIVER") {
+        options.channel_objects.driver = parse_driver_enum(
+            driver_str,
+            options.channel_objects.driver.clone(),
+            "ChannelObjects Backend",
+        );
+    }
     if let Ok(driver_str) = std::env::var("PUSH_STORAGE_DRIVER") {
         options.push.storage_driver = parse_driver_enum(
             driver_str,
@@ -147,6 +154,24 @@ pub(super) fn apply(options: &mut ServerOptions) -> Result<(), Box<dyn std::erro
     );
     options.annotations.enabled =
         parse_bool_env("ANNOTATIONS_ENABLED", options.annotations.enabled);
+    options.channel_objects.enabled =
+        parse_bool_env("CHANNEL_OBJECTS_ENABLED", options.channel_objects.enabled);
+    options.channel_objects.max_operations_per_request = parse_env::<usize>(
+        "CHANNEL_OBJECTS_MAX_OPERATIONS_PER_REQUEST",
+        options.channel_objects.max_operations_per_request,
+    );
+    options.channel_objects.max_objects_per_channel = parse_env::<usize>(
+        "CHANNEL_OBJECTS_MAX_OBJECTS_PER_CHANNEL",
+        options.channel_objects.max_objects_per_channel,
+    );
+    options.channel_objects.max_map_entries = parse_env::<usize>(
+        "CHANNEL_OBJECTS_MAX_MAP_ENTRIES",
+        options.channel_objects.max_map_entries,
+    );
+    options.channel_objects.max_value_bytes = parse_env::<usize>(
+        "CHANNEL_OBJECTS_MAX_VALUE_BYTES",
+        options.channel_objects.max_value_bytes,
+    );
 
     Ok(())
 }
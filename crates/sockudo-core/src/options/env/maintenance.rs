@@ -114,6 +114,76 @@ pub(super) fn apply(options: &mut ServerOptions) -> Result<(), Box<dyn std::erro
         options.websocket.compression = mode;
     }
 
+    // Long-polling transport
+    options.long_polling.enabled =
+        parse_bool_env("LONG_POLLING_ENABLED", options.long_polling.enabled);
+    options.long_polling.poll_timeout_ms = parse_env::<u64>(
+        "LONG_POLLING_POLL_TIMEOUT_MS",
+        options.long_polling.poll_timeout_ms,
+    );
+    options.long_polling.session_idle_timeout_seconds = parse_env::<u64>(
+        "LONG_POLLING_SESSION_IDLE_TIMEOUT",
+        options.long_polling.session_idle_timeout_seconds,
+    );
+    options.long_polling.max_messages_per_poll = parse_env::<usize>(
+        "LONG_POLLING_MAX_MESSAGES_PER_POLL",
+        options.long_polling.max_messages_per_poll,
+    );
+    options.long_polling.sticky_routing = parse_bool_env(
+        "LONG_POLLING_STICKY_ROUTING",
+        options.long_polling.sticky_routing,
+    );
+
+    // MQTT gateway
+    options.mqtt.enabled = parse_bool_env("MQTT_ENABLED", options.mqtt.enabled);
+    if let Ok(host) = std::env::var("MQTT_HOST") {
+        options.mqtt.host = host;
+    }
+    options.mqtt.port = parse_env::<u16>("MQTT_PORT", options.mqtt.port);
+    if let Ok(separator) = std::env::var("MQTT_TOPIC_SEPARATOR") {
+        options.mqtt.topic_separator = separator;
+    }
+    if let Ok(event) = std::env::var("MQTT_PUBLISH_EVENT") {
+        options.mqtt.publish_event = event;
+    }
+    options.mqtt.max_packet_size_kb =
+        parse_env::<usize>("MQTT_MAX_PACKET_SIZE_KB", options.mqtt.max_packet_size_kb);
+    options.mqtt.max_inflight_messages = parse_env::<u16>(
+        "MQTT_MAX_INFLIGHT_MESSAGES",
+        options.mqtt.max_inflight_messages,
+    );
+    options.mqtt.session_expiry_seconds = parse_env::<u64>(
+        "MQTT_SESSION_EXPIRY_SECONDS",
+        options.mqtt.session_expiry_seconds,
+    );
+
+    // gRPC API
+    options.grpc.enabled = parse_bool_env("GRPC_ENABLED", options.grpc.enabled);
+    if let Ok(host) = std::env::var("GRPC_HOST") {
+        options.grpc.host = host;
+    }
+    options.grpc.port = parse_env::<u16>("GRPC_PORT", options.grpc.port);
+    options.grpc.max_message_size_kb =
+        parse_env::<usize>("GRPC_MAX_MESSAGE_SIZE_KB", options.grpc.max_message_size_kb);
+
+    // JWKS-backed capability tokens
+    options.jwks.cache_ttl_seconds =
+        parse_env::<u64>("JWKS_CACHE_TTL_SECONDS", options.jwks.cache_ttl_seconds);
+    options.jwks.refresh_interval_seconds = parse_env::<u64>(
+        "JWKS_REFRESH_INTERVAL_SECONDS",
+        options.jwks.refresh_interval_seconds,
+    );
+    options.jwks.negative_cache_ttl_seconds = parse_env::<u64>(
+        "JWKS_NEGATIVE_CACHE_TTL_SECONDS",
+        options.jwks.negative_cache_ttl_seconds,
+    );
+    options.jwks.min_refetch_interval_seconds = parse_env::<u64>(
+        "JWKS_MIN_REFETCH_INTERVAL_SECONDS",
+        options.jwks.min_refetch_interval_seconds,
+    );
+    options.jwks.request_timeout_ms =
+        parse_env::<u64>("JWKS_REQUEST_TIMEOUT_MS", options.jwks.request_timeout_ms);
+
     // Connection recovery (includes serial + message_id + replay buffer)
     options.connection_recovery.enabled = parse_bool_env(
         "CONNECTION_RECOVERY_ENABLED",
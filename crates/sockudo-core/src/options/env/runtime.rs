@@ -110,6 +110,9 @@ pub(super) fn apply(options: &mut ServerOptions) -> Result<(), Box<dyn std::erro
         "PRESENCE_UNGRACEFUL_TIMEOUT_SECONDS",
         options.presence.ungraceful_timeout_seconds,
     );
+    if let Ok(delimiters) = std::env::var("WILDCARD_SEGMENT_DELIMITERS") {
+        options.wildcard_subscriptions.segment_delimiters = delimiters;
+    }
     if let Ok(prefix) = std::env::var("RATE_LIMITER_REDIS_PREFIX") {
         options.rate_limiter.redis.prefix = Some(prefix);
     }
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::{CacheDriver, MetricsDriver, RedisConfig};
+use super::{AdapterConfig, AdapterDriver, CacheDriver, MetricsDriver, RedisConfig};
 
 // Custom deserializer for octal permission mode (string format only, like chmod)
 fn deserialize_octal_permission<'de, D>(deserializer: D) -> Result<u32, D::Error>
@@ -123,6 +123,136 @@ impl Default for WebSocketConfig {
     }
 }
 
+/// HTTP long-polling fallback transport for clients that cannot keep an upgraded or
+/// streaming connection open.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LongPollingConfig {
+    pub enabled: bool,
+    /// Maximum time a poll request is held open waiting for messages.
+    pub poll_timeout_ms: u64,
+    /// Sessions that are not polled for this long are closed.
+    pub session_idle_timeout_seconds: u64,
+    /// Maximum number of frames returned by a single poll.
+    pub max_messages_per_poll: usize,
+    /// Confirms that the load balancer routes polls to the node that opened the session, using
+    /// the `sockudo_node` cookie or `x-sockudo-node` header. Required with a horizontal adapter.
+    pub sticky_routing: bool,
+}
+
+impl Default for LongPollingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            poll_timeout_ms: 25_000,
+            session_idle_timeout_seconds: 60,
+            max_messages_per_poll: 100,
+            sticky_routing: false,
+        }
+    }
+}
+
+impl LongPollingConfig {
+    /// Sessions live on the node that opened them, so a cluster only works when every poll is
+    /// routed back to that node.
+    pub fn validate_deployment(&self, adapter: &AdapterConfig) -> Result<(), String> {
+        if self.enabled && adapter.driver != AdapterDriver::Local && !self.sticky_routing {
+            return Err(
+                "long_polling with a horizontal adapter requires sticky routing on the sockudo_node cookie or x-sockudo-node header; set long_polling.sticky_routing = true once the load balancer provides it".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// gRPC API for backend services: `Publish`, `BatchPublish` and a server-streaming
+/// `Subscribe`, authenticated with app credentials in request metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GrpcConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    /// Largest encoded request or response message accepted on the gRPC listener.
+    pub max_message_size_kb: usize,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "0.0.0.0".to_string(),
+            port: 50051,
+            max_message_size_kb: 1024,
+        }
+    }
+}
+
+/// MQTT 3.1.1 / 5 gateway. Clients authenticate with the app key as username and an optional
+/// capability token as password; topics map onto channels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MqttConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    /// Replaces `/` when translating topics into channel names.
+    pub topic_separator: String,
+    /// Event name used for messages published by MQTT clients. Client-event publishes are
+    /// sent as `client-<publish_event>`.
+    pub publish_event: String,
+    pub max_packet_size_kb: usize,
+    /// How long a persistent (`clean_session = false`) session is kept after disconnect when
+    /// the client does not set an MQTT 5 session expiry.
+    pub session_expiry_seconds: u64,
+    /// QoS 1 deliveries per connection that may await a PUBACK. Further deliveries are sent at
+    /// QoS 0 until the client acknowledges. MQTT 5 clients can lower it with receive maximum.
+    pub max_inflight_messages: u16,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "0.0.0.0".to_string(),
+            port: 1883,
+            topic_separator: ".".to_string(),
+            publish_event: "mqtt-message".to_string(),
+            max_packet_size_kb: 256,
+            session_expiry_seconds: 3600,
+            max_inflight_messages: 100,
+        }
+    }
+}
+
+/// Fetching and caching of the JWKS documents apps reference from `token_auth.jwks_url`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JwksConfig {
+    /// How long a fetched key set is served from the cache manager.
+    pub cache_ttl_seconds: u64,
+    /// Key sets in use are refetched this often, so rotations are picked up before the cached
+    /// copy expires.
+    pub refresh_interval_seconds: u64,
+    /// How long a `kid` that the endpoint does not know is rejected without asking it again.
+    pub negative_cache_ttl_seconds: u64,
+    /// Minimum time between on-demand fetches of the same URL.
+    pub min_refetch_interval_seconds: u64,
+    pub request_timeout_ms: u64,
+}
+
+impl Default for JwksConfig {
+    fn default() -> Self {
+        Self {
+            cache_ttl_seconds: 3600,
+            refresh_interval_seconds: 300,
+            negative_cache_ttl_seconds: 60,
+            min_refetch_interval_seconds: 10,
+            request_timeout_ms: 5000,
+        }
+    }
+}
+
 impl WebSocketConfig {
     /// Convert to WebSocketBufferConfig for runtime use
     pub fn to_buffer_config(&self) -> crate::websocket::WebSocketBufferConfig {
@@ -508,3 +638,28 @@ impl ClusterHealthConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_polling_in_a_cluster_requires_sticky_routing() {
+        let mut config = LongPollingConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let mut adapter = AdapterConfig::default();
+        assert!(config.validate_deployment(&adapter).is_ok());
+
+        adapter.driver = AdapterDriver::Redis;
+        let error = config.validate_deployment(&adapter).unwrap_err();
+        assert!(
+            error.contains("sticky routing"),
+            "unexpected error: {error}"
+        );
+
+        config.sticky_routing = true;
+        assert!(config.validate_deployment(&adapter).is_ok());
+    }
+}
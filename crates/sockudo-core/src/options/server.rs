@@ -30,6 +30,7 @@ pub struct ServerOptions {
     pub user_authentication_timeout: u64,
     pub webhooks: WebhooksConfig,
     pub websocket_max_payload_kb: u32,
+    pub wildcard_subscriptions: WildcardSubscriptionsConfig,
     pub cleanup: CleanupConfig,
     pub activity_timeout: u64,
     pub cluster_health: ClusterHealthConfig,
@@ -37,6 +38,10 @@ pub struct ServerOptions {
     pub delta_compression: DeltaCompressionOptionsConfig,
     pub tag_filtering: TagFilteringConfig,
     pub websocket: WebSocketConfig,
+    pub long_polling: LongPollingConfig,
+    pub mqtt: MqttConfig,
+    pub grpc: GrpcConfig,
+    pub jwks: JwksConfig,
     pub connection_recovery: ConnectionRecoveryConfig,
     pub history: HistoryConfig,
     pub presence_history: PresenceHistoryConfig,
@@ -46,6 +51,7 @@ pub struct ServerOptions {
     pub event_name_filtering: EventNameFilteringConfig,
     pub versioned_messages: VersionedMessagesConfig,
     pub annotations: AnnotationsConfig,
+    pub channel_objects: ChannelObjectsConfig,
     pub ai_transport: AiTransportConfig,
     pub push: PushConfig,
     pub push_rules: Vec<PushRuleConfig>,
@@ -83,12 +89,17 @@ impl Default for ServerOptions {
             user_authentication_timeout: 3600,
             webhooks: WebhooksConfig::default(),
             websocket_max_payload_kb: 64,
+            wildcard_subscriptions: WildcardSubscriptionsConfig::default(),
             cleanup: CleanupConfig::default(),
             activity_timeout: 120,
             cluster_health: ClusterHealthConfig::default(),
             unix_socket: UnixSocketConfig::default(),
             delta_compression: DeltaCompressionOptionsConfig::default(),
             websocket: WebSocketConfig::default(),
+            long_polling: LongPollingConfig::default(),
+            mqtt: MqttConfig::default(),
+            grpc: GrpcConfig::default(),
+            jwks: JwksConfig::default(),
             connection_recovery: ConnectionRecoveryConfig::default(),
             history: HistoryConfig::default(),
             presence_history: PresenceHistoryConfig::default(),
@@ -98,6 +109,7 @@ impl Default for ServerOptions {
             event_name_filtering: EventNameFilteringConfig::default(),
             versioned_messages: VersionedMessagesConfig::default(),
             annotations: AnnotationsConfig::default(),
+            channel_objects: ChannelObjectsConfig::default(),
             ai_transport: AiTransportConfig::default(),
             push: PushConfig::default(),
             push_rules: Vec::new(),
@@ -175,6 +187,13 @@ impl ServerOptions {
             }
         }
 
+        self.long_polling.validate_deployment(&self.adapter)?;
+        self.adapter
+            .validate_subscriber_limits(&self.app_manager.array.apps)?;
+        if self.mqtt.enabled && self.mqtt.max_inflight_messages == 0 {
+            return Err("mqtt.max_inflight_messages must be greater than 0".to_string());
+        }
+
         if self.versioned_messages.enabled && self.versioned_messages.max_page_size == 0 {
             return Err("versioned_messages.max_page_size must be greater than 0".to_string());
         }
@@ -187,6 +206,24 @@ impl ServerOptions {
         if self.annotations.enabled && !self.versioned_messages.enabled {
             return Err("annotations require versioned_messages.enabled".to_string());
         }
+        if self.channel_objects.enabled {
+            for (key, value) in [
+                (
+                    "max_operations_per_request",
+                    self.channel_objects.max_operations_per_request,
+                ),
+                (
+                    "max_objects_per_channel",
+                    self.channel_objects.max_objects_per_channel,
+                ),
+                ("max_map_entries", self.channel_objects.max_map_entries),
+                ("max_value_bytes", self.channel_objects.max_value_bytes),
+            ] {
+                if value == 0 {
+                    return Err(format!("channel_objects.{key} must be greater than 0"));
+                }
+            }
+        }
         if self.ai_transport.enabled {
             self.ai_transport.validate_deployment_matrix(
                 &self.adapter,
@@ -240,6 +277,18 @@ impl ServerOptions {
             rule.validate(index)?;
         }
 
+        if let Some(delimiter) = self
+            .wildcard_subscriptions
+            .segment_delimiters
+            .chars()
+            .find(|c| !crate::utils::WILDCARD_SEGMENT_DELIMITER_CHARS.contains(c))
+        {
+            return Err(format!(
+                "wildcard_subscriptions.segment_delimiters contains '{delimiter}'; allowed delimiters are {:?}",
+                crate::utils::WILDCARD_SEGMENT_DELIMITER_CHARS
+            ));
+        }
+
         if self.adapter.nats.presence_sync_chunk_size == Some(0) {
             return Err("nats.presence_sync_chunk_size must be > 0 when set".to_string());
         }
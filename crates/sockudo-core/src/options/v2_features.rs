@@ -118,6 +118,54 @@ pub struct AnnotationsConfig {
     pub enabled: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelObjectStoreDriver {
+    #[default]
+    Memory,
+    Postgres,
+    Mysql,
+}
+
+impl FromStr for ChannelObjectStoreDriver {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "postgres" | "postgresql" | "pgsql" => Ok(Self::Postgres),
+            "mysql" => Ok(Self::Mysql),
+            "memory" => Ok(Self::Memory),
+            _ => Err(format!("Unknown channel object store driver: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelObjectsConfig {
+    /// Whether channel-attached objects (counters, maps, registers) are enabled.
+    pub enabled: bool,
+    /// Storage driver for channel object state. Defaults to memory.
+    pub driver: ChannelObjectStoreDriver,
+    /// Maximum number of operations accepted in a single batch.
+    pub max_operations_per_request: usize,
+    /// Maximum number of objects attached to one channel.
+    pub max_objects_per_channel: usize,
+    /// Maximum number of entries in one map object.
+    pub max_map_entries: usize,
+    /// Maximum encoded JSON size of one map entry or register value.
+    pub max_value_bytes: usize,
+}
+
+impl ChannelObjectsConfig {
+    pub fn limits(&self) -> crate::channel_objects::ChannelObjectLimits {
+        crate::channel_objects::ChannelObjectLimits {
+            max_objects: self.max_objects_per_channel,
+            max_map_entries: self.max_map_entries,
+            max_value_bytes: self.max_value_bytes,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum HistoryBackend {
@@ -285,6 +333,19 @@ impl Default for VersionedMessagesConfig {
     }
 }
 
+impl Default for ChannelObjectsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            driver: ChannelObjectStoreDriver::Memory,
+            max_operations_per_request: 100,
+            max_objects_per_channel: 100,
+            max_map_entries: 1000,
+            max_value_bytes: 65_536,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct PresenceHistoryConfig {
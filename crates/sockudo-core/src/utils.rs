@@ -1,5 +1,5 @@
 use std::env;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, OnceLock};
 
 use crate::app::{App, ChannelNamespace};
 use crate::error::Error;
@@ -87,8 +87,79 @@ pub fn strip_channel_user_limit_suffix(channel: &str) -> &str {
     channel.split_once('#').map_or(channel, |(base, _)| base)
 }
 
+static WILDCARD_SEGMENT_DELIMITERS: OnceLock<Vec<char>> = OnceLock::new();
+
+/// Characters a wildcard subscription may use as segment delimiters.
+pub const WILDCARD_SEGMENT_DELIMITER_CHARS: &[char] = &['.', ':', '-', '_', '=', '@'];
+
+/// Installs the server-wide segment delimiters for hierarchical wildcard subscriptions. Only
+/// the first call takes effect; without one every wildcard is a single character-level `*`.
+pub fn set_wildcard_segment_delimiters(delimiters: &str) {
+    let _ = WILDCARD_SEGMENT_DELIMITERS.set(delimiters.chars().collect());
+}
+
+pub fn wildcard_segment_delimiters() -> &'static [char] {
+    WILDCARD_SEGMENT_DELIMITERS
+        .get()
+        .map_or(&[], |delimiters| delimiters.as_slice())
+}
+
+pub fn split_channel_segments<'a>(
+    channel: &'a str,
+    delimiters: &'a [char],
+) -> impl Iterator<Item = &'a str> + 'a {
+    channel.split(move |c: char| delimiters.contains(&c))
+}
+
+/// `*` as a whole segment matches exactly one segment.
+pub const SEGMENT_WILDCARD: &str = "*";
+
+/// `>` or `#` as the last segment matches one or more remaining segments.
+pub fn is_tail_segment_wildcard(segment: &str) -> bool {
+    segment == ">" || segment == "#"
+}
+
+/// Whether `pattern` uses segment wildcards under `delimiters`, i.e. has a segment that is
+/// exactly `*`, `>` or `#`. Such patterns match segment by segment instead of by character.
+pub fn is_segment_wildcard_pattern_with(pattern: &str, delimiters: &[char]) -> bool {
+    !delimiters.is_empty()
+        && !pattern.starts_with("#server-to-user-")
+        && split_channel_segments(pattern, delimiters)
+            .any(|segment| segment == SEGMENT_WILDCARD || is_tail_segment_wildcard(segment))
+}
+
+pub fn is_segment_wildcard_pattern(pattern: &str) -> bool {
+    is_segment_wildcard_pattern_with(pattern, wildcard_segment_delimiters())
+}
+
 pub fn is_wildcard_subscription_pattern(channel: &str) -> bool {
-    !channel.starts_with("#server-to-user-") && channel.contains('*')
+    is_wildcard_subscription_pattern_with(channel, wildcard_segment_delimiters())
+}
+
+pub fn is_wildcard_subscription_pattern_with(channel: &str, delimiters: &[char]) -> bool {
+    !channel.starts_with("#server-to-user-")
+        && (channel.contains('*') || is_segment_wildcard_pattern_with(channel, delimiters))
+}
+
+/// The literal part of a wildcard pattern before its first wildcard.
+pub fn wildcard_literal_prefix(pattern: &str) -> &str {
+    wildcard_literal_prefix_with(pattern, wildcard_segment_delimiters())
+}
+
+pub fn wildcard_literal_prefix_with<'a>(pattern: &'a str, delimiters: &[char]) -> &'a str {
+    if !is_segment_wildcard_pattern_with(pattern, delimiters) {
+        return pattern
+            .split_once('*')
+            .map_or(pattern, |(prefix, _)| prefix);
+    }
+    let mut offset = 0;
+    for segment in split_channel_segments(pattern, delimiters) {
+        if segment == SEGMENT_WILDCARD || is_tail_segment_wildcard(segment) {
+            break;
+        }
+        offset += segment.len() + 1;
+    }
+    &pattern[..offset.min(pattern.len())]
 }
 
 pub fn is_meta_channel(channel: &str) -> bool {
@@ -104,10 +175,18 @@ pub fn meta_channel_for(channel: &str) -> Option<String> {
 }
 
 pub fn wildcard_pattern_matches(channel: &str, pattern: &str) -> bool {
+    wildcard_pattern_matches_with(channel, pattern, wildcard_segment_delimiters())
+}
+
+pub fn wildcard_pattern_matches_with(channel: &str, pattern: &str, delimiters: &[char]) -> bool {
     if channel == pattern {
         return true;
     }
 
+    if is_segment_wildcard_pattern_with(pattern, delimiters) {
+        return segment_pattern_matches(channel, pattern, delimiters);
+    }
+
     if pattern.contains('*') {
         if let Some(prefix) = pattern.strip_suffix('*') {
             return channel.starts_with(prefix);
@@ -124,19 +203,59 @@ pub fn wildcard_pattern_matches(channel: &str, pattern: &str) -> bool {
     false
 }
 
+fn segment_pattern_matches(channel: &str, pattern: &str, delimiters: &[char]) -> bool {
+    let mut channel_segments = split_channel_segments(channel, delimiters);
+    let mut pattern_segments = split_channel_segments(pattern, delimiters).peekable();
+    while let Some(pattern_segment) = pattern_segments.next() {
+        if is_tail_segment_wildcard(pattern_segment) && pattern_segments.peek().is_none() {
+            return channel_segments.next().is_some();
+        }
+        match channel_segments.next() {
+            Some(segment) if pattern_segment == SEGMENT_WILDCARD || pattern_segment == segment => {}
+            _ => return false,
+        }
+    }
+    channel_segments.next().is_none()
+}
+
 pub fn validate_wildcard_subscription_pattern(channel: &str) -> crate::error::Result<()> {
-    if !is_wildcard_subscription_pattern(channel) {
+    validate_wildcard_subscription_pattern_with(channel, wildcard_segment_delimiters())
+}
+
+pub fn validate_wildcard_subscription_pattern_with(
+    channel: &str,
+    delimiters: &[char],
+) -> crate::error::Result<()> {
+    if !is_wildcard_subscription_pattern_with(channel, delimiters) {
         return Ok(());
     }
 
-    if channel.matches('*').count() != 1 {
+    let is_segmented = is_segment_wildcard_pattern_with(channel, delimiters);
+    if is_segmented {
+        let segments: Vec<&str> = split_channel_segments(channel, delimiters).collect();
+        for (index, segment) in segments.iter().enumerate() {
+            if is_tail_segment_wildcard(segment) {
+                if index + 1 != segments.len() {
+                    return Err(Error::Channel(format!(
+                        "Wildcard subscription '{}' may only use '{}' as its last segment",
+                        channel, segment
+                    )));
+                }
+            } else if *segment != SEGMENT_WILDCARD && segment.contains(['*', '>', '#']) {
+                return Err(Error::Channel(format!(
+                    "Wildcard subscription '{}' must use wildcards as whole segments",
+                    channel
+                )));
+            }
+        }
+    } else if channel.matches('*').count() != 1 {
         return Err(Error::Channel(format!(
             "Wildcard subscription '{}' must contain exactly one '*'",
             channel
         )));
     }
 
-    if channel.contains('#') {
+    if !is_segmented && channel.contains('#') {
         return Err(Error::Channel(format!(
             "Wildcard subscription '{}' cannot use user-limited syntax",
             channel
@@ -162,6 +281,7 @@ pub fn validate_wildcard_subscription_pattern(channel: &str) -> crate::error::Re
             || c == '.'
             || c == ':'
             || c == '*'
+            || (is_segmented && (c == '>' || c == '#'))
     }) {
         return Err(Error::Channel(format!(
             "Wildcard subscription '{}' contains invalid characters",
@@ -587,6 +707,10 @@ mod tests {
                         allow_presence_for_client: None,
                         history: None,
                         presence_history: None,
+                        proxy: None,
+                        connection_recovery: None,
+                        delta_compression: None,
+                        limits: None,
                     }]),
                     ..Default::default()
                 },
@@ -637,6 +761,10 @@ mod tests {
                         allow_presence_for_client: None,
                         history: None,
                         presence_history: None,
+                        proxy: None,
+                        connection_recovery: None,
+                        delta_compression: None,
+                        limits: None,
                     }]),
                     ..Default::default()
                 },
@@ -676,6 +804,10 @@ mod tests {
                         allow_presence_for_client: None,
                         history: None,
                         presence_history: None,
+                        proxy: None,
+                        connection_recovery: None,
+                        delta_compression: None,
+                        limits: None,
                     }]),
                     ..Default::default()
                 },
@@ -706,4 +838,49 @@ mod tests {
         assert!(validate_wildcard_subscription_pattern("news.*.*").is_err());
         assert!(validate_wildcard_subscription_pattern("news.*#user-1").is_err());
     }
+
+    #[test]
+    fn test_segment_wildcard_pattern_matches() {
+        let dots = &['.'];
+        assert!(wildcard_pattern_matches_with(
+            "prices.eu.equity.AAPL",
+            "prices.*.equity.>",
+            dots
+        ));
+        assert!(wildcard_pattern_matches_with(
+            "prices.us.equity.MSFT.bid",
+            "prices.*.equity.#",
+            dots
+        ));
+        assert!(!wildcard_pattern_matches_with(
+            "prices.eu.equity",
+            "prices.*.equity.>",
+            dots
+        ));
+        assert!(!wildcard_pattern_matches_with(
+            "prices.eu.fx.EURUSD",
+            "prices.*.equity.>",
+            dots
+        ));
+        assert!(wildcard_pattern_matches_with("news.btc", "news.*", dots));
+        assert!(!wildcard_pattern_matches_with(
+            "news.btc.usd",
+            "news.*",
+            dots
+        ));
+        assert!(wildcard_pattern_matches_with("news.btc.usd", "news.*", &[]));
+        assert!(wildcard_pattern_matches_with("room-1", "room-*", dots));
+    }
+
+    #[test]
+    fn test_validate_segment_wildcard_subscription_pattern() {
+        let dots = &['.'];
+        assert!(validate_wildcard_subscription_pattern_with("prices.*.equity.>", dots).is_ok());
+        assert!(validate_wildcard_subscription_pattern_with("prices.*.*.#", dots).is_ok());
+        assert!(validate_wildcard_subscription_pattern_with("prices.>.equity", dots).is_err());
+        assert!(validate_wildcard_subscription_pattern_with("prices.*.eq*", dots).is_err());
+        assert!(validate_wildcard_subscription_pattern_with("private-prices.>", dots).is_err());
+        assert!(validate_wildcard_subscription_pattern_with("prices.>", &[]).is_ok());
+        assert!(!is_wildcard_subscription_pattern_with("prices.>", &[]));
+    }
 }
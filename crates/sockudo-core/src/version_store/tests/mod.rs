@@ -42,6 +42,7 @@ fn base_record(
                 push: None,
                 echo: None,
                 ai: None,
+                priority: None,
             }),
         ),
     }
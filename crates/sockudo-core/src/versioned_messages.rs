@@ -377,6 +377,7 @@ mod tests {
                 push: None,
                 echo: Some(true),
                 ai: None,
+                priority: None,
             }),
         )
     }
@@ -154,6 +154,8 @@ impl ByteCounter {
 pub struct SizedMessage {
     pub bytes: Bytes,
     pub size: usize,
+    /// Sent as a binary frame instead of a text frame.
+    pub binary: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -170,6 +172,7 @@ pub struct RewindGate {
 
 pub(super) type MessageChannelFlavor = mpsc::Array<Message>;
 pub(super) type MessageSenderHandle = crossfire::MAsyncTx<MessageChannelFlavor>;
+pub(super) type MessageReceiverHandle = crossfire::AsyncRx<MessageChannelFlavor>;
 pub(super) type SizedMessageChannelFlavor = mpsc::Array<SizedMessage>;
 pub type SizedMessageSenderHandle = crossfire::MAsyncTx<SizedMessageChannelFlavor>;
 pub(super) type SizedMessageReceiverHandle = crossfire::AsyncRx<SizedMessageChannelFlavor>;
@@ -178,6 +181,18 @@ impl SizedMessage {
     #[inline]
     pub fn new(bytes: Bytes) -> Self {
         let size = bytes.len();
-        Self { bytes, size }
+        Self {
+            bytes,
+            size,
+            binary: false,
+        }
+    }
+
+    #[inline]
+    pub fn binary(bytes: Bytes) -> Self {
+        Self {
+            binary: true,
+            ..Self::new(bytes)
+        }
     }
 }
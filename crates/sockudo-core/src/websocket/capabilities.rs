@@ -1,6 +1,6 @@
 use crate::utils::wildcard_pattern_matches;
 use serde::{Deserialize, Serialize};
-use sonic_rs::Value;
+use sonic_rs::{JsonValueTrait, Value};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct UserInfo {
@@ -11,6 +11,13 @@ pub struct UserInfo {
     pub meta: Option<Value>,
 }
 
+impl UserInfo {
+    /// `device_class` from the sign-in `user_data`, used by per-device connection limits.
+    pub fn device_class(&self) -> Option<&str> {
+        self.info.as_ref()?.get("device_class")?.as_str()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 #[serde(default)]
 pub struct ConnectionCapabilities {
@@ -26,6 +33,8 @@ pub struct ConnectionCapabilities {
     pub annotation_delete_own: Option<Vec<String>>,
     #[serde(rename = "annotation-delete-any", alias = "annotation_delete_any")]
     pub annotation_delete_any: Option<Vec<String>>,
+    #[serde(rename = "object-publish", alias = "object_publish")]
+    pub object_publish: Option<Vec<String>>,
     pub message_update_own: Option<Vec<String>>,
     pub message_update_any: Option<Vec<String>>,
     pub message_delete_own: Option<Vec<String>>,
@@ -89,6 +98,12 @@ impl ConnectionCapabilities {
             .is_some_and(|patterns| Self::matches_any(patterns, channel))
     }
 
+    pub fn allows_object_publish(&self, channel: &str) -> bool {
+        self.object_publish
+            .as_deref()
+            .is_some_and(|patterns| Self::matches_any(patterns, channel))
+    }
+
     pub fn allows_message_mutation_own(
         &self,
         kind: crate::versioned_message_auth::MutationKind,
@@ -1,8 +1,11 @@
-use super::buffer::{ByteCounter, SizedMessage, SizedMessageSenderHandle, WebSocketBufferConfig};
+use super::buffer::{
+    ByteCounter, MessageSenderHandle, SizedMessage, SizedMessageSenderHandle, WebSocketBufferConfig,
+};
 use super::capabilities::UserInfo;
-use super::sender::MessageSender;
+use super::sender::{MessageSender, PriorityLaneReceivers};
 use super::socket_id::SocketId;
 use super::state::{ConnectionState, ConnectionStatus};
+use super::transport::TransportWriter;
 use crate::capability_token::TokenAuthContext;
 use crate::channel::PresenceMemberInfo;
 use crate::error::{Error, Result};
@@ -12,7 +15,6 @@ use crossfire::mpsc;
 use sockudo_filter::FilterNode;
 use sockudo_protocol::messages::PusherMessage;
 use sockudo_ws::Message;
-use sockudo_ws::axum_integration::WebSocketWriter;
 use std::hash::Hash;
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
@@ -22,19 +24,23 @@ pub struct WebSocket {
     pub state: ConnectionState,
     pub message_sender: MessageSender,
     pub broadcast_tx: SizedMessageSenderHandle,
+    /// Lane for `extras.priority: high` messages, drained ahead of normal traffic.
+    pub high_priority_tx: MessageSenderHandle,
+    /// Lane for `extras.priority: low` messages, drained last and shed under backpressure.
+    pub low_priority_tx: MessageSenderHandle,
     pub buffer_config: WebSocketBufferConfig,
     pub byte_counter: Option<Arc<ByteCounter>>,
     pub shutdown_token: CancellationToken,
 }
 
 impl WebSocket {
-    pub fn new(socket_id: SocketId, socket: WebSocketWriter) -> Self {
+    pub fn new(socket_id: SocketId, socket: impl Into<TransportWriter>) -> Self {
         Self::with_buffer_config(socket_id, socket, WebSocketBufferConfig::default())
     }
 
     pub fn with_buffer_config(
         socket_id: SocketId,
-        socket: WebSocketWriter,
+        socket: impl Into<TransportWriter>,
         buffer_config: WebSocketBufferConfig,
     ) -> Self {
         let byte_counter = if buffer_config.tracks_bytes() {
@@ -45,11 +51,17 @@ impl WebSocket {
 
         let channel_capacity = buffer_config.channel_capacity();
         let (broadcast_tx, broadcast_rx) = mpsc::bounded_async::<SizedMessage>(channel_capacity);
+        let (high_priority_tx, high_priority_rx) = mpsc::bounded_async::<Message>(channel_capacity);
+        let (low_priority_tx, low_priority_rx) = mpsc::bounded_async::<Message>(channel_capacity);
         let shutdown_token = CancellationToken::new();
 
         let message_sender = MessageSender::new_with_broadcast(
-            socket,
+            socket.into(),
             broadcast_rx,
+            PriorityLaneReceivers {
+                high: high_priority_rx,
+                low: low_priority_rx,
+            },
             channel_capacity,
             byte_counter.clone(),
             shutdown_token.clone(),
@@ -59,6 +71,8 @@ impl WebSocket {
             state: ConnectionState::with_socket_id(socket_id),
             message_sender,
             broadcast_tx,
+            high_priority_tx,
+            low_priority_tx,
             buffer_config,
             byte_counter,
             shutdown_token,
@@ -0,0 +1,47 @@
+use sonic_rs::Value;
+use sonic_rs::prelude::*;
+
+pub const MIN_DELIVERY_WINDOW_MS: u64 = 10;
+pub const MAX_DELIVERY_WINDOW_MS: u64 = 5_000;
+
+/// What a paced subscription receives at the end of each window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryMode {
+    /// Every message of the window, combined into one `sockudo:batch` frame.
+    Batch,
+    /// Only the newest message of the window.
+    Latest,
+}
+
+/// V2 per-subscription pacing. The first message after a quiet window goes out at once;
+/// anything arriving while the window is open waits for the window to close.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeliveryWindow {
+    pub mode: DeliveryMode,
+    pub window_ms: u64,
+}
+
+impl DeliveryWindow {
+    /// Parses the `delivery` subscription option, e.g. `{"mode": "latest", "window_ms": 100}`.
+    /// `mode` defaults to `batch`.
+    pub fn from_value(value: &Value) -> Result<Self, String> {
+        let obj = value
+            .as_object()
+            .ok_or("delivery must be an object with a window_ms field")?;
+        let mode = match obj.get(&"mode").map(|mode| mode.as_str()) {
+            None | Some(Some("batch")) => DeliveryMode::Batch,
+            Some(Some("latest")) => DeliveryMode::Latest,
+            Some(_) => return Err("delivery.mode must be 'batch' or 'latest'".to_string()),
+        };
+        let window_ms = obj
+            .get(&"window_ms")
+            .and_then(|window| window.as_u64())
+            .ok_or("delivery.window_ms must be a positive integer")?;
+        if !(MIN_DELIVERY_WINDOW_MS..=MAX_DELIVERY_WINDOW_MS).contains(&window_ms) {
+            return Err(format!(
+                "delivery.window_ms must be between {MIN_DELIVERY_WINDOW_MS} and {MAX_DELIVERY_WINDOW_MS}"
+            ));
+        }
+        Ok(Self { mode, window_ms })
+    }
+}
@@ -3,18 +3,22 @@
 mod buffer;
 mod capabilities;
 mod connection;
+mod delivery;
 mod reference;
 mod sender;
 mod socket_id;
 mod state;
+mod transport;
 
 pub use buffer::{BufferLimit, BufferedRewindMessage, ByteCounter, WebSocketBufferConfig};
 pub use capabilities::{ConnectionCapabilities, UserInfo};
 pub use connection::WebSocket;
+pub use delivery::{DeliveryMode, DeliveryWindow, MAX_DELIVERY_WINDOW_MS, MIN_DELIVERY_WINDOW_MS};
 pub use reference::{BufferStats, WebSocketExt, WebSocketRef};
 pub use sender::MessageSender;
 pub use socket_id::SocketId;
 pub use state::{ConnectionState, ConnectionStatus, ConnectionTimeouts};
+pub use transport::TransportWriter;
 
 #[cfg(test)]
 mod tests;
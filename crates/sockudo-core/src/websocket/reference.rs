@@ -4,33 +4,48 @@ use super::buffer::{
 };
 use super::capabilities::ConnectionCapabilities;
 use super::connection::WebSocket;
+use super::delivery::DeliveryWindow;
 use super::socket_id::SocketId;
 use crate::capability_token::TokenAuthContext;
 use crate::error::{Error, Result};
+use crate::utils::{is_wildcard_subscription_pattern, wildcard_pattern_matches};
 use bytes::Bytes;
-use crossfire::TrySendError;
+use crossfire::{AsyncTxTrait, TrySendError};
 use dashmap::DashMap;
 use sockudo_filter::FilterNode;
-use sockudo_protocol::messages::PusherMessage;
+use sockudo_protocol::messages::{MessagePriority, PusherMessage};
 use sockudo_protocol::{ProtocolVersion, WireFormat};
+use sockudo_ws::Message;
 use sonic_rs::Value;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::sync::Arc;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
-use tracing::warn;
+use tracing::{debug, warn};
+
+/// Concrete channels whose wildcard delivery window a socket keeps resolved.
+const MAX_RESOLVED_DELIVERY_WINDOWS: usize = 1024;
 
 #[derive(Clone)]
 pub struct WebSocketRef {
     pub broadcast_tx: SizedMessageSenderHandle,
     pub message_sender: MessageSenderHandle,
+    pub high_priority_tx: MessageSenderHandle,
+    pub low_priority_tx: MessageSenderHandle,
     pub channel_filters: Arc<DashMap<String, Option<Arc<FilterNode>>>>,
     /// V2 event name filters per channel. None = receive all events.
     pub event_name_filters: Arc<DashMap<String, Option<Vec<String>>>>,
     /// V2 raw annotation delivery mode per channel.
     pub annotation_subscriptions: Arc<DashMap<String, bool>>,
+    /// V2 batching / latest-only pacing per subscribed channel.
+    pub delivery_windows: Arc<DashMap<String, DeliveryWindow>>,
+    /// Windows resolved for concrete channels, tagged with the
+    /// `delivery_window_generation` they were resolved under.
+    resolved_delivery_windows: Arc<DashMap<String, (u64, Option<DeliveryWindow>)>>,
+    /// Bumped whenever `delivery_windows` changes, which invalidates every resolved window.
+    delivery_window_generation: Arc<AtomicU64>,
     /// V2 history head captured at subscription acknowledgement time.
     pub attach_serials: Arc<DashMap<String, u64>>,
     pub rewind_gates: Arc<DashMap<String, Arc<Mutex<RewindGate>>>>,
@@ -53,6 +68,8 @@ impl WebSocketRef {
     pub fn new(websocket: WebSocket) -> Self {
         let broadcast_tx = websocket.broadcast_tx.clone();
         let message_sender = websocket.message_sender.sender_handle();
+        let high_priority_tx = websocket.high_priority_tx.clone();
+        let low_priority_tx = websocket.low_priority_tx.clone();
         let socket_id = *websocket.get_socket_id();
         let buffer_config = websocket.buffer_config;
         let byte_counter = websocket.byte_counter.clone();
@@ -68,15 +85,21 @@ impl WebSocketRef {
 
         let event_name_filters = Arc::new(DashMap::new());
         let annotation_subscriptions = Arc::new(DashMap::new());
+        let delivery_windows = Arc::new(DashMap::new());
         let attach_serials = Arc::new(DashMap::new());
         let rewind_gates = Arc::new(DashMap::new());
 
         Self {
             broadcast_tx,
             message_sender,
+            high_priority_tx,
+            low_priority_tx,
             channel_filters,
             event_name_filters,
             annotation_subscriptions,
+            delivery_windows,
+            resolved_delivery_windows: Arc::new(DashMap::new()),
+            delivery_window_generation: Arc::new(AtomicU64::new(0)),
             attach_serials,
             rewind_gates,
             socket_id,
@@ -93,7 +116,11 @@ impl WebSocketRef {
 
     #[inline]
     pub fn send_broadcast(&self, bytes: Bytes) -> Result<()> {
-        let msg_size = bytes.len();
+        self.enqueue_broadcast(SizedMessage::new(bytes))
+    }
+
+    fn enqueue_broadcast(&self, sized_msg: SizedMessage) -> Result<()> {
+        let msg_size = sized_msg.size;
 
         if let Some(ref counter) = self.byte_counter
             && let Some(byte_limit) = self.buffer_config.limit.byte_limit()
@@ -102,8 +129,6 @@ impl WebSocketRef {
             return self.handle_buffer_full("byte limit", byte_limit, Some(msg_size));
         }
 
-        let sized_msg = SizedMessage::new(bytes);
-
         match self.broadcast_tx.try_send(sized_msg) {
             Ok(()) => {
                 if let Some(ref counter) = self.byte_counter {
@@ -121,6 +146,68 @@ impl WebSocketRef {
         }
     }
 
+    /// Queues a pre-serialized broadcast in the lane for `priority`.
+    #[inline]
+    pub fn send_broadcast_with_priority(
+        &self,
+        bytes: Bytes,
+        priority: MessagePriority,
+    ) -> Result<()> {
+        match priority {
+            MessagePriority::Normal => self.send_broadcast(bytes),
+            MessagePriority::High => self.send_high_priority(Message::Text(bytes)),
+            MessagePriority::Low => self.send_low_priority(Message::Text(bytes)),
+        }
+    }
+
+    /// High-priority frames have a lane of their own, so a backlog of other traffic never
+    /// pushes them out. Only a full high-priority lane counts as a full buffer: these frames
+    /// are exempt from the byte limit, and the lane's message capacity bounds them instead.
+    fn send_high_priority(&self, frame: Message) -> Result<()> {
+        match self.high_priority_tx.try_send(frame) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => self.handle_buffer_full(
+                "high priority limit",
+                self.buffer_config.channel_capacity(),
+                None,
+            ),
+            Err(TrySendError::Disconnected(_)) => Err(Error::ConnectionClosed(
+                "Priority channel closed".to_string(),
+            )),
+        }
+    }
+
+    /// Low-priority frames are dropped, never disconnecting the client, as soon as the
+    /// connection falls behind.
+    fn send_low_priority(&self, frame: Message) -> Result<()> {
+        if self.is_backlogged() {
+            debug!(socket_id = %self.socket_id, "Dropping low priority message for slow consumer");
+            return Ok(());
+        }
+        match self.low_priority_tx.try_send(frame) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                debug!(socket_id = %self.socket_id, "Dropping low priority message (lane full)");
+                Ok(())
+            }
+            Err(TrySendError::Disconnected(_)) => Err(Error::ConnectionClosed(
+                "Priority channel closed".to_string(),
+            )),
+        }
+    }
+
+    /// True once normal or control traffic fills half of its buffer.
+    fn is_backlogged(&self) -> bool {
+        let threshold = (self.buffer_config.channel_capacity() / 2).max(1);
+        if self.message_sender.len() >= threshold || self.broadcast_tx.len() >= threshold {
+            return true;
+        }
+        match (&self.byte_counter, self.buffer_config.limit.byte_limit()) {
+            (Some(counter), Some(byte_limit)) => counter.get() >= byte_limit / 2,
+            _ => false,
+        }
+    }
+
     #[inline]
     fn handle_buffer_full(
         &self,
@@ -156,6 +243,31 @@ impl WebSocketRef {
     }
 
     pub fn send_message(&self, message: &PusherMessage) -> Result<()> {
+        let frame = self.encode_frame(message)?;
+        match message.priority() {
+            MessagePriority::High => self.send_high_priority(frame),
+            MessagePriority::Low => self.send_low_priority(frame),
+            MessagePriority::Normal => self.message_sender.try_send(frame).map_err(|e| match e {
+                TrySendError::Full(_) => Error::BufferFull("Message buffer full".into()),
+                TrySendError::Disconnected(_) => Error::ConnectionClosed("Channel closed".into()),
+            }),
+        }
+    }
+
+    /// Queues `message` behind the broadcasts already queued for this socket. Control frames
+    /// take turns with broadcasts, so replies that must follow earlier broadcasts, such as
+    /// `resume_success` after the replayed messages, go through the broadcast lane instead.
+    pub fn send_message_after_broadcasts(&self, message: &PusherMessage) -> Result<()> {
+        match self.encode_frame(message)? {
+            Message::Binary(bytes) => self.enqueue_broadcast(SizedMessage::binary(bytes)),
+            Message::Text(bytes) => self.enqueue_broadcast(SizedMessage::new(bytes)),
+            _ => Err(Error::InvalidMessageFormat(
+                "Unexpected frame type for an ordered message".to_string(),
+            )),
+        }
+    }
+
+    fn encode_frame(&self, message: &PusherMessage) -> Result<Message> {
         if self.closing.load(Ordering::Acquire) || self.shutdown_token.is_cancelled() {
             return Err(Error::ConnectionClosed("Connection shutting down".into()));
         }
@@ -164,27 +276,12 @@ impl WebSocketRef {
             .map_err(|e| Error::InvalidMessageFormat(format!("Serialization failed: {e}")))?;
 
         if self.wire_format.is_binary() {
-            self.message_sender
-                .try_send(sockudo_ws::Message::Binary(Bytes::from(payload)))
-                .map_err(|e| match e {
-                    TrySendError::Full(_) => Error::BufferFull("Message buffer full".into()),
-                    TrySendError::Disconnected(_) => {
-                        Error::ConnectionClosed("Channel closed".into())
-                    }
-                })
+            Ok(Message::Binary(Bytes::from(payload)))
         } else {
             let text = String::from_utf8(payload).map_err(|e| {
                 Error::InvalidMessageFormat(format!("JSON payload is not UTF-8: {e}"))
             })?;
-
-            self.message_sender
-                .try_send(sockudo_ws::Message::text(text))
-                .map_err(|e| match e {
-                    TrySendError::Full(_) => Error::BufferFull("Message buffer full".into()),
-                    TrySendError::Disconnected(_) => {
-                        Error::ConnectionClosed("Channel closed".into())
-                    }
-                })
+            Ok(Message::text(text))
         }
     }
 
@@ -259,6 +356,7 @@ impl WebSocketRef {
         self.channel_filters.insert(channel.clone(), None);
         self.event_name_filters.insert(channel.clone(), None);
         self.annotation_subscriptions.insert(channel.clone(), false);
+        self.clear_delivery_window(&channel);
         self.attach_serials.remove(&channel);
     }
 
@@ -277,6 +375,7 @@ impl WebSocketRef {
             .insert(channel.clone(), filter.map(Arc::new));
         self.event_name_filters.insert(channel.clone(), None);
         self.annotation_subscriptions.insert(channel.clone(), false);
+        self.clear_delivery_window(&channel);
         self.attach_serials.remove(&channel);
     }
 
@@ -300,6 +399,7 @@ impl WebSocketRef {
             .insert(channel.clone(), event_name_filter);
         self.annotation_subscriptions
             .insert(channel.clone(), annotation_subscribe);
+        self.clear_delivery_window(&channel);
         self.attach_serials.remove(&channel);
     }
 
@@ -309,6 +409,7 @@ impl WebSocketRef {
         self.channel_filters.remove(channel);
         self.event_name_filters.remove(channel);
         self.annotation_subscriptions.remove(channel);
+        self.clear_delivery_window(channel);
         self.attach_serials.remove(channel);
         result
     }
@@ -338,6 +439,56 @@ impl WebSocketRef {
             .is_some_and(|entry| *entry.value())
     }
 
+    pub fn set_delivery_window(&self, channel: String, window: Option<DeliveryWindow>) {
+        match window {
+            Some(window) => {
+                self.delivery_windows.insert(channel, window);
+                self.invalidate_resolved_delivery_windows();
+            }
+            None => self.clear_delivery_window(&channel),
+        }
+    }
+
+    fn clear_delivery_window(&self, channel: &str) {
+        if self.delivery_windows.remove(channel).is_some() {
+            self.invalidate_resolved_delivery_windows();
+        }
+    }
+
+    fn invalidate_resolved_delivery_windows(&self) {
+        self.delivery_window_generation
+            .fetch_add(1, Ordering::AcqRel);
+        self.resolved_delivery_windows.clear();
+    }
+
+    /// The pacing that applies to `channel`, either set on the channel itself or on a
+    /// wildcard subscription matching it. Wildcard matches are resolved once per concrete
+    /// channel and cached until the socket's windows change.
+    pub fn delivery_window_for(&self, channel: &str) -> Option<DeliveryWindow> {
+        if self.delivery_windows.is_empty() {
+            return None;
+        }
+        if let Some(window) = self.delivery_windows.get(channel) {
+            return Some(*window.value());
+        }
+        let generation = self.delivery_window_generation.load(Ordering::Acquire);
+        if let Some(resolved) = self.resolved_delivery_windows.get(channel)
+            && resolved.0 == generation
+        {
+            return resolved.1;
+        }
+        let window = self.delivery_windows.iter().find_map(|entry| {
+            (is_wildcard_subscription_pattern(entry.key())
+                && wildcard_pattern_matches(channel, entry.key()))
+            .then(|| *entry.value())
+        });
+        if self.resolved_delivery_windows.len() < MAX_RESOLVED_DELIVERY_WINDOWS {
+            self.resolved_delivery_windows
+                .insert(channel.to_string(), (generation, window));
+        }
+        window
+    }
+
     pub fn set_attach_serial(&self, channel: String, serial: u64) {
         self.attach_serials.insert(channel, serial);
     }
@@ -1,8 +1,11 @@
-use super::buffer::{ByteCounter, MessageSenderHandle, SizedMessageReceiverHandle};
+use super::buffer::{
+    ByteCounter, MessageReceiverHandle, MessageSenderHandle, SizedMessage,
+    SizedMessageReceiverHandle,
+};
+use super::transport::TransportWriter;
 use crate::error::{Error, Result};
 use crossfire::{TrySendError, mpsc};
 use sockudo_ws::Message;
-use sockudo_ws::axum_integration::WebSocketWriter;
 use std::sync::Arc;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
@@ -38,6 +41,18 @@ impl std::fmt::Display for SocketOperation {
     }
 }
 
+/// Receiving halves of a connection's high- and low-priority lanes.
+pub(super) struct PriorityLaneReceivers {
+    pub(super) high: MessageReceiverHandle,
+    pub(super) low: MessageReceiverHandle,
+}
+
+enum Outbound {
+    Control(Message),
+    Broadcast(SizedMessage),
+    Frame(Message),
+}
+
 impl SocketOperation {
     fn is_close_operation(&self) -> bool {
         matches!(self, SocketOperation::SendCloseFrame)
@@ -45,80 +60,174 @@ impl SocketOperation {
 }
 
 impl MessageSender {
-    pub fn new_with_broadcast(
-        mut socket: WebSocketWriter,
+    pub(super) fn new_with_broadcast(
+        mut socket: TransportWriter,
         broadcast_rx: SizedMessageReceiverHandle,
+        lanes: PriorityLaneReceivers,
         buffer_capacity: usize,
         byte_counter: Option<Arc<ByteCounter>>,
         shutdown_token: CancellationToken,
     ) -> Self {
         let (sender, receiver) = mpsc::bounded_async::<Message>(buffer_capacity);
+        let PriorityLaneReceivers { high, low } = lanes;
 
         let receiver_handle = tokio::spawn(async move {
             let mut msg_count = 0;
             let mut is_shutting_down = false;
             let mut broadcast_closed = false;
             let mut receiver_closed = false;
+            let mut high_closed = false;
+            let mut low_closed = false;
+            // High-priority events take turns with normal traffic, and within normal traffic
+            // broadcasts take turns with control frames, so no lane starves another.
+            // Low-priority events go out only when everything else is idle.
+            let mut prefer_high = true;
+            let mut prefer_control = true;
 
             loop {
-                tokio::select! {
+                let outbound = tokio::select! {
                     biased;
 
                     _ = shutdown_token.cancelled() => {
                         debug!("Receiver task shutting down via cancellation token");
                         break;
                     }
+                    recv_result = high.recv(), if !high_closed && prefer_high => {
+                        match recv_result {
+                            Ok(message) => {
+                                prefer_high = false;
+                                Outbound::Frame(message)
+                            }
+                            Err(_) => {
+                                high_closed = true;
+                                continue;
+                            }
+                        }
+                    }
+                    recv_result = receiver.recv(), if !receiver_closed && prefer_control => {
+                        match recv_result {
+                            Ok(message) => {
+                                prefer_high = true;
+                                prefer_control = false;
+                                Outbound::Control(message)
+                            }
+                            Err(_) => {
+                                receiver_closed = true;
+                                continue;
+                            }
+                        }
+                    }
                     recv_result = broadcast_rx.recv(), if !broadcast_closed => {
                         match recv_result {
                             Ok(sized_msg) => {
-                                msg_count += 1;
-                                let msg_size = sized_msg.size;
-                                let msg = Message::Text(sized_msg.bytes);
-
-                                if let Err(e) = socket.send(msg).await {
-                                    Self::log_connection_error(
-                                        &e,
-                                        SocketOperation::WriteFrame,
-                                        msg_count,
-                                        is_shutting_down,
-                                    );
-                                    break;
-                                }
-
-                                if let Some(ref counter) = byte_counter {
-                                    counter.sub(msg_size);
-                                }
+                                prefer_high = true;
+                                prefer_control = true;
+                                Outbound::Broadcast(sized_msg)
                             }
                             Err(_) => {
                                 broadcast_closed = true;
+                                continue;
                             }
                         }
                     }
-                    recv_result = receiver.recv(), if !receiver_closed => {
+                    recv_result = receiver.recv(), if !receiver_closed && !prefer_control => {
                         match recv_result {
                             Ok(message) => {
-                                msg_count += 1;
-
-                                if matches!(message, Message::Close(_)) {
-                                    is_shutting_down = true;
-                                }
-
-                                if let Err(e) = socket.send(message).await {
-                                    Self::log_connection_error(
-                                        &e,
-                                        SocketOperation::WriteFrame,
-                                        msg_count,
-                                        is_shutting_down,
-                                    );
-                                    break;
-                                }
+                                prefer_high = true;
+                                Outbound::Control(message)
                             }
                             Err(_) => {
                                 receiver_closed = true;
+                                continue;
+                            }
+                        }
+                    }
+                    recv_result = high.recv(), if !high_closed && !prefer_high => {
+                        match recv_result {
+                            Ok(message) => Outbound::Frame(message),
+                            Err(_) => {
+                                high_closed = true;
+                                continue;
+                            }
+                        }
+                    }
+                    recv_result = low.recv(), if !low_closed => {
+                        match recv_result {
+                            Ok(message) => Outbound::Frame(message),
+                            Err(_) => {
+                                low_closed = true;
+                                continue;
                             }
                         }
                     }
                     else => break,
+                };
+
+                let written = match outbound {
+                    Outbound::Frame(message) => {
+                        Self::write_frame(&mut socket, message, &mut msg_count, is_shutting_down)
+                            .await
+                    }
+                    Outbound::Broadcast(sized_msg) => {
+                        Self::write_broadcast(
+                            &mut socket,
+                            sized_msg,
+                            byte_counter.as_deref(),
+                            &mut msg_count,
+                            is_shutting_down,
+                        )
+                        .await
+                    }
+                    Outbound::Control(message) => {
+                        let mut written = true;
+                        if matches!(message, Message::Close(_)) {
+                            is_shutting_down = true;
+                            // Data queued before the close frame still goes out ahead of it;
+                            // low-priority events are dropped. Each lane is drained only up to
+                            // its length at this point, so a busy publisher cannot hold the
+                            // close frame back.
+                            for _ in 0..high.len() {
+                                if !written {
+                                    break;
+                                }
+                                let Ok(queued) = high.try_recv() else { break };
+                                written = Self::write_frame(
+                                    &mut socket,
+                                    queued,
+                                    &mut msg_count,
+                                    is_shutting_down,
+                                )
+                                .await;
+                            }
+                            for _ in 0..broadcast_rx.len() {
+                                if !written {
+                                    break;
+                                }
+                                let Ok(queued) = broadcast_rx.try_recv() else {
+                                    break;
+                                };
+                                written = Self::write_broadcast(
+                                    &mut socket,
+                                    queued,
+                                    byte_counter.as_deref(),
+                                    &mut msg_count,
+                                    is_shutting_down,
+                                )
+                                .await;
+                            }
+                        }
+                        written
+                            && Self::write_frame(
+                                &mut socket,
+                                message,
+                                &mut msg_count,
+                                is_shutting_down,
+                            )
+                            .await
+                    }
+                };
+                if !written {
+                    break;
                 }
             }
 
@@ -133,6 +242,47 @@ impl MessageSender {
         }
     }
 
+    async fn write_frame(
+        socket: &mut TransportWriter,
+        message: Message,
+        msg_count: &mut usize,
+        is_shutting_down: bool,
+    ) -> bool {
+        *msg_count += 1;
+        match socket.send(message).await {
+            Ok(()) => true,
+            Err(e) => {
+                Self::log_connection_error(
+                    &e,
+                    SocketOperation::WriteFrame,
+                    *msg_count,
+                    is_shutting_down,
+                );
+                false
+            }
+        }
+    }
+
+    async fn write_broadcast(
+        socket: &mut TransportWriter,
+        sized_msg: SizedMessage,
+        byte_counter: Option<&ByteCounter>,
+        msg_count: &mut usize,
+        is_shutting_down: bool,
+    ) -> bool {
+        let msg_size = sized_msg.size;
+        let frame = if sized_msg.binary {
+            Message::Binary(sized_msg.bytes)
+        } else {
+            Message::Text(sized_msg.bytes)
+        };
+        let written = Self::write_frame(socket, frame, msg_count, is_shutting_down).await;
+        if written && let Some(counter) = byte_counter {
+            counter.sub(msg_size);
+        }
+        written
+    }
+
     fn is_connection_error(error: &sockudo_ws::Error) -> bool {
         matches!(
             error,
@@ -170,7 +320,7 @@ impl MessageSender {
         }
     }
 
-    pub fn new(mut socket: WebSocketWriter, buffer_capacity: usize) -> Self {
+    pub fn new(mut socket: TransportWriter, buffer_capacity: usize) -> Self {
         let (sender, receiver) = mpsc::bounded_async::<Message>(buffer_capacity);
 
         let receiver_handle = tokio::spawn(async move {
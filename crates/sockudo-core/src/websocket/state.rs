@@ -3,6 +3,7 @@ use super::socket_id::SocketId;
 use crate::app::App;
 use crate::capability_token::TokenAuthContext;
 use crate::channel::PresenceMemberInfo;
+use crate::history::now_ms;
 use ahash::AHashMap as HashMap;
 use sockudo_filter::FilterNode;
 use sockudo_protocol::{ProtocolVersion, WireFormat};
@@ -73,6 +74,8 @@ pub struct ConnectionState {
     pub token_auth_context: Option<TokenAuthContext>,
     pub connection_meta: Option<Value>,
     pub last_ping: Instant,
+    /// Wall-clock connect time in milliseconds, comparable across nodes.
+    pub connected_at_ms: i64,
     pub presence: Option<HashMap<String, PresenceMemberInfo>>,
     pub user: Option<Value>,
     pub timeouts: ConnectionTimeouts,
@@ -104,6 +107,7 @@ impl ConnectionState {
             token_auth_context: None,
             connection_meta: None,
             last_ping: Instant::now(),
+            connected_at_ms: now_ms(),
             presence: None,
             user: None,
             timeouts: ConnectionTimeouts::new(),
@@ -127,6 +131,7 @@ impl ConnectionState {
             token_auth_context: None,
             connection_meta: None,
             last_ping: Instant::now(),
+            connected_at_ms: now_ms(),
             presence: None,
             user: None,
             timeouts: ConnectionTimeouts::new(),
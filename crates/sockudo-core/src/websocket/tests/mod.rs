@@ -115,6 +115,16 @@ fn test_connection_capabilities_parse_hyphenated_annotation_grants() {
     assert!(!capabilities.allows_annotation_publish("news:room-1"));
 }
 
+#[test]
+fn test_connection_capabilities_parse_object_publish_grant() {
+    let capabilities: ConnectionCapabilities =
+        sonic_rs::from_str(r#"{"object-publish":["poll:*"]}"#).unwrap();
+
+    assert!(capabilities.allows_object_publish("poll:42"));
+    assert!(!capabilities.allows_object_publish("chat:room-1"));
+    assert!(!ConnectionCapabilities::default().allows_object_publish("poll:42"));
+}
+
 #[test]
 fn test_socket_id_display() {
     let id = SocketId::from_string("123.456").unwrap();
@@ -543,3 +553,204 @@ async fn test_shutdown_token_cancels_receiver() {
         "shutdown() must cancel the CancellationToken so the receiver task exits"
     );
 }
+
+#[tokio::test]
+async fn test_channel_transport_delivers_frames_and_close() {
+    let socket_id = SocketId::new();
+    let (writer, mut rx) = TransportWriter::channel(16);
+    let mut ws = WebSocket::new(socket_id, writer);
+
+    ws.send_message(&PusherMessage::ping()).unwrap();
+    let frame = tokio::time::timeout(std::time::Duration::from_secs(1), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    match frame {
+        sockudo_ws::Message::Text(bytes) => {
+            assert!(std::str::from_utf8(&bytes).unwrap().contains("ping"));
+        }
+        other => panic!("expected text frame, got {other:?}"),
+    }
+
+    ws.close(1000, "bye".to_string()).await.unwrap();
+    let frame = tokio::time::timeout(std::time::Duration::from_secs(1), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(frame, sockudo_ws::Message::Close(_)));
+}
+
+#[tokio::test]
+async fn test_channel_transport_reports_dropped_receiver() {
+    let (mut writer, rx) = TransportWriter::channel(1);
+    assert!(!writer.is_closed());
+    drop(rx);
+    assert!(writer.is_closed());
+    assert!(
+        writer
+            .send(sockudo_ws::Message::text("late".to_string()))
+            .await
+            .is_err()
+    );
+}
+
+fn frame_text(frame: sockudo_ws::Message) -> String {
+    match frame {
+        sockudo_ws::Message::Text(bytes) => String::from_utf8(bytes.to_vec()).unwrap(),
+        other => panic!("expected text frame, got {other:?}"),
+    }
+}
+
+async fn drain_frames(rx: &mut tokio::sync::mpsc::Receiver<sockudo_ws::Message>) -> Vec<String> {
+    let mut frames = Vec::new();
+    while let Ok(Some(frame)) =
+        tokio::time::timeout(std::time::Duration::from_millis(100), rx.recv()).await
+    {
+        frames.push(frame_text(frame));
+    }
+    frames
+}
+
+#[tokio::test]
+async fn test_high_priority_overtakes_queued_traffic() {
+    use sockudo_protocol::messages::{MessageExtras, MessagePriority};
+
+    let (writer, mut rx) = TransportWriter::channel(1);
+    let ws =
+        WebSocket::with_buffer_config(SocketId::new(), writer, WebSocketBufferConfig::new(8, true));
+    let ws_ref = WebSocketRef::new(ws);
+
+    for i in 0..3 {
+        ws_ref
+            .send_broadcast(Bytes::from(format!("normal-{i}")))
+            .unwrap();
+    }
+    let mut alert = PusherMessage::ping();
+    alert.event = Some("alert".to_string());
+    alert.extras = Some(MessageExtras {
+        priority: Some(MessagePriority::High),
+        ..Default::default()
+    });
+    ws_ref.send_message(&alert).unwrap();
+
+    let frames = drain_frames(&mut rx).await;
+    assert_eq!(frames.len(), 4);
+    assert!(frames[0].contains("alert"), "got {frames:?}");
+    assert_eq!(&frames[1..], ["normal-0", "normal-1", "normal-2"]);
+}
+
+#[tokio::test]
+async fn test_high_priority_lane_has_its_own_buffer() {
+    use crate::error::Error;
+    use sockudo_protocol::messages::MessagePriority;
+
+    let (writer, _rx) = TransportWriter::channel(1);
+    let ws =
+        WebSocket::with_buffer_config(SocketId::new(), writer, WebSocketBufferConfig::new(2, true));
+    let ws_ref = WebSocketRef::new(ws);
+
+    ws_ref.send_broadcast(Bytes::from_static(b"a")).unwrap();
+    ws_ref.send_broadcast(Bytes::from_static(b"b")).unwrap();
+    assert!(matches!(
+        ws_ref.send_broadcast(Bytes::from_static(b"c")),
+        Err(Error::BufferFull(_))
+    ));
+    ws_ref
+        .send_broadcast_with_priority(Bytes::from_static(b"alert"), MessagePriority::High)
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_low_priority_is_shed_without_disconnecting() {
+    use sockudo_protocol::messages::MessagePriority;
+
+    let (writer, mut rx) = TransportWriter::channel(1);
+    let ws =
+        WebSocket::with_buffer_config(SocketId::new(), writer, WebSocketBufferConfig::new(4, true));
+    let ws_ref = WebSocketRef::new(ws);
+
+    ws_ref
+        .send_broadcast_with_priority(Bytes::from_static(b"telemetry-0"), MessagePriority::Low)
+        .unwrap();
+    ws_ref
+        .send_broadcast(Bytes::from_static(b"normal-0"))
+        .unwrap();
+    ws_ref
+        .send_broadcast(Bytes::from_static(b"normal-1"))
+        .unwrap();
+    ws_ref
+        .send_broadcast_with_priority(Bytes::from_static(b"telemetry-1"), MessagePriority::Low)
+        .expect("shedding low priority traffic must not disconnect the client");
+
+    let frames = drain_frames(&mut rx).await;
+    assert_eq!(frames, ["normal-0", "normal-1", "telemetry-0"]);
+}
+
+#[tokio::test]
+async fn test_control_frames_take_turns_with_a_broadcast_flood() {
+    let (writer, mut rx) = TransportWriter::channel(1);
+    let ws = WebSocket::with_buffer_config(
+        SocketId::new(),
+        writer,
+        WebSocketBufferConfig::new(256, true),
+    );
+    let ws_ref = WebSocketRef::new(ws);
+
+    for i in 0..200 {
+        ws_ref
+            .send_broadcast(Bytes::from(format!("normal-{i}")))
+            .unwrap();
+    }
+    ws_ref.send_message(&PusherMessage::pong()).unwrap();
+
+    let frames = drain_frames(&mut rx).await;
+    assert_eq!(frames.len(), 201);
+    let pong = frames
+        .iter()
+        .position(|frame| frame.contains("pong"))
+        .expect("pong delivered");
+    assert!(pong <= 4, "pong went out after {pong} broadcasts");
+}
+
+#[tokio::test]
+async fn test_send_message_after_broadcasts_keeps_broadcast_order() {
+    let (writer, mut rx) = TransportWriter::channel(1);
+    let ws =
+        WebSocket::with_buffer_config(SocketId::new(), writer, WebSocketBufferConfig::new(8, true));
+    let ws_ref = WebSocketRef::new(ws);
+
+    for i in 0..3 {
+        ws_ref
+            .send_broadcast(Bytes::from(format!("replay-{i}")))
+            .unwrap();
+    }
+    ws_ref
+        .send_message_after_broadcasts(&PusherMessage::pong())
+        .unwrap();
+
+    let frames = drain_frames(&mut rx).await;
+    assert_eq!(frames.len(), 4);
+    assert_eq!(&frames[..3], ["replay-0", "replay-1", "replay-2"]);
+    assert!(frames[3].contains("pong"), "got {frames:?}");
+}
+
+#[tokio::test]
+async fn test_wildcard_delivery_windows_follow_subscription_changes() {
+    let (writer, _rx) = TransportWriter::channel(1);
+    let ws_ref = WebSocketRef::new(WebSocket::new(SocketId::new(), writer));
+    let window = |window_ms| DeliveryWindow {
+        mode: DeliveryMode::Batch,
+        window_ms,
+    };
+
+    assert_eq!(ws_ref.delivery_window_for("prices.aapl"), None);
+    ws_ref.set_delivery_window("prices.*".to_string(), Some(window(50)));
+    assert_eq!(ws_ref.delivery_window_for("prices.aapl"), Some(window(50)));
+    assert_eq!(ws_ref.delivery_window_for("prices.aapl"), Some(window(50)));
+
+    ws_ref.set_delivery_window("prices.*".to_string(), Some(window(100)));
+    assert_eq!(ws_ref.delivery_window_for("prices.aapl"), Some(window(100)));
+
+    ws_ref.set_delivery_window("prices.*".to_string(), None);
+    assert_eq!(ws_ref.delivery_window_for("prices.aapl"), None);
+}
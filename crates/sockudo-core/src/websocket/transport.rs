@@ -0,0 +1,71 @@
+use sockudo_ws::Message;
+use sockudo_ws::axum_integration::WebSocketWriter;
+use sockudo_ws::error::CloseReason;
+use tokio::sync::mpsc;
+
+/// Outbound half of a client connection.
+///
+/// WebSocket clients are written to directly. Other transports (SSE, long-polling, ...)
+/// receive the same frames over an in-process channel and encode them for their own wire,
+/// so everything above the sender task stays transport-agnostic.
+pub enum TransportWriter {
+    WebSocket(Box<WebSocketWriter>),
+    Channel(Option<mpsc::Sender<Message>>),
+}
+
+impl TransportWriter {
+    /// Creates a channel-backed writer and the receiver its frames are delivered to.
+    pub fn channel(capacity: usize) -> (Self, mpsc::Receiver<Message>) {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        (TransportWriter::Channel(Some(tx)), rx)
+    }
+
+    pub async fn send(&mut self, msg: Message) -> sockudo_ws::Result<()> {
+        match self {
+            TransportWriter::WebSocket(writer) => writer.send(msg).await,
+            TransportWriter::Channel(tx) => match tx {
+                Some(sender) => sender
+                    .send(msg)
+                    .await
+                    .map_err(|_| sockudo_ws::Error::ConnectionClosed),
+                None => Err(sockudo_ws::Error::ConnectionClosed),
+            },
+        }
+    }
+
+    /// Sends a close frame and releases the transport.
+    ///
+    /// For channel-backed writers the close frame is delivered best-effort and the sender is
+    /// dropped, which ends the receiving stream.
+    pub async fn close(&mut self, code: u16, reason: &str) -> sockudo_ws::Result<()> {
+        match self {
+            TransportWriter::WebSocket(writer) => writer.close(code, reason).await,
+            TransportWriter::Channel(tx) => {
+                if let Some(sender) = tx.take() {
+                    let _ = sender.try_send(Message::Close(Some(CloseReason::new(code, reason))));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub async fn flush(&mut self) -> sockudo_ws::Result<()> {
+        match self {
+            TransportWriter::WebSocket(writer) => writer.flush().await,
+            TransportWriter::Channel(_) => Ok(()),
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        match self {
+            TransportWriter::WebSocket(writer) => writer.is_closed(),
+            TransportWriter::Channel(tx) => tx.as_ref().is_none_or(|sender| sender.is_closed()),
+        }
+    }
+}
+
+impl From<WebSocketWriter> for TransportWriter {
+    fn from(writer: WebSocketWriter) -> Self {
+        TransportWriter::WebSocket(Box::new(writer))
+    }
+}
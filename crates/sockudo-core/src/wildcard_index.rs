@@ -0,0 +1,207 @@
+use crate::utils::{SEGMENT_WILDCARD, is_tail_segment_wildcard, split_channel_segments};
+use ahash::{AHashMap as HashMap, AHashSet};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Segment trie of hierarchical wildcard subscriptions. A lookup walks one path per literal
+/// segment plus the `*` branch, so its cost follows the channel depth rather than the number
+/// of subscribed patterns.
+pub struct SegmentWildcardIndex {
+    delimiters: Vec<char>,
+    root: RwLock<TrieNode>,
+}
+
+#[derive(Default)]
+struct TrieNode {
+    literal: HashMap<String, TrieNode>,
+    single: Option<Box<TrieNode>>,
+    /// Patterns that end exactly at this node.
+    patterns: AHashSet<String>,
+    /// Patterns whose `>` or `#` follows this node.
+    tail_patterns: AHashSet<String>,
+}
+
+impl TrieNode {
+    fn is_empty(&self) -> bool {
+        self.literal.is_empty()
+            && self.single.is_none()
+            && self.patterns.is_empty()
+            && self.tail_patterns.is_empty()
+    }
+
+    fn insert(&mut self, segments: &[&str], pattern: &str) {
+        match segments {
+            [] => {
+                self.patterns.insert(pattern.to_string());
+            }
+            [tail] if is_tail_segment_wildcard(tail) => {
+                self.tail_patterns.insert(pattern.to_string());
+            }
+            [segment, rest @ ..] if *segment == SEGMENT_WILDCARD => self
+                .single
+                .get_or_insert_with(Default::default)
+                .insert(rest, pattern),
+            [segment, rest @ ..] => self
+                .literal
+                .entry((*segment).to_string())
+                .or_default()
+                .insert(rest, pattern),
+        }
+    }
+
+    fn remove(&mut self, segments: &[&str], pattern: &str) -> bool {
+        match segments {
+            [] => self.patterns.remove(pattern),
+            [tail] if is_tail_segment_wildcard(tail) => self.tail_patterns.remove(pattern),
+            [segment, rest @ ..] if *segment == SEGMENT_WILDCARD => {
+                let Some(child) = self.single.as_mut() else {
+                    return false;
+                };
+                let removed = child.remove(rest, pattern);
+                if child.is_empty() {
+                    self.single = None;
+                }
+                removed
+            }
+            [segment, rest @ ..] => {
+                let Some(child) = self.literal.get_mut(*segment) else {
+                    return false;
+                };
+                let removed = child.remove(rest, pattern);
+                if child.is_empty() {
+                    self.literal.remove(*segment);
+                }
+                removed
+            }
+        }
+    }
+
+    fn collect(&self, segments: &[&str], matches: &mut Vec<String>) {
+        let Some((segment, rest)) = segments.split_first() else {
+            matches.extend(self.patterns.iter().cloned());
+            return;
+        };
+        matches.extend(self.tail_patterns.iter().cloned());
+        if let Some(child) = self.literal.get(*segment) {
+            child.collect(rest, matches);
+        }
+        if let Some(child) = self.single.as_deref() {
+            child.collect(rest, matches);
+        }
+    }
+}
+
+impl SegmentWildcardIndex {
+    pub fn new(delimiters: &[char]) -> Self {
+        Self {
+            delimiters: delimiters.to_vec(),
+            root: RwLock::new(TrieNode::default()),
+        }
+    }
+
+    pub fn delimiters(&self) -> &[char] {
+        &self.delimiters
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, TrieNode> {
+        self.root.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, TrieNode> {
+        self.root.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    pub fn insert(&self, pattern: &str) {
+        let segments: Vec<&str> = split_channel_segments(pattern, &self.delimiters).collect();
+        self.write().insert(&segments, pattern);
+    }
+
+    /// Returns whether `pattern` was indexed.
+    pub fn remove(&self, pattern: &str) -> bool {
+        let segments: Vec<&str> = split_channel_segments(pattern, &self.delimiters).collect();
+        self.write().remove(&segments, pattern)
+    }
+
+    /// Every indexed pattern that matches the concrete `channel`.
+    pub fn matching_patterns(&self, channel: &str) -> Vec<String> {
+        let segments: Vec<&str> = split_channel_segments(channel, &self.delimiters).collect();
+        let mut matches = Vec::new();
+        self.read().collect(&segments, &mut matches);
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::wildcard_pattern_matches_with;
+
+    fn sorted(mut patterns: Vec<String>) -> Vec<String> {
+        patterns.sort();
+        patterns
+    }
+
+    #[test]
+    fn lookup_follows_literal_single_and_tail_branches() {
+        let index = SegmentWildcardIndex::new(&['.']);
+        index.insert("prices.*.equity.>");
+        index.insert("prices.eu.#");
+        index.insert("prices.*.equity.AAPL");
+        index.insert("prices.us.*.*");
+        index.insert("news.*");
+
+        assert_eq!(
+            sorted(index.matching_patterns("prices.eu.equity.AAPL")),
+            ["prices.*.equity.>", "prices.*.equity.AAPL", "prices.eu.#"]
+        );
+        assert_eq!(
+            sorted(index.matching_patterns("prices.us.equity.MSFT")),
+            ["prices.*.equity.>", "prices.us.*.*"]
+        );
+        assert_eq!(
+            index.matching_patterns("prices.us.fx.EUR"),
+            ["prices.us.*.*"]
+        );
+        assert!(index.matching_patterns("prices.eu").is_empty());
+        assert!(index.matching_patterns("news.btc.usd").is_empty());
+    }
+
+    #[test]
+    fn lookup_agrees_with_pattern_matching() {
+        let delimiters = &['.', ':'];
+        let patterns = ["a.*.c", "a.>", "*:b.#", "*.*.*"];
+        let channels = ["a.b.c", "a:b.c", "x:b.y.z", "a.b", "a", "q.r.s"];
+        let index = SegmentWildcardIndex::new(delimiters);
+        for pattern in patterns {
+            index.insert(pattern);
+        }
+
+        for channel in channels {
+            let expected: Vec<String> = patterns
+                .iter()
+                .filter(|pattern| wildcard_pattern_matches_with(channel, pattern, delimiters))
+                .map(|pattern| pattern.to_string())
+                .collect();
+            assert_eq!(
+                sorted(index.matching_patterns(channel)),
+                sorted(expected),
+                "{channel}"
+            );
+        }
+    }
+
+    #[test]
+    fn removal_prunes_empty_branches() {
+        let index = SegmentWildcardIndex::new(&['.']);
+        index.insert("prices.*.equity.>");
+        index.insert("prices.*.equity.>");
+        assert!(!index.is_empty());
+
+        assert!(index.remove("prices.*.equity.>"));
+        assert!(!index.remove("prices.*.equity.>"));
+        assert!(index.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use sockudo_core::options::{
-    HistoryBackend, PushQueueDriver, PushRuleConfig, PushStorageDriver, ServerOptions,
-    VersionStoreDriver,
+    ChannelObjectStoreDriver, HistoryBackend, PushQueueDriver, PushRuleConfig, PushStorageDriver,
+    ServerOptions, VersionStoreDriver,
 };
 use std::fs;
 use std::path::PathBuf;
@@ -120,6 +120,36 @@ fn documented_ai_transport_related_defaults_match_code() {
         options.presence_history.max_page_size,
     );
     assert_bool(&docs, "annotations.enabled", options.annotations.enabled);
+    assert_bool(
+        &docs,
+        "channel_objects.enabled",
+        options.channel_objects.enabled,
+    );
+    assert_str(
+        &docs,
+        "channel_objects.driver",
+        channel_object_store_driver_name(&options.channel_objects.driver),
+    );
+    assert_usize(
+        &docs,
+        "channel_objects.max_operations_per_request",
+        options.channel_objects.max_operations_per_request,
+    );
+    assert_usize(
+        &docs,
+        "channel_objects.max_objects_per_channel",
+        options.channel_objects.max_objects_per_channel,
+    );
+    assert_usize(
+        &docs,
+        "channel_objects.max_map_entries",
+        options.channel_objects.max_map_entries,
+    );
+    assert_usize(
+        &docs,
+        "channel_objects.max_value_bytes",
+        options.channel_objects.max_value_bytes,
+    );
 
     assert_bool(&docs, "ai_transport.enabled", options.ai_transport.enabled);
     assert_usize(
@@ -425,6 +455,14 @@ fn version_store_driver_name(driver: &VersionStoreDriver) -> &'static str {
     }
 }
 
+fn channel_object_store_driver_name(driver: &ChannelObjectStoreDriver) -> &'static str {
+    match driver {
+        ChannelObjectStoreDriver::Memory => "memory",
+        ChannelObjectStoreDriver::Postgres => "postgres",
+        ChannelObjectStoreDriver::Mysql => "mysql",
+    }
+}
+
 fn history_backend_name(backend: &HistoryBackend) -> &'static str {
     match backend {
         HistoryBackend::Postgres => "postgres",
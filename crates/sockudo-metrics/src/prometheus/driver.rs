@@ -187,6 +187,8 @@ pub struct PrometheusMetricsDriver {
     pub(super) presence_history_queue_depth: GaugeVec,
     pub(super) presence_history_degraded_channels: GaugeVec,
     pub(super) presence_history_reset_required_channels: GaugeVec,
+    pub(super) proxy_requests_total: CounterVec,
+    pub(super) proxy_request_latency_ms: HistogramVec,
     pub(super) annotations_published_total: CounterVec,
     pub(super) annotations_deleted_total: CounterVec,
     pub(super) annotation_summary_deliveries_total: CounterVec,
@@ -972,6 +974,25 @@ impl PrometheusMetricsDriver {
         )
         .unwrap();
 
+        let proxy_requests_total = register_counter_vec!(
+            Opts::new(
+                format!("{prefix}proxy_requests_total"),
+                "Total number of proxy backend calls by proxy and result"
+            ),
+            &["app_id", "port", "proxy", "result"]
+        )
+        .unwrap();
+
+        let proxy_request_latency_ms = register_histogram_vec!(
+            histogram_opts!(
+                format!("{prefix}proxy_request_latency_ms"),
+                "Proxy backend round-trip latency in milliseconds",
+                END_TO_END_LATENCY_HISTOGRAM_BUCKETS.to_vec()
+            ),
+            &["app_id", "port", "proxy"]
+        )
+        .unwrap();
+
         let annotations_published_total = register_counter_vec!(
             Opts::new(
                 format!("{prefix}annotations_published_total"),
@@ -1212,6 +1233,8 @@ impl PrometheusMetricsDriver {
             presence_history_queue_depth,
             presence_history_degraded_channels,
             presence_history_reset_required_channels,
+            proxy_requests_total,
+            proxy_request_latency_ms,
             annotations_published_total,
             annotations_deleted_total,
             annotation_summary_deliveries_total,
@@ -668,6 +668,18 @@ impl MetricsInterface for PrometheusMetricsDriver {
             .set(count as f64);
     }
 
+    fn mark_proxy_request(&self, app_id: &str, proxy: &str, result: &str) {
+        self.proxy_requests_total
+            .with_label_values(&[app_id, &self.port.to_string(), proxy, result])
+            .inc();
+    }
+
+    fn track_proxy_request_latency(&self, app_id: &str, proxy: &str, latency_ms: f64) {
+        self.proxy_request_latency_ms
+            .with_label_values(&[app_id, &self.port.to_string(), proxy])
+            .observe(latency_ms);
+    }
+
     async fn get_metrics_as_plaintext(&self) -> String {
         // Update process and runtime metrics before gathering
         self.update_process_metrics();
@@ -100,6 +100,21 @@ pub struct MessageExtras {
     /// AI Transport metadata convention over the existing V2 extras envelope.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ai: Option<AiExtras>,
+
+    /// Delivery lane in each subscriber's send buffer. High-priority messages
+    /// overtake queued traffic; low-priority ones are shed first when a
+    /// connection falls behind. Defaults to normal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<MessagePriority>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum MessagePriority {
+    High,
+    #[default]
+    Normal,
+    Low,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -428,6 +443,8 @@ pub fn generate_message_id() -> String {
 pub const ANNOTATION_EVENT_NAME: &str = "sockudo_internal:annotation";
 pub const MESSAGE_SUMMARY_EVENT_NAME: &str = "sockudo_internal:message";
 pub const ANNOTATION_SUBSCRIBE_MODE: &str = "ANNOTATION_SUBSCRIBE";
+pub const OBJECT_EVENT_NAME: &str = "sockudo_internal:object";
+pub const OBJECT_SNAPSHOT_EVENT_NAME: &str = "sockudo_internal:object_snapshot";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum AnnotationEventAction {
@@ -795,6 +812,52 @@ impl PusherMessage {
         }
     }
 
+    /// Tells the client its subscription to `channel` was refused.
+    pub fn subscription_error(channel: String, code: u32, message: String) -> Self {
+        Self {
+            event: Some("pusher:subscription_error".to_string()),
+            channel: Some(channel),
+            data: Some(MessageData::Json(json!({
+                "type": "LimitReached",
+                "error": message,
+                "status": code
+            }))),
+            name: None,
+            user_id: None,
+            sequence: None,
+            conflation_key: None,
+            tags: None,
+            message_id: None,
+            stream_id: None,
+            serial: None,
+            idempotency_key: None,
+            extras: None,
+            delta_sequence: None,
+            delta_conflation_key: None,
+        }
+    }
+
+    /// Tells the client the server removed it from `channel`.
+    pub fn unsubscribed(channel: String) -> Self {
+        Self {
+            event: Some("pusher_internal:unsubscribed".to_string()),
+            channel: Some(channel),
+            data: Some(MessageData::String("{}".to_string())),
+            name: None,
+            user_id: None,
+            sequence: None,
+            conflation_key: None,
+            tags: None,
+            message_id: None,
+            stream_id: None,
+            serial: None,
+            idempotency_key: None,
+            extras: None,
+            delta_sequence: None,
+            delta_conflation_key: None,
+        }
+    }
+
     pub fn error(code: u32, message: String, channel: Option<String>) -> Self {
         Self {
             event: Some("pusher:error".to_string()),
@@ -1153,6 +1216,14 @@ impl PusherMessage {
             .unwrap_or(false)
     }
 
+    /// Returns the delivery priority from `extras.priority`, normal when unset.
+    pub fn priority(&self) -> MessagePriority {
+        self.extras
+            .as_ref()
+            .and_then(|e| e.priority)
+            .unwrap_or_default()
+    }
+
     /// Returns the extras-level idempotency key, if set.
     pub fn extras_idempotency_key(&self) -> Option<&str> {
         self.extras
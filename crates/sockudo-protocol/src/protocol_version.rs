@@ -123,6 +123,8 @@ pub const CANONICAL_SIGNIN: &str = "signin";
 pub const CANONICAL_SIGNIN_SUCCESS: &str = "signin_success";
 pub const CANONICAL_AUTH: &str = "auth";
 pub const CANONICAL_AUTH_SUCCESS: &str = "auth_success";
+pub const CANONICAL_REFRESH: &str = "refresh";
+pub const CANONICAL_REFRESH_SUCCESS: &str = "refresh_success";
 pub const CANONICAL_TOKEN_EXPIRED: &str = "token_expired";
 pub const CANONICAL_CACHE_MISS: &str = "cache_miss";
 
@@ -146,6 +148,13 @@ pub const CANONICAL_RESUME_SUCCESS: &str = "resume_success";
 pub const CANONICAL_RESUME_FAILED: &str = "resume_failed";
 pub const CANONICAL_CHANNEL_HISTORY: &str = "channel_history";
 
+// Client RPC canonical names
+pub const CANONICAL_RPC: &str = "rpc";
+pub const CANONICAL_RPC_RESULT: &str = "rpc_result";
+
+// Channel object canonical names
+pub const CANONICAL_OBJECT: &str = "object";
+
 #[cfg(test)]
 mod tests {
     use super::*;
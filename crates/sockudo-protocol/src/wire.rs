@@ -1,10 +1,13 @@
 use ahash::AHashMap;
+use ciborium::Value as CborValue;
 use prost::Message;
 use serde::{Deserialize, Serialize};
-use sonic_rs::Value;
+use sonic_rs::{JsonContainerTrait, JsonValueTrait, Value};
 use std::collections::{BTreeMap, HashMap};
 
-use crate::messages::{AiExtras, ExtrasValue, MessageData, MessageExtras, PusherMessage};
+use crate::messages::{
+    AiExtras, ExtrasValue, MessageData, MessageExtras, MessagePriority, PusherMessage,
+};
 use crate::versioned_messages::{MessageAction, MessageVersionMetadata, VersionedRealtimeMessage};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
@@ -14,6 +17,7 @@ pub enum WireFormat {
     Json,
     MessagePack,
     Protobuf,
+    Cbor,
 }
 
 impl WireFormat {
@@ -27,6 +31,7 @@ impl WireFormat {
             Some(v) if v.is_empty() || v == "json" => Ok(Self::Json),
             Some(v) if v == "msgpack" || v == "messagepack" => Ok(Self::MessagePack),
             Some(v) if v == "protobuf" || v == "proto" => Ok(Self::Protobuf),
+            Some(v) if v == "cbor" => Ok(Self::Cbor),
             Some(v) => Err(format!("unsupported wire format '{v}'")),
         }
     }
@@ -51,6 +56,7 @@ pub fn serialize_message(message: &PusherMessage, format: WireFormat) -> Result<
                 .map_err(|e| format!("Protobuf serialization failed: {e}"))?;
             Ok(buf)
         }
+        WireFormat::Cbor => to_deterministic_cbor(&CborPusherMessage::from(message.clone())),
     }
 }
 
@@ -69,6 +75,12 @@ pub fn deserialize_message(bytes: &[u8], format: WireFormat) -> Result<PusherMes
                 .map_err(|e| format!("Protobuf deserialization failed: {e}"))?;
             Ok(proto.into())
         }
+        WireFormat::Cbor => {
+            let msg: CborPusherMessage = ciborium::from_reader(bytes)
+                .map_err(|e| format!("CBOR deserialization failed: {e}"))?;
+            msg.try_into()
+                .map_err(|e| format!("CBOR deserialization failed: {e}"))
+        }
     }
 }
 
@@ -92,6 +104,9 @@ pub fn serialize_versioned_message(
                 .map_err(|e| format!("Protobuf serialization failed: {e}"))?;
             Ok(buf)
         }
+        WireFormat::Cbor => {
+            to_deterministic_cbor(&CborVersionedRealtimeMessage::from(message.clone()))
+        }
     }
 }
 
@@ -113,12 +128,59 @@ pub fn deserialize_versioned_message(
                 .map_err(|e| format!("Protobuf deserialization failed: {e}"))?;
             Ok(proto.into())
         }
+        WireFormat::Cbor => {
+            let msg: CborVersionedRealtimeMessage = ciborium::from_reader(bytes)
+                .map_err(|e| format!("CBOR deserialization failed: {e}"))?;
+            msg.try_into()
+                .map_err(|e| format!("CBOR deserialization failed: {e}"))
+        }
     }?;
 
     message.validate_v2()?;
     Ok(message)
 }
 
+/// Encodes `value` as deterministic CBOR (RFC 8949 section 4.2.1).
+///
+/// Integers, lengths and floats already use their preferred (shortest) serialization and
+/// every container is definite-length; map keys are additionally sorted by the bytewise order
+/// of their encoded form, so equal messages always produce identical bytes regardless of
+/// struct field order or hash map iteration order.
+fn to_deterministic_cbor<T: Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    let value =
+        CborValue::serialized(value).map_err(|e| format!("CBOR serialization failed: {e}"))?;
+    let value = canonicalize_cbor(value)?;
+    let mut buf = Vec::new();
+    ciborium::into_writer(&value, &mut buf)
+        .map_err(|e| format!("CBOR serialization failed: {e}"))?;
+    Ok(buf)
+}
+
+fn canonicalize_cbor(value: CborValue) -> Result<CborValue, String> {
+    Ok(match value {
+        CborValue::Map(entries) => {
+            let mut keyed = Vec::with_capacity(entries.len());
+            for (key, value) in entries {
+                let key = canonicalize_cbor(key)?;
+                let mut encoded = Vec::new();
+                ciborium::into_writer(&key, &mut encoded)
+                    .map_err(|e| format!("CBOR serialization failed: {e}"))?;
+                keyed.push((encoded, key, canonicalize_cbor(value)?));
+            }
+            keyed.sort_by(|a, b| a.0.cmp(&b.0));
+            CborValue::Map(keyed.into_iter().map(|(_, k, v)| (k, v)).collect())
+        }
+        CborValue::Array(items) => CborValue::Array(
+            items
+                .into_iter()
+                .map(canonicalize_cbor)
+                .collect::<Result<_, _>>()?,
+        ),
+        CborValue::Tag(tag, inner) => CborValue::Tag(tag, Box::new(canonicalize_cbor(*inner)?)),
+        other => other,
+    })
+}
+
 #[derive(Clone, PartialEq, Message)]
 struct ProtoPusherMessage {
     #[prost(string, optional, tag = "1")]
@@ -672,6 +734,7 @@ impl From<ProtoMessageExtras> for MessageExtras {
             push: None,
             echo: value.echo,
             ai: value.ai.map(Into::into),
+            priority: None,
         }
     }
 }
@@ -687,6 +750,7 @@ impl From<MsgpackMessageExtras> for MessageExtras {
             push: None,
             echo: value.echo,
             ai: value.ai.map(Into::into),
+            priority: None,
         }
     }
 }
@@ -807,6 +871,350 @@ impl From<MsgpackMessageVersionMetadata> for MessageVersionMetadata {
     }
 }
 
+/// CBOR mirror of [`PusherMessage`].
+///
+/// Absent fields are omitted rather than encoded as `null`, and JSON payloads are carried as
+/// native CBOR data items so constrained clients never need a JSON parser.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CborPusherMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<CborMessageData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sequence: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    conflation_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    serial: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    idempotency_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extras: Option<CborMessageExtras>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delta_sequence: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delta_conflation_key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CborVersionedRealtimeMessage {
+    message: CborPusherMessage,
+    action: MessageAction,
+    message_serial: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    history_serial: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delivery_serial: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<CborMessageVersionMetadata>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+enum CborMessageData {
+    String(String),
+    Structured(CborStructuredData),
+    Json(CborValue),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CborStructuredData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_data: Option<String>,
+    #[serde(default)]
+    extra: BTreeMap<String, CborValue>,
+}
+
+/// Same field names as the JSON encoding of [`MessageExtras`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CborMessageExtras {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    headers: Option<BTreeMap<String, ExtrasValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ephemeral: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    idempotency_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    push: Option<CborValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    echo: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ai: Option<AiExtras>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<MessagePriority>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CborMessageVersionMetadata {
+    serial: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_id: Option<String>,
+    timestamp_ms: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<CborValue>,
+}
+
+impl From<PusherMessage> for CborPusherMessage {
+    fn from(value: PusherMessage) -> Self {
+        Self {
+            event: value.event,
+            channel: value.channel,
+            data: value.data.map(Into::into),
+            name: value.name,
+            user_id: value.user_id,
+            tags: value.tags,
+            sequence: value.sequence,
+            conflation_key: value.conflation_key,
+            message_id: value.message_id,
+            stream_id: value.stream_id,
+            serial: value.serial,
+            idempotency_key: value.idempotency_key,
+            extras: value.extras.map(Into::into),
+            delta_sequence: value.delta_sequence,
+            delta_conflation_key: value.delta_conflation_key,
+        }
+    }
+}
+
+impl TryFrom<CborPusherMessage> for PusherMessage {
+    type Error = String;
+
+    fn try_from(value: CborPusherMessage) -> Result<Self, Self::Error> {
+        Ok(Self {
+            event: value.event,
+            channel: value.channel,
+            data: value.data.map(TryInto::try_into).transpose()?,
+            name: value.name,
+            user_id: value.user_id,
+            tags: value.tags,
+            sequence: value.sequence,
+            conflation_key: value.conflation_key,
+            message_id: value.message_id,
+            stream_id: value.stream_id,
+            serial: value.serial,
+            idempotency_key: value.idempotency_key,
+            extras: value.extras.map(TryInto::try_into).transpose()?,
+            delta_sequence: value.delta_sequence,
+            delta_conflation_key: value.delta_conflation_key,
+        })
+    }
+}
+
+impl From<VersionedRealtimeMessage> for CborVersionedRealtimeMessage {
+    fn from(value: VersionedRealtimeMessage) -> Self {
+        Self {
+            message: CborPusherMessage::from(value.message),
+            action: value.action,
+            message_serial: value.message_serial,
+            history_serial: value.history_serial,
+            delivery_serial: value.delivery_serial,
+            version: value.version.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<CborVersionedRealtimeMessage> for VersionedRealtimeMessage {
+    type Error = String;
+
+    fn try_from(value: CborVersionedRealtimeMessage) -> Result<Self, Self::Error> {
+        Ok(Self {
+            message: value.message.try_into()?,
+            action: value.action,
+            message_serial: value.message_serial,
+            history_serial: value.history_serial,
+            delivery_serial: value.delivery_serial,
+            version: value.version.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<MessageData> for CborMessageData {
+    fn from(value: MessageData) -> Self {
+        match value {
+            MessageData::String(s) => Self::String(s),
+            MessageData::Structured {
+                channel_data,
+                channel,
+                user_data,
+                extra,
+            } => Self::Structured(CborStructuredData {
+                channel_data,
+                channel,
+                user_data,
+                extra: extra
+                    .into_iter()
+                    .map(|(k, v)| (k, json_to_cbor(&v)))
+                    .collect(),
+            }),
+            MessageData::Json(v) => Self::Json(json_to_cbor(&v)),
+        }
+    }
+}
+
+impl TryFrom<CborMessageData> for MessageData {
+    type Error = String;
+
+    fn try_from(value: CborMessageData) -> Result<Self, Self::Error> {
+        Ok(match value {
+            CborMessageData::String(s) => MessageData::String(s),
+            CborMessageData::Structured(s) => MessageData::Structured {
+                channel_data: s.channel_data,
+                channel: s.channel,
+                user_data: s.user_data,
+                extra: s
+                    .extra
+                    .into_iter()
+                    .map(|(k, v)| Ok((k, cbor_to_json(v)?)))
+                    .collect::<Result<AHashMap<_, _>, String>>()?,
+            },
+            CborMessageData::Json(v) => MessageData::Json(cbor_to_json(v)?),
+        })
+    }
+}
+
+impl From<MessageExtras> for CborMessageExtras {
+    fn from(value: MessageExtras) -> Self {
+        Self {
+            headers: value.headers.map(|headers| headers.into_iter().collect()),
+            ephemeral: value.ephemeral,
+            idempotency_key: value.idempotency_key,
+            push: value.push.as_ref().map(json_to_cbor),
+            echo: value.echo,
+            ai: value.ai,
+            priority: value.priority,
+        }
+    }
+}
+
+impl TryFrom<CborMessageExtras> for MessageExtras {
+    type Error = String;
+
+    fn try_from(value: CborMessageExtras) -> Result<Self, Self::Error> {
+        Ok(Self {
+            headers: value.headers.map(|headers| headers.into_iter().collect()),
+            ephemeral: value.ephemeral,
+            idempotency_key: value.idempotency_key,
+            push: value.push.map(cbor_to_json).transpose()?,
+            echo: value.echo,
+            ai: value.ai,
+            priority: value.priority,
+        })
+    }
+}
+
+impl From<MessageVersionMetadata> for CborMessageVersionMetadata {
+    fn from(value: MessageVersionMetadata) -> Self {
+        Self {
+            serial: value.serial,
+            client_id: value.client_id,
+            timestamp_ms: value.timestamp_ms,
+            description: value.description,
+            metadata: value.metadata.as_ref().map(json_to_cbor),
+        }
+    }
+}
+
+impl TryFrom<CborMessageVersionMetadata> for MessageVersionMetadata {
+    type Error = String;
+
+    fn try_from(value: CborMessageVersionMetadata) -> Result<Self, Self::Error> {
+        Ok(Self {
+            serial: value.serial,
+            client_id: value.client_id,
+            timestamp_ms: value.timestamp_ms,
+            description: value.description,
+            metadata: value.metadata.map(cbor_to_json).transpose()?,
+        })
+    }
+}
+
+fn json_to_cbor(value: &Value) -> CborValue {
+    if let Some(b) = value.as_bool() {
+        CborValue::Bool(b)
+    } else if let Some(n) = value.as_u64() {
+        CborValue::Integer(n.into())
+    } else if let Some(n) = value.as_i64() {
+        CborValue::Integer(n.into())
+    } else if let Some(n) = value.as_f64() {
+        CborValue::Float(n)
+    } else if let Some(s) = value.as_str() {
+        CborValue::Text(s.to_string())
+    } else if let Some(items) = value.as_array() {
+        CborValue::Array(items.iter().map(json_to_cbor).collect())
+    } else if let Some(object) = value.as_object() {
+        CborValue::Map(
+            object
+                .iter()
+                .map(|(k, v)| (CborValue::Text(k.to_string()), json_to_cbor(v)))
+                .collect(),
+        )
+    } else {
+        CborValue::Null
+    }
+}
+
+/// Converts a decoded CBOR data item back into JSON. Items without a JSON equivalent (byte
+/// strings, non-text map keys, non-finite floats) are rejected; tags are ignored.
+fn cbor_to_json(value: CborValue) -> Result<Value, String> {
+    Ok(match value {
+        CborValue::Null => Value::new_null(),
+        CborValue::Bool(b) => Value::from(b),
+        CborValue::Integer(n) => {
+            let n = i128::from(n);
+            if let Ok(n) = u64::try_from(n) {
+                Value::from(n)
+            } else if let Ok(n) = i64::try_from(n) {
+                Value::from(n)
+            } else {
+                return Err(format!("CBOR integer {n} is out of range"));
+            }
+        }
+        CborValue::Float(n) => {
+            Value::new_f64(n).ok_or_else(|| format!("CBOR float {n} has no JSON representation"))?
+        }
+        CborValue::Text(s) => Value::from(s.as_str()),
+        CborValue::Array(items) => {
+            let mut array = sonic_rs::Array::with_capacity(items.len());
+            for item in items {
+                array.push(cbor_to_json(item)?);
+            }
+            array.into_value()
+        }
+        CborValue::Map(entries) => {
+            let mut object = sonic_rs::Object::with_capacity(entries.len());
+            for (key, value) in entries {
+                let CborValue::Text(key) = key else {
+                    return Err("CBOR map keys must be text strings".to_string());
+                };
+                object.insert(&key, cbor_to_json(value)?);
+            }
+            object.into_value()
+        }
+        CborValue::Tag(_, inner) => cbor_to_json(*inner)?,
+        _ => return Err("CBOR byte strings have no JSON representation".to_string()),
+    })
+}
+
 fn parse_message_action(raw: &str) -> MessageAction {
     match raw {
         "message.create" => MessageAction::Create,
@@ -860,6 +1268,7 @@ mod tests {
                 push: None,
                 echo: Some(false),
                 ai: None,
+                priority: None,
             }),
             delta_sequence: Some(11),
             delta_conflation_key: Some("btc".to_string()),
@@ -906,6 +1315,16 @@ mod tests {
         assert_eq!(decoded.delta_conflation_key, msg.delta_conflation_key);
     }
 
+    #[test]
+    fn round_trip_cbor() {
+        let msg = sample_message();
+        let bytes = serialize_message(&msg, WireFormat::Cbor).unwrap();
+        let decoded = deserialize_message(&bytes, WireFormat::Cbor).unwrap();
+        assert_eq!(decoded.event, msg.event);
+        assert_eq!(decoded.extras, msg.extras);
+        assert_eq!(decoded.delta_conflation_key, msg.delta_conflation_key);
+    }
+
     #[test]
     fn round_trip_versioned_messagepack() {
         let msg = sample_versioned_message();
@@ -995,6 +1414,10 @@ mod tests {
             WireFormat::parse_query_param(Some("proto")).unwrap(),
             WireFormat::Protobuf
         );
+        assert_eq!(
+            WireFormat::parse_query_param(Some("cbor")).unwrap(),
+            WireFormat::Cbor
+        );
     }
 
     #[test]
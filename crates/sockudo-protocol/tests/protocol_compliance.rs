@@ -1,7 +1,14 @@
 use sockudo_protocol::messages::{
     AI_ERROR_HEADER_TOO_LARGE, AI_ERROR_INVALID_TRANSPORT_HEADER, AI_EVENT_CANCEL, AI_EVENT_INPUT,
     AI_EVENT_OUTPUT, AI_EVENT_TURN_END, AI_EVENT_TURN_START, AiExtras, ExtrasValue, MessageData,
-    MessageExtras, PusherMessage, is_ai_event,
+    MessageExtras, MessagePriority, PusherMessage, is_ai_event,
+};
+use sockudo_protocol::versioned_messages::{
+    MessageAction, MessageVersionMetadata, VersionedRealtimeMessage,
+};
+use sockudo_protocol::wire::{
+    WireFormat, deserialize_message, deserialize_versioned_message, serialize_message,
+    serialize_versioned_message,
 };
 use sonic_rs::prelude::*;
 use sonic_rs::{Value, json};
@@ -1054,6 +1061,7 @@ fn test_extras_round_trip_serialize_deserialize() {
         push: None,
         echo: Some(false),
         ai: None,
+        priority: None,
     };
 
     let json_str = sonic_rs::to_string(&extras).expect("serialize");
@@ -1133,6 +1141,23 @@ fn test_should_echo_false_overrides_connection_default() {
     assert!(!msg.should_echo(false));
 }
 
+#[test]
+fn test_priority_defaults_to_normal() {
+    let mut msg = PusherMessage::channel_event("test", "ch", json!({}));
+    assert_eq!(msg.priority(), MessagePriority::Normal);
+    msg.extras = Some(MessageExtras::default());
+    assert_eq!(msg.priority(), MessagePriority::Normal);
+}
+
+#[test]
+fn test_priority_parses_from_extras() {
+    let extras: MessageExtras = sonic_rs::from_str(r#"{"priority":"high"}"#).unwrap();
+    assert_eq!(extras.priority, Some(MessagePriority::High));
+    let extras: MessageExtras = sonic_rs::from_str(r#"{"priority":"low"}"#).unwrap();
+    assert_eq!(extras.priority, Some(MessagePriority::Low));
+    assert!(sonic_rs::from_str::<MessageExtras>(r#"{"priority":"urgent"}"#).is_err());
+}
+
 #[test]
 fn test_filter_headers_returns_none_when_extras_none() {
     let msg = PusherMessage::channel_event("test", "ch", json!({}));
@@ -1306,3 +1331,213 @@ fn test_extras_idempotency_key() {
     });
     assert_eq!(msg.extras_idempotency_key(), Some("dedup-key-1"));
 }
+
+// ============================================================================
+// CBOR wire format
+// ============================================================================
+
+fn cbor_sample_message() -> PusherMessage {
+    let mut msg = PusherMessage::channel_event("sensor-reading", "private-device-42", json!({}));
+    msg.data = Some(MessageData::Json(
+        json!({"temp": 21.5, "unit": "C", "samples": [1, 2, 3], "ok": true}),
+    ));
+    msg.tags = Some(
+        [("region", "eu"), ("tier", "gold")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    );
+    msg.message_id = Some("mid-1".to_string());
+    msg.stream_id = Some("stream-1".to_string());
+    msg.serial = Some(9);
+    msg.extras = Some(MessageExtras {
+        headers: Some(HashMap::from([
+            (
+                "priority".to_string(),
+                ExtrasValue::String("high".to_string()),
+            ),
+            ("ttl".to_string(), ExtrasValue::Number(5.5)),
+            ("retain".to_string(), ExtrasValue::Bool(true)),
+        ])),
+        ephemeral: Some(true),
+        idempotency_key: Some("idem-1".to_string()),
+        echo: Some(false),
+        ai: Some(AiExtras {
+            transport: Some(HashMap::from([(
+                "turn-id".to_string(),
+                "turn-1".to_string(),
+            )])),
+            codec: None,
+        }),
+        ..Default::default()
+    });
+    msg
+}
+
+fn cbor_sample_versioned_message() -> VersionedRealtimeMessage {
+    let mut message = cbor_sample_message();
+    message.event = Some("sockudo:message.update".to_string());
+    message.name = Some("sensor-reading".to_string());
+
+    VersionedRealtimeMessage {
+        message,
+        action: MessageAction::Update,
+        message_serial: "msg:1".to_string(),
+        history_serial: Some(7),
+        delivery_serial: Some(9),
+        version: Some(MessageVersionMetadata {
+            serial: "ver:2".to_string(),
+            client_id: Some("device-42".to_string()),
+            timestamp_ms: 1_713_100_805_000,
+            description: Some("calibrated".to_string()),
+            metadata: Some(json!({"source": "firmware", "build": 17})),
+        }),
+    }
+}
+
+/// Collects the text keys of a CBOR map in encoded order.
+fn cbor_map_keys(value: &ciborium::Value) -> Vec<String> {
+    value
+        .as_map()
+        .expect("expected a CBOR map")
+        .iter()
+        .map(|(k, _)| k.as_text().expect("expected a text key").to_string())
+        .collect()
+}
+
+#[test]
+fn test_cbor_format_is_negotiated_by_query_param() {
+    assert_eq!(
+        WireFormat::parse_query_param(Some("cbor")).unwrap(),
+        WireFormat::Cbor
+    );
+    assert_eq!(
+        WireFormat::parse_query_param(Some(" CBOR ")).unwrap(),
+        WireFormat::Cbor
+    );
+    assert!(WireFormat::Cbor.is_binary());
+}
+
+#[test]
+fn test_cbor_round_trips_pusher_message() {
+    let msg = cbor_sample_message();
+    let bytes = serialize_message(&msg, WireFormat::Cbor).unwrap();
+    let decoded = deserialize_message(&bytes, WireFormat::Cbor).unwrap();
+
+    assert_eq!(decoded.event, msg.event);
+    assert_eq!(decoded.channel, msg.channel);
+    assert_eq!(decoded.tags, msg.tags);
+    assert_eq!(decoded.serial, msg.serial);
+    assert_eq!(decoded.extras, msg.extras);
+
+    let Some(MessageData::Json(data)) = decoded.data else {
+        panic!("JSON payload should decode as MessageData::Json");
+    };
+    assert_eq!(data["temp"].as_f64(), Some(21.5));
+    assert_eq!(data["unit"], "C");
+    assert_eq!(data["samples"].as_array().map(|a| a.len()), Some(3));
+    assert_eq!(data["ok"].as_bool(), Some(true));
+}
+
+#[test]
+fn test_cbor_round_trips_string_and_structured_data() {
+    let msg = PusherMessage::connection_established("123.456".to_string(), 120);
+    let bytes = serialize_message(&msg, WireFormat::Cbor).unwrap();
+    let decoded = deserialize_message(&bytes, WireFormat::Cbor).unwrap();
+    assert_eq!(decoded.data, msg.data);
+
+    let mut msg = PusherMessage::channel_event("pusher:subscribe", "presence-room", json!({}));
+    msg.data = Some(MessageData::Structured {
+        channel_data: Some(r#"{"user_id":"u1"}"#.to_string()),
+        channel: Some("presence-room".to_string()),
+        user_data: None,
+        extra: [("auth".to_string(), json!("key:signature"))]
+            .into_iter()
+            .collect(),
+    });
+    let bytes = serialize_message(&msg, WireFormat::Cbor).unwrap();
+    let decoded = deserialize_message(&bytes, WireFormat::Cbor).unwrap();
+    assert_eq!(decoded.data, msg.data);
+}
+
+#[test]
+fn test_cbor_round_trips_versioned_message() {
+    let msg = cbor_sample_versioned_message();
+    let bytes = serialize_versioned_message(&msg, WireFormat::Cbor).unwrap();
+    let decoded = deserialize_versioned_message(&bytes, WireFormat::Cbor).unwrap();
+
+    assert_eq!(decoded.action, msg.action);
+    assert_eq!(decoded.message_serial, msg.message_serial);
+    assert_eq!(decoded.history_serial, msg.history_serial);
+    assert_eq!(decoded.delivery_serial, msg.delivery_serial);
+    assert_eq!(decoded.message.extras, msg.message.extras);
+
+    let version = decoded.version.expect("version metadata should survive");
+    let expected = msg.version.unwrap();
+    assert_eq!(version.serial, expected.serial);
+    assert_eq!(version.client_id, expected.client_id);
+    assert_eq!(version.timestamp_ms, expected.timestamp_ms);
+    assert_eq!(version.description, expected.description);
+    let metadata = version.metadata.expect("metadata should survive");
+    assert_eq!(metadata["source"], "firmware");
+    assert_eq!(metadata["build"].as_u64(), Some(17));
+}
+
+#[test]
+fn test_cbor_encoding_is_deterministic() {
+    let first = cbor_sample_versioned_message();
+
+    // Rebuild every hash map with a different insertion order.
+    let mut second = first.clone();
+    let extras = second.message.extras.as_mut().unwrap();
+    let mut headers: Vec<_> = extras.headers.take().unwrap().into_iter().collect();
+    headers.reverse();
+    let mut reordered = HashMap::with_capacity(16);
+    reordered.extend(headers);
+    extras.headers = Some(reordered);
+    second.message.data = Some(MessageData::Json(
+        json!({"ok": true, "samples": [1, 2, 3], "unit": "C", "temp": 21.5}),
+    ));
+
+    let a = serialize_versioned_message(&first, WireFormat::Cbor).unwrap();
+    let b = serialize_versioned_message(&second, WireFormat::Cbor).unwrap();
+    assert_eq!(a, b, "equal messages must encode to identical bytes");
+    assert_eq!(
+        a,
+        serialize_versioned_message(&first, WireFormat::Cbor).unwrap()
+    );
+}
+
+#[test]
+fn test_cbor_map_keys_use_core_deterministic_order() {
+    let bytes = serialize_message(&cbor_sample_message(), WireFormat::Cbor).unwrap();
+    let value: ciborium::Value = ciborium::from_reader(bytes.as_slice()).unwrap();
+
+    // Shorter keys sort first, then bytewise, because the length prefix is part of the key.
+    let keys = cbor_map_keys(&value);
+    let mut expected = keys.clone();
+    expected.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    assert_eq!(keys, expected);
+    assert!(
+        !keys.contains(&"user_id".to_string()),
+        "absent fields are omitted"
+    );
+
+    let extras = value
+        .as_map()
+        .unwrap()
+        .iter()
+        .find(|(k, _)| k.as_text() == Some("extras"))
+        .map(|(_, v)| v)
+        .unwrap();
+    assert_eq!(
+        cbor_map_keys(extras),
+        vec!["ai", "echo", "headers", "ephemeral", "idempotencyKey"]
+    );
+}
+
+#[test]
+fn test_cbor_rejects_malformed_input() {
+    let err = deserialize_message(&[0xff, 0x00], WireFormat::Cbor).unwrap_err();
+    assert!(err.contains("CBOR deserialization failed"), "{err}");
+}
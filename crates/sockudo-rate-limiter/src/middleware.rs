@@ -10,7 +10,7 @@ use sockudo_core::rate_limiter::{RateLimitResult, RateLimiter};
 use sonic_rs::json;
 use std::{
     fmt,
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     sync::Arc,
     task::{Context, Poll},
 };
@@ -271,6 +271,19 @@ impl IpKeyExtractor {
         Self { trust_hops }
     }
 
+    /// Client address of `req` for access decisions. Proxy headers (`X-Forwarded-For`, then
+    /// `X-Real-IP`) are only believed when `trust_hops` is set; otherwise the peer address is
+    /// used, since any client can send those headers.
+    pub fn client_ip<B>(&self, req: &HyperRequest<B>) -> Option<IpAddr> {
+        if self.trust_hops == 0 {
+            return req
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip());
+        }
+        self.get_ip(req).and_then(|ip| ip.parse().ok())
+    }
+
     fn get_ip<B>(&self, req: &HyperRequest<B>) -> Option<String> {
         if self.trust_hops > 0
             && let Some(value) = req.headers().get("x-forwarded-for")
@@ -1,13 +1,19 @@
 use super::SockudoServer;
 use crate::http_handler::{
-    append_message, batch_events, channel, channel_history, channel_history_purge,
-    channel_history_reset, channel_history_state, channel_message, channel_message_annotations,
-    channel_message_versions, channel_presence_history, channel_presence_history_reset,
-    channel_presence_history_snapshot, channel_presence_history_state, channel_users, channels,
-    delete_annotation, delete_message, events, fallback_404, live, metrics, publish_annotation,
-    revoke_capability_tokens, stats, terminate_user_connections, up, update_message, usage,
+    append_message, apply_channel_objects, batch_events, channel, channel_history,
+    channel_history_purge, channel_history_reset, channel_history_state, channel_message,
+    channel_message_annotations, channel_message_versions, channel_objects,
+    channel_presence_history, channel_presence_history_reset, channel_presence_history_snapshot,
+    channel_presence_history_state, channel_subscribe, channel_unsubscribe, channel_users,
+    channels, delete_annotation, delete_message, events, fallback_404, live, metrics,
+    publish_annotation, revoke_capability_tokens, stats, terminate_user_connections, up,
+    update_message, usage,
 };
-use crate::middleware::pusher_api_auth_middleware;
+use crate::long_polling_handler::{
+    LongPollSessions, handle_long_poll_close, handle_long_poll_open, handle_long_poll_receive,
+    handle_long_poll_send,
+};
+use crate::middleware::{connection_ip_filter_middleware, pusher_api_auth_middleware};
 #[cfg(feature = "push")]
 use crate::push_http::{
     batch_publish as push_batch_publish, delete_channel_subscriptions, delete_device,
@@ -17,6 +23,7 @@ use crate::push_http::{
     post_fcm_credential, post_hms_credential, post_template, post_webpush_credential,
     post_wns_credential, publish as push_publish, register_device, upsert_channel_subscription,
 };
+use crate::sse_handler::handle_sse_connect;
 use crate::ws_handler::handle_ws_upgrade;
 use axum::extract::DefaultBodyLimit;
 use axum::http::HeaderValue;
@@ -193,7 +200,37 @@ impl SockudoServer {
             self.config.http_api.request_limit_in_mb, body_limit_bytes
         );
 
-        let mut websocket_router = Router::new().route("/app/{appKey}", get(handle_ws_upgrade));
+        let mut websocket_router = Router::new()
+            .route("/app/{appKey}", get(handle_ws_upgrade))
+            .route("/app/{appKey}/sse", get(handle_sse_connect));
+        // Only opening a long-polling session counts as a connection attempt; the polls that
+        // follow are routed separately so they are not throttled by the upgrade rate limit,
+        // but they still pass the app's connection IP rules.
+        let mut long_polling_router = Router::new();
+        if self.config.long_polling.enabled {
+            let sessions =
+                LongPollSessions::new(self.handler.clone(), self.config.long_polling.clone());
+            sessions.spawn_reaper();
+            websocket_router = websocket_router
+                .route("/app/{appKey}/poll", post(handle_long_poll_open))
+                .layer(axum::Extension(sessions.clone()));
+            long_polling_router = long_polling_router
+                .route(
+                    "/app/{appKey}/poll/{sessionId}",
+                    get(handle_long_poll_receive)
+                        .post(handle_long_poll_send)
+                        .delete(handle_long_poll_close),
+                )
+                .route_layer(axum_middleware::from_fn_with_state(
+                    self.handler.clone(),
+                    connection_ip_filter_middleware,
+                ))
+                .layer(axum::Extension(sessions));
+        }
+        websocket_router = websocket_router.route_layer(axum_middleware::from_fn_with_state(
+            self.handler.clone(),
+            connection_ip_filter_middleware,
+        ));
         if let Some(middleware) = websocket_rate_limiter_middleware_layer {
             websocket_router = websocket_router.layer(middleware);
         }
@@ -326,6 +363,15 @@ impl SockudoServer {
                     ),
                 ),
             )
+            .route(
+                "/apps/{appId}/channels/{channelName}/objects",
+                get(channel_objects).post(apply_channel_objects).route_layer(
+                    axum_middleware::from_fn_with_state(
+                        self.handler.clone(),
+                        pusher_api_auth_middleware,
+                    ),
+                ),
+            )
             .route(
                 "/apps/{appId}/channels/{channelName}/history/state",
                 get(channel_history_state).route_layer(axum_middleware::from_fn_with_state(
@@ -354,6 +400,20 @@ impl SockudoServer {
                     pusher_api_auth_middleware,
                 )),
             )
+            .route(
+                "/apps/{appId}/channels/{channelName}/subscribe",
+                post(channel_subscribe).route_layer(axum_middleware::from_fn_with_state(
+                    self.handler.clone(),
+                    pusher_api_auth_middleware,
+                )),
+            )
+            .route(
+                "/apps/{appId}/channels/{channelName}/unsubscribe",
+                post(channel_unsubscribe).route_layer(axum_middleware::from_fn_with_state(
+                    self.handler.clone(),
+                    pusher_api_auth_middleware,
+                )),
+            )
             .route(
                 "/apps/{appId}/users/{userId}/terminate_connections",
                 post(terminate_user_connections).route_layer(axum_middleware::from_fn_with_state(
@@ -522,6 +582,7 @@ impl SockudoServer {
 
         let mut router = Router::new()
             .merge(websocket_router)
+            .merge(long_polling_router)
             .merge(api_router)
             .route("/up", get(up))
             .route("/up/{appId}", get(up))
@@ -218,6 +218,25 @@ impl SockudoServer {
             }
         }
 
+        if self.config.mqtt.enabled {
+            crate::mqtt::spawn_gateway(self.handler.clone(), self.config.mqtt.clone()).await;
+        }
+
+        if self.config.grpc.enabled {
+            let api_rate_limiter = self
+                .config
+                .rate_limiter
+                .enabled
+                .then(|| self.state.http_api_rate_limiter.clone())
+                .flatten();
+            crate::grpc::spawn_server(
+                self.handler.clone(),
+                self.config.grpc.clone(),
+                api_rate_limiter,
+            )
+            .await;
+        }
+
         let http_router = self.configure_http_routes();
 
         // Choose between Unix socket OR HTTP/HTTPS for main server
@@ -5,9 +5,9 @@ use super::push::{create_push_queue, create_push_store};
 use super::{MetricsFactory, ServerState, SockudoServer};
 use crate::cleanup::CleanupSender;
 use crate::cleanup::multi_worker::MultiWorkerCleanupSystem;
-use crate::history::create_history_store;
 #[cfg(feature = "versioned-messages")]
 use crate::history::create_version_store;
+use crate::history::{create_channel_object_store, create_history_store};
 use crate::presence_history::create_presence_history_store;
 use sockudo_adapter::ConnectionHandler;
 use sockudo_adapter::factory::AdapterFactory;
@@ -108,6 +108,9 @@ impl SockudoServer {
             None
         };
 
+        // Subscription changes from the HTTP API that peers forward to this node's sockets
+        let subscription_event_receiver = connection_manager.configure_subscription_events();
+
         let auth_validator = Arc::new(AuthValidator::new(app_manager.clone()));
 
         let metrics =
@@ -845,6 +848,16 @@ impl SockudoServer {
 
             builder = builder.version_store(version_store);
         }
+        if config.channel_objects.enabled {
+            let channel_object_store = create_channel_object_store(
+                &config.channel_objects,
+                &config.history,
+                &config.database,
+                &config.database_pooling,
+            )
+            .await?;
+            builder = builder.channel_object_store(channel_object_store);
+        }
         builder = builder.presence_history_store(presence_history_store);
 
         if let Some(adapter) = state.local_adapter.clone() {
@@ -879,6 +892,17 @@ impl SockudoServer {
             });
         }
 
+        if let Some(event_receiver) = subscription_event_receiver {
+            let handler_clone = handler.clone();
+            tokio::spawn(async move {
+                while let Ok(event) = event_receiver.recv().await {
+                    if let Err(e) = handler_clone.handle_subscription_change_event(event).await {
+                        error!("Error applying remote subscription change: {}", e);
+                    }
+                }
+            });
+        }
+
         // Start replay buffer eviction task (only when connection recovery is enabled)
         #[cfg(feature = "recovery")]
         if config.connection_recovery.enabled
@@ -0,0 +1,863 @@
+//! gRPC API for backend services (`proto/sockudo/realtime/v1/realtime.proto`).
+//!
+//! `Publish` and `BatchPublish` go through the HTTP events processor. `Subscribe` opens a
+//! channel-backed transport session, so streamed events come from the adapter's regular
+//! per-channel fanout, exactly like an SSE or long-polling client.
+
+mod proto;
+
+use crate::http_handler::{AppError, publish_server_event};
+use crate::sse_handler::SessionGuard;
+use futures_util::Stream;
+use proto::{
+    BatchPublishRequest, BatchPublishResponse, ChannelEvent, PublishRequest, PublishResponse,
+    SubscribeRequest,
+};
+use serde::Deserialize;
+use sockudo_adapter::ConnectionHandler;
+use sockudo_adapter::channel_manager::ChannelManager;
+use sockudo_adapter::handler::transport_session::{TransportSession, TransportSessionOptions};
+use sockudo_core::app::{ApiKeyScope, App, AppApiKey};
+use sockudo_core::auth::AuthValidator;
+use sockudo_core::error::Error;
+use sockudo_core::options::GrpcConfig;
+use sockudo_core::rate_limiter::RateLimiter;
+use sockudo_core::token::secure_compare;
+use sockudo_core::websocket::SocketId;
+use sockudo_protocol::ProtocolVersion;
+use sockudo_protocol::messages::{ApiMessageData, MessageExtras, PusherApiMessage, PusherMessage};
+use sockudo_ws::Message;
+use sonic_rs::{JsonValueTrait, Value};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::body::Body;
+use tonic::codegen::{Body as HttpBody, BoxFuture, StdError, http};
+use tonic::metadata::MetadataMap;
+use tonic::server::{Grpc, NamedService};
+use tonic::{Request, Response, Status};
+use tonic_prost::ProstCodec;
+use tracing::{debug, info, warn};
+
+const SERVICE_NAME: &str = "sockudo.realtime.v1.Realtime";
+const PUBLISH_PATH: &str = "/sockudo.realtime.v1.Realtime/Publish";
+const BATCH_PUBLISH_PATH: &str = "/sockudo.realtime.v1.Realtime/BatchPublish";
+const SUBSCRIBE_PATH: &str = "/sockudo.realtime.v1.Realtime/Subscribe";
+
+const APP_KEY_METADATA: &str = "sockudo-app-key";
+const APP_SECRET_METADATA: &str = "sockudo-app-secret";
+/// Required when `sockudo-app-key` is one of the app's scoped `policy.api_keys`.
+const APP_ID_METADATA: &str = "sockudo-app-id";
+
+type ChannelEventStream = Pin<Box<dyn Stream<Item = Result<ChannelEvent, Status>> + Send>>;
+
+/// The app a request authenticated as and, for scoped API keys, the key it used.
+struct Caller {
+    app: App,
+    api_key: Option<AppApiKey>,
+}
+
+impl Caller {
+    /// Applies the scoped API key rules of the HTTP API. Primary app credentials may do
+    /// anything.
+    fn authorize(&self, scope: ApiKeyScope, channels: &[String]) -> Result<(), Status> {
+        match &self.api_key {
+            Some(api_key) => AuthValidator::authorize_api_key_scope(api_key, scope, channels)
+                .map_err(|e| Status::permission_denied(e.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The `sockudo.realtime.v1.Realtime` service.
+#[derive(Clone)]
+pub struct RealtimeService {
+    handler: Arc<ConnectionHandler>,
+    max_message_size: usize,
+    api_rate_limiter: Option<Arc<dyn RateLimiter + Send + Sync>>,
+}
+
+impl RealtimeService {
+    pub fn new(handler: Arc<ConnectionHandler>, config: &GrpcConfig) -> Self {
+        Self {
+            handler,
+            max_message_size: config.max_message_size_kb.max(1) * 1024,
+            api_rate_limiter: None,
+        }
+    }
+
+    /// Counts every call against the HTTP API rate limit of the caller's address.
+    #[must_use]
+    pub fn with_api_rate_limiter(mut self, limiter: Arc<dyn RateLimiter + Send + Sync>) -> Self {
+        self.api_rate_limiter = Some(limiter);
+        self
+    }
+
+    fn grpc<T, U>(&self) -> Grpc<ProstCodec<T, U>>
+    where
+        T: prost::Message + Send + 'static,
+        U: prost::Message + Default + Send + 'static,
+    {
+        Grpc::new(ProstCodec::default())
+            .apply_max_message_size_config(Some(self.max_message_size), Some(self.max_message_size))
+    }
+
+    /// Rate-limits the call and resolves the caller from the `sockudo-app-key` /
+    /// `sockudo-app-secret` metadata. The key is either the app key or, together with
+    /// `sockudo-app-id`, one of the app's scoped API keys.
+    async fn authenticate<T>(&self, request: &Request<T>) -> Result<Caller, Status> {
+        self.check_rate_limit(request.remote_addr()).await?;
+
+        let metadata = request.metadata();
+        let key = metadata_value(metadata, APP_KEY_METADATA)?;
+        let secret = metadata_value(metadata, APP_SECRET_METADATA)?;
+
+        let caller = match self.handler.app_manager().find_by_key(key).await {
+            Ok(Some(app)) => Caller { app, api_key: None },
+            Ok(None) => self.find_scoped_key(metadata, key).await?,
+            Err(e) => {
+                warn!("App lookup failed for gRPC request: {}", e);
+                return Err(Status::internal("App lookup failed"));
+            }
+        };
+        let secret_matches = match &caller.api_key {
+            Some(api_key) => secure_compare(&api_key.secret, secret),
+            None => caller
+                .app
+                .active_secrets()
+                .into_iter()
+                .any(|active| secure_compare(active, secret)),
+        };
+        if !secret_matches {
+            return Err(Status::unauthenticated("Invalid app credentials"));
+        }
+        if !caller.app.enabled {
+            return Err(Status::permission_denied("Application is disabled"));
+        }
+        let ip = request.remote_addr().map(|addr| addr.ip());
+        if !caller.app.permits_api_ip(ip) {
+            warn!(
+                "Rejected gRPC request for app {} from disallowed address {:?}",
+                caller.app.id, ip
+            );
+            return Err(Status::permission_denied(
+                "API requests from this address are not allowed",
+            ));
+        }
+        Ok(caller)
+    }
+
+    async fn find_scoped_key(&self, metadata: &MetadataMap, key: &str) -> Result<Caller, Status> {
+        let invalid = || Status::unauthenticated("Invalid app credentials");
+        let app_id = metadata
+            .get(APP_ID_METADATA)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(invalid)?;
+        let app = match self.handler.app_manager().find_by_id(app_id).await {
+            Ok(Some(app)) => app,
+            Ok(None) => return Err(invalid()),
+            Err(e) => {
+                warn!("App lookup failed for gRPC request: {}", e);
+                return Err(Status::internal("App lookup failed"));
+            }
+        };
+        let api_key = app.api_key(key).cloned().ok_or_else(invalid)?;
+        Ok(Caller {
+            app,
+            api_key: Some(api_key),
+        })
+    }
+
+    /// Same budget and key as the HTTP API rate limiter, so a client cannot sidestep its limit
+    /// by switching to gRPC.
+    async fn check_rate_limit(&self, remote_addr: Option<SocketAddr>) -> Result<(), Status> {
+        let Some(limiter) = &self.api_rate_limiter else {
+            return Ok(());
+        };
+        let ip = remote_addr.map_or_else(|| "unknown_ip".to_string(), |addr| addr.ip().to_string());
+        match limiter.increment(&format!("api:{ip}")).await {
+            Ok(result) if result.allowed => Ok(()),
+            Ok(result) => Err(Status::resource_exhausted(format!(
+                "Rate limit exceeded, retry after {} seconds",
+                result.reset_after
+            ))),
+            Err(e) => {
+                warn!("gRPC rate limit check failed: {}", e);
+                Err(Status::unavailable("Rate limiter unavailable"))
+            }
+        }
+    }
+
+    async fn publish(
+        &self,
+        request: Request<PublishRequest>,
+    ) -> Result<Response<PublishResponse>, Status> {
+        let caller = self.authenticate(&request).await?;
+        let request = request.into_inner();
+        caller.authorize(ApiKeyScope::Publish, &request.channels)?;
+        let response = self.publish_one(&caller.app, request).await?;
+        Ok(Response::new(response))
+    }
+
+    async fn batch_publish(
+        &self,
+        request: Request<BatchPublishRequest>,
+    ) -> Result<Response<BatchPublishResponse>, Status> {
+        let caller = self.authenticate(&request).await?;
+        let events = request.into_inner().events;
+        let channels: Vec<String> = events
+            .iter()
+            .flat_map(|event| event.channels.iter().cloned())
+            .collect();
+        caller.authorize(ApiKeyScope::Publish, &channels)?;
+        let app = caller.app;
+        if let Some(max_batch) = app.event_batch_size_limit()
+            && events.len() > max_batch as usize
+        {
+            return Err(Status::invalid_argument(format!(
+                "Batch size ({}) exceeds limit ({max_batch})",
+                events.len()
+            )));
+        }
+
+        let mut results = Vec::with_capacity(events.len());
+        for event in events {
+            results.push(self.publish_one(&app, event).await?);
+        }
+        Ok(Response::new(BatchPublishResponse { results }))
+    }
+
+    async fn publish_one(
+        &self,
+        app: &App,
+        request: PublishRequest,
+    ) -> Result<PublishResponse, Status> {
+        let event = api_message(request)?;
+        let channels = publish_server_event(&self.handler, app, event)
+            .await
+            .map_err(status_from_app_error)?
+            .into_iter()
+            .map(|(channel, info)| {
+                let info = sonic_rs::to_string(&info).unwrap_or_else(|_| "{}".to_string());
+                (channel, info)
+            })
+            .collect();
+        Ok(PublishResponse { channels })
+    }
+
+    async fn subscribe(
+        &self,
+        request: Request<SubscribeRequest>,
+    ) -> Result<Response<ChannelEventStream>, Status> {
+        let caller = self.authenticate(&request).await?;
+        let request = request.into_inner();
+
+        let mut channels: Vec<String> = Vec::with_capacity(request.channels.len());
+        for channel in &request.channels {
+            let channel = channel.trim();
+            if !channel.is_empty() && !channels.iter().any(|c| c == channel) {
+                channels.push(channel.to_string());
+            }
+        }
+        if channels.is_empty() {
+            return Err(Status::invalid_argument("At least one channel is required"));
+        }
+        if let Some(channel) = channels.iter().find(|c| c.starts_with("presence-")) {
+            return Err(Status::invalid_argument(format!(
+                "Presence channel {channel} cannot be subscribed over gRPC"
+            )));
+        }
+        caller.authorize(ApiKeyScope::Read, &channels)?;
+        let app = caller.app;
+        let filter = request
+            .filter
+            .as_deref()
+            .map(sonic_rs::from_str::<Value>)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("filter must be valid JSON"))?;
+
+        let TransportSession {
+            socket_id,
+            app,
+            receiver,
+            ..
+        } = self
+            .handler
+            .open_transport_session(
+                &app.key,
+                TransportSessionOptions {
+                    origin: None,
+                    protocol_version: ProtocolVersion::V2,
+                    echo_messages: true,
+                    token: None,
+                    connect_context: None,
+                },
+            )
+            .await
+            .map_err(status_from_error)?;
+        let guard = SessionGuard {
+            handler: self.handler.clone(),
+            socket_id,
+            app: app.clone(),
+        };
+
+        for channel in &channels {
+            let message = subscribe_message(&app, &socket_id, channel, filter.as_ref())
+                .map_err(status_from_error)?;
+            self.handler
+                .handle_transport_message(&socket_id, &app, &message)
+                .await
+                .map_err(status_from_error)?;
+            let subscribed = self
+                .handler
+                .connection_manager()
+                .is_in_channel(&app.id, channel, &socket_id)
+                .await
+                .unwrap_or(false);
+            if !subscribed {
+                return Err(Status::permission_denied(format!(
+                    "Subscription to {channel} was rejected"
+                )));
+            }
+        }
+
+        debug!(
+            "gRPC subscriber {} joined {} channel(s) of app {}",
+            socket_id,
+            channels.len(),
+            app.id
+        );
+        let stream = event_stream(SubscribeStreamState {
+            receiver,
+            closed: false,
+            _guard: guard,
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+impl NamedService for RealtimeService {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+impl<B> tower::Service<http::Request<B>> for RealtimeService
+where
+    B: HttpBody + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move {
+            let response = match req.uri().path() {
+                PUBLISH_PATH => {
+                    let method = service.clone();
+                    let call = tower::service_fn(move |request| {
+                        let method = method.clone();
+                        async move { method.publish(request).await }
+                    });
+                    service.grpc().unary(call, req).await
+                }
+                BATCH_PUBLISH_PATH => {
+                    let method = service.clone();
+                    let call = tower::service_fn(move |request| {
+                        let method = method.clone();
+                        async move { method.batch_publish(request).await }
+                    });
+                    service.grpc().unary(call, req).await
+                }
+                SUBSCRIBE_PATH => {
+                    let method = service.clone();
+                    let call = tower::service_fn(move |request| {
+                        let method = method.clone();
+                        async move { method.subscribe(request).await }
+                    });
+                    service.grpc().server_streaming(call, req).await
+                }
+                _ => Status::unimplemented("Unknown method").into_http(),
+            };
+            Ok(response)
+        })
+    }
+}
+
+fn metadata_value<'a>(metadata: &'a MetadataMap, key: &str) -> Result<&'a str, Status> {
+    metadata
+        .get(key)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| Status::unauthenticated(format!("Missing {key} metadata")))
+}
+
+fn api_message(request: PublishRequest) -> Result<PusherApiMessage, Status> {
+    let extras = request
+        .extras_json
+        .as_deref()
+        .map(sonic_rs::from_str::<MessageExtras>)
+        .transpose()
+        .map_err(|e| Status::invalid_argument(format!("Invalid extras_json: {e}")))?;
+
+    Ok(PusherApiMessage {
+        name: (!request.name.is_empty()).then_some(request.name),
+        data: Some(ApiMessageData::String(request.data)),
+        channel: None,
+        channels: Some(request.channels),
+        socket_id: request.socket_id,
+        info: request.info,
+        tags: (!request.tags.is_empty()).then(|| request.tags.into_iter().collect()),
+        delta: request.delta,
+        idempotency_key: request.idempotency_key,
+        message_id: request.message_id,
+        extras,
+    })
+}
+
+/// Builds the subscribe request for a backend subscriber. The caller already proved it holds
+/// the app secret, so private channels are signed on its behalf.
+fn subscribe_message(
+    app: &App,
+    socket_id: &SocketId,
+    channel: &str,
+    filter: Option<&Value>,
+) -> Result<PusherMessage, Error> {
+    let mut data = sonic_rs::json!({ "channel": channel });
+    if let Some(filter) = filter {
+        data["filter"] = filter.clone();
+    }
+    if channel.starts_with("private-") {
+        let unsigned: PusherMessage = sonic_rs::from_value(&sonic_rs::json!({
+            "event": "sockudo:subscribe",
+            "data": data.clone(),
+        }))
+        .map_err(|e| Error::InvalidMessageFormat(format!("Invalid subscribe request: {e}")))?;
+        data["auth"] =
+            Value::from(ChannelManager::get_expected_signature(app, socket_id, unsigned).as_str());
+    }
+    sonic_rs::from_value(&sonic_rs::json!({ "event": "sockudo:subscribe", "data": data }))
+        .map_err(|e| Error::InvalidMessageFormat(format!("Invalid subscribe request: {e}")))
+}
+
+#[derive(Debug, Deserialize)]
+struct OutboundFrame {
+    event: Option<String>,
+    channel: Option<String>,
+    data: Option<Value>,
+    user_id: Option<String>,
+    serial: Option<u64>,
+    message_id: Option<String>,
+    stream_id: Option<String>,
+    tags: Option<HashMap<String, String>>,
+    extras: Option<Value>,
+}
+
+/// Maps an outbound frame onto a `ChannelEvent`. Protocol frames that do not belong to a
+/// channel, and internal channel frames such as `subscription_succeeded`, are not forwarded.
+fn channel_event(frame: &[u8]) -> Option<ChannelEvent> {
+    let frame: OutboundFrame = sonic_rs::from_slice(frame).ok()?;
+    let event = frame.event?;
+    let channel = frame.channel?;
+    if event.starts_with("sockudo_internal:") || event.starts_with("pusher_internal:") {
+        return None;
+    }
+
+    let data = match frame.data {
+        Some(value) => match value.as_str() {
+            Some(data) => data.to_string(),
+            None => sonic_rs::to_string(&value).unwrap_or_default(),
+        },
+        None => String::new(),
+    };
+    Some(ChannelEvent {
+        channel,
+        event,
+        data,
+        user_id: frame.user_id,
+        serial: frame.serial,
+        message_id: frame.message_id,
+        stream_id: frame.stream_id,
+        tags: frame.tags.unwrap_or_default(),
+        extras_json: frame
+            .extras
+            .and_then(|extras| sonic_rs::to_string(&extras).ok()),
+    })
+}
+
+struct SubscribeStreamState {
+    receiver: mpsc::Receiver<Message>,
+    closed: bool,
+    _guard: SessionGuard,
+}
+
+fn event_stream(
+    state: SubscribeStreamState,
+) -> impl Stream<Item = Result<ChannelEvent, Status>> + Send {
+    futures_util::stream::unfold(state, |mut state| async move {
+        if state.closed {
+            return None;
+        }
+        loop {
+            match state.receiver.recv().await? {
+                Message::Text(bytes) => {
+                    if let Some(event) = channel_event(&bytes) {
+                        return Some((Ok(event), state));
+                    }
+                }
+                Message::Close(reason) => {
+                    state.closed = true;
+                    let reason = reason
+                        .map(|reason| reason.reason.to_string())
+                        .unwrap_or_default();
+                    let status = Status::unavailable(format!("Connection closed: {reason}"));
+                    return Some((Err(status), state));
+                }
+                _ => continue,
+            }
+        }
+    })
+}
+
+fn status_from_error(error: Error) -> Status {
+    let message = error.to_string();
+    match error {
+        Error::ApplicationNotFound | Error::InvalidAppKey => Status::not_found(message),
+        Error::ApplicationDisabled | Error::OriginNotAllowed => Status::permission_denied(message),
+        Error::OverConnectionQuota => Status::resource_exhausted(message),
+        Error::Auth(_) => Status::unauthenticated(message),
+        Error::InvalidMessageFormat(_) | Error::InvalidChannelName(_) | Error::Channel(_) => {
+            Status::invalid_argument(message)
+        }
+        _ => Status::internal(message),
+    }
+}
+
+fn status_from_app_error(error: AppError) -> Status {
+    let message = error.to_string();
+    match error {
+        AppError::AppNotFound(_) | AppError::NotFound(_) => Status::not_found(message),
+        AppError::ApiAuthFailed(_) => Status::unauthenticated(message),
+        AppError::Forbidden(_) => Status::permission_denied(message),
+        AppError::MissingChannelInfo | AppError::InvalidInput(_) | AppError::LimitExceeded(_) => {
+            Status::invalid_argument(message)
+        }
+        AppError::PayloadTooLarge(_) | AppError::TooManyRequests { .. } => {
+            Status::resource_exhausted(message)
+        }
+        AppError::Backpressure { .. } => Status::unavailable(message),
+        AppError::FeatureDisabled(_) => Status::failed_precondition(message),
+        AppError::NotImplemented(_) => Status::unimplemented(message),
+        AppError::AiTransport { status, .. } => match status {
+            http::StatusCode::PAYLOAD_TOO_LARGE => Status::resource_exhausted(message),
+            http::StatusCode::FORBIDDEN => Status::permission_denied(message),
+            _ => Status::invalid_argument(message),
+        },
+        _ => Status::internal(message),
+    }
+}
+
+/// Binds the gRPC listener and serves it in the background. `api_rate_limiter` is the HTTP API
+/// limiter, shared so both APIs draw from one budget.
+pub async fn spawn_server(
+    handler: Arc<ConnectionHandler>,
+    config: GrpcConfig,
+    api_rate_limiter: Option<Arc<dyn RateLimiter + Send + Sync>>,
+) {
+    let addr =
+        sockudo_core::utils::resolve_socket_addr(&config.host, config.port, "gRPC API").await;
+    match TcpListener::bind(addr).await {
+        Ok(listener) => {
+            info!("gRPC API listening on {}", addr);
+            let mut service = RealtimeService::new(handler, &config);
+            if let Some(limiter) = api_rate_limiter {
+                service = service.with_api_rate_limiter(limiter);
+            }
+            tokio::spawn(async move {
+                if let Err(e) = tonic::transport::Server::builder()
+                    .add_service(service)
+                    .serve_with_incoming(TcpListenerStream::new(listener))
+                    .await
+                {
+                    warn!("gRPC API stopped: {}", e);
+                }
+            });
+        }
+        Err(e) => {
+            warn!(
+                "Failed to bind gRPC API on {}: {}. Backend gRPC clients will not be able to connect.",
+                addr, e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_handler::test_support::{test_app, test_realtime_handler_harness};
+    use futures_util::StreamExt;
+    use sockudo_core::app::AppManager;
+    use std::time::Duration;
+
+    async fn test_service() -> (RealtimeService, App) {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = test_app();
+        app_manager.create_app(app.clone()).await.unwrap();
+        (RealtimeService::new(handler, &GrpcConfig::default()), app)
+    }
+
+    fn authed<T>(message: T, app: &App) -> Request<T> {
+        let mut request = Request::new(message);
+        let metadata = request.metadata_mut();
+        metadata.insert(APP_KEY_METADATA, app.key.parse().unwrap());
+        metadata.insert(APP_SECRET_METADATA, app.secret.parse().unwrap());
+        request
+    }
+
+    fn scoped<T>(message: T, app: &App) -> Request<T> {
+        let mut request = Request::new(message);
+        let metadata = request.metadata_mut();
+        metadata.insert(APP_KEY_METADATA, "publisher-key".parse().unwrap());
+        metadata.insert(APP_SECRET_METADATA, "publisher-secret".parse().unwrap());
+        metadata.insert(APP_ID_METADATA, app.id.parse().unwrap());
+        request
+    }
+
+    fn publish_request(channel: &str, data: &str) -> PublishRequest {
+        PublishRequest {
+            name: "reading".to_string(),
+            channels: vec![channel.to_string()],
+            data: data.to_string(),
+            ..Default::default()
+        }
+    }
+
+    async fn next_event(stream: &mut ChannelEventStream) -> ChannelEvent {
+        tokio::time::timeout(Duration::from_secs(2), stream.next())
+            .await
+            .expect("timed out waiting for a channel event")
+            .expect("stream ended")
+            .expect("stream failed")
+    }
+
+    #[tokio::test]
+    async fn requests_without_valid_credentials_are_rejected() {
+        let (service, app) = test_service().await;
+
+        let status = service
+            .publish(Request::new(publish_request("news", "{}")))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        let mut request = authed(publish_request("news", "{}"), &app);
+        request
+            .metadata_mut()
+            .insert(APP_SECRET_METADATA, "not-the-secret".parse().unwrap());
+        let status = service.publish(request).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn scoped_api_keys_are_limited_to_their_scopes_and_channels() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let mut app = test_app();
+        app.policy.api_keys = Some(vec![AppApiKey {
+            key: "publisher-key".to_string(),
+            secret: "publisher-secret".to_string(),
+            scopes: vec![ApiKeyScope::Publish],
+            channels: Some(vec!["orders-*".to_string()]),
+        }]);
+        app_manager.create_app(app.clone()).await.unwrap();
+        let service = RealtimeService::new(handler, &GrpcConfig::default());
+
+        service
+            .publish(scoped(publish_request("orders-eu", "{}"), &app))
+            .await
+            .unwrap();
+        let status = service
+            .publish(scoped(publish_request("news", "{}"), &app))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+        let status = service
+            .subscribe(scoped(
+                SubscribeRequest {
+                    channels: vec!["orders-eu".to_string()],
+                    filter: None,
+                },
+                &app,
+            ))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+        let mut request = scoped(publish_request("orders-eu", "{}"), &app);
+        request.metadata_mut().remove(APP_ID_METADATA);
+        let status = service.publish(request).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn calls_draw_from_the_api_rate_limit() {
+        let (service, app) = test_service().await;
+        let service = service.with_api_rate_limiter(Arc::new(
+            sockudo_rate_limiter::memory_limiter::MemoryRateLimiter::new(1, 60),
+        ));
+
+        service
+            .publish(authed(publish_request("news", "{}"), &app))
+            .await
+            .unwrap();
+        let status = service
+            .publish(authed(publish_request("news", "{}"), &app))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    }
+
+    #[tokio::test]
+    async fn subscribe_streams_events_from_public_and_private_channels() {
+        let (service, app) = test_service().await;
+        let mut stream = service
+            .subscribe(authed(
+                SubscribeRequest {
+                    channels: vec!["news".to_string(), "private-orders".to_string()],
+                    filter: None,
+                },
+                &app,
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+
+        service
+            .publish(authed(
+                publish_request("private-orders", r#"{"id":1}"#),
+                &app,
+            ))
+            .await
+            .unwrap();
+        let event = next_event(&mut stream).await;
+        assert_eq!(event.channel, "private-orders");
+        assert_eq!(event.event, "reading");
+        assert_eq!(event.data, r#"{"id":1}"#);
+
+        service
+            .publish(authed(publish_request("news", "hello"), &app))
+            .await
+            .unwrap();
+        let event = next_event(&mut stream).await;
+        assert_eq!(event.channel, "news");
+        assert_eq!(event.data, "hello");
+    }
+
+    #[tokio::test]
+    async fn subscribe_rejects_presence_and_empty_requests() {
+        let (service, app) = test_service().await;
+
+        let status = service
+            .subscribe(authed(
+                SubscribeRequest {
+                    channels: vec![" ".to_string()],
+                    filter: None,
+                },
+                &app,
+            ))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let status = service
+            .subscribe(authed(
+                SubscribeRequest {
+                    channels: vec!["presence-lobby".to_string()],
+                    filter: None,
+                },
+                &app,
+            ))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn batch_publish_is_served_over_http2() {
+        let (service, app) = test_service().await;
+        let mut stream = service
+            .subscribe(authed(
+                SubscribeRequest {
+                    channels: vec!["news".to_string()],
+                    filter: None,
+                },
+                &app,
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service.clone())
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        let channel = tonic::transport::Endpoint::from_shared(format!("http://{addr}"))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        let mut client = tonic::client::Grpc::new(channel);
+
+        let mut counted = publish_request("news", "second");
+        counted.info = Some("subscription_count".to_string());
+        client.ready().await.unwrap();
+        let response: Response<BatchPublishResponse> = client
+            .unary(
+                authed(
+                    BatchPublishRequest {
+                        events: vec![publish_request("news", "first"), counted],
+                    },
+                    &app,
+                ),
+                http::uri::PathAndQuery::from_static(BATCH_PUBLISH_PATH),
+                ProstCodec::default(),
+            )
+            .await
+            .unwrap();
+        let results = response.into_inner().results;
+        assert_eq!(results.len(), 2);
+        let info: Value = sonic_rs::from_str(&results[1].channels["news"]).unwrap();
+        assert_eq!(info["subscription_count"].as_u64(), Some(1));
+
+        assert_eq!(next_event(&mut stream).await.data, "first");
+        assert_eq!(next_event(&mut stream).await.data, "second");
+
+        client.ready().await.unwrap();
+        let status = client
+            .unary::<_, PublishResponse, _>(
+                authed(publish_request("news", "x"), &app),
+                http::uri::PathAndQuery::from_static("/sockudo.realtime.v1.Realtime/Missing"),
+                ProstCodec::<PublishRequest, PublishResponse>::default(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unimplemented);
+    }
+}
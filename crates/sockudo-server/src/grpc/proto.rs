@@ -0,0 +1,78 @@
+//! Message types of `proto/sockudo/realtime/v1/realtime.proto`.
+//!
+//! Kept in sync with the `.proto` file by hand, like the wire-format messages in
+//! `sockudo-protocol`, so building the server does not require `protoc`.
+
+use std::collections::HashMap;
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PublishRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, repeated, tag = "2")]
+    pub channels: Vec<String>,
+    #[prost(string, tag = "3")]
+    pub data: String,
+    #[prost(string, optional, tag = "4")]
+    pub socket_id: Option<String>,
+    #[prost(map = "string, string", tag = "5")]
+    pub tags: HashMap<String, String>,
+    #[prost(string, optional, tag = "6")]
+    pub idempotency_key: Option<String>,
+    #[prost(string, optional, tag = "7")]
+    pub message_id: Option<String>,
+    #[prost(string, optional, tag = "8")]
+    pub info: Option<String>,
+    #[prost(string, optional, tag = "9")]
+    pub extras_json: Option<String>,
+    #[prost(bool, optional, tag = "10")]
+    pub delta: Option<bool>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PublishResponse {
+    #[prost(map = "string, string", tag = "1")]
+    pub channels: HashMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BatchPublishRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub events: Vec<PublishRequest>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BatchPublishResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub results: Vec<PublishResponse>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SubscribeRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub channels: Vec<String>,
+    #[prost(string, optional, tag = "2")]
+    pub filter: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ChannelEvent {
+    #[prost(string, tag = "1")]
+    pub channel: String,
+    #[prost(string, tag = "2")]
+    pub event: String,
+    #[prost(string, tag = "3")]
+    pub data: String,
+    #[prost(string, optional, tag = "4")]
+    pub user_id: Option<String>,
+    #[prost(uint64, optional, tag = "5")]
+    pub serial: Option<u64>,
+    #[prost(string, optional, tag = "6")]
+    pub message_id: Option<String>,
+    #[prost(string, optional, tag = "7")]
+    pub stream_id: Option<String>,
+    #[prost(map = "string, string", tag = "8")]
+    pub tags: HashMap<String, String>,
+    #[prost(string, optional, tag = "9")]
+    pub extras_json: Option<String>,
+}
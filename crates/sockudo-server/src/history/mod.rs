@@ -1,13 +1,17 @@
 use sockudo_core::cache::CacheManager;
+use sockudo_core::channel_objects::{ChannelObjectStore, MemoryChannelObjectStore};
 use sockudo_core::error::Result;
 use sockudo_core::history::{HistoryStore, MemoryHistoryStore, MemoryHistoryStoreConfig};
 use sockudo_core::metrics::MetricsInterface;
-use sockudo_core::options::{DatabaseConfig, DatabasePooling, HistoryBackend, HistoryConfig};
+use sockudo_core::options::{
+    ChannelObjectStoreDriver, ChannelObjectsConfig, DatabaseConfig, DatabasePooling,
+    HistoryBackend, HistoryConfig,
+};
 #[cfg(feature = "versioned-messages")]
 use sockudo_core::options::{VersionStoreDriver, VersionedMessagesConfig};
 use std::sync::Arc;
 use std::time::Duration;
-#[cfg(all(feature = "postgres", feature = "versioned-messages"))]
+#[cfg(any(feature = "postgres", feature = "mysql"))]
 use tracing::info;
 
 #[cfg(feature = "dynamodb")]
@@ -250,3 +254,55 @@ pub async fn create_version_store(
         }
     }
 }
+
+pub async fn create_channel_object_store(
+    config: &ChannelObjectsConfig,
+    history_config: &HistoryConfig,
+    db_config: &DatabaseConfig,
+    pooling: &DatabasePooling,
+) -> Result<Arc<dyn ChannelObjectStore + Send + Sync>> {
+    match config.driver {
+        ChannelObjectStoreDriver::Memory => Ok(Arc::new(MemoryChannelObjectStore::new())),
+        ChannelObjectStoreDriver::Postgres => {
+            #[cfg(feature = "postgres")]
+            {
+                let store = postgres::PostgresChannelObjectStore::new(
+                    &db_config.postgres,
+                    pooling,
+                    &history_config.postgres.table_prefix,
+                )
+                .await?;
+                info!("ChannelObjectStore initialized with driver: postgres");
+                Ok(Arc::new(store))
+            }
+            #[cfg(not(feature = "postgres"))]
+            {
+                let _ = (history_config, db_config, pooling);
+                Err(sockudo_core::error::Error::Configuration(
+                    "Channel object store driver 'postgres' requires the 'postgres' feature"
+                        .to_string(),
+                ))
+            }
+        }
+        ChannelObjectStoreDriver::Mysql => {
+            #[cfg(feature = "mysql")]
+            {
+                let store = mysql::MysqlChannelObjectStore::new(
+                    &db_config.mysql,
+                    pooling,
+                    &history_config.mysql.table_prefix,
+                )
+                .await?;
+                info!("ChannelObjectStore initialized with driver: mysql");
+                Ok(Arc::new(store))
+            }
+            #[cfg(not(feature = "mysql"))]
+            {
+                let _ = (history_config, db_config, pooling);
+                Err(sockudo_core::error::Error::Configuration(
+                    "Channel object store driver 'mysql' requires the 'mysql' feature".to_string(),
+                ))
+            }
+        }
+    }
+}
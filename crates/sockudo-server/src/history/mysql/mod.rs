@@ -257,5 +257,8 @@ mod store_impl;
 mod version_store;
 pub(super) use version_store::create_mysql_version_store;
 
+mod object_store;
+pub(super) use object_store::MysqlChannelObjectStore;
+
 #[cfg(test)]
 mod tests;
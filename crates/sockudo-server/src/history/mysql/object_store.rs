@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use sockudo_core::channel_objects::{
+    ChannelObjectLimits, ChannelObjectStore, ChannelObjects, ObjectOperation,
+};
+use sockudo_core::error::{Error, Result};
+use sockudo_core::options::{DatabaseConnection, DatabasePooling};
+use sqlx::{MySqlPool, Row, mysql::MySqlPoolOptions};
+use std::time::Duration;
+
+/// Stores each channel's objects as one JSON document per row. Every batch locks the row,
+/// decodes the whole document and writes it back, so write cost grows with the channel's total
+/// object size rather than the size of the batch.
+pub struct MysqlChannelObjectStore {
+    pool: MySqlPool,
+    table: String,
+}
+
+impl MysqlChannelObjectStore {
+    pub(in crate::history) async fn new(
+        db_config: &DatabaseConnection,
+        pooling: &DatabasePooling,
+        table_prefix: &str,
+    ) -> Result<Self> {
+        let password = urlencoding::encode(&db_config.password);
+        let connection_string = format!(
+            "mysql://{}:{}@{}:{}/{}",
+            db_config.username, password, db_config.host, db_config.port, db_config.database
+        );
+
+        let mut opts = MySqlPoolOptions::new();
+        opts = if pooling.enabled {
+            let min = db_config.pool_min.unwrap_or(pooling.min);
+            let max = db_config.pool_max.unwrap_or(pooling.max);
+            opts.min_connections(min).max_connections(max)
+        } else {
+            opts.max_connections(db_config.connection_pool_size)
+        };
+
+        let pool = opts
+            .acquire_timeout(Duration::from_secs(5))
+            .idle_timeout(Duration::from_secs(180))
+            .connect(&connection_string)
+            .await
+            .map_err(|e| {
+                Error::Internal(format!(
+                    "Failed to connect channel object store to MySQL: {e}"
+                ))
+            })?;
+
+        let store = Self {
+            pool,
+            table: format!("{}_channel_objects", table_prefix),
+        };
+        store.ensure_table().await?;
+        Ok(store)
+    }
+
+    async fn ensure_table(&self) -> Result<()> {
+        let create_table = format!(
+            r#"CREATE TABLE IF NOT EXISTS `{}` (
+                app_id VARCHAR(255) NOT NULL,
+                channel VARCHAR(255) NOT NULL,
+                serial BIGINT NOT NULL,
+                objects LONGTEXT NOT NULL,
+                updated_at_ms BIGINT NOT NULL,
+                PRIMARY KEY (app_id, channel)
+            ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci"#,
+            self.table
+        );
+        sqlx::query(sqlx::AssertSqlSafe(create_table.as_str()))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                Error::Internal(format!("Failed to initialize channel object table: {e}"))
+            })?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ChannelObjectStore for MysqlChannelObjectStore {
+    async fn apply_operations(
+        &self,
+        app_id: &str,
+        channel: &str,
+        operations: &[ObjectOperation],
+        limits: &ChannelObjectLimits,
+    ) -> Result<u64> {
+        let now_ms = sockudo_core::history::now_ms();
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            Error::Internal(format!("Failed to begin channel object transaction: {e}"))
+        })?;
+
+        let insert_sql = format!(
+            "INSERT IGNORE INTO `{}` (app_id, channel, serial, objects, updated_at_ms) VALUES (?, ?, 0, '{{\"serial\":0,\"objects\":{{}}}}', ?)",
+            self.table
+        );
+        sqlx::query(sqlx::AssertSqlSafe(insert_sql.as_str()))
+            .bind(app_id)
+            .bind(channel)
+            .bind(now_ms)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                Error::Internal(format!("Failed to initialize channel object row: {e}"))
+            })?;
+
+        let select_sql = format!(
+            "SELECT objects FROM `{}` WHERE app_id = ? AND channel = ? FOR UPDATE",
+            self.table
+        );
+        let row = sqlx::query(sqlx::AssertSqlSafe(select_sql.as_str()))
+            .bind(app_id)
+            .bind(channel)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                Error::Internal(format!("Failed to read channel objects for update: {e}"))
+            })?;
+        let mut objects: ChannelObjects = sonic_rs::from_str(&row.get::<String, _>("objects"))
+            .map_err(|e| Error::Internal(format!("Failed to decode channel objects: {e}")))?;
+
+        let serial = objects.apply(operations, limits)?;
+        let encoded = sonic_rs::to_string(&objects)
+            .map_err(|e| Error::Internal(format!("Failed to encode channel objects: {e}")))?;
+
+        let update_sql = format!(
+            "UPDATE `{}` SET serial = ?, objects = ?, updated_at_ms = ? WHERE app_id = ? AND channel = ?",
+            self.table
+        );
+        sqlx::query(sqlx::AssertSqlSafe(update_sql.as_str()))
+            .bind(serial as i64)
+            .bind(encoded)
+            .bind(now_ms)
+            .bind(app_id)
+            .bind(channel)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to store channel objects: {e}")))?;
+
+        tx.commit().await.map_err(|e| {
+            Error::Internal(format!("Failed to commit channel object transaction: {e}"))
+        })?;
+        Ok(serial)
+    }
+
+    async fn snapshot(&self, app_id: &str, channel: &str) -> Result<ChannelObjects> {
+        let select_sql = format!(
+            "SELECT objects FROM `{}` WHERE app_id = ? AND channel = ?",
+            self.table
+        );
+        let row = sqlx::query(sqlx::AssertSqlSafe(select_sql.as_str()))
+            .bind(app_id)
+            .bind(channel)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to read channel objects: {e}")))?;
+        match row {
+            Some(row) => sonic_rs::from_str(&row.get::<String, _>("objects"))
+                .map_err(|e| Error::Internal(format!("Failed to decode channel objects: {e}"))),
+            None => Ok(ChannelObjects::default()),
+        }
+    }
+}
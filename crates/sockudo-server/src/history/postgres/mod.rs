@@ -1,6 +1,7 @@
 mod history_record;
 mod history_store;
 mod history_store_impl;
+mod object_store;
 mod schema;
 mod stream_state;
 #[cfg(feature = "versioned-messages")]
@@ -11,6 +12,7 @@ use sockudo_core::error::{Error, Result};
 use sqlx::PgConnection;
 
 pub(super) use history_store::PostgresHistoryStore;
+pub(super) use object_store::PostgresChannelObjectStore;
 #[cfg(feature = "versioned-messages")]
 pub(super) use version_store::PostgresVersionStore;
 
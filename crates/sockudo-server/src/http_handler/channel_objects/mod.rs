@@ -0,0 +1,98 @@
+//! Channel object HTTP surfaces: apply operations to channel-attached state and read its
+//! current snapshot.
+
+use axum::{
+    Json,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response as AxumResponse},
+};
+use serde::{Deserialize, Serialize};
+use sockudo_adapter::ConnectionHandler;
+use sockudo_core::app::App;
+use sockudo_core::channel_objects::ObjectOperation;
+use sockudo_core::utils::validate_channel_name;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::AppError;
+
+#[derive(Debug, Deserialize)]
+pub struct ChannelObjectsPath {
+    #[serde(rename = "appId")]
+    pub app_id: String,
+    #[serde(rename = "channelName")]
+    pub channel_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApplyObjectOperationsRequest {
+    pub operations: Vec<ObjectOperation>,
+    #[serde(default, alias = "clientId")]
+    pub client_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApplyObjectOperationsResponse {
+    pub serial: u64,
+}
+
+fn require_channel_objects_enabled(
+    handler: &ConnectionHandler,
+    channel_name: &str,
+) -> Result<(), AppError> {
+    if !handler.server_options().channel_objects.enabled {
+        return Err(AppError::FeatureDisabled(format!(
+            "Channel objects are disabled globally for channel '{channel_name}'"
+        )));
+    }
+    Ok(())
+}
+
+/// POST /apps/{app_id}/channels/{channel_name}/objects
+#[instrument(skip(handler, request), fields(app_id = %path.app_id, channel = %path.channel_name))]
+pub async fn apply_channel_objects(
+    Path(path): Path<ChannelObjectsPath>,
+    Extension(app): Extension<App>,
+    State(handler): State<Arc<ConnectionHandler>>,
+    Json(request): Json<ApplyObjectOperationsRequest>,
+) -> Result<AxumResponse, AppError> {
+    validate_channel_name(&app, &path.channel_name).await?;
+    require_channel_objects_enabled(&handler, &path.channel_name)?;
+
+    let serial = handler
+        .apply_channel_object_operations(
+            &app,
+            &path.channel_name,
+            request.operations,
+            request.client_id,
+        )
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApplyObjectOperationsResponse { serial }),
+    )
+        .into_response())
+}
+
+/// GET /apps/{app_id}/channels/{channel_name}/objects
+#[instrument(skip(handler), fields(app_id = %path.app_id, channel = %path.channel_name))]
+pub async fn channel_objects(
+    Path(path): Path<ChannelObjectsPath>,
+    Extension(app): Extension<App>,
+    State(handler): State<Arc<ConnectionHandler>>,
+) -> Result<AxumResponse, AppError> {
+    validate_channel_name(&app, &path.channel_name).await?;
+    require_channel_objects_enabled(&handler, &path.channel_name)?;
+
+    let snapshot = handler
+        .channel_object_store()
+        .snapshot(&path.app_id, &path.channel_name)
+        .await?;
+
+    Ok((StatusCode::OK, Json(snapshot)).into_response())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,127 @@
+use super::*;
+use crate::http_handler::test_support::*;
+use sockudo_adapter::ConnectionHandlerBuilder;
+use sockudo_adapter::local_adapter::LocalAdapter;
+use sockudo_app::memory_app_manager::MemoryAppManager;
+use sockudo_cache::memory_cache_manager::MemoryCacheManager;
+use sockudo_core::channel_objects::{ChannelObject, ChannelObjects};
+use sockudo_core::options::{MemoryCacheOptions, ServerOptions};
+use sonic_rs::json;
+
+fn test_channel_objects_handler(enabled: bool) -> Arc<ConnectionHandler> {
+    let mut options = ServerOptions::default();
+    options.channel_objects.enabled = enabled;
+    options.channel_objects.max_operations_per_request = 2;
+    Arc::new(
+        ConnectionHandlerBuilder::new(
+            Arc::new(MemoryAppManager::new()),
+            Arc::new(LocalAdapter::new()),
+            Arc::new(MemoryCacheManager::new(
+                "test".to_string(),
+                MemoryCacheOptions::default(),
+            )),
+            options,
+        )
+        .build(),
+    )
+}
+
+fn path() -> Path<ChannelObjectsPath> {
+    Path(ChannelObjectsPath {
+        app_id: "app-1".to_string(),
+        channel_name: "poll-room".to_string(),
+    })
+}
+
+fn increment(amount: i64) -> ObjectOperation {
+    ObjectOperation::CounterIncrement {
+        object: "votes".to_string(),
+        amount,
+    }
+}
+
+async fn apply(handler: &Arc<ConnectionHandler>, operations: Vec<ObjectOperation>) -> AxumResponse {
+    match apply_channel_objects(
+        path(),
+        Extension(test_app()),
+        State(handler.clone()),
+        Json(ApplyObjectOperationsRequest {
+            operations,
+            client_id: Some("backend".to_string()),
+        }),
+    )
+    .await
+    {
+        Ok(response) => response,
+        Err(err) => err.into_response(),
+    }
+}
+
+#[tokio::test]
+async fn channel_objects_require_feature_flag() {
+    let handler = test_channel_objects_handler(false);
+
+    let response = apply(&handler, vec![increment(1)]).await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn channel_objects_apply_and_read_back() {
+    let handler = test_channel_objects_handler(true);
+
+    assert_eq!(
+        apply(&handler, vec![increment(2), increment(3)])
+            .await
+            .status(),
+        StatusCode::OK
+    );
+    apply(
+        &handler,
+        vec![ObjectOperation::MapSet {
+            object: "choices".to_string(),
+            key: "a".to_string(),
+            value: json!("Tabs"),
+        }],
+    )
+    .await;
+
+    let response = channel_objects(path(), Extension(test_app()), State(handler))
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let snapshot: ChannelObjects = sonic_rs::from_slice(&body).unwrap();
+
+    assert_eq!(snapshot.serial, 2);
+    assert_eq!(
+        snapshot.objects["votes"],
+        ChannelObject::Counter { value: 5 }
+    );
+    assert!(matches!(
+        snapshot.objects["choices"],
+        ChannelObject::Map { .. }
+    ));
+}
+
+#[tokio::test]
+async fn channel_objects_reject_oversized_and_conflicting_batches() {
+    let handler = test_channel_objects_handler(true);
+
+    let response = apply(&handler, vec![increment(1), increment(1), increment(1)]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = apply(
+        &handler,
+        vec![
+            increment(1),
+            ObjectOperation::RegisterSet {
+                object: "votes".to_string(),
+                value: json!(1),
+            },
+        ],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -1,5 +1,5 @@
-//! Channel info/list/users endpoints, user-connection termination, and
-//! capability-token revocation.
+//! Channel info/list/users endpoints, server-side subscribe/unsubscribe,
+//! user-connection termination, and capability-token revocation.
 
 use ahash::AHashMap;
 use axum::{
@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use sockudo_adapter::ConnectionHandler;
 use sockudo_adapter::channel_manager::ChannelManager;
 use sockudo_adapter::handler::auth_tokens::RevocationRequest as CapabilityRevocationRequest;
+use sockudo_adapter::horizontal_adapter::SubscriptionChangeEvent;
 use sockudo_core::app::App;
 use sockudo_core::auth::EventQuery;
 use sockudo_core::utils::{self, validate_channel_name};
@@ -40,6 +41,19 @@ pub struct RevokeCapabilityTokensResponse {
     pub closed_connections: usize,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ChannelSubscriptionRequest {
+    pub socket_id: Option<String>,
+    pub user_id: Option<String>,
+    /// Presence member `user_info` for presence channels.
+    pub user_info: Option<sonic_rs::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChannelSubscriptionResponse {
+    pub sockets: usize,
+}
+
 #[derive(Debug)]
 pub struct ChannelQuery {
     pub info: Option<String>,
@@ -344,6 +358,62 @@ pub async fn revoke_capability_tokens(
     Ok((StatusCode::OK, Json(response)))
 }
 
+/// POST /apps/{app_id}/channels/{channel_name}/subscribe
+#[instrument(skip(handler, body), fields(app_id = %app_id, channel = %channel_name))]
+pub async fn channel_subscribe(
+    Path((app_id, channel_name)): Path<(String, String)>,
+    Extension(app): Extension<App>,
+    State(handler): State<Arc<ConnectionHandler>>,
+    Json(body): Json<ChannelSubscriptionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    validate_channel_name(&app, &channel_name).await?;
+    change_channel_subscriptions(handler, app, channel_name, body, true).await
+}
+
+/// POST /apps/{app_id}/channels/{channel_name}/unsubscribe
+#[instrument(skip(handler, body), fields(app_id = %app_id, channel = %channel_name))]
+pub async fn channel_unsubscribe(
+    Path((app_id, channel_name)): Path<(String, String)>,
+    Extension(app): Extension<App>,
+    State(handler): State<Arc<ConnectionHandler>>,
+    Json(body): Json<ChannelSubscriptionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    change_channel_subscriptions(handler, app, channel_name, body, false).await
+}
+
+async fn change_channel_subscriptions(
+    handler: Arc<ConnectionHandler>,
+    app: App,
+    channel_name: String,
+    body: ChannelSubscriptionRequest,
+    subscribe: bool,
+) -> Result<(StatusCode, Json<ChannelSubscriptionResponse>), AppError> {
+    if body.socket_id.is_some() == body.user_id.is_some() {
+        return Err(AppError::InvalidInput(
+            "Exactly one of socket_id or user_id is required".to_string(),
+        ));
+    }
+
+    let sockets = handler
+        .change_subscriptions(
+            &app,
+            SubscriptionChangeEvent {
+                app_id: app.id.clone(),
+                channel: channel_name,
+                socket_id: body.socket_id,
+                user_id: body.user_id,
+                user_info: body.user_info,
+                subscribe,
+            },
+        )
+        .await?;
+
+    let response = ChannelSubscriptionResponse { sockets };
+    let response_json_bytes = sonic_rs::to_vec(&response)?;
+    record_api_metrics(&handler, &app.id, 0, response_json_bytes.len()).await;
+    Ok((StatusCode::OK, Json(response)))
+}
+
 /// POST /apps/{app_id}/users/{user_id}/terminate_connections
 #[instrument(skip(handler), fields(app_id = %app_id, user_id = %user_id))]
 pub async fn terminate_user_connections(
@@ -396,7 +466,7 @@ mod tests {
             .connection_manager()
             .add_socket(
                 socket_id,
-                test_websocket_writer().await,
+                test_websocket_writer().await.into(),
                 &app.id,
                 app_manager as Arc<dyn AppManager + Send + Sync>,
                 WebSocketBufferConfig::default(),
@@ -442,4 +512,88 @@ mod tests {
             Some(1)
         );
     }
+
+    #[tokio::test]
+    async fn channel_subscribe_and_unsubscribe_change_a_live_socket() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = test_app();
+        app_manager.create_app(app.clone()).await.unwrap();
+
+        let socket_id = SocketId::from_string("32.32").unwrap();
+        handler
+            .connection_manager()
+            .add_socket(
+                socket_id,
+                test_websocket_writer().await.into(),
+                &app.id,
+                app_manager as Arc<dyn AppManager + Send + Sync>,
+                WebSocketBufferConfig::default(),
+                ProtocolVersion::V1,
+                WireFormat::Json,
+                true,
+            )
+            .await
+            .unwrap();
+
+        let target = || ChannelSubscriptionRequest {
+            socket_id: Some(socket_id.to_string()),
+            user_id: None,
+            user_info: None,
+        };
+        let (status, Json(response)) = change_channel_subscriptions(
+            handler.clone(),
+            app.clone(),
+            "private-project-1".to_string(),
+            target(),
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response.sockets, 1);
+        assert!(
+            handler
+                .connection_manager()
+                .is_in_channel(&app.id, "private-project-1", &socket_id)
+                .await
+                .unwrap()
+        );
+
+        let (_, Json(response)) = change_channel_subscriptions(
+            handler.clone(),
+            app.clone(),
+            "private-project-1".to_string(),
+            target(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.sockets, 1);
+        assert!(
+            !handler
+                .connection_manager()
+                .is_in_channel(&app.id, "private-project-1", &socket_id)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn channel_subscribe_requires_exactly_one_target() {
+        let (handler, _app_manager) = test_realtime_handler_harness();
+        let result = change_channel_subscriptions(
+            handler,
+            test_app(),
+            "project-1".to_string(),
+            ChannelSubscriptionRequest {
+                socket_id: Some("1.1".to_string()),
+                user_id: Some("user-1".to_string()),
+                user_info: None,
+            },
+            true,
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+    }
 }
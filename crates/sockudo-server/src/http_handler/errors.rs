@@ -180,6 +180,7 @@ impl From<sockudo_core::error::Error> for AppError {
             sockudo_core::error::Error::Channel(s) => AppError::InvalidInput(s),
             sockudo_core::error::Error::InvalidMessageFormat(s) => AppError::InvalidInput(s),
             sockudo_core::error::Error::Auth(s) => AppError::ApiAuthFailed(s),
+            sockudo_core::error::Error::Forbidden(s) => AppError::Forbidden(s),
             sockudo_core::error::Error::AiTransport {
                 code,
                 name,
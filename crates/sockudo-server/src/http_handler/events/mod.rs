@@ -316,6 +316,65 @@ pub async fn events(
     Ok((StatusCode::OK, Json(response_payload)))
 }
 
+/// Publishes a server event on behalf of an in-process gateway (MQTT, gRPC) through the same
+/// pipeline as `POST /apps/{app_id}/events` and returns the requested channel info.
+///
+/// The event's own `idempotency_key` deduplicates like a batch entry: a repeated key is
+/// skipped and yields an empty info map. Push rules are HTTP concerns and are not applied.
+pub(crate) async fn publish_server_event(
+    handler: &Arc<ConnectionHandler>,
+    app: &App,
+    event: PusherApiMessage,
+) -> Result<HashMap<String, Value>, AppError> {
+    let idempotency_config = app.resolved_idempotency(&handler.server_options().idempotency);
+    let idempotency_key = resolve_idempotency_key(
+        &event.idempotency_key,
+        &HeaderMap::new(),
+        &idempotency_config,
+    )?;
+
+    if let Some(ref key) = idempotency_key {
+        if let Some(metrics_arc) = handler.metrics() {
+            metrics_arc.mark_idempotency_publish(&app.id);
+        }
+        let cache_key = idempotency_cache_key(&app.id, key);
+        if let Ok(Some(_)) = handler.cache_manager().get(&cache_key).await {
+            if let Some(metrics_arc) = handler.metrics() {
+                metrics_arc.mark_idempotency_duplicate(&app.id);
+            }
+            debug!(idempotency_key = %key, "Skipping duplicate server event");
+            return Ok(HashMap::new());
+        }
+    }
+
+    let collect_info = event.info.is_some()
+        || event.message_id.is_some()
+        || event.name.as_deref().is_some_and(is_ai_event);
+    let channel_info_map = process_single_event_parallel(
+        handler,
+        app,
+        event,
+        collect_info,
+        None,
+        idempotency_key.clone(),
+        MessageIdIdempotencyContext {
+            enabled: idempotency_config.enabled,
+            ttl_seconds: idempotency_config.ttl_seconds,
+        },
+    )
+    .await?;
+
+    if let Some(ref key) = idempotency_key {
+        let cache_key = idempotency_cache_key(&app.id, key);
+        let _ = handler
+            .cache_manager()
+            .set(&cache_key, "1", idempotency_config.ttl_seconds)
+            .await;
+    }
+
+    Ok(channel_info_map)
+}
+
 /// POST /apps/{app_id}/batch_events
 #[instrument(skip_all, fields(app_id = %app_id, batch_len = field::Empty))]
 #[allow(clippy::too_many_arguments)]
@@ -326,19 +326,20 @@ pub(super) async fn process_single_event_parallel(
                     .map_err(AppError::SerializationError)?;
                 match build_cache_payload(&event_name_for_task, &message_data, &target_channel_str) {
                     Ok(cache_payload_str) => {
-                        let cache_key_str =
-                            format!("app:{}:channel:{}:cache_miss", &app.id, target_channel_str);
-
                         match handler_clone
-                            .cache_manager()
-                            .set(&cache_key_str, &cache_payload_str, 3600)
+                            .store_cache_payload_for_channel(
+                                app,
+                                &target_channel_str,
+                                &cache_payload_str,
+                                3600,
+                            )
                             .await
                         {
                             Ok(_) => {
-                                debug!(channel = %target_channel_str, cache_key = %cache_key_str, "Cached event for channel");
+                                debug!(channel = %target_channel_str, "Cached event for channel");
                             }
                             Err(e) => {
-                                error!(channel = %target_channel_str, cache_key = %cache_key_str, error = %e, "Failed to cache event (internal error: {:?})", e);
+                                error!(channel = %target_channel_str, error = %e, "Failed to cache event (internal error: {:?})", e);
                             }
                         }
                     }
@@ -703,6 +703,10 @@ async fn namespace_history_retention_override_beats_app_default() {
                     max_bytes_per_channel: None,
                 }),
                 presence_history: None,
+                proxy: None,
+                connection_recovery: None,
+                delta_compression: None,
+                limits: None,
             }]),
             ..Default::default()
         },
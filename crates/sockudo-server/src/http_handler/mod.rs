@@ -2,6 +2,7 @@
 
 mod ai;
 mod annotations;
+mod channel_objects;
 mod channels;
 mod errors;
 mod events;
@@ -14,10 +15,13 @@ mod versioned_messages;
 pub(crate) mod test_support;
 
 pub use annotations::{channel_message_annotations, delete_annotation, publish_annotation};
+pub use channel_objects::{apply_channel_objects, channel_objects};
 pub use channels::{
-    channel, channel_users, channels, revoke_capability_tokens, terminate_user_connections,
+    channel, channel_subscribe, channel_unsubscribe, channel_users, channels,
+    revoke_capability_tokens, terminate_user_connections,
 };
 pub use errors::AppError;
+pub(crate) use events::publish_server_event;
 pub use events::{batch_events, events};
 pub use history::{
     channel_history, channel_history_purge, channel_history_reset, channel_history_state,
@@ -42,7 +42,9 @@ use sockudo_ws::{Config as WsConfig, Http1, Stream as WsStream, WebSocketStream}
 use sonic_rs::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
 
 use super::{EventQuery, events};
 
@@ -85,6 +87,10 @@ pub(crate) fn test_notifications_app() -> App {
                 allow_presence_for_client: None,
                 history: None,
                 presence_history: None,
+                proxy: None,
+                connection_recovery: None,
+                delta_compression: None,
+                limits: None,
             }]),
             ..Default::default()
         },
@@ -506,7 +512,7 @@ pub(crate) async fn attach_signed_in_mutation_actor(
         .connection_manager()
         .add_socket(
             socket_id,
-            test_websocket_writer().await,
+            test_websocket_writer().await.into(),
             &app.id,
             app_manager as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -659,3 +665,66 @@ impl PresenceHistoryStore for InspectablePresenceHistoryStore {
             .await
     }
 }
+
+/// Stand-in proxy backend that answers every request with `body`.
+pub(crate) struct ProxyBackend {
+    pub(crate) url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl ProxyBackend {
+    pub(crate) async fn start(body: &'static str) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/proxy", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let received = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut chunk = [0u8; 4096];
+                while !request_complete(&request) {
+                    match stream.read(&mut chunk).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => request.extend_from_slice(&chunk[..read]),
+                    }
+                }
+                received
+                    .lock()
+                    .await
+                    .push(String::from_utf8_lossy(&request).into_owned());
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Lowercased request head and raw body of the first call.
+    pub(crate) async fn request(&self) -> (String, String) {
+        let requests = self.requests.lock().await;
+        let request = requests.first().expect("proxy was not called");
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        (head.to_ascii_lowercase(), body.to_string())
+    }
+}
+
+fn request_complete(request: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(request);
+    let Some((head, body)) = text.split_once("\r\n\r\n") else {
+        return false;
+    };
+    let length = head
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("content-length")
+                .then(|| value.trim().parse::<usize>().ok())?
+        })
+        .unwrap_or(0);
+    body.len() >= length
+}
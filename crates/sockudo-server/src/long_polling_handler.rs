@@ -0,0 +1,672 @@
+use crate::ws_handler::connect_context;
+use sockudo_adapter::ConnectionHandler;
+use sockudo_adapter::handler::transport_session::{TransportSession, TransportSessionOptions};
+
+use axum::Extension;
+use axum::extract::{ConnectInfo, Path, Query, RawQuery, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use dashmap::DashMap;
+use serde::Deserialize;
+use sockudo_core::app::App;
+use sockudo_core::error::Error;
+use sockudo_core::options::LongPollingConfig;
+use sockudo_core::websocket::SocketId;
+use sockudo_protocol::ProtocolVersion;
+use sockudo_protocol::messages::PusherMessage;
+use sockudo_ws::Message;
+use sonic_rs::{JsonContainerTrait, Value};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+/// Cookie (and header) naming the node that owns a long-polling session. Load balancers can
+/// use either one for sticky routing.
+const AFFINITY_COOKIE: &str = "sockudo_node";
+const AFFINITY_HEADER: &str = "x-sockudo-node";
+
+#[derive(Debug, Deserialize)]
+pub struct LongPollOpenQuery {
+    protocol: Option<u8>,
+    /// V2 only. Set to false to disable echo (publisher won't receive own messages).
+    echo_messages: Option<bool>,
+    /// V2 capability-token authentication.
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LongPollQuery {
+    /// Client-requested wait, capped by `long_polling.poll_timeout_ms`.
+    timeout_ms: Option<u64>,
+}
+
+struct PollSession {
+    socket_id: SocketId,
+    app: App,
+    receiver: tokio::sync::Mutex<mpsc::Receiver<Message>>,
+    last_activity: Mutex<Instant>,
+}
+
+impl PollSession {
+    fn touch(&self) {
+        if let Ok(mut last_activity) = self.last_activity.lock() {
+            *last_activity = Instant::now();
+        }
+    }
+
+    fn idle_since(&self) -> Instant {
+        self.last_activity
+            .lock()
+            .map(|last_activity| *last_activity)
+            .unwrap_or_else(|_| Instant::now())
+    }
+}
+
+/// Long-polling sessions owned by this node.
+///
+/// Each session is a regular adapter socket with a channel-backed writer; polls drain the
+/// frames buffered since the previous poll. Sessions that stop polling are reaped after
+/// `session_idle_timeout_seconds`.
+///
+/// Sessions are keyed by a random token handed out when the session opens, never by the
+/// socket id, which other clients can learn from presence data and member events.
+pub struct LongPollSessions {
+    handler: Arc<ConnectionHandler>,
+    config: LongPollingConfig,
+    sessions: DashMap<String, Arc<PollSession>>,
+}
+
+impl LongPollSessions {
+    pub fn new(handler: Arc<ConnectionHandler>, config: LongPollingConfig) -> Arc<Self> {
+        Arc::new(Self {
+            handler,
+            config,
+            sessions: DashMap::new(),
+        })
+    }
+
+    /// Starts the background task that closes idle sessions. It stops once the registry is
+    /// dropped.
+    pub fn spawn_reaper(self: &Arc<Self>) {
+        let registry: Weak<Self> = Arc::downgrade(self);
+        let period = Duration::from_secs((self.config.session_idle_timeout_seconds / 2).max(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                let Some(registry) = registry.upgrade() else {
+                    break;
+                };
+                let reaped = registry.reap_idle().await;
+                if reaped > 0 {
+                    debug!("Closed {} idle long-polling sessions", reaped);
+                }
+            }
+        });
+    }
+
+    fn node_id(&self) -> String {
+        self.handler.connection_manager().get_node_id()
+    }
+
+    fn idle_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.session_idle_timeout_seconds)
+    }
+
+    /// Closes sessions that have not been polled within the idle timeout. Sessions with a poll
+    /// in flight are never reaped.
+    async fn reap_idle(&self) -> usize {
+        let idle_timeout = self.idle_timeout();
+        let expired: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|entry| {
+                entry.value().idle_since().elapsed() >= idle_timeout
+                    && entry.value().receiver.try_lock().is_ok()
+            })
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        let mut reaped = 0;
+        for token in expired {
+            if self.close_session(&token).await {
+                reaped += 1;
+            }
+        }
+        reaped
+    }
+
+    async fn close_session(&self, token: &str) -> bool {
+        let Some((_, session)) = self.sessions.remove(token) else {
+            return false;
+        };
+        self.handler
+            .close_transport_session(&session.socket_id, &session.app)
+            .await;
+        true
+    }
+}
+
+fn error_status(error: &Error) -> StatusCode {
+    match error {
+        Error::ApplicationNotFound => StatusCode::NOT_FOUND,
+        Error::ApplicationDisabled | Error::OriginNotAllowed => StatusCode::FORBIDDEN,
+        Error::OverConnectionQuota => StatusCode::TOO_MANY_REQUESTS,
+        Error::Auth(_) => StatusCode::UNAUTHORIZED,
+        Error::InvalidMessageFormat(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn requested_node(headers: &HeaderMap) -> Option<String> {
+    if let Some(node) = headers.get(AFFINITY_HEADER).and_then(|h| h.to_str().ok()) {
+        return Some(node.to_string());
+    }
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|h| h.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == AFFINITY_COOKIE)
+        .map(|(_, value)| value.to_string())
+}
+
+fn with_affinity(mut response: Response, node_id: &str) -> Response {
+    if let Ok(value) = HeaderValue::from_str(node_id) {
+        response.headers_mut().insert(AFFINITY_HEADER, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&format!(
+        "{AFFINITY_COOKIE}={node_id}; Path=/; HttpOnly; SameSite=Lax"
+    )) {
+        response.headers_mut().insert(header::SET_COOKIE, value);
+    }
+    response
+}
+
+/// Resolves a session for a request. A miss on a node other than the one named by the affinity
+/// cookie is reported as `421 Misdirected Request` so clients and proxies can tell a broken
+/// sticky route apart from an expired session.
+fn lookup_session(
+    registry: &LongPollSessions,
+    app_key: &str,
+    session_id: &str,
+    headers: &HeaderMap,
+) -> Result<Arc<PollSession>, (StatusCode, String)> {
+    match registry.sessions.get(session_id) {
+        Some(session) if session.app.key == app_key => Ok(session.clone()),
+        Some(_) => Err((StatusCode::NOT_FOUND, "unknown session".to_string())),
+        None => match requested_node(headers) {
+            Some(node) if node != registry.node_id() => Err((
+                StatusCode::MISDIRECTED_REQUEST,
+                format!("session is owned by node {node}"),
+            )),
+            _ => Err((StatusCode::NOT_FOUND, "unknown session".to_string())),
+        },
+    }
+}
+
+fn parse_frames(body: &str) -> Result<Vec<PusherMessage>, Error> {
+    let value: Value = sonic_rs::from_str(body)
+        .map_err(|e| Error::InvalidMessageFormat(format!("Invalid JSON: {e}")))?;
+    let frames = match value.as_array() {
+        Some(frames) => frames.iter().cloned().collect(),
+        None => vec![value],
+    };
+    frames
+        .iter()
+        .map(|frame| {
+            sonic_rs::from_value(frame)
+                .map_err(|e| Error::InvalidMessageFormat(format!("Invalid message: {e}")))
+        })
+        .collect()
+}
+
+// Opens a long-polling session: the HTTP equivalent of a WebSocket upgrade.
+pub async fn handle_long_poll_open(
+    Path(app_key): Path<String>,
+    Query(params): Query<LongPollOpenQuery>,
+    RawQuery(query): RawQuery,
+    headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    State(handler): State<Arc<ConnectionHandler>>,
+    Extension(registry): Extension<Arc<LongPollSessions>>,
+) -> Response {
+    if !handler.is_accepting() {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+
+    let origin = headers
+        .get(header::ORIGIN)
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+    let server_options = handler.server_options();
+    let protocol_version = ProtocolVersion::from_query_param(params.protocol);
+    let echo_messages = if server_options.echo_control.enabled {
+        params
+            .echo_messages
+            .unwrap_or(server_options.echo_control.default_echo_messages)
+    } else {
+        true
+    };
+
+    let session = match handler
+        .open_transport_session(
+            &app_key,
+            TransportSessionOptions {
+                origin,
+                protocol_version,
+                echo_messages,
+                token: params.token,
+                connect_context: Some(connect_context(
+                    "long_polling",
+                    &headers,
+                    query,
+                    connect_info,
+                )),
+            },
+        )
+        .await
+    {
+        Ok(session) => session,
+        Err(e) => {
+            debug!("Rejected long-polling session for app {}: {}", app_key, e);
+            return (error_status(&e), e.to_string()).into_response();
+        }
+    };
+
+    let TransportSession {
+        socket_id,
+        app,
+        receiver,
+        ..
+    } = session;
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    registry.sessions.insert(
+        token.clone(),
+        Arc::new(PollSession {
+            socket_id,
+            app,
+            receiver: tokio::sync::Mutex::new(receiver),
+            last_activity: Mutex::new(Instant::now()),
+        }),
+    );
+
+    let node_id = registry.node_id();
+    let body = sonic_rs::json!({ "session_id": &token, "node_id": &node_id });
+    with_affinity(
+        (
+            StatusCode::CREATED,
+            [(header::CONTENT_TYPE, "application/json")],
+            body.to_string(),
+        )
+            .into_response(),
+        &node_id,
+    )
+}
+
+// Waits for outbound frames and returns them as a JSON array.
+pub async fn handle_long_poll_receive(
+    Path((app_key, session_id)): Path<(String, String)>,
+    Query(params): Query<LongPollQuery>,
+    headers: HeaderMap,
+    Extension(registry): Extension<Arc<LongPollSessions>>,
+) -> Response {
+    let session = match lookup_session(&registry, &app_key, &session_id, &headers) {
+        Ok(found) => found,
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    let Ok(mut receiver) = session.receiver.try_lock() else {
+        return (StatusCode::CONFLICT, "a poll is already in progress").into_response();
+    };
+    session.touch();
+
+    let max_wait = registry.config.poll_timeout_ms;
+    let wait = Duration::from_millis(params.timeout_ms.map_or(max_wait, |t| t.min(max_wait)));
+    let max_frames = registry.config.max_messages_per_poll.max(1);
+
+    let mut frames: Vec<String> = Vec::new();
+    let mut closed = false;
+    // A timeout simply yields an empty batch; the client polls again.
+    if let Ok(mut next) = tokio::time::timeout(wait, receiver.recv()).await {
+        loop {
+            match next {
+                Some(Message::Text(bytes)) => match std::str::from_utf8(&bytes) {
+                    Ok(frame) => frames.push(frame.to_string()),
+                    Err(_) => warn!("Dropping non UTF-8 frame on long-polling session"),
+                },
+                Some(Message::Close(_)) | None => {
+                    closed = true;
+                    break;
+                }
+                Some(_) => {}
+            }
+            if frames.len() >= max_frames {
+                break;
+            }
+            next = match receiver.try_recv() {
+                Ok(message) => Some(message),
+                Err(mpsc::error::TryRecvError::Empty) => break,
+                Err(mpsc::error::TryRecvError::Disconnected) => None,
+            };
+        }
+    }
+    drop(receiver);
+    session.touch();
+
+    if closed {
+        registry.close_session(&session_id).await;
+        if frames.is_empty() {
+            return (StatusCode::GONE, "session closed").into_response();
+        }
+    }
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json")],
+        format!("[{}]", frames.join(",")),
+    )
+        .into_response()
+}
+
+// Delivers one client frame, or a JSON array of frames, to the session.
+pub async fn handle_long_poll_send(
+    Path((app_key, session_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Extension(registry): Extension<Arc<LongPollSessions>>,
+    body: String,
+) -> Response {
+    let session = match lookup_session(&registry, &app_key, &session_id, &headers) {
+        Ok(found) => found,
+        Err(rejection) => return rejection.into_response(),
+    };
+    session.touch();
+
+    let frames = match parse_frames(&body) {
+        Ok(frames) => frames,
+        Err(e) => return (error_status(&e), e.to_string()).into_response(),
+    };
+
+    for frame in &frames {
+        match registry
+            .handler
+            .handle_transport_message(&session.socket_id, &session.app, frame)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => return (StatusCode::GONE, "session closed").into_response(),
+            Err(e) => {
+                warn!(
+                    "Failed to process long-polling frame for {}: {}",
+                    session.socket_id, e
+                );
+                return (error_status(&e), e.to_string()).into_response();
+            }
+        }
+    }
+
+    StatusCode::NO_CONTENT.into_response()
+}
+
+// Closes a session on client request.
+pub async fn handle_long_poll_close(
+    Path((app_key, session_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Extension(registry): Extension<Arc<LongPollSessions>>,
+) -> Response {
+    let session = match lookup_session(&registry, &app_key, &session_id, &headers) {
+        Ok(found) => found,
+        Err(rejection) => return rejection.into_response(),
+    };
+    registry.close_session(&session_id).await;
+    info!(
+        "Long-polling session for {} closed by client",
+        session.socket_id
+    );
+    StatusCode::NO_CONTENT.into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_handler::test_support::{test_app, test_realtime_handler_harness};
+    use http_body_util::BodyExt;
+    use sockudo_core::app::AppManager;
+
+    async fn body_string(response: Response) -> String {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn open_query() -> Query<LongPollOpenQuery> {
+        Query(LongPollOpenQuery {
+            protocol: Some(2),
+            echo_messages: None,
+            token: None,
+        })
+    }
+
+    fn poll_query() -> Query<LongPollQuery> {
+        Query(LongPollQuery {
+            timeout_ms: Some(200),
+        })
+    }
+
+    async fn open_session(
+        handler: &Arc<ConnectionHandler>,
+        registry: &Arc<LongPollSessions>,
+        app: &App,
+    ) -> (String, Response) {
+        let response = handle_long_poll_open(
+            Path(app.key.clone()),
+            open_query(),
+            RawQuery(None),
+            HeaderMap::new(),
+            None,
+            State(handler.clone()),
+            Extension(registry.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let session_id = registry.sessions.iter().next().unwrap().key().to_string();
+        (session_id, response)
+    }
+
+    #[test]
+    fn affinity_node_is_read_from_header_or_cookie() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("theme=dark; sockudo_node=node-b"),
+        );
+        assert_eq!(requested_node(&headers).as_deref(), Some("node-b"));
+
+        headers.insert(AFFINITY_HEADER, HeaderValue::from_static("node-c"));
+        assert_eq!(requested_node(&headers).as_deref(), Some("node-c"));
+        assert!(requested_node(&HeaderMap::new()).is_none());
+    }
+
+    #[test]
+    fn frames_accept_single_message_or_array() {
+        assert_eq!(
+            parse_frames(r#"{"event":"pusher:ping","data":{}}"#)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            parse_frames(r#"[{"event":"pusher:ping"},{"event":"pusher:pong"}]"#)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(parse_frames("not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn long_poll_session_round_trip() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = test_app();
+        app_manager.create_app(app.clone()).await.unwrap();
+        let registry = LongPollSessions::new(handler.clone(), LongPollingConfig::default());
+
+        let (session_id, response) = open_session(&handler, &registry, &app).await;
+        assert!(response.headers().contains_key(header::SET_COOKIE));
+        assert!(body_string(response).await.contains(&session_id));
+
+        // The socket id is public, so it must not open the session.
+        let socket_id = registry.sessions.get(&session_id).unwrap().socket_id;
+        assert_ne!(socket_id.to_string(), session_id);
+        let response = handle_long_poll_send(
+            Path((app.key.clone(), socket_id.to_string())),
+            HeaderMap::new(),
+            Extension(registry.clone()),
+            r#"{"event":"sockudo:ping","data":{}}"#.to_string(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = handle_long_poll_receive(
+            Path((app.key.clone(), session_id.clone())),
+            poll_query(),
+            HeaderMap::new(),
+            Extension(registry.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(
+            body_string(response)
+                .await
+                .contains("connection_established")
+        );
+
+        let response = handle_long_poll_send(
+            Path((app.key.clone(), session_id.clone())),
+            HeaderMap::new(),
+            Extension(registry.clone()),
+            r#"{"event":"sockudo:subscribe","data":{"channel":"news"}}"#.to_string(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = handle_long_poll_receive(
+            Path((app.key.clone(), session_id.clone())),
+            poll_query(),
+            HeaderMap::new(),
+            Extension(registry.clone()),
+        )
+        .await;
+        assert!(
+            body_string(response)
+                .await
+                .contains("subscription_succeeded")
+        );
+        assert_eq!(
+            handler
+                .connection_manager()
+                .get_channel_socket_count(&app.id, "news")
+                .await,
+            1
+        );
+
+        let response = handle_long_poll_close(
+            Path((app.key.clone(), session_id.clone())),
+            HeaderMap::new(),
+            Extension(registry.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(registry.sessions.is_empty());
+        assert_eq!(
+            handler
+                .connection_manager()
+                .get_channel_socket_count(&app.id, "news")
+                .await,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_poll_times_out_with_empty_batch() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = test_app();
+        app_manager.create_app(app.clone()).await.unwrap();
+        let registry = LongPollSessions::new(handler.clone(), LongPollingConfig::default());
+        let (session_id, _) = open_session(&handler, &registry, &app).await;
+
+        let first = handle_long_poll_receive(
+            Path((app.key.clone(), session_id.clone())),
+            poll_query(),
+            HeaderMap::new(),
+            Extension(registry.clone()),
+        )
+        .await;
+        assert_eq!(first.status(), StatusCode::OK);
+
+        let response = handle_long_poll_receive(
+            Path((app.key.clone(), session_id)),
+            poll_query(),
+            HeaderMap::new(),
+            Extension(registry.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_string(response).await, "[]");
+    }
+
+    #[tokio::test]
+    async fn unknown_session_is_misdirected_when_owned_elsewhere() {
+        let (handler, _) = test_realtime_handler_harness();
+        let registry = LongPollSessions::new(handler.clone(), LongPollingConfig::default());
+        let session_id = uuid::Uuid::new_v4().simple().to_string();
+
+        let response = handle_long_poll_receive(
+            Path(("key".to_string(), session_id.clone())),
+            poll_query(),
+            HeaderMap::new(),
+            Extension(registry.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(AFFINITY_HEADER, HeaderValue::from_static("another-node"));
+        let response = handle_long_poll_receive(
+            Path(("key".to_string(), session_id)),
+            poll_query(),
+            headers,
+            Extension(registry),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::MISDIRECTED_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn idle_sessions_are_reaped() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = test_app();
+        app_manager.create_app(app.clone()).await.unwrap();
+        let registry = LongPollSessions::new(
+            handler.clone(),
+            LongPollingConfig {
+                session_idle_timeout_seconds: 0,
+                ..LongPollingConfig::default()
+            },
+        );
+        open_session(&handler, &registry, &app).await;
+
+        assert_eq!(registry.reap_idle().await, 1);
+        assert!(registry.sessions.is_empty());
+        assert_eq!(
+            handler
+                .connection_manager()
+                .get_sockets_count(&app.id)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+}
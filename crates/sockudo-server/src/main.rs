@@ -4,12 +4,16 @@
 
 mod bootstrap;
 pub mod cleanup;
+mod grpc;
 mod history;
 mod http_handler;
+mod long_polling_handler;
 mod middleware;
+mod mqtt;
 mod presence_history;
 #[cfg(feature = "push")]
 mod push_http;
+mod sse_handler;
 mod ws_handler;
 
 pub use bootstrap::MetricsFactory;
@@ -156,6 +160,9 @@ async fn main() -> Result<()> {
             e
         )));
     }
+    sockudo_core::utils::set_wildcard_segment_delimiters(
+        &config.wildcard_subscriptions.segment_delimiters,
+    );
 
     // --- Update logging configuration if needed ---
     if let (Some(filter_handle), Some(fmt_handle)) = (filter_reload_handle, fmt_reload_handle) {
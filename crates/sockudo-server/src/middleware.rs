@@ -5,7 +5,8 @@ use axum::{
 use http_body_util::BodyExt;
 use sockudo_adapter::ConnectionHandler;
 use sockudo_core::auth::AuthValidator;
-use std::{collections::BTreeMap, sync::Arc};
+use sockudo_rate_limiter::middleware::IpKeyExtractor;
+use std::{collections::BTreeMap, net::IpAddr, sync::Arc};
 
 // Helper to extract query parameters for the signature
 fn get_params_for_signature(
@@ -35,6 +36,52 @@ fn get_params_for_signature(
     Ok(params_map)
 }
 
+// Client address, trusting the same number of proxy hops as the matching rate limiter
+fn client_ip(request: &HttpRequest<Body>, trust_hops: Option<u32>) -> Option<IpAddr> {
+    IpKeyExtractor::new(trust_hops.unwrap_or(0) as usize).client_ip(request)
+}
+
+/// Axum middleware enforcing an app's `policy.network.connections` rules on WebSocket
+/// upgrades, SSE connects and every long-polling request.
+///
+/// Requests for unknown apps pass through so the transport handler reports them as usual.
+pub async fn connection_ip_filter_middleware(
+    State(handler_state): State<Arc<ConnectionHandler>>,
+    request: HttpRequest<Body>,
+    next: Next,
+) -> Result<Response, AppError> {
+    let mut segments = request
+        .uri()
+        .path()
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+    let app_key = match (segments.next(), segments.next()) {
+        (Some("app"), Some(app_key)) => app_key.to_string(),
+        _ => return Ok(next.run(request).await),
+    };
+
+    if let Some(app) = handler_state.app_manager().find_by_key(&app_key).await? {
+        let trust_hops = handler_state
+            .server_options()
+            .rate_limiter
+            .websocket_rate_limit
+            .trust_hops;
+        let ip = client_ip(&request, trust_hops);
+        if !app.permits_connection_ip(ip) {
+            tracing::warn!(
+                "Rejected connection to app {} from disallowed address {:?}",
+                app.id,
+                ip
+            );
+            return Err(AppError::Forbidden(
+                "Connections from this address are not allowed".to_string(),
+            ));
+        }
+    }
+
+    Ok(next.run(request).await)
+}
+
 /// Axum middleware for Pusher API authentication.
 ///
 /// This middleware authenticates incoming requests based on the Pusher protocol,
@@ -72,6 +119,13 @@ pub async fn pusher_api_auth_middleware(
     // 2. Collect all query parameters (excluding auth_signature) for the signature string.
     let all_query_params_for_sig_map = get_params_for_signature(query_str_option)?;
 
+    let trust_hops = handler_state
+        .server_options()
+        .rate_limiter
+        .api_rate_limit
+        .trust_hops;
+    let ip = client_ip(&request, trust_hops);
+
     // 3. Buffer the request body.
     let (parts, body) = request.into_parts();
     let body_bytes = match body.collect().await {
@@ -98,6 +152,16 @@ pub async fn pusher_api_auth_middleware(
         )
         .await
     {
+        Ok(app) if !app.permits_api_ip(ip) => {
+            tracing::warn!(
+                "Rejected API request for app {} from disallowed address {:?}",
+                app.id,
+                ip
+            );
+            Err(AppError::Forbidden(
+                "API requests from this address are not allowed".to_string(),
+            ))
+        }
         Ok(app) => {
             tracing::debug!("Pusher API authentication successful for path: {}", path);
             let mut request = HttpRequest::from_parts(parts, Body::from(body_bytes.clone()));
@@ -151,6 +215,23 @@ mod tests {
         assert_eq!(result.get("auth_signature"), None);
     }
 
+    #[test]
+    fn proxy_headers_are_ignored_without_trust_hops() {
+        let peer: std::net::SocketAddr = "192.0.2.10:4000".parse().unwrap();
+        let mut request = HttpRequest::builder()
+            .header("x-real-ip", "10.0.0.1")
+            .header("x-forwarded-for", "10.0.0.2")
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(axum::extract::ConnectInfo(peer));
+
+        assert_eq!(client_ip(&request, None), Some(peer.ip()));
+        assert_eq!(client_ip(&request, Some(0)), Some(peer.ip()));
+        assert_eq!(client_ip(&request, Some(1)), "10.0.0.2".parse().ok());
+    }
+
     #[test]
     fn test_get_params_for_signature_invalid_queries() {
         let invalid_queries = ["&", ""];
@@ -0,0 +1,628 @@
+//! Minimal MQTT 3.1.1 / 5.0 packet codec covering the packets a gateway client needs:
+//! CONNECT, PUBLISH (QoS 0/1), PUBACK, SUBSCRIBE, UNSUBSCRIBE, PINGREQ and DISCONNECT.
+
+use thiserror::Error;
+
+pub const PROTOCOL_V311: u8 = 4;
+pub const PROTOCOL_V5: u8 = 5;
+
+const CONNECT: u8 = 1;
+const CONNACK: u8 = 2;
+const PUBLISH: u8 = 3;
+const PUBACK: u8 = 4;
+const PUBREC: u8 = 5;
+const PUBREL: u8 = 6;
+const PUBCOMP: u8 = 7;
+const SUBSCRIBE: u8 = 8;
+const SUBACK: u8 = 9;
+const UNSUBSCRIBE: u8 = 10;
+const UNSUBACK: u8 = 11;
+const PINGREQ: u8 = 12;
+const PINGRESP: u8 = 13;
+const DISCONNECT: u8 = 14;
+
+const PROPERTY_SESSION_EXPIRY_INTERVAL: u8 = 0x11;
+const PROPERTY_RECEIVE_MAXIMUM: u8 = 0x21;
+const PROPERTY_MAXIMUM_QOS: u8 = 0x24;
+const PROPERTY_RETAIN_AVAILABLE: u8 = 0x25;
+const PROPERTY_SHARED_SUBSCRIPTION_AVAILABLE: u8 = 0x2A;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CodecError {
+    #[error("malformed packet: {0}")]
+    Malformed(&'static str),
+    #[error("unsupported protocol level {0}")]
+    UnsupportedProtocol(u8),
+    #[error("packet of {0} bytes exceeds the maximum packet size")]
+    PacketTooLarge(usize),
+    #[error("unsupported packet type {0}")]
+    UnsupportedPacket(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Connect {
+    pub protocol_level: u8,
+    pub clean_session: bool,
+    pub keep_alive: u16,
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<Vec<u8>>,
+    /// MQTT 5 session expiry interval in seconds.
+    pub session_expiry: Option<u32>,
+    /// MQTT 5 receive maximum: QoS 1 deliveries the client accepts before acknowledging any.
+    pub receive_maximum: Option<u16>,
+}
+
+/// The MQTT 5 properties the gateway acts on; every other property is skipped.
+#[derive(Debug, Default)]
+struct Properties {
+    session_expiry: Option<u32>,
+    receive_maximum: Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Publish {
+    pub topic: String,
+    pub qos: u8,
+    pub retain: bool,
+    pub dup: bool,
+    pub packet_id: Option<u16>,
+    pub payload: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Connect(Connect),
+    Publish(Publish),
+    PubAck {
+        packet_id: u16,
+    },
+    Subscribe {
+        packet_id: u16,
+        filters: Vec<(String, u8)>,
+    },
+    Unsubscribe {
+        packet_id: u16,
+        filters: Vec<String>,
+    },
+    PingReq,
+    Disconnect,
+}
+
+/// CONNACK return codes, expressed in MQTT 3.1.1 terms and translated for MQTT 5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectReturnCode {
+    Accepted,
+    UnsupportedProtocol,
+    BadCredentials,
+    NotAuthorized,
+    ServerUnavailable,
+    QuotaExceeded,
+}
+
+impl ConnectReturnCode {
+    fn code(self, protocol_level: u8) -> u8 {
+        if protocol_level >= PROTOCOL_V5 {
+            match self {
+                ConnectReturnCode::Accepted => 0x00,
+                ConnectReturnCode::UnsupportedProtocol => 0x84,
+                ConnectReturnCode::BadCredentials => 0x86,
+                ConnectReturnCode::NotAuthorized => 0x87,
+                ConnectReturnCode::ServerUnavailable => 0x88,
+                ConnectReturnCode::QuotaExceeded => 0x97,
+            }
+        } else {
+            match self {
+                ConnectReturnCode::Accepted => 0x00,
+                ConnectReturnCode::UnsupportedProtocol => 0x01,
+                ConnectReturnCode::BadCredentials => 0x04,
+                ConnectReturnCode::NotAuthorized => 0x05,
+                ConnectReturnCode::ServerUnavailable | ConnectReturnCode::QuotaExceeded => 0x03,
+            }
+        }
+    }
+}
+
+/// SUBACK failure code; identical in both protocol versions.
+pub const SUBACK_FAILURE: u8 = 0x80;
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    fn u8(&mut self) -> Result<u8, CodecError> {
+        let byte = *self
+            .buf
+            .get(self.pos)
+            .ok_or(CodecError::Malformed("unexpected end of packet"))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn u16(&mut self) -> Result<u16, CodecError> {
+        Ok(u16::from_be_bytes([self.u8()?, self.u8()?]))
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], CodecError> {
+        if self.remaining() < len {
+            return Err(CodecError::Malformed("unexpected end of packet"));
+        }
+        let slice = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn binary(&mut self) -> Result<Vec<u8>, CodecError> {
+        let len = self.u16()? as usize;
+        Ok(self.bytes(len)?.to_vec())
+    }
+
+    fn string(&mut self) -> Result<String, CodecError> {
+        let len = self.u16()? as usize;
+        std::str::from_utf8(self.bytes(len)?)
+            .map(str::to_string)
+            .map_err(|_| CodecError::Malformed("invalid UTF-8 string"))
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        let slice = &self.buf[self.pos..];
+        self.pos = self.buf.len();
+        slice
+    }
+
+    fn varint(&mut self) -> Result<usize, CodecError> {
+        let mut value = 0usize;
+        for shift in 0..4 {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7F) as usize) << (7 * shift);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(CodecError::Malformed("variable byte integer too long"))
+    }
+
+    /// Reads an MQTT 5 property block.
+    fn properties(&mut self) -> Result<Properties, CodecError> {
+        let len = self.varint()?;
+        let mut properties = Reader::new(self.bytes(len)?);
+        let mut parsed = Properties::default();
+        while properties.remaining() > 0 {
+            let id = properties.u8()?;
+            match id {
+                // Byte properties.
+                0x01 | 0x17 | 0x19 | 0x24 | 0x25 | 0x28 | 0x29 | 0x2A => {
+                    properties.u8()?;
+                }
+                // Two byte integers.
+                0x13 | 0x22 | 0x23 => {
+                    properties.u16()?;
+                }
+                PROPERTY_RECEIVE_MAXIMUM => {
+                    parsed.receive_maximum = Some(properties.u16()?);
+                }
+                // Four byte integers.
+                0x02 | 0x18 | 0x27 => {
+                    properties.bytes(4)?;
+                }
+                PROPERTY_SESSION_EXPIRY_INTERVAL => {
+                    let bytes = properties.bytes(4)?;
+                    parsed.session_expiry =
+                        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
+                }
+                // Variable byte integer.
+                0x0B => {
+                    properties.varint()?;
+                }
+                // UTF-8 strings and binary data share the same length-prefixed layout.
+                0x03 | 0x08 | 0x09 | 0x12 | 0x15 | 0x16 | 0x1A | 0x1C | 0x1F => {
+                    properties.binary()?;
+                }
+                // User property: string pair.
+                0x26 => {
+                    properties.binary()?;
+                    properties.binary()?;
+                }
+                _ => return Err(CodecError::Malformed("unknown property")),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let mut byte = (value % 128) as u8;
+        value /= 128;
+        if value > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+fn write_string(out: &mut Vec<u8>, value: &str) {
+    out.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    out.extend_from_slice(value.as_bytes());
+}
+
+fn frame(first_byte: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(body.len() + 5);
+    out.push(first_byte);
+    write_varint(&mut out, body.len());
+    out.extend_from_slice(body);
+    out
+}
+
+/// Attempts to decode one packet from the front of `buf`.
+///
+/// Returns the packet and the number of bytes consumed, or `None` when more data is needed.
+/// `protocol_level` is the level negotiated by CONNECT (ignored for the CONNECT packet itself).
+pub fn decode(
+    buf: &[u8],
+    protocol_level: u8,
+    max_packet_size: usize,
+) -> Result<Option<(Packet, usize)>, CodecError> {
+    if buf.len() < 2 {
+        return Ok(None);
+    }
+
+    let mut remaining_len = 0usize;
+    let mut header_len = 1;
+    loop {
+        let Some(&byte) = buf.get(header_len) else {
+            return Ok(None);
+        };
+        remaining_len |= ((byte & 0x7F) as usize) << (7 * (header_len - 1));
+        header_len += 1;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        if header_len > 4 {
+            return Err(CodecError::Malformed("remaining length too long"));
+        }
+    }
+
+    let total_len = header_len + remaining_len;
+    if total_len > max_packet_size {
+        return Err(CodecError::PacketTooLarge(total_len));
+    }
+    if buf.len() < total_len {
+        return Ok(None);
+    }
+
+    let first_byte = buf[0];
+    let flags = first_byte & 0x0F;
+    let mut body = Reader::new(&buf[header_len..total_len]);
+    let v5 = protocol_level >= PROTOCOL_V5;
+
+    let packet = match first_byte >> 4 {
+        CONNECT => Packet::Connect(decode_connect(&mut body)?),
+        PUBLISH => {
+            let qos = (flags >> 1) & 0x03;
+            if qos == 3 {
+                return Err(CodecError::Malformed("invalid QoS"));
+            }
+            let topic = body.string()?;
+            let packet_id = if qos > 0 { Some(body.u16()?) } else { None };
+            if v5 {
+                body.properties()?;
+            }
+            Packet::Publish(Publish {
+                topic,
+                qos,
+                retain: flags & 0x01 != 0,
+                dup: flags & 0x08 != 0,
+                packet_id,
+                payload: body.rest().to_vec(),
+            })
+        }
+        PUBACK => Packet::PubAck {
+            packet_id: body.u16()?,
+        },
+        SUBSCRIBE => {
+            let packet_id = body.u16()?;
+            if v5 {
+                body.properties()?;
+            }
+            let mut filters = Vec::new();
+            while body.remaining() > 0 {
+                let filter = body.string()?;
+                let options = body.u8()?;
+                filters.push((filter, options & 0x03));
+            }
+            if filters.is_empty() {
+                return Err(CodecError::Malformed("SUBSCRIBE without topic filters"));
+            }
+            Packet::Subscribe { packet_id, filters }
+        }
+        UNSUBSCRIBE => {
+            let packet_id = body.u16()?;
+            if v5 {
+                body.properties()?;
+            }
+            let mut filters = Vec::new();
+            while body.remaining() > 0 {
+                filters.push(body.string()?);
+            }
+            Packet::Unsubscribe { packet_id, filters }
+        }
+        PINGREQ => Packet::PingReq,
+        DISCONNECT => Packet::Disconnect,
+        // QoS 2 flows are never negotiated: CONNACK advertises a maximum QoS of 1.
+        other @ (PUBREC | PUBREL | PUBCOMP) => return Err(CodecError::UnsupportedPacket(other)),
+        other => return Err(CodecError::UnsupportedPacket(other)),
+    };
+
+    Ok(Some((packet, total_len)))
+}
+
+fn decode_connect(body: &mut Reader<'_>) -> Result<Connect, CodecError> {
+    let protocol_name = body.string()?;
+    let protocol_level = body.u8()?;
+    if protocol_name != "MQTT" || !(PROTOCOL_V311..=PROTOCOL_V5).contains(&protocol_level) {
+        return Err(CodecError::UnsupportedProtocol(protocol_level));
+    }
+    let v5 = protocol_level >= PROTOCOL_V5;
+
+    let connect_flags = body.u8()?;
+    let keep_alive = body.u16()?;
+    let properties = if v5 {
+        body.properties()?
+    } else {
+        Properties::default()
+    };
+
+    let client_id = body.string()?;
+    if connect_flags & 0x04 != 0 {
+        // Will messages are accepted but not published by the gateway.
+        if v5 {
+            body.properties()?;
+        }
+        body.string()?;
+        body.binary()?;
+    }
+    let username = if connect_flags & 0x80 != 0 {
+        Some(body.string()?)
+    } else {
+        None
+    };
+    let password = if connect_flags & 0x40 != 0 {
+        Some(body.binary()?)
+    } else {
+        None
+    };
+
+    Ok(Connect {
+        protocol_level,
+        clean_session: connect_flags & 0x02 != 0,
+        keep_alive,
+        client_id,
+        username,
+        password,
+        session_expiry: properties.session_expiry,
+        receive_maximum: properties.receive_maximum,
+    })
+}
+
+pub fn encode_connack(
+    protocol_level: u8,
+    session_present: bool,
+    code: ConnectReturnCode,
+) -> Vec<u8> {
+    let mut body = vec![
+        u8::from(session_present && code == ConnectReturnCode::Accepted),
+        code.code(protocol_level),
+    ];
+    if protocol_level >= PROTOCOL_V5 {
+        let properties = [
+            PROPERTY_MAXIMUM_QOS,
+            1,
+            PROPERTY_RETAIN_AVAILABLE,
+            0,
+            PROPERTY_SHARED_SUBSCRIPTION_AVAILABLE,
+            0,
+        ];
+        write_varint(&mut body, properties.len());
+        body.extend_from_slice(&properties);
+    }
+    frame(CONNACK << 4, &body)
+}
+
+pub fn encode_publish(protocol_level: u8, publish: &Publish) -> Vec<u8> {
+    let mut body = Vec::with_capacity(publish.topic.len() + publish.payload.len() + 5);
+    write_string(&mut body, &publish.topic);
+    if let Some(packet_id) = publish.packet_id {
+        body.extend_from_slice(&packet_id.to_be_bytes());
+    }
+    if protocol_level >= PROTOCOL_V5 {
+        body.push(0);
+    }
+    body.extend_from_slice(&publish.payload);
+    let first_byte = (PUBLISH << 4)
+        | (u8::from(publish.dup) << 3)
+        | (publish.qos << 1)
+        | u8::from(publish.retain);
+    frame(first_byte, &body)
+}
+
+/// Encodes a PUBACK. MQTT 5 clients also receive `reason` when it is not success.
+pub fn encode_puback(protocol_level: u8, packet_id: u16, reason: u8) -> Vec<u8> {
+    let mut body = packet_id.to_be_bytes().to_vec();
+    if protocol_level >= PROTOCOL_V5 && reason != 0 {
+        body.push(reason);
+    }
+    frame(PUBACK << 4, &body)
+}
+
+pub fn encode_suback(protocol_level: u8, packet_id: u16, return_codes: &[u8]) -> Vec<u8> {
+    let mut body = packet_id.to_be_bytes().to_vec();
+    if protocol_level >= PROTOCOL_V5 {
+        body.push(0);
+    }
+    body.extend_from_slice(return_codes);
+    frame(SUBACK << 4, &body)
+}
+
+pub fn encode_unsuback(protocol_level: u8, packet_id: u16, filter_count: usize) -> Vec<u8> {
+    let mut body = packet_id.to_be_bytes().to_vec();
+    if protocol_level >= PROTOCOL_V5 {
+        body.push(0);
+        body.extend(std::iter::repeat_n(0u8, filter_count));
+    }
+    frame(UNSUBACK << 4, &body)
+}
+
+pub fn encode_pingresp() -> Vec<u8> {
+    vec![PINGRESP << 4, 0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connect_packet(protocol_level: u8, flags: u8, tail: &[u8]) -> Vec<u8> {
+        let mut body = Vec::new();
+        write_string(&mut body, "MQTT");
+        body.push(protocol_level);
+        body.push(flags);
+        body.extend_from_slice(&30u16.to_be_bytes());
+        if protocol_level >= PROTOCOL_V5 {
+            body.extend_from_slice(&[
+                8,
+                PROPERTY_SESSION_EXPIRY_INTERVAL,
+                0,
+                0,
+                0,
+                60,
+                PROPERTY_RECEIVE_MAXIMUM,
+                0,
+                20,
+            ]);
+        }
+        body.extend_from_slice(tail);
+        frame(CONNECT << 4, &body)
+    }
+
+    #[test]
+    fn decodes_v311_connect_with_credentials() {
+        let mut tail = Vec::new();
+        write_string(&mut tail, "device-1");
+        write_string(&mut tail, "app-key");
+        write_string(&mut tail, "token");
+        let packet = connect_packet(PROTOCOL_V311, 0x80 | 0x40, &tail);
+
+        let (decoded, consumed) = decode(&packet, 0, 1024).unwrap().unwrap();
+        assert_eq!(consumed, packet.len());
+        let Packet::Connect(connect) = decoded else {
+            panic!("expected CONNECT");
+        };
+        assert_eq!(connect.client_id, "device-1");
+        assert_eq!(connect.username.as_deref(), Some("app-key"));
+        assert_eq!(connect.password.as_deref(), Some(b"token".as_slice()));
+        assert!(!connect.clean_session);
+        assert_eq!(connect.keep_alive, 30);
+    }
+
+    #[test]
+    fn decodes_v5_connect_properties() {
+        let mut tail = Vec::new();
+        write_string(&mut tail, "device-2");
+        let packet = connect_packet(PROTOCOL_V5, 0x02, &tail);
+
+        let (Packet::Connect(connect), _) = decode(&packet, 0, 1024).unwrap().unwrap() else {
+            panic!("expected CONNECT");
+        };
+        assert_eq!(connect.protocol_level, PROTOCOL_V5);
+        assert!(connect.clean_session);
+        assert_eq!(connect.session_expiry, Some(60));
+        assert_eq!(connect.receive_maximum, Some(20));
+    }
+
+    #[test]
+    fn waits_for_complete_packet() {
+        let publish = encode_publish(
+            PROTOCOL_V311,
+            &Publish {
+                topic: "a/b".to_string(),
+                qos: 1,
+                retain: false,
+                dup: false,
+                packet_id: Some(7),
+                payload: b"hello".to_vec(),
+            },
+        );
+        assert_eq!(decode(&publish[..publish.len() - 1], 4, 1024), Ok(None));
+
+        let (Packet::Publish(decoded), _) = decode(&publish, 4, 1024).unwrap().unwrap() else {
+            panic!("expected PUBLISH");
+        };
+        assert_eq!(decoded.topic, "a/b");
+        assert_eq!(decoded.packet_id, Some(7));
+        assert_eq!(decoded.payload, b"hello");
+    }
+
+    #[test]
+    fn rejects_oversized_packets_and_qos2_flow() {
+        let publish = encode_publish(
+            PROTOCOL_V311,
+            &Publish {
+                topic: "a".to_string(),
+                qos: 0,
+                retain: false,
+                dup: false,
+                packet_id: None,
+                payload: vec![0; 64],
+            },
+        );
+        assert!(matches!(
+            decode(&publish, 4, 16),
+            Err(CodecError::PacketTooLarge(_))
+        ));
+        assert_eq!(
+            decode(&[PUBREL << 4 | 0x02, 2, 0, 1], 4, 16),
+            Err(CodecError::UnsupportedPacket(PUBREL))
+        );
+    }
+
+    #[test]
+    fn decodes_v5_subscribe_options() {
+        let mut body = 9u16.to_be_bytes().to_vec();
+        body.push(0);
+        write_string(&mut body, "sensors/#");
+        body.push(0x01 | 0x04);
+        let packet = frame(SUBSCRIBE << 4 | 0x02, &body);
+
+        let (decoded, _) = decode(&packet, PROTOCOL_V5, 1024).unwrap().unwrap();
+        assert_eq!(
+            decoded,
+            Packet::Subscribe {
+                packet_id: 9,
+                filters: vec![("sensors/#".to_string(), 1)],
+            }
+        );
+    }
+
+    #[test]
+    fn connack_codes_follow_protocol_version() {
+        assert_eq!(
+            encode_connack(PROTOCOL_V311, true, ConnectReturnCode::NotAuthorized),
+            vec![0x20, 2, 0, 0x05]
+        );
+        let v5 = encode_connack(PROTOCOL_V5, false, ConnectReturnCode::Accepted);
+        assert_eq!(&v5[..4], &[0x20, 9, 0, 0]);
+    }
+}
@@ -0,0 +1,1182 @@
+//! MQTT gateway: serves MQTT 3.1.1 / 5 clients as regular Sockudo connections.
+//!
+//! Each MQTT connection is a channel-backed transport session, so quotas, capability tokens,
+//! cluster fan-out and presence behave exactly as for WebSocket clients. The username selects
+//! the app (by key) and the password, when present, is a V2 capability token.
+//!
+//! QoS 1 deliveries are acknowledged per channel position; a persistent session that reconnects
+//! resumes from the last acknowledged position through the V2 replay buffer, so messages missed
+//! while offline are redelivered (at least once).
+
+mod codec;
+mod topic;
+
+use crate::http_handler::{AppError, publish_server_event};
+use crate::sse_handler::{ChannelPosition, resume_message, subscribe_message};
+use codec::{CodecError, Connect, ConnectReturnCode, Packet, Publish};
+use dashmap::DashMap;
+use serde::Deserialize;
+use sockudo_adapter::ConnectionHandler;
+use sockudo_adapter::handler::connect_proxy::ConnectContext;
+use sockudo_adapter::handler::transport_session::{TransportSession, TransportSessionOptions};
+use sockudo_core::app::App;
+use sockudo_core::error::Error;
+use sockudo_core::options::MqttConfig;
+use sockudo_core::utils::wildcard_pattern_matches;
+use sockudo_core::websocket::{ConnectionCapabilities, SocketId};
+use sockudo_protocol::ProtocolVersion;
+use sockudo_protocol::messages::{ApiMessageData, PusherApiMessage, PusherMessage};
+use sockudo_protocol::protocol_version::CANONICAL_UNSUBSCRIBE;
+use sockudo_ws::Message;
+use sonic_rs::{JsonValueTrait, Value};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const READ_CHUNK_SIZE: usize = 4096;
+
+/// MQTT 5 PUBACK reason codes. MQTT 3.1.1 PUBACKs carry no reason.
+const PUBACK_NOT_AUTHORIZED: u8 = 0x87;
+const PUBACK_TOPIC_NAME_INVALID: u8 = 0x90;
+const PUBACK_PAYLOAD_FORMAT_INVALID: u8 = 0x99;
+const PUBACK_UNSPECIFIED_ERROR: u8 = 0x80;
+
+/// Maps a rejected publish onto a PUBACK reason: permission and client-event policy failures
+/// are "not authorized", anything else is unspecified.
+fn puback_reason(error: &Error) -> u8 {
+    match error {
+        Error::Unauthorized
+        | Error::Auth(_)
+        | Error::Forbidden(_)
+        | Error::Channel(_)
+        | Error::ClientEvent(_) => PUBACK_NOT_AUTHORIZED,
+        _ => PUBACK_UNSPECIFIED_ERROR,
+    }
+}
+
+/// Persistent sessions are keyed by app id and MQTT client id.
+type SessionKey = (String, String);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Subscription {
+    channel: String,
+    qos: u8,
+}
+
+#[derive(Debug, Default)]
+struct SessionState {
+    /// Topic filter -> mapped channel and granted QoS.
+    subscriptions: BTreeMap<String, Subscription>,
+    /// Last acknowledged position per channel, replayed from on reconnect.
+    positions: BTreeMap<String, ChannelPosition>,
+}
+
+struct SessionEntry {
+    state: Arc<Mutex<SessionState>>,
+    owner: Option<(SocketId, CancellationToken)>,
+    expires_at: Option<Instant>,
+}
+
+/// Accepts MQTT connections and keeps persistent session state across reconnects.
+pub struct MqttGateway {
+    handler: Arc<ConnectionHandler>,
+    config: MqttConfig,
+    sessions: DashMap<SessionKey, SessionEntry>,
+}
+
+impl MqttGateway {
+    pub fn new(handler: Arc<ConnectionHandler>, config: MqttConfig) -> Arc<Self> {
+        Arc::new(Self {
+            handler,
+            config,
+            sessions: DashMap::new(),
+        })
+    }
+
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    let _ = stream.set_nodelay(true);
+                    let gateway = self.clone();
+                    tokio::spawn(async move {
+                        debug!("MQTT client connected from {}", peer);
+                        gateway.handle_connection(stream, Some(peer)).await;
+                    });
+                }
+                Err(e) => {
+                    warn!("Failed to accept MQTT connection: {}", e);
+                }
+            }
+        }
+    }
+
+    fn max_packet_size(&self) -> usize {
+        self.config.max_packet_size_kb.saturating_mul(1024).max(64)
+    }
+
+    /// Serves one MQTT connection until either side closes it.
+    pub async fn handle_connection<S>(self: Arc<Self>, stream: S, peer: Option<SocketAddr>)
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let (mut reader, mut writer) = tokio::io::split(stream);
+        let mut buffer = Vec::with_capacity(READ_CHUNK_SIZE);
+
+        let connect = match tokio::time::timeout(
+            CONNECT_TIMEOUT,
+            self.read_connect(&mut reader, &mut buffer),
+        )
+        .await
+        {
+            Ok(Ok(connect)) => connect,
+            Ok(Err(Some(code))) => {
+                let _ = writer
+                    .write_all(&codec::encode_connack(codec::PROTOCOL_V311, false, code))
+                    .await;
+                return;
+            }
+            Ok(Err(None)) | Err(_) => return,
+        };
+        let protocol_level = connect.protocol_level;
+
+        let session = match self.open_session(&connect, peer).await {
+            Ok(session) => session,
+            Err(code) => {
+                let _ = writer
+                    .write_all(&codec::encode_connack(protocol_level, false, code))
+                    .await;
+                return;
+            }
+        };
+
+        let key: SessionKey = (session.app.id.clone(), connect.client_id.clone());
+        let shutdown = CancellationToken::new();
+        let (state, session_present) =
+            self.claim_session(&key, connect.clean_session, session.socket_id, &shutdown);
+
+        if writer
+            .write_all(&codec::encode_connack(
+                protocol_level,
+                session_present,
+                ConnectReturnCode::Accepted,
+            ))
+            .await
+            .is_err()
+        {
+            self.release_session(&key, session.socket_id, 0);
+            self.handler
+                .close_transport_session(&session.socket_id, &session.app)
+                .await;
+            return;
+        }
+        info!(
+            "MQTT client '{}' connected as socket {} (app {})",
+            connect.client_id, session.socket_id, session.app.id
+        );
+
+        let mut connection = MqttConnection {
+            gateway: self.clone(),
+            protocol_level,
+            socket_id: session.socket_id,
+            app: session.app,
+            state,
+            inflight: BTreeMap::new(),
+            max_inflight: self.max_inflight(&connect),
+            next_packet_id: 0,
+        };
+        if session_present {
+            connection.restore_subscriptions().await;
+        }
+
+        let keep_alive = (connect.keep_alive > 0)
+            .then(|| Duration::from_millis(u64::from(connect.keep_alive) * 1500));
+        connection
+            .run(
+                &mut reader,
+                &mut writer,
+                buffer,
+                session.receiver,
+                keep_alive,
+                shutdown,
+            )
+            .await;
+
+        let expiry = self.session_expiry(&connect);
+        self.release_session(&key, connection.socket_id, expiry);
+        self.handler
+            .close_transport_session(&connection.socket_id, &connection.app)
+            .await;
+        debug!("MQTT client '{}' disconnected", connect.client_id);
+    }
+
+    /// Reads the CONNECT packet. `Err(Some(code))` asks the caller to refuse the connection
+    /// with a CONNACK, `Err(None)` to drop it silently.
+    async fn read_connect<R>(
+        &self,
+        reader: &mut R,
+        buffer: &mut Vec<u8>,
+    ) -> Result<Connect, Option<ConnectReturnCode>>
+    where
+        R: AsyncRead + Unpin,
+    {
+        loop {
+            match codec::decode(buffer, 0, self.max_packet_size()) {
+                Ok(Some((Packet::Connect(connect), consumed))) => {
+                    buffer.drain(..consumed);
+                    return Ok(connect);
+                }
+                Ok(Some(_)) => return Err(None),
+                Ok(None) => {}
+                Err(CodecError::UnsupportedProtocol(_)) => {
+                    return Err(Some(ConnectReturnCode::UnsupportedProtocol));
+                }
+                Err(_) => return Err(None),
+            }
+            if read_more(reader, buffer).await.is_err() {
+                return Err(None);
+            }
+        }
+    }
+
+    async fn open_session(
+        &self,
+        connect: &Connect,
+        peer: Option<SocketAddr>,
+    ) -> Result<TransportSession, ConnectReturnCode> {
+        if !self.handler.is_accepting() {
+            return Err(ConnectReturnCode::ServerUnavailable);
+        }
+        let Some(app_key) = connect.username.as_deref() else {
+            return Err(ConnectReturnCode::BadCredentials);
+        };
+        // Same `policy.network.connections` rules the HTTP transports apply. Unknown apps are
+        // reported by the session open below.
+        if let Ok(Some(app)) = self.handler.app_manager().find_by_key(app_key).await
+            && !app.permits_connection_ip(peer.map(|addr| addr.ip()))
+        {
+            warn!(
+                "Rejected MQTT client '{}' for app {} from disallowed address {:?}",
+                connect.client_id, app.id, peer
+            );
+            return Err(ConnectReturnCode::NotAuthorized);
+        }
+        let token = match connect.password.as_deref() {
+            Some(password) => Some(
+                String::from_utf8(password.to_vec())
+                    .map_err(|_| ConnectReturnCode::BadCredentials)?,
+            ),
+            None => None,
+        };
+
+        self.handler
+            .open_transport_session(
+                app_key,
+                TransportSessionOptions {
+                    origin: None,
+                    protocol_version: ProtocolVersion::V2,
+                    echo_messages: true,
+                    token,
+                    connect_context: Some(ConnectContext {
+                        transport: "mqtt".to_string(),
+                        remote_addr: peer.map(|addr| addr.to_string()),
+                        ..Default::default()
+                    }),
+                },
+            )
+            .await
+            .map_err(|e| {
+                debug!("Rejected MQTT client '{}': {}", connect.client_id, e);
+                match e {
+                    Error::ApplicationNotFound | Error::Auth(_) => {
+                        ConnectReturnCode::BadCredentials
+                    }
+                    Error::ApplicationDisabled | Error::OriginNotAllowed => {
+                        ConnectReturnCode::NotAuthorized
+                    }
+                    Error::OverConnectionQuota => ConnectReturnCode::QuotaExceeded,
+                    _ => ConnectReturnCode::ServerUnavailable,
+                }
+            })
+    }
+
+    /// Attaches a connection to its session state, taking over any live connection with the
+    /// same client id. Returns the state and whether an existing session was resumed.
+    fn claim_session(
+        &self,
+        key: &SessionKey,
+        clean_session: bool,
+        socket_id: SocketId,
+        shutdown: &CancellationToken,
+    ) -> (Arc<Mutex<SessionState>>, bool) {
+        let now = Instant::now();
+        self.sessions
+            .retain(|_, entry| entry.expires_at.is_none_or(|expires_at| expires_at > now));
+
+        let mut entry = self
+            .sessions
+            .entry(key.clone())
+            .or_insert_with(|| SessionEntry {
+                state: Arc::new(Mutex::new(SessionState::default())),
+                owner: None,
+                expires_at: None,
+            });
+
+        if let Some((previous, token)) = entry.owner.take() {
+            debug!("MQTT client id taken over from socket {}", previous);
+            token.cancel();
+        }
+        entry.owner = Some((socket_id, shutdown.clone()));
+        entry.expires_at = None;
+
+        let session_present = !clean_session
+            && entry
+                .state
+                .lock()
+                .is_ok_and(|state| !state.subscriptions.is_empty() || !state.positions.is_empty());
+        if clean_session {
+            entry.state = Arc::new(Mutex::new(SessionState::default()));
+        }
+        (entry.state.clone(), session_present)
+    }
+
+    fn release_session(&self, key: &SessionKey, socket_id: SocketId, expiry_seconds: u64) {
+        let owned = self
+            .sessions
+            .get(key)
+            .is_some_and(|entry| entry.owner.as_ref().is_some_and(|(id, _)| *id == socket_id));
+        if !owned {
+            // Another connection has taken the session over.
+            return;
+        }
+        if expiry_seconds == 0 {
+            self.sessions.remove(key);
+        } else if let Some(mut entry) = self.sessions.get_mut(key) {
+            entry.owner = None;
+            entry.expires_at = Some(Instant::now() + Duration::from_secs(expiry_seconds));
+        }
+    }
+
+    /// QoS 1 deliveries allowed to await a PUBACK: the configured cap, lowered to the client's
+    /// MQTT 5 receive maximum when it asks for fewer.
+    fn max_inflight(&self, connect: &Connect) -> usize {
+        let client_limit = connect.receive_maximum.unwrap_or(u16::MAX);
+        usize::from(self.config.max_inflight_messages.min(client_limit).max(1))
+    }
+
+    fn session_expiry(&self, connect: &Connect) -> u64 {
+        if connect.protocol_level >= codec::PROTOCOL_V5 {
+            u64::from(connect.session_expiry.unwrap_or(0)).min(self.config.session_expiry_seconds)
+        } else if connect.clean_session {
+            0
+        } else {
+            self.config.session_expiry_seconds
+        }
+    }
+}
+
+async fn read_more<R>(reader: &mut R, buffer: &mut Vec<u8>) -> std::io::Result<()>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut chunk = [0u8; READ_CHUNK_SIZE];
+    let read = reader.read(&mut chunk).await?;
+    if read == 0 {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    buffer.extend_from_slice(&chunk[..read]);
+    Ok(())
+}
+
+/// Outbound frame fields the gateway needs to turn an event into a PUBLISH.
+#[derive(Debug, Deserialize)]
+struct OutboundFrame {
+    event: Option<String>,
+    channel: Option<String>,
+    data: Option<Value>,
+    serial: Option<u64>,
+    stream_id: Option<String>,
+    message_id: Option<String>,
+}
+
+struct Inflight {
+    channel: String,
+    position: Option<ChannelPosition>,
+}
+
+enum Flow {
+    Continue,
+    Close,
+}
+
+struct MqttConnection {
+    gateway: Arc<MqttGateway>,
+    protocol_level: u8,
+    socket_id: SocketId,
+    app: App,
+    state: Arc<Mutex<SessionState>>,
+    inflight: BTreeMap<u16, Inflight>,
+    max_inflight: usize,
+    next_packet_id: u16,
+}
+
+impl MqttConnection {
+    fn separator(&self) -> &str {
+        &self.gateway.config.topic_separator
+    }
+
+    fn handler(&self) -> &Arc<ConnectionHandler> {
+        &self.gateway.handler
+    }
+
+    async fn run<R, W>(
+        &mut self,
+        reader: &mut R,
+        writer: &mut W,
+        mut buffer: Vec<u8>,
+        mut receiver: mpsc::Receiver<Message>,
+        keep_alive: Option<Duration>,
+        shutdown: CancellationToken,
+    ) where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut chunk = vec![0u8; READ_CHUNK_SIZE];
+        let mut deadline = keep_alive.map(|timeout| tokio::time::Instant::now() + timeout);
+
+        loop {
+            // Drain complete packets before waiting for more input.
+            loop {
+                match codec::decode(&buffer, self.protocol_level, self.gateway.max_packet_size()) {
+                    Ok(Some((packet, consumed))) => {
+                        buffer.drain(..consumed);
+                        match self.handle_packet(packet, writer).await {
+                            Ok(Flow::Continue) => {}
+                            Ok(Flow::Close) | Err(_) => return,
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        debug!("Closing MQTT socket {}: {}", self.socket_id, e);
+                        return;
+                    }
+                }
+            }
+
+            let keep_alive_expired = async {
+                match deadline {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => std::future::pending().await,
+                }
+            };
+
+            tokio::select! {
+                read = reader.read(&mut chunk) => match read {
+                    Ok(0) | Err(_) => return,
+                    Ok(read) => {
+                        buffer.extend_from_slice(&chunk[..read]);
+                        deadline = keep_alive.map(|timeout| tokio::time::Instant::now() + timeout);
+                    }
+                },
+                frame = receiver.recv() => match frame {
+                    Some(Message::Text(bytes)) => {
+                        if self.deliver(&bytes, writer).await.is_err() {
+                            return;
+                        }
+                    }
+                    Some(Message::Close(_)) | None => return,
+                    Some(_) => {}
+                },
+                _ = keep_alive_expired => {
+                    debug!("MQTT socket {} missed its keep-alive", self.socket_id);
+                    return;
+                }
+                _ = shutdown.cancelled() => return,
+            }
+        }
+    }
+
+    async fn handle_packet<W>(&mut self, packet: Packet, writer: &mut W) -> std::io::Result<Flow>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        match packet {
+            Packet::Connect(_) => return Ok(Flow::Close),
+            Packet::Disconnect => return Ok(Flow::Close),
+            Packet::PingReq => writer.write_all(&codec::encode_pingresp()).await?,
+            Packet::PubAck { packet_id } => self.acknowledge(packet_id),
+            Packet::Subscribe { packet_id, filters } => {
+                let mut return_codes = Vec::with_capacity(filters.len());
+                for (filter, qos) in filters {
+                    return_codes.push(self.subscribe(filter, qos).await);
+                }
+                writer
+                    .write_all(&codec::encode_suback(
+                        self.protocol_level,
+                        packet_id,
+                        &return_codes,
+                    ))
+                    .await?;
+            }
+            Packet::Unsubscribe { packet_id, filters } => {
+                for filter in &filters {
+                    self.unsubscribe(filter).await;
+                }
+                writer
+                    .write_all(&codec::encode_unsuback(
+                        self.protocol_level,
+                        packet_id,
+                        filters.len(),
+                    ))
+                    .await?;
+            }
+            Packet::Publish(publish) => {
+                if publish.qos > 1 {
+                    debug!("MQTT socket {} sent a QoS 2 publish", self.socket_id);
+                    return Ok(Flow::Close);
+                }
+                let reason = self.publish(&publish).await;
+                if let Some(packet_id) = publish.packet_id {
+                    writer
+                        .write_all(&codec::encode_puback(
+                            self.protocol_level,
+                            packet_id,
+                            reason,
+                        ))
+                        .await?;
+                }
+            }
+        }
+        Ok(Flow::Continue)
+    }
+
+    /// Runs one request through the handler. Non-fatal failures come back as errors so the
+    /// caller can report them in the MQTT acknowledgement.
+    async fn send_to_handler(&self, message: &PusherMessage) -> Result<bool, Error> {
+        self.handler()
+            .handle_transport_request(&self.socket_id, &self.app, message)
+            .await
+    }
+
+    async fn send_best_effort(&self, message: &PusherMessage) {
+        if let Err(e) = self.send_to_handler(message).await {
+            debug!("MQTT socket {} request failed: {}", self.socket_id, e);
+        }
+    }
+
+    /// Subscribes to the channel a filter maps to and returns the SUBACK code.
+    async fn subscribe(&mut self, filter: String, qos: u8) -> u8 {
+        let Some(channel) = topic::filter_to_channel(
+            &filter,
+            self.separator(),
+            sockudo_core::utils::wildcard_segment_delimiters(),
+        ) else {
+            return codec::SUBACK_FAILURE;
+        };
+        if !self.join_channel(&channel).await {
+            return codec::SUBACK_FAILURE;
+        }
+
+        let qos = qos.min(1);
+        if let Ok(mut state) = self.state.lock() {
+            state
+                .subscriptions
+                .insert(filter, Subscription { channel, qos });
+        }
+        qos
+    }
+
+    async fn join_channel(&self, channel: &str) -> bool {
+        let Ok(message) = subscribe_message(channel, None) else {
+            return false;
+        };
+        self.send_best_effort(&message).await;
+        self.handler()
+            .connection_manager()
+            .is_in_channel(&self.app.id, channel, &self.socket_id)
+            .await
+            .unwrap_or(false)
+    }
+
+    async fn unsubscribe(&mut self, filter: &str) {
+        let removed = self
+            .state
+            .lock()
+            .ok()
+            .and_then(|mut state| state.subscriptions.remove(filter));
+        let Some(subscription) = removed else {
+            return;
+        };
+        let still_used = self.state.lock().is_ok_and(|state| {
+            state
+                .subscriptions
+                .values()
+                .any(|other| other.channel == subscription.channel)
+        });
+        if still_used {
+            return;
+        }
+
+        let message = sonic_rs::json!({
+            "event": ProtocolVersion::V2.wire_event(CANONICAL_UNSUBSCRIBE),
+            "data": { "channel": &subscription.channel },
+        });
+        if let Ok(message) = sonic_rs::from_value::<PusherMessage>(&message) {
+            self.send_best_effort(&message).await;
+        }
+    }
+
+    /// Re-joins the channels of a resumed session and replays what was missed since the last
+    /// acknowledged positions.
+    async fn restore_subscriptions(&mut self) {
+        let (channels, positions) = match self.state.lock() {
+            Ok(state) => {
+                let mut channels: Vec<String> = state
+                    .subscriptions
+                    .values()
+                    .map(|subscription| subscription.channel.clone())
+                    .collect();
+                channels.sort();
+                channels.dedup();
+                (channels, state.positions.clone())
+            }
+            Err(_) => return,
+        };
+
+        for channel in &channels {
+            if !self.join_channel(channel).await {
+                warn!(
+                    "MQTT socket {} could not restore subscription to {}",
+                    self.socket_id, channel
+                );
+            }
+        }
+
+        let positions: BTreeMap<String, ChannelPosition> = positions
+            .into_iter()
+            .filter(|(channel, _)| {
+                channels
+                    .iter()
+                    .any(|pattern| wildcard_pattern_matches(channel, pattern))
+            })
+            .collect();
+        if !positions.is_empty()
+            && let Ok(message) = resume_message(&positions)
+        {
+            self.send_best_effort(&message).await;
+        }
+    }
+
+    /// Handles an inbound PUBLISH and returns the MQTT 5 PUBACK reason code.
+    ///
+    /// Connections whose capability token grants `publish` on the channel publish a server
+    /// event; everyone else sends a client event, which goes through the usual client-event
+    /// checks.
+    async fn publish(&self, publish: &Publish) -> u8 {
+        let Some(channel) = topic::topic_to_channel(&publish.topic, self.separator()) else {
+            return PUBACK_TOPIC_NAME_INVALID;
+        };
+        let Ok(payload) = String::from_utf8(publish.payload.clone()) else {
+            return PUBACK_PAYLOAD_FORMAT_INVALID;
+        };
+
+        let capabilities = match self
+            .handler()
+            .connection_manager()
+            .get_connection(&self.socket_id, &self.app.id)
+            .await
+        {
+            Some(connection) => connection.get_connection_capabilities().await,
+            None => return PUBACK_UNSPECIFIED_ERROR,
+        };
+
+        if grants_server_publish(capabilities.as_ref(), &channel) {
+            let event = PusherApiMessage {
+                name: Some(self.gateway.config.publish_event.clone()),
+                data: Some(ApiMessageData::String(payload)),
+                channel: Some(channel),
+                channels: None,
+                socket_id: None,
+                info: None,
+                tags: None,
+                delta: None,
+                idempotency_key: None,
+                message_id: None,
+                extras: None,
+            };
+            return match publish_server_event(self.handler(), &self.app, event).await {
+                Ok(_) => 0,
+                Err(AppError::Forbidden(reason)) => {
+                    debug!("MQTT publish from {} refused: {}", self.socket_id, reason);
+                    PUBACK_NOT_AUTHORIZED
+                }
+                Err(e) => {
+                    debug!("MQTT publish from {} failed: {}", self.socket_id, e);
+                    PUBACK_UNSPECIFIED_ERROR
+                }
+            };
+        }
+
+        let message = sonic_rs::json!({
+            "event": format!("client-{}", self.gateway.config.publish_event),
+            "channel": channel,
+            "data": payload,
+        });
+        let Ok(message) = sonic_rs::from_value::<PusherMessage>(&message) else {
+            return PUBACK_UNSPECIFIED_ERROR;
+        };
+        match self.send_to_handler(&message).await {
+            Ok(_) => 0,
+            Err(e) => {
+                debug!("MQTT client event from {} rejected: {}", self.socket_id, e);
+                puback_reason(&e)
+            }
+        }
+    }
+
+    /// Turns an outbound event frame into a PUBLISH for the matching subscription.
+    async fn deliver<W>(&mut self, frame: &[u8], writer: &mut W) -> std::io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let Ok(frame) = sonic_rs::from_slice::<OutboundFrame>(frame) else {
+            return Ok(());
+        };
+        let (Some(event), Some(channel)) = (frame.event, frame.channel) else {
+            return Ok(());
+        };
+        if ProtocolVersion::parse_any_protocol_event(&event).is_some() {
+            return Ok(());
+        }
+
+        let Some(qos) = self.granted_qos(&channel) else {
+            return Ok(());
+        };
+        let position = frame.serial.map(|serial| ChannelPosition {
+            serial,
+            stream_id: frame.stream_id,
+            last_message_id: frame.message_id,
+        });
+        let payload = match frame.data {
+            Some(data) => match data.as_str() {
+                Some(text) => text.as_bytes().to_vec(),
+                None => data.to_string().into_bytes(),
+            },
+            None => Vec::new(),
+        };
+
+        let packet_id = if qos > 0 {
+            self.allocate_packet_id()
+        } else {
+            None
+        };
+        match packet_id {
+            Some(packet_id) => {
+                self.inflight.insert(
+                    packet_id,
+                    Inflight {
+                        channel: channel.clone(),
+                        position,
+                    },
+                );
+            }
+            // A client that stopped acknowledging gets QoS 0 until it catches up. The position
+            // is left alone, so a resume still replays from the oldest unacknowledged message.
+            None if qos > 0 => {
+                debug!(
+                    "MQTT client {} has {} unacknowledged deliveries; sending at QoS 0",
+                    self.socket_id,
+                    self.inflight.len()
+                );
+            }
+            None => {
+                if let Some(position) = position {
+                    self.record_position(channel.clone(), position);
+                }
+            }
+        }
+        let qos = if packet_id.is_some() { qos } else { 0 };
+
+        writer
+            .write_all(&codec::encode_publish(
+                self.protocol_level,
+                &Publish {
+                    topic: topic::channel_to_topic(&channel, self.separator()),
+                    qos,
+                    retain: false,
+                    dup: false,
+                    packet_id,
+                    payload,
+                },
+            ))
+            .await
+    }
+
+    fn granted_qos(&self, channel: &str) -> Option<u8> {
+        let state = self.state.lock().ok()?;
+        state
+            .subscriptions
+            .values()
+            .filter(|subscription| {
+                subscription.channel == channel
+                    || wildcard_pattern_matches(channel, &subscription.channel)
+            })
+            .map(|subscription| subscription.qos)
+            .max()
+    }
+
+    /// Picks a free packet id, or `None` once `max_inflight` deliveries await a PUBACK.
+    fn allocate_packet_id(&mut self) -> Option<u16> {
+        if self.inflight.len() >= self.max_inflight {
+            return None;
+        }
+        for _ in 0..u16::MAX {
+            self.next_packet_id = self.next_packet_id.wrapping_add(1).max(1);
+            if !self.inflight.contains_key(&self.next_packet_id) {
+                return Some(self.next_packet_id);
+            }
+        }
+        None
+    }
+
+    /// Records a PUBACK. A channel position only advances once every earlier delivery on that
+    /// channel has been acknowledged, so a resume never skips an unacknowledged message.
+    fn acknowledge(&mut self, packet_id: u16) {
+        let Some(acked) = self.inflight.remove(&packet_id) else {
+            return;
+        };
+        let Some(position) = acked.position else {
+            return;
+        };
+        let earlier_pending = self.inflight.values().any(|inflight| {
+            inflight.channel == acked.channel
+                && inflight
+                    .position
+                    .as_ref()
+                    .is_some_and(|pending| pending.serial < position.serial)
+        });
+        if !earlier_pending {
+            self.record_position(acked.channel, position);
+        }
+    }
+
+    fn record_position(&self, channel: String, position: ChannelPosition) {
+        if let Ok(mut state) = self.state.lock() {
+            let advance = state.positions.get(&channel).is_none_or(|current| {
+                current.stream_id != position.stream_id || current.serial < position.serial
+            });
+            if advance {
+                state.positions.insert(channel, position);
+            }
+        }
+    }
+}
+
+fn grants_server_publish(capabilities: Option<&ConnectionCapabilities>, channel: &str) -> bool {
+    capabilities
+        .and_then(|capabilities| capabilities.publish.as_deref())
+        .is_some_and(|patterns| ConnectionCapabilities::matches_any(patterns, channel))
+}
+
+/// Binds the MQTT listener and serves it in the background.
+pub async fn spawn_gateway(handler: Arc<ConnectionHandler>, config: MqttConfig) {
+    let addr =
+        sockudo_core::utils::resolve_socket_addr(&config.host, config.port, "MQTT gateway").await;
+    match TcpListener::bind(addr).await {
+        Ok(listener) => {
+            info!("MQTT gateway listening on {}", addr);
+            let gateway = MqttGateway::new(handler, config);
+            tokio::spawn(gateway.serve(listener));
+        }
+        Err(e) => {
+            warn!(
+                "Failed to bind MQTT gateway on {}: {}. MQTT clients will not be able to connect.",
+                addr, e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_handler::test_support::{test_app, test_realtime_handler_harness};
+    use sockudo_core::app::{AppIpRules, AppManager, AppNetworkPolicy};
+    use tokio::io::DuplexStream;
+
+    fn mqtt_string(out: &mut Vec<u8>, value: &str) {
+        out.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        out.extend_from_slice(value.as_bytes());
+    }
+
+    fn packet(first_byte: u8, body: Vec<u8>) -> Vec<u8> {
+        assert!(body.len() < 128);
+        let mut out = vec![first_byte, body.len() as u8];
+        out.extend(body);
+        out
+    }
+
+    fn connect_packet(client_id: &str, app_key: &str, clean_session: bool) -> Vec<u8> {
+        let mut body = Vec::new();
+        mqtt_string(&mut body, "MQTT");
+        body.push(codec::PROTOCOL_V311);
+        body.push(0x80 | if clean_session { 0x02 } else { 0 });
+        body.extend_from_slice(&60u16.to_be_bytes());
+        mqtt_string(&mut body, client_id);
+        mqtt_string(&mut body, app_key);
+        packet(0x10, body)
+    }
+
+    fn subscribe_packet(packet_id: u16, filter: &str, qos: u8) -> Vec<u8> {
+        let mut body = packet_id.to_be_bytes().to_vec();
+        mqtt_string(&mut body, filter);
+        body.push(qos);
+        packet(0x82, body)
+    }
+
+    async fn read_packet(stream: &mut DuplexStream) -> (u8, Vec<u8>) {
+        tokio::time::timeout(Duration::from_secs(2), async {
+            let first_byte = stream.read_u8().await.unwrap();
+            let mut len = 0usize;
+            let mut shift = 0;
+            loop {
+                let byte = stream.read_u8().await.unwrap();
+                len |= ((byte & 0x7F) as usize) << shift;
+                shift += 7;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            let mut body = vec![0u8; len];
+            stream.read_exact(&mut body).await.unwrap();
+            (first_byte, body)
+        })
+        .await
+        .expect("MQTT packet timed out")
+    }
+
+    async fn connect_client(
+        gateway: &Arc<MqttGateway>,
+        client_id: &str,
+        app_key: &str,
+        clean_session: bool,
+    ) -> (DuplexStream, Vec<u8>) {
+        let (mut client, server) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(gateway.clone().handle_connection(server, None));
+        client
+            .write_all(&connect_packet(client_id, app_key, clean_session))
+            .await
+            .unwrap();
+        let (first_byte, body) = read_packet(&mut client).await;
+        assert_eq!(first_byte, 0x20);
+        (client, body)
+    }
+
+    fn publish_event(channel: &str, data: &str) -> PusherApiMessage {
+        PusherApiMessage {
+            name: Some("reading".to_string()),
+            data: Some(ApiMessageData::String(data.to_string())),
+            channel: Some(channel.to_string()),
+            channels: None,
+            socket_id: None,
+            info: None,
+            tags: None,
+            delta: None,
+            idempotency_key: None,
+            message_id: None,
+            extras: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_app_is_refused_with_bad_credentials() {
+        let (handler, _) = test_realtime_handler_harness();
+        let gateway = MqttGateway::new(handler, MqttConfig::default());
+
+        let (_client, connack) = connect_client(&gateway, "device-1", "missing", true).await;
+        assert_eq!(connack, vec![0, 0x04]);
+    }
+
+    #[tokio::test]
+    async fn network_rules_refuse_disallowed_clients() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let mut app = test_app();
+        app.policy.network = Some(AppNetworkPolicy {
+            connections: Some(AppIpRules {
+                allow: vec!["10.0.0.0/8".to_string()],
+                deny: Vec::new(),
+            }),
+            api: None,
+        });
+        app_manager.create_app(app.clone()).await.unwrap();
+        let gateway = MqttGateway::new(handler, MqttConfig::default());
+
+        let (_client, connack) = connect_client(&gateway, "device-1", &app.key, true).await;
+        assert_eq!(connack, vec![0, 0x05]);
+    }
+
+    #[tokio::test]
+    async fn wildcard_subscription_receives_channel_events_as_qos1_publishes() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = test_app();
+        app_manager.create_app(app.clone()).await.unwrap();
+        let gateway = MqttGateway::new(handler.clone(), MqttConfig::default());
+
+        let (mut client, connack) = connect_client(&gateway, "device-1", &app.key, true).await;
+        assert_eq!(connack, vec![0, 0]);
+
+        client
+            .write_all(&subscribe_packet(1, "sensors/#", 1))
+            .await
+            .unwrap();
+        let (first_byte, suback) = read_packet(&mut client).await;
+        assert_eq!(first_byte, 0x90);
+        assert_eq!(suback, vec![0, 1, 1]);
+
+        publish_server_event(&handler, &app, publish_event("sensors.room-1", "21.5"))
+            .await
+            .unwrap();
+
+        let (first_byte, body) = read_packet(&mut client).await;
+        assert_eq!(first_byte, 0x32, "QoS 1 PUBLISH");
+        let topic_len = u16::from_be_bytes([body[0], body[1]]) as usize;
+        assert_eq!(&body[2..2 + topic_len], b"sensors/room-1");
+        assert_eq!(&body[2 + topic_len + 2..], b"21.5");
+    }
+
+    #[tokio::test]
+    async fn unacknowledged_deliveries_fall_back_to_qos0_at_the_inflight_cap() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = test_app();
+        app_manager.create_app(app.clone()).await.unwrap();
+        let gateway = MqttGateway::new(
+            handler.clone(),
+            MqttConfig {
+                max_inflight_messages: 2,
+                ..Default::default()
+            },
+        );
+
+        let (mut client, _) = connect_client(&gateway, "device-1", &app.key, true).await;
+        client
+            .write_all(&subscribe_packet(1, "sensors/#", 1))
+            .await
+            .unwrap();
+        read_packet(&mut client).await;
+
+        let mut first_bytes = Vec::new();
+        for reading in ["1", "2", "3"] {
+            publish_server_event(&handler, &app, publish_event("sensors.room-1", reading))
+                .await
+                .unwrap();
+            first_bytes.push(read_packet(&mut client).await.0);
+        }
+        assert_eq!(first_bytes, vec![0x32, 0x32, 0x30]);
+
+        client.write_all(&[0x40, 2, 0, 1]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        publish_server_event(&handler, &app, publish_event("sensors.room-1", "4"))
+            .await
+            .unwrap();
+        assert_eq!(
+            read_packet(&mut client).await.0,
+            0x32,
+            "QoS 1 after a PUBACK"
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_filter_is_refused_in_suback() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = test_app();
+        app_manager.create_app(app.clone()).await.unwrap();
+        let gateway = MqttGateway::new(handler, MqttConfig::default());
+
+        let (mut client, _) = connect_client(&gateway, "device-1", &app.key, true).await;
+        client
+            .write_all(&subscribe_packet(2, "a/#/b", 0))
+            .await
+            .unwrap();
+        let (_, suback) = read_packet(&mut client).await;
+        assert_eq!(suback, vec![0, 2, codec::SUBACK_FAILURE]);
+    }
+
+    #[tokio::test]
+    async fn persistent_session_restores_subscriptions_on_reconnect() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = test_app();
+        app_manager.create_app(app.clone()).await.unwrap();
+        let gateway = MqttGateway::new(handler.clone(), MqttConfig::default());
+
+        let (mut client, connack) = connect_client(&gateway, "device-7", &app.key, false).await;
+        assert_eq!(connack, vec![0, 0]);
+        client
+            .write_all(&subscribe_packet(1, "alerts", 0))
+            .await
+            .unwrap();
+        read_packet(&mut client).await;
+        client.write_all(&[0xE0, 0]).await.unwrap();
+        drop(client);
+
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while handler
+                .connection_manager()
+                .get_channel_socket_count(&app.id, "alerts")
+                .await
+                > 0
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let (_client, connack) = connect_client(&gateway, "device-7", &app.key, false).await;
+        assert_eq!(connack, vec![1, 0], "session present");
+        assert_eq!(
+            handler
+                .connection_manager()
+                .get_channel_socket_count(&app.id, "alerts")
+                .await,
+            1
+        );
+    }
+
+    #[test]
+    fn rejected_publishes_map_to_puback_reasons() {
+        assert_eq!(
+            puback_reason(&Error::ClientEvent("disabled".to_string())),
+            PUBACK_NOT_AUTHORIZED
+        );
+        assert_eq!(
+            puback_reason(&Error::Auth("not subscribed".to_string())),
+            PUBACK_NOT_AUTHORIZED
+        );
+        assert_eq!(
+            puback_reason(&Error::Internal("boom".to_string())),
+            PUBACK_UNSPECIFIED_ERROR
+        );
+    }
+
+    #[test]
+    fn server_publish_requires_explicit_publish_capability() {
+        assert!(!grants_server_publish(None, "news"));
+        assert!(!grants_server_publish(
+            Some(&ConnectionCapabilities::default()),
+            "news"
+        ));
+        let capabilities = ConnectionCapabilities {
+            publish: Some(vec!["sensors.*".to_string()]),
+            ..Default::default()
+        };
+        assert!(grants_server_publish(Some(&capabilities), "sensors.room-1"));
+        assert!(!grants_server_publish(Some(&capabilities), "alerts"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Translation between MQTT topics and Sockudo channel names.
+//!
+//! Topic levels are joined with the configured separator (`sensors/room-1` becomes
+//! `sensors.room-1` with the default `.`). When the separator is one of the configured
+//! `wildcard_subscriptions.segment_delimiters`, filters map onto segment wildcards: `+` becomes
+//! `*` (exactly one level) and a trailing `#` becomes `>` (one or more levels), and a filter may
+//! use any number of `+`. Otherwise they map onto Sockudo's single character-level `*`, so `+`
+//! and `#` match across levels and a filter may use at most one of them.
+//!
+//! Unlike MQTT, a trailing `#` does not match the parent level itself: `sensors/#` receives
+//! `sensors/room-1` but not `sensors`.
+
+const MULTI_LEVEL: &str = "#";
+const SINGLE_LEVEL: &str = "+";
+const SEGMENT_TAIL: &str = ">";
+
+/// Maps a PUBLISH topic name to a channel. Topic names may not contain wildcards.
+pub fn topic_to_channel(topic: &str, separator: &str) -> Option<String> {
+    if topic.is_empty() || topic.starts_with('$') || topic.contains(['#', '+', '*']) {
+        return None;
+    }
+    Some(topic.split('/').collect::<Vec<_>>().join(separator))
+}
+
+/// Maps a SUBSCRIBE topic filter to a (possibly wildcard) channel. `delimiters` are the
+/// server's wildcard segment delimiters; segment wildcards are used when they include
+/// `separator`.
+pub fn filter_to_channel(filter: &str, separator: &str, delimiters: &[char]) -> Option<String> {
+    if filter.is_empty() || filter.starts_with('$') || filter.contains(['*', '>']) {
+        return None;
+    }
+
+    let segmented = is_segment_separator(separator, delimiters);
+    let levels: Vec<&str> = filter.split('/').collect();
+    let mut wildcards = 0;
+    let mut mapped = Vec::with_capacity(levels.len());
+    for (index, level) in levels.iter().enumerate() {
+        match *level {
+            MULTI_LEVEL if index + 1 == levels.len() => {
+                wildcards += 1;
+                mapped.push(if segmented { SEGMENT_TAIL } else { "*" });
+            }
+            SINGLE_LEVEL => {
+                wildcards += 1;
+                mapped.push("*");
+            }
+            level if level.contains(['#', '+']) => return None,
+            level => mapped.push(level),
+        }
+    }
+
+    if !segmented && wildcards > 1 {
+        return None;
+    }
+    Some(mapped.join(separator))
+}
+
+fn is_segment_separator(separator: &str, delimiters: &[char]) -> bool {
+    let mut chars = separator.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if delimiters.contains(&c))
+}
+
+/// Maps a channel back to the topic name used for outbound PUBLISH packets.
+pub fn channel_to_topic(channel: &str, separator: &str) -> String {
+    if separator.is_empty() {
+        return channel.to_string();
+    }
+    channel.replace(separator, "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_round_trip_through_separator() {
+        let channel = topic_to_channel("sensors/room-1/temp", ".").unwrap();
+        assert_eq!(channel, "sensors.room-1.temp");
+        assert_eq!(channel_to_topic(&channel, "."), "sensors/room-1/temp");
+        assert_eq!(topic_to_channel("sensors/+", "."), None);
+        assert_eq!(topic_to_channel("$SYS/uptime", "."), None);
+    }
+
+    #[test]
+    fn filters_map_to_single_wildcard_channels() {
+        assert_eq!(
+            filter_to_channel("sensors/#", ".", &[]).as_deref(),
+            Some("sensors.*")
+        );
+        assert_eq!(
+            filter_to_channel("sensors/+/temp", ".", &[]).as_deref(),
+            Some("sensors.*.temp")
+        );
+        assert_eq!(filter_to_channel("#", ".", &[]).as_deref(), Some("*"));
+        assert_eq!(filter_to_channel("a/b", "-", &[]).as_deref(), Some("a-b"));
+        assert_eq!(
+            filter_to_channel("sensors/#", ".", &[':']).as_deref(),
+            Some("sensors.*")
+        );
+    }
+
+    #[test]
+    fn filters_map_to_segment_wildcards_when_separator_is_a_delimiter() {
+        assert_eq!(
+            filter_to_channel("sensors/#", ".", &['.']).as_deref(),
+            Some("sensors.>")
+        );
+        assert_eq!(
+            filter_to_channel("a/+/b/#", ".", &['.', ':']).as_deref(),
+            Some("a.*.b.>")
+        );
+        assert_eq!(
+            filter_to_channel("+/+", ":", &[':']).as_deref(),
+            Some("*:*")
+        );
+        assert_eq!(filter_to_channel("#", ".", &['.']).as_deref(), Some(">"));
+        assert_eq!(filter_to_channel("sensors/#/temp", ".", &['.']), None);
+        assert_eq!(filter_to_channel("sensors/>", ".", &['.']), None);
+    }
+
+    #[test]
+    fn unsupported_filters_are_rejected() {
+        assert_eq!(filter_to_channel("sensors/#/temp", ".", &[]), None);
+        assert_eq!(filter_to_channel("+/+", ".", &[]), None);
+        assert_eq!(filter_to_channel("sensors/ro+om", ".", &[]), None);
+        assert_eq!(filter_to_channel("$share/group/a", ".", &[]), None);
+    }
+}
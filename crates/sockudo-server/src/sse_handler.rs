@@ -0,0 +1,437 @@
+use crate::ws_handler::connect_context;
+use sockudo_adapter::ConnectionHandler;
+use sockudo_adapter::handler::transport_session::{TransportSession, TransportSessionOptions};
+
+use axum::Extension;
+use axum::extract::{ConnectInfo, Path, Query, RawQuery, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use sockudo_core::app::App;
+use sockudo_core::error::Error;
+use sockudo_core::websocket::SocketId;
+use sockudo_protocol::ProtocolVersion;
+use sockudo_protocol::messages::PusherMessage;
+use sockudo_ws::Message;
+use sonic_rs::Value;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+#[derive(Debug, Deserialize)]
+pub struct SseQuery {
+    /// Comma-separated list of channels to subscribe to.
+    channels: Option<String>,
+    /// Tag filter (JSON) applied to every requested channel.
+    filter: Option<String>,
+    /// V2 capability-token authentication.
+    token: Option<String>,
+    /// Recovery position for clients that cannot set the `Last-Event-ID` header.
+    last_event_id: Option<String>,
+}
+
+/// Last delivered position of a channel. The SSE event id carries the positions of every
+/// channel seen on the stream, so `Last-Event-ID` maps directly onto `channel_positions`
+/// in a resume request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ChannelPosition {
+    pub(crate) serial: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) stream_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) last_message_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FramePosition {
+    channel: Option<String>,
+    serial: Option<u64>,
+    stream_id: Option<String>,
+    message_id: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct PositionTracker {
+    positions: BTreeMap<String, ChannelPosition>,
+}
+
+impl PositionTracker {
+    fn from_last_event_id(last_event_id: Option<&str>) -> Self {
+        let positions = last_event_id
+            .and_then(|id| sonic_rs::from_str(id).ok())
+            .unwrap_or_default();
+        Self { positions }
+    }
+
+    /// Records the position carried by an outbound frame and returns the new event id.
+    fn observe(&mut self, frame: &str) -> Option<String> {
+        let position: FramePosition = sonic_rs::from_str(frame).ok()?;
+        let channel = position.channel?;
+        let serial = position.serial?;
+        self.positions.insert(
+            channel,
+            ChannelPosition {
+                serial,
+                stream_id: position.stream_id,
+                last_message_id: position.message_id,
+            },
+        );
+        sonic_rs::to_string(&self.positions).ok()
+    }
+}
+
+fn parse_channels(raw: Option<&str>) -> Vec<String> {
+    let mut channels: Vec<String> = Vec::new();
+    for channel in raw.unwrap_or("").split(',') {
+        let channel = channel.trim();
+        if !channel.is_empty() && !channels.iter().any(|c| c == channel) {
+            channels.push(channel.to_string());
+        }
+    }
+    channels
+}
+
+pub(crate) fn subscribe_message(
+    channel: &str,
+    filter: Option<&Value>,
+) -> Result<PusherMessage, Error> {
+    let data = match filter {
+        Some(filter) => sonic_rs::json!({ "channel": channel, "filter": filter.clone() }),
+        None => sonic_rs::json!({ "channel": channel }),
+    };
+    let message = sonic_rs::json!({ "event": "sockudo:subscribe", "data": data });
+    sonic_rs::from_value(&message)
+        .map_err(|e| Error::InvalidMessageFormat(format!("Invalid subscribe request: {e}")))
+}
+
+pub(crate) fn resume_message(
+    positions: &BTreeMap<String, ChannelPosition>,
+) -> Result<PusherMessage, Error> {
+    let data = sonic_rs::to_string(&sonic_rs::json!({ "channel_positions": positions }))
+        .map_err(|e| Error::InvalidMessageFormat(format!("Invalid resume request: {e}")))?;
+    let message = sonic_rs::json!({ "event": "sockudo:resume", "data": data });
+    sonic_rs::from_value(&message)
+        .map_err(|e| Error::InvalidMessageFormat(format!("Invalid resume request: {e}")))
+}
+
+fn error_status(error: &Error) -> StatusCode {
+    match error {
+        Error::ApplicationNotFound => StatusCode::NOT_FOUND,
+        Error::ApplicationDisabled | Error::OriginNotAllowed => StatusCode::FORBIDDEN,
+        Error::OverConnectionQuota => StatusCode::TOO_MANY_REQUESTS,
+        Error::Auth(_) => StatusCode::UNAUTHORIZED,
+        Error::InvalidMessageFormat(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Closes the transport session once the streaming response body is dropped, i.e. when the
+/// client disconnects or the server ends the stream.
+pub(crate) struct SessionGuard {
+    pub(crate) handler: Arc<ConnectionHandler>,
+    pub(crate) socket_id: SocketId,
+    pub(crate) app: App,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        let handler = self.handler.clone();
+        let socket_id = self.socket_id;
+        let app = self.app.clone();
+        tokio::spawn(async move {
+            handler.close_transport_session(&socket_id, &app).await;
+        });
+    }
+}
+
+struct SseStreamState {
+    receiver: mpsc::Receiver<Message>,
+    tracker: PositionTracker,
+    _guard: SessionGuard,
+}
+
+fn event_stream(
+    state: SseStreamState,
+) -> impl futures_util::Stream<Item = Result<Event, Infallible>> {
+    futures_util::stream::unfold(state, |mut state| async move {
+        loop {
+            match state.receiver.recv().await? {
+                Message::Text(bytes) => {
+                    let Ok(frame) = std::str::from_utf8(&bytes) else {
+                        warn!("Dropping non UTF-8 frame on SSE stream");
+                        continue;
+                    };
+                    let mut event = Event::default().data(frame);
+                    if let Some(id) = state.tracker.observe(frame) {
+                        event = event.id(id);
+                    }
+                    return Some((Ok(event), state));
+                }
+                Message::Close(_) => return None,
+                _ => continue,
+            }
+        }
+    })
+}
+
+// Server-Sent Events handler: a subscribe-only connection served by the regular
+// connection pipeline.
+pub async fn handle_sse_connect(
+    Path(app_key): Path<String>,
+    Query(params): Query<SseQuery>,
+    RawQuery(query): RawQuery,
+    headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    State(handler): State<Arc<ConnectionHandler>>,
+) -> Response {
+    if !handler.is_accepting() {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+
+    let channels = parse_channels(params.channels.as_deref());
+    if channels.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            "channels query parameter is required",
+        )
+            .into_response();
+    }
+
+    let filter = match params.filter.as_deref().map(sonic_rs::from_str::<Value>) {
+        Some(Ok(filter)) => Some(filter),
+        Some(Err(_)) => {
+            return (StatusCode::BAD_REQUEST, "filter must be valid JSON").into_response();
+        }
+        None => None,
+    };
+
+    let origin = headers
+        .get(axum::http::header::ORIGIN)
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+    let last_event_id = headers
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string())
+        .or(params.last_event_id);
+
+    let session = match handler
+        .open_transport_session(
+            &app_key,
+            TransportSessionOptions {
+                origin,
+                protocol_version: ProtocolVersion::V2,
+                echo_messages: true,
+                token: params.token,
+                connect_context: Some(connect_context("sse", &headers, query, connect_info)),
+            },
+        )
+        .await
+    {
+        Ok(session) => session,
+        Err(e) => {
+            debug!("Rejected SSE connection for app {}: {}", app_key, e);
+            return (error_status(&e), e.to_string()).into_response();
+        }
+    };
+
+    let TransportSession {
+        socket_id,
+        app,
+        receiver,
+        ..
+    } = session;
+    let guard = SessionGuard {
+        handler: handler.clone(),
+        socket_id,
+        app: app.clone(),
+    };
+
+    let tracker = PositionTracker::from_last_event_id(last_event_id.as_deref());
+    let mut requests = Vec::with_capacity(channels.len() + 1);
+    for channel in &channels {
+        match subscribe_message(channel, filter.as_ref()) {
+            Ok(message) => requests.push(message),
+            Err(e) => return (error_status(&e), e.to_string()).into_response(),
+        }
+    }
+    let resume_positions: BTreeMap<String, ChannelPosition> = tracker
+        .positions
+        .iter()
+        .filter(|(channel, _)| channels.contains(channel))
+        .map(|(channel, position)| (channel.clone(), position.clone()))
+        .collect();
+    if !resume_positions.is_empty() {
+        match resume_message(&resume_positions) {
+            Ok(message) => requests.push(message),
+            Err(e) => return (error_status(&e), e.to_string()).into_response(),
+        }
+    }
+
+    for request in &requests {
+        match handler
+            .handle_transport_message(&socket_id, &app, request)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => {
+                warn!("Failed to set up SSE connection {}: {}", socket_id, e);
+                return (error_status(&e), e.to_string()).into_response();
+            }
+        }
+    }
+
+    let stream = event_stream(SseStreamState {
+        receiver,
+        tracker,
+        _guard: guard,
+    });
+
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_handler::test_support::{test_app, test_realtime_handler_harness};
+    use axum::body::Body;
+    use http_body_util::BodyExt;
+    use sockudo_core::app::AppManager;
+
+    async fn next_chunk(body: &mut Body) -> String {
+        let frame = tokio::time::timeout(std::time::Duration::from_secs(2), body.frame())
+            .await
+            .expect("SSE frame timed out")
+            .expect("SSE body ended")
+            .expect("SSE body errored");
+        String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn channels_are_split_trimmed_and_deduplicated() {
+        assert_eq!(
+            parse_channels(Some("news, sport,,news")),
+            vec!["news".to_string(), "sport".to_string()]
+        );
+        assert!(parse_channels(None).is_empty());
+    }
+
+    #[test]
+    fn event_id_accumulates_channel_positions() {
+        let mut tracker = PositionTracker::default();
+        assert!(
+            tracker
+                .observe(r#"{"event":"sockudo:connection_established","data":"{}"}"#)
+                .is_none()
+        );
+
+        tracker
+            .observe(r#"{"event":"a","channel":"news","serial":3,"stream_id":"s1"}"#)
+            .unwrap();
+        let id = tracker
+            .observe(r#"{"event":"b","channel":"sport","serial":7,"message_id":"m7"}"#)
+            .unwrap();
+
+        let restored = PositionTracker::from_last_event_id(Some(&id));
+        assert_eq!(restored.positions, tracker.positions);
+        assert_eq!(restored.positions["news"].serial, 3);
+        assert_eq!(restored.positions["news"].stream_id.as_deref(), Some("s1"));
+        assert_eq!(
+            restored.positions["sport"].last_message_id.as_deref(),
+            Some("m7")
+        );
+    }
+
+    #[test]
+    fn malformed_last_event_id_is_ignored() {
+        assert!(
+            PositionTracker::from_last_event_id(Some("not-json"))
+                .positions
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn sse_connection_streams_handshake_and_subscription() {
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = test_app();
+        app_manager.create_app(app.clone()).await.unwrap();
+
+        let response = handle_sse_connect(
+            Path(app.key.clone()),
+            Query(SseQuery {
+                channels: Some("news".to_string()),
+                filter: None,
+                token: None,
+                last_event_id: None,
+            }),
+            RawQuery(None),
+            HeaderMap::new(),
+            None,
+            State(handler.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut body = response.into_body();
+        let established = next_chunk(&mut body).await;
+        assert!(established.contains("connection_established"));
+        let subscribed = next_chunk(&mut body).await;
+        assert!(subscribed.contains("subscription_succeeded"));
+        assert!(subscribed.contains("news"));
+
+        assert_eq!(
+            handler
+                .connection_manager()
+                .get_channel_socket_count(&app.id, "news")
+                .await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn sse_connection_rejects_unknown_app_and_missing_channels() {
+        let (handler, _) = test_realtime_handler_harness();
+
+        let response = handle_sse_connect(
+            Path("missing".to_string()),
+            Query(SseQuery {
+                channels: Some("news".to_string()),
+                filter: None,
+                token: None,
+                last_event_id: None,
+            }),
+            RawQuery(None),
+            HeaderMap::new(),
+            None,
+            State(handler.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = handle_sse_connect(
+            Path("missing".to_string()),
+            Query(SseQuery {
+                channels: None,
+                filter: None,
+                token: None,
+                last_event_id: None,
+            }),
+            RawQuery(None),
+            HeaderMap::new(),
+            None,
+            State(handler),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -2,13 +2,17 @@
 #![allow(dead_code)]
 
 use sockudo_adapter::ConnectionHandler;
+use sockudo_adapter::handler::connect_proxy::ConnectContext;
 
-use axum::extract::{Path, Query, State};
+use axum::Extension;
+use axum::extract::{ConnectInfo, Path, Query, RawQuery, State};
 use axum::http::HeaderMap;
 use axum::response::IntoResponse;
 use serde::Deserialize;
 use sockudo_protocol::{ProtocolVersion, WireFormat};
 use sockudo_ws::axum_integration::WebSocketUpgrade;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tracing::log::error;
 
@@ -27,11 +31,45 @@ pub struct ConnectionQuery {
     token: Option<String>,
 }
 
+/// Builds the context forwarded to an app's connect proxy from an HTTP connect request.
+///
+/// `connect_info` is absent when the server listens on a Unix socket.
+pub(crate) fn connect_context(
+    transport: &str,
+    headers: &HeaderMap,
+    query: Option<String>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+) -> ConnectContext {
+    let mut header_values = BTreeMap::<String, String>::new();
+    for (name, value) in headers {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        header_values
+            .entry(name.as_str().to_string())
+            .and_modify(|joined| {
+                joined.push_str(", ");
+                joined.push_str(value);
+            })
+            .or_insert_with(|| value.to_string());
+    }
+
+    ConnectContext {
+        transport: transport.to_string(),
+        origin: header_values.get("origin").cloned(),
+        headers: header_values,
+        query,
+        remote_addr: connect_info.map(|Extension(ConnectInfo(addr))| addr.to_string()),
+    }
+}
+
 // WebSocket upgrade handler
 pub async fn handle_ws_upgrade(
     Path(app_key): Path<String>,
     Query(params): Query<ConnectionQuery>,
+    RawQuery(query): RawQuery,
     headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
     ws: WebSocketUpgrade,
     State(handler): State<Arc<ConnectionHandler>>,
 ) -> impl IntoResponse {
@@ -73,6 +111,7 @@ pub async fn handle_ws_upgrade(
     {
         return axum::http::StatusCode::BAD_REQUEST.into_response();
     }
+    let connect_context = connect_context("websocket", &headers, query, connect_info);
     let ws_cfg = server_options.websocket.to_sockudo_ws_config(
         server_options.websocket_max_payload_kb,
         server_options.activity_timeout,
@@ -89,6 +128,7 @@ pub async fn handle_ws_upgrade(
                     wire_format,
                     echo_messages,
                     params.token,
+                    connect_context,
                 )
                 .await
             {
@@ -114,6 +154,627 @@ pub async fn handle_ws_upgrade(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::http_handler::test_support::{
+        ProxyBackend, test_app_with_policy, test_realtime_handler_harness,
+    };
+    use sockudo_adapter::handler::transport_session::{TransportSession, TransportSessionOptions};
+    use sockudo_core::app::{
+        AppChannelsPolicy, AppFeaturesPolicy, AppManager, AppPolicy, AppProxyConfig,
+        AppProxyEndpoint, ChannelNamespace, NamespaceProxyConfig,
+    };
+    use sockudo_core::error::Error;
+    use sockudo_core::token::Token;
+    use sockudo_protocol::messages::PusherMessage;
+    use sockudo_ws::Message;
+    use sonic_rs::{JsonContainerTrait, JsonValueTrait, Value};
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+
+    fn proxied_app(url: String, fail_open: bool) -> sockudo_core::app::App {
+        test_app_with_policy(AppPolicy {
+            proxy: Some(AppProxyConfig {
+                connect: Some(AppProxyEndpoint {
+                    url,
+                    timeout_ms: 1000,
+                    fail_open,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    fn browser_context() -> ConnectContext {
+        let mut headers = HeaderMap::new();
+        headers.insert("origin", "https://app.example".parse().unwrap());
+        headers.insert("cookie", "session=abc".parse().unwrap());
+        connect_context(
+            "websocket",
+            &headers,
+            Some("protocol=7".to_string()),
+            Some(Extension(ConnectInfo("203.0.113.7:5123".parse().unwrap()))),
+        )
+    }
+
+    fn session_options(context: ConnectContext) -> TransportSessionOptions {
+        TransportSessionOptions {
+            origin: context.origin.clone(),
+            protocol_version: ProtocolVersion::V1,
+            echo_messages: true,
+            token: None,
+            connect_context: Some(context),
+        }
+    }
+
+    async fn drain_events(session: &mut TransportSession) -> Vec<Value> {
+        let mut events = Vec::new();
+        while let Ok(Some(message)) =
+            tokio::time::timeout(Duration::from_millis(200), session.receiver.recv()).await
+        {
+            if let Message::Text(bytes) = message {
+                events.push(sonic_rs::from_slice(&bytes).unwrap());
+            }
+        }
+        events
+    }
+
+    fn event_names(events: &[Value]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| {
+                let name = event["event"].as_str().unwrap_or_default();
+                match event["channel"].as_str() {
+                    Some(channel) => format!("{name}@{channel}"),
+                    None => name.to_string(),
+                }
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn connect_proxy_signs_in_and_subscribes_granted_channels() {
+        let backend = ProxyBackend::start(
+            r#"{"allow":true,"user_id":"user-1","user_info":{"name":"Ada"},"channels":["private-orders","presence-room"]}"#,
+        )
+        .await;
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = proxied_app(backend.url.clone(), false);
+        app_manager.create_app(app.clone()).await.unwrap();
+
+        let mut session = handler
+            .open_transport_session(&app.key, session_options(browser_context()))
+            .await
+            .unwrap();
+
+        let events = drain_events(&mut session).await;
+        assert_eq!(
+            event_names(&events),
+            [
+                "pusher:connection_established",
+                "pusher:signin_success",
+                "pusher_internal:subscription_succeeded@private-orders",
+                "pusher_internal:subscription_succeeded@presence-room",
+            ]
+        );
+        let user_sockets = handler
+            .connection_manager()
+            .get_user_sockets("user-1", &app.id)
+            .await
+            .unwrap();
+        assert_eq!(user_sockets.len(), 1);
+
+        let (head, raw_body) = backend.request().await;
+        let signature = Token::new(app.key.clone(), app.secret.clone()).sign(&raw_body);
+        let body: Value = sonic_rs::from_str(&raw_body).unwrap();
+        assert!(head.contains(&format!("x-pusher-key: {}", app.key)));
+        assert!(head.contains(&format!("x-pusher-signature: {signature}")));
+        assert_eq!(
+            body["socket_id"].as_str(),
+            Some(session.socket_id.to_string().as_str())
+        );
+        assert_eq!(body["transport"].as_str(), Some("websocket"));
+        assert_eq!(body["headers"]["cookie"].as_str(), Some("session=abc"));
+        assert_eq!(body["origin"].as_str(), Some("https://app.example"));
+        assert_eq!(body["query"].as_str(), Some("protocol=7"));
+        assert_eq!(body["remote_addr"].as_str(), Some("203.0.113.7:5123"));
+        assert_eq!(body["headers"].as_object().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn connect_proxy_denial_rejects_the_connection() {
+        let backend = ProxyBackend::start(r#"{"allow":false,"reason":"session expired"}"#).await;
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = proxied_app(backend.url.clone(), false);
+        app_manager.create_app(app.clone()).await.unwrap();
+
+        let error = handler
+            .open_transport_session(&app.key, session_options(browser_context()))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, Error::Auth(reason) if reason == "session expired"));
+        assert_eq!(
+            handler
+                .connection_manager()
+                .get_sockets_count(&app.id)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn unreachable_connect_proxy_follows_fail_open_policy() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/connect", listener.local_addr().unwrap());
+        drop(listener);
+        let (handler, app_manager) = test_realtime_handler_harness();
+
+        let closed = proxied_app(url.clone(), false);
+        app_manager.create_app(closed.clone()).await.unwrap();
+        let error = handler
+            .open_transport_session(&closed.key, session_options(browser_context()))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Auth(_)));
+
+        let mut open = proxied_app(url, true);
+        open.id = "app-open".to_string();
+        open.key = "key-open".to_string();
+        app_manager.create_app(open.clone()).await.unwrap();
+        let mut session = handler
+            .open_transport_session(&open.key, session_options(browser_context()))
+            .await
+            .unwrap();
+        assert_eq!(
+            event_names(&drain_events(&mut session).await),
+            ["pusher:connection_established"]
+        );
+    }
+
+    fn subscribe_proxied_app(url: String) -> sockudo_core::app::App {
+        test_app_with_policy(AppPolicy {
+            channels: AppChannelsPolicy {
+                channel_namespaces: Some(vec![ChannelNamespace {
+                    name: "orders".to_string(),
+                    proxy: Some(NamespaceProxyConfig {
+                        subscribe: Some(AppProxyEndpoint {
+                            url,
+                            timeout_ms: 1000,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    async fn subscribe(
+        handler: &ConnectionHandler,
+        session: &mut TransportSession,
+        data: Value,
+    ) -> Vec<Value> {
+        let message: PusherMessage =
+            sonic_rs::from_value(&sonic_rs::json!({ "event": "pusher:subscribe", "data": data }))
+                .unwrap();
+        handler
+            .handle_transport_message(&session.socket_id, &session.app, &message)
+            .await
+            .unwrap();
+        drain_events(session).await
+    }
+
+    #[tokio::test]
+    async fn subscribe_proxy_authorizes_public_namespace_channels() {
+        let denying = ProxyBackend::start(r#"{"allow":false,"reason":"not your order"}"#).await;
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = subscribe_proxied_app(denying.url.clone());
+        app_manager.create_app(app.clone()).await.unwrap();
+        let mut session = handler
+            .open_transport_session(&app.key, session_options(ConnectContext::default()))
+            .await
+            .unwrap();
+        drain_events(&mut session).await;
+
+        let events = subscribe(
+            &handler,
+            &mut session,
+            sonic_rs::json!({ "channel": "orders:eu" }),
+        )
+        .await;
+        assert_eq!(event_names(&events), ["pusher:error"]);
+        assert!(
+            events[0]["data"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("not your order")
+        );
+
+        let events = subscribe(
+            &handler,
+            &mut session,
+            sonic_rs::json!({ "channel": "news" }),
+        )
+        .await;
+        assert_eq!(
+            event_names(&events),
+            ["pusher_internal:subscription_succeeded@news"]
+        );
+
+        let (_, raw_body) = denying.request().await;
+        let body: Value = sonic_rs::from_str(&raw_body).unwrap();
+        assert_eq!(body["channel"].as_str(), Some("orders:eu"));
+        assert_eq!(
+            body["socket_id"].as_str(),
+            Some(session.socket_id.to_string().as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn subscribe_proxy_supplies_presence_data_without_a_signature() {
+        let backend = ProxyBackend::start(
+            r#"{"allow":true,"channel_data":{"user_id":"user-9","user_info":{"name":"Grace"}}}"#,
+        )
+        .await;
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = subscribe_proxied_app(backend.url.clone());
+        app_manager.create_app(app.clone()).await.unwrap();
+        let mut session = handler
+            .open_transport_session(&app.key, session_options(ConnectContext::default()))
+            .await
+            .unwrap();
+        drain_events(&mut session).await;
+
+        let events = subscribe(
+            &handler,
+            &mut session,
+            sonic_rs::json!({
+                "channel": "presence-orders:room",
+                "channel_data": "{\"user_id\":\"forged\"}",
+            }),
+        )
+        .await;
+        assert_eq!(
+            event_names(&events),
+            ["pusher_internal:subscription_succeeded@presence-orders:room"]
+        );
+        let presence: Value = sonic_rs::from_str(events[0]["data"].as_str().unwrap()).unwrap();
+        assert_eq!(presence["presence"]["ids"][0].as_str(), Some("user-9"));
+        assert_eq!(
+            presence["presence"]["hash"]["user-9"]["name"].as_str(),
+            Some("Grace")
+        );
+
+        let (_, raw_body) = backend.request().await;
+        let body: Value = sonic_rs::from_str(&raw_body).unwrap();
+        assert_eq!(
+            body["channel_data"].as_str(),
+            Some("{\"user_id\":\"forged\"}")
+        );
+    }
+
+    #[cfg(feature = "tag-filtering")]
+    #[tokio::test]
+    async fn subscribe_proxy_overrides_the_tag_filter() {
+        let backend = ProxyBackend::start(
+            r#"{"allow":true,"tags_filter":{"key":"region","cmp":"eq","val":"eu"}}"#,
+        )
+        .await;
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = subscribe_proxied_app(backend.url.clone());
+        app_manager.create_app(app.clone()).await.unwrap();
+        let mut session = handler
+            .open_transport_session(&app.key, session_options(ConnectContext::default()))
+            .await
+            .unwrap();
+        drain_events(&mut session).await;
+
+        let events = subscribe(
+            &handler,
+            &mut session,
+            sonic_rs::json!({
+                "channel": "orders:all",
+                "tags_filter": { "key": "region", "cmp": "eq", "val": "us" },
+            }),
+        )
+        .await;
+        assert_eq!(
+            event_names(&events),
+            ["pusher_internal:subscription_succeeded@orders:all"]
+        );
+        let connection = handler
+            .connection_manager()
+            .get_connection(&session.socket_id, &app.id)
+            .await
+            .unwrap();
+        let filter = connection
+            .get_channel_filter_sync("orders:all")
+            .expect("tag filter should be stored");
+        assert_eq!(filter.val.as_deref(), Some("eu"));
+    }
+
+    fn publish_proxied_app(url: String) -> sockudo_core::app::App {
+        test_app_with_policy(AppPolicy {
+            features: AppFeaturesPolicy {
+                enable_client_messages: true,
+                ..Default::default()
+            },
+            channels: AppChannelsPolicy {
+                channel_namespaces: Some(vec![ChannelNamespace {
+                    name: "chat".to_string(),
+                    proxy: Some(NamespaceProxyConfig {
+                        publish: Some(AppProxyEndpoint {
+                            url,
+                            timeout_ms: 1000,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    async fn join_private_channel(
+        handler: &ConnectionHandler,
+        app: &sockudo_core::app::App,
+        channel: &str,
+    ) -> TransportSession {
+        let mut session = handler
+            .open_transport_session(&app.key, session_options(ConnectContext::default()))
+            .await
+            .unwrap();
+        drain_events(&mut session).await;
+        let signature = Token::new(app.key.clone(), app.secret.clone())
+            .sign(&format!("{}:{channel}", session.socket_id));
+        let events = subscribe(
+            handler,
+            &mut session,
+            sonic_rs::json!({ "channel": channel, "auth": format!("{}:{signature}", app.key) }),
+        )
+        .await;
+        assert_eq!(
+            event_names(&events),
+            [format!("pusher_internal:subscription_succeeded@{channel}")]
+        );
+        session
+    }
+
+    async fn publish_client_event(
+        handler: &ConnectionHandler,
+        session: &TransportSession,
+        channel: &str,
+        data: Value,
+    ) {
+        let message: PusherMessage = sonic_rs::from_value(&sonic_rs::json!({
+            "event": "client-message",
+            "channel": channel,
+            "data": data,
+        }))
+        .unwrap();
+        handler
+            .handle_transport_message(&session.socket_id, &session.app, &message)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn publish_proxy_rewrites_client_events_before_broadcast() {
+        let backend = ProxyBackend::start(
+            r#"{"allow":true,"event":"client-message-reviewed","data":{"text":"***","moderated":true}}"#,
+        )
+        .await;
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = publish_proxied_app(backend.url.clone());
+        app_manager.create_app(app.clone()).await.unwrap();
+        let publisher = join_private_channel(&handler, &app, "private-chat:room").await;
+        let mut subscriber = join_private_channel(&handler, &app, "private-chat:room").await;
+
+        publish_client_event(
+            &handler,
+            &publisher,
+            "private-chat:room",
+            sonic_rs::json!({ "text": "rude words" }),
+        )
+        .await;
+
+        let events = drain_events(&mut subscriber).await;
+        assert_eq!(
+            event_names(&events),
+            ["client-message-reviewed@private-chat:room"]
+        );
+        let data: Value = match events[0]["data"].as_str() {
+            Some(raw) => sonic_rs::from_str(raw).unwrap(),
+            None => events[0]["data"].clone(),
+        };
+        assert_eq!(data["text"].as_str(), Some("***"));
+
+        let (_, raw_body) = backend.request().await;
+        let body: Value = sonic_rs::from_str(&raw_body).unwrap();
+        assert_eq!(body["event"].as_str(), Some("client-message"));
+        assert_eq!(body["data"]["text"].as_str(), Some("rude words"));
+        assert_eq!(
+            body["socket_id"].as_str(),
+            Some(publisher.socket_id.to_string().as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_proxy_rejections_are_not_broadcast() {
+        let backend = ProxyBackend::start(r#"{"allow":false,"reason":"spam"}"#).await;
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = publish_proxied_app(backend.url.clone());
+        app_manager.create_app(app.clone()).await.unwrap();
+        let mut publisher = join_private_channel(&handler, &app, "private-chat:room").await;
+        let mut subscriber = join_private_channel(&handler, &app, "private-chat:room").await;
+
+        publish_client_event(
+            &handler,
+            &publisher,
+            "private-chat:room",
+            sonic_rs::json!({ "text": "buy now" }),
+        )
+        .await;
+
+        assert!(drain_events(&mut subscriber).await.is_empty());
+        let events = drain_events(&mut publisher).await;
+        assert_eq!(event_names(&events), ["pusher:error"]);
+        assert!(
+            events[0]["data"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("spam")
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_proxy_cannot_rewrite_into_reserved_events() {
+        let backend =
+            ProxyBackend::start(r#"{"allow":true,"event":"pusher:connection_established"}"#).await;
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = publish_proxied_app(backend.url.clone());
+        app_manager.create_app(app.clone()).await.unwrap();
+        let publisher = join_private_channel(&handler, &app, "private-chat:room").await;
+        let mut subscriber = join_private_channel(&handler, &app, "private-chat:room").await;
+
+        publish_client_event(
+            &handler,
+            &publisher,
+            "private-chat:room",
+            sonic_rs::json!({ "text": "hi" }),
+        )
+        .await;
+
+        assert!(drain_events(&mut subscriber).await.is_empty());
+    }
+
+    async fn call_rpc(
+        handler: &ConnectionHandler,
+        session: &mut TransportSession,
+        data: Value,
+    ) -> Vec<Value> {
+        let message: PusherMessage =
+            sonic_rs::from_value(&sonic_rs::json!({ "event": "sockudo:rpc", "data": data }))
+                .unwrap();
+        handler
+            .handle_transport_message(&session.socket_id, &session.app, &message)
+            .await
+            .unwrap();
+        drain_events(session).await
+    }
+
+    fn rpc_app(url: String) -> sockudo_core::app::App {
+        test_app_with_policy(AppPolicy {
+            proxy: Some(AppProxyConfig {
+                rpc: Some(AppProxyEndpoint {
+                    url,
+                    timeout_ms: 1000,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    async fn open_v2_session(
+        handler: &ConnectionHandler,
+        app: &sockudo_core::app::App,
+    ) -> TransportSession {
+        let mut session = handler
+            .open_transport_session(
+                &app.key,
+                TransportSessionOptions {
+                    protocol_version: ProtocolVersion::V2,
+                    ..session_options(ConnectContext::default())
+                },
+            )
+            .await
+            .unwrap();
+        drain_events(&mut session).await;
+        session
+    }
+
+    #[tokio::test]
+    async fn rpc_frames_are_answered_only_on_the_calling_socket() {
+        let backend = ProxyBackend::start(r#"{"result":{"unread":0}}"#).await;
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = rpc_app(backend.url.clone());
+        app_manager.create_app(app.clone()).await.unwrap();
+        let mut caller = open_v2_session(&handler, &app).await;
+        let mut bystander = open_v2_session(&handler, &app).await;
+
+        let events = call_rpc(
+            &handler,
+            &mut caller,
+            sonic_rs::json!({ "id": "call-1", "method": "mark_read", "payload": { "message_id": 42 } }),
+        )
+        .await;
+
+        assert_eq!(event_names(&events), ["sockudo:rpc_result"]);
+        assert_eq!(events[0]["data"]["id"].as_str(), Some("call-1"));
+        assert_eq!(events[0]["data"]["result"]["unread"].as_u64(), Some(0));
+        assert!(drain_events(&mut bystander).await.is_empty());
+
+        let (head, raw_body) = backend.request().await;
+        let signature = Token::new(app.key.clone(), app.secret.clone()).sign(&raw_body);
+        assert!(head.contains(&format!("x-pusher-signature: {signature}")));
+        let body: Value = sonic_rs::from_str(&raw_body).unwrap();
+        assert_eq!(body["method"].as_str(), Some("mark_read"));
+        assert_eq!(body["id"].as_str(), Some("call-1"));
+        assert_eq!(body["payload"]["message_id"].as_u64(), Some(42));
+        assert_eq!(
+            body["socket_id"].as_str(),
+            Some(caller.socket_id.to_string().as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn rpc_backend_errors_are_returned_under_the_call_id() {
+        let backend =
+            ProxyBackend::start(r#"{"error":{"code":"forbidden","message":"not your thread"}}"#)
+                .await;
+        let (handler, app_manager) = test_realtime_handler_harness();
+        let app = rpc_app(backend.url.clone());
+        app_manager.create_app(app.clone()).await.unwrap();
+        let mut session = open_v2_session(&handler, &app).await;
+
+        let events = call_rpc(
+            &handler,
+            &mut session,
+            sonic_rs::json!({ "id": "call-7", "method": "archive" }),
+        )
+        .await;
+        assert_eq!(events[0]["data"]["id"].as_str(), Some("call-7"));
+        assert_eq!(
+            events[0]["data"]["error"]["code"].as_str(),
+            Some("forbidden")
+        );
+
+        let mut unconfigured = test_app_with_policy(AppPolicy::default());
+        unconfigured.id = "app-plain".to_string();
+        unconfigured.key = "key-plain".to_string();
+        app_manager.create_app(unconfigured.clone()).await.unwrap();
+        let mut session = open_v2_session(&handler, &unconfigured).await;
+        let events = call_rpc(
+            &handler,
+            &mut session,
+            sonic_rs::json!({ "id": "call-8", "method": "archive" }),
+        )
+        .await;
+        assert_eq!(
+            events[0]["data"]["error"]["code"].as_str(),
+            Some("not_configured")
+        );
+    }
 
     #[test]
     fn v2_rejects_unknown_wire_format() {
@@ -133,6 +794,28 @@ mod tests {
         assert_eq!(wire_format, WireFormat::Json);
     }
 
+    #[test]
+    fn connect_context_joins_repeated_headers_and_keeps_remote_addr() {
+        let mut headers = HeaderMap::new();
+        headers.insert("origin", "https://app.example".parse().unwrap());
+        headers.append("cookie", "session=abc".parse().unwrap());
+        headers.append("cookie", "theme=dark".parse().unwrap());
+        let addr: SocketAddr = "203.0.113.7:5123".parse().unwrap();
+
+        let context = connect_context(
+            "websocket",
+            &headers,
+            Some("protocol=7&client=js".to_string()),
+            Some(Extension(ConnectInfo(addr))),
+        );
+
+        assert_eq!(context.transport, "websocket");
+        assert_eq!(context.origin.as_deref(), Some("https://app.example"));
+        assert_eq!(context.headers["cookie"], "session=abc, theme=dark");
+        assert_eq!(context.query.as_deref(), Some("protocol=7&client=js"));
+        assert_eq!(context.remote_addr.as_deref(), Some("203.0.113.7:5123"));
+    }
+
     #[test]
     fn append_rollup_window_accepts_locked_values() {
         let rollup = sockudo_core::options::AiTransportRollupConfig::default();
@@ -305,7 +305,7 @@ async fn connect_v2_socket(node: &ClusterNode, app: &App) -> (SocketId, ClientRe
     node.adapter
         .add_socket(
             socket_id,
-            server_writer,
+            server_writer.into(),
             &app.id,
             node.app_manager.clone() as Arc<dyn AppManager + Send + Sync>,
             WebSocketBufferConfig::default(),
@@ -508,6 +508,7 @@ async fn run_cross_node_cold_recovery_test(history_store: Arc<dyn HistoryStore +
                 rewind: None,
                 event_name_filter: None,
                 annotation_subscribe: false,
+                delivery: None,
             },
         )
         .await
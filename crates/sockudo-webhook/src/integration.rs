@@ -316,7 +316,7 @@ impl WebhookIntegration {
         JobData {
             app_key: app.key.clone(),
             app_id: app.id.clone(),
-            app_secret: app.secret.clone(),
+            app_secret: app.signing_secret().to_string(),
             payload: job_payload,
             original_signature: original_signature_for_queue.to_string(),
         }